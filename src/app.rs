@@ -2,17 +2,30 @@
 //!
 //! Defines the main application controller and the logic for mutating app state
 //! in response to user input. Submodules handle actions, navigation, key mapping,
-//! preview pane and parent pane requests.
+//! preview pane and parent pane requests, the jump list, notifications, session statistics, state
+//! snapshots for bug reports, the guard of root sessions and the quick-start tutorial.
 
 pub mod actions;
+mod guard;
 mod handlers;
+pub mod jumplist;
 mod keymap;
 mod nav;
+pub mod notifications;
 mod parent;
 pub mod preview;
+mod snapshot;
 mod state;
+mod stats;
+pub mod tutorial;
 
-pub use nav::NavState;
+pub use guard::RootGuard;
+pub use jumplist::{Jump, JumpList};
+pub use nav::{DirSizeStatus, FilterMode, MarkerPolicy, NavState};
+pub use notifications::{Notification, Notifications, Severity};
 pub use parent::ParentState;
 pub use preview::{PreviewData, PreviewState};
-pub use state::{AppState, KeypressResult, LayoutMetrics};
+pub use snapshot::{SNAPSHOT_VERSION, snapshot_dir};
+pub use state::{AppState, KEY_SEQUENCE_TIMEOUT, KeypressResult, LayoutMetrics};
+pub use stats::{SLOW_LOG_LIMIT, SessionStats, SlowKind, SlowOperation};
+pub use tutorial::Tutorial;
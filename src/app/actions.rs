@@ -4,13 +4,18 @@
 //! Defines available modes/actions for file operations (copy, paste, rename, create, delete, filter).
 
 use crate::app::nav::NavState;
+use crate::config::Template;
 use crate::core::FileInfo;
-use crate::core::proc::FindResult;
-use crate::core::worker::{FileOperation, WorkerTask};
-
-use crossbeam_channel::Sender;
-use std::collections::HashSet;
-use std::path::PathBuf;
+use crate::core::proc::{FindKind, FindResult};
+use crate::core::worker::{FileOperation, PasteConflict, TaskSender, WorkerTask};
+use crate::utils::{NameIssue, check_filename, expand_create_input, resumable_copy_offset};
+
+use std::collections::{HashSet, VecDeque};
+use std::ffi::OsString;
+use std::fs;
+use std::io;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::Duration;
@@ -43,15 +48,33 @@ pub enum ActionMode {
 /// * `NewFolder` - Create new folder prompt.
 /// * `Filter` - Filter files in the current directory prompt.
 /// * `ConfirmDelete` - Confirm delete files prompt.
+/// * `ConfirmResume` - Resume or restart interrupted copies prompt.
+/// * `ConfirmRetry` - Retry deleting or renaming files in use by another process prompt.
+/// * `ConfirmOverwrite` - Overwrite, rename or skip entries whose names are taken on paste prompt.
 /// * `Find` - Fuzzy find files prompt.
-#[derive(Clone, Copy, PartialEq)]
+/// * `Grep` - Search file contents prompt.
+/// * `Jump` - Jump to a directory known to zoxide prompt.
+/// * `Command` - Shell command prompt.
+/// * `Template` - Choose the template of new files, after the new-file prompt.
+/// * `OpenWith` - Choose how to open a file, for the `prompt` action of `[open.enter]`.
+#[derive(Clone, Copy, PartialEq, Debug)]
 pub enum InputMode {
     Rename,
     NewFile,
     NewFolder,
     Filter,
     ConfirmDelete,
+    ConfirmResume,
+    ConfirmRetry,
+    ConfirmOverwrite,
     Find,
+    Grep,
+    Jump,
+    QuickFind,
+    Navigate,
+    Command,
+    Template,
+    OpenWith,
 }
 
 /// Tracks current user action and input buffer state for file operations and commands.
@@ -68,9 +91,21 @@ pub enum InputMode {
 /// * `mode` - Current [ActionMode] (Normal, Input, ShowInfo).
 /// * `input_buffer` - Current input string buffer.
 /// * `input_cursor_pos` - Cursor position within the input buffer.
+/// * `input_anchor` - Start of the selected input, which spans from here to the cursor.
 /// * `clipboard` - Optional set of file paths for copy/paste operations.
 /// * `is_cut` - Flag indicating if clipboard items are cut or copied.
+/// * `resume_targets` - Interrupted copies in the current directory which can be resumed.
+/// * `conflict_targets` - Entries of the current directory a paste would collide with.
+/// * `resume_paste` - Whether the paste waiting for the overwrite prompt resumes copies.
+/// * `busy_retry` - The part of a delete or rename which failed as the files were in use.
+/// * `pending_ops` - Request ids, targets and the entry to focus afterwards of the dispatched
+///   deletes and pastes.
+/// * `delete` - Embedded [DeleteState] holding the targets of the delete confirmation.
+/// * `template` - Embedded [TemplateState] holding the templates offered for new files.
+/// * `quick_find_origin` - The selection before the quick find, restored when it is cancelled.
+/// * `navigate_filter` - The filter of the current directory before the navigation narrowed it.
 /// * `find` - Embedded [FindState] for managing fuzzy find operations.
+/// * `history` - The [FindHistory] of the find and grep dialogs.
 ///
 /// Methods to manipulate input, clipboard, and perform file actions.
 /// Also find management methods.
@@ -78,9 +113,20 @@ pub struct ActionContext {
     mode: ActionMode,
     input_buffer: String,
     input_cursor_pos: usize,
+    input_anchor: Option<usize>,
     clipboard: Option<HashSet<PathBuf>>,
     is_cut: bool,
+    resume_targets: Vec<PathBuf>,
+    conflict_targets: Vec<PathBuf>,
+    resume_paste: bool,
+    busy_retry: Option<FileOperation>,
+    pending_ops: Vec<(u64, Vec<PathBuf>, Option<OsString>)>,
+    delete: DeleteState,
+    template: TemplateState,
+    quick_find_origin: Option<usize>,
+    navigate_filter: Option<String>,
     find: FindState,
+    history: FindHistory,
 }
 
 impl ActionContext {
@@ -94,18 +140,109 @@ impl ActionContext {
         &self.input_buffer
     }
 
+    /// The problem with the name typed into the create or rename dialog, if any.
+    pub fn name_issue(&self) -> Option<NameIssue> {
+        match self.mode {
+            ActionMode::Input {
+                mode: InputMode::NewFile | InputMode::NewFolder,
+                ..
+            } => expand_create_input(&self.input_buffer).1,
+            ActionMode::Input {
+                mode: InputMode::Rename,
+                ..
+            } => check_filename(&self.input_buffer),
+            _ => None,
+        }
+    }
+
     pub fn input_cursor_pos(&self) -> usize {
         self.input_cursor_pos
     }
 
+    /// The byte range of the selected input, typing replaces it.
+    pub fn input_selection(&self) -> Option<Range<usize>> {
+        self.input_anchor
+            .map(|anchor| anchor.min(self.input_cursor_pos)..anchor.max(self.input_cursor_pos))
+    }
+
     pub fn clipboard(&self) -> &Option<HashSet<PathBuf>> {
         &self.clipboard
     }
 
+    /// Whether the clipboard entries are moved on paste instead of copied.
+    pub fn is_cut(&self) -> bool {
+        self.is_cut
+    }
+
     pub fn clipboard_mut(&mut self) -> &mut Option<HashSet<PathBuf>> {
         &mut self.clipboard
     }
 
+    pub fn resume_targets(&self) -> &[PathBuf] {
+        &self.resume_targets
+    }
+
+    pub fn conflict_targets(&self) -> &[PathBuf] {
+        &self.conflict_targets
+    }
+
+    pub fn resume_paste(&self) -> bool {
+        self.resume_paste
+    }
+
+    /// The files in use of the operation offered for a retry.
+    pub fn busy_targets(&self) -> &[PathBuf] {
+        match &self.busy_retry {
+            Some(FileOperation::Delete(paths)) => paths,
+            Some(FileOperation::Rename { old, .. }) => std::slice::from_ref(old),
+            _ => &[],
+        }
+    }
+
+    pub fn delete_state(&self) -> &DeleteState {
+        &self.delete
+    }
+
+    pub fn delete_state_mut(&mut self) -> &mut DeleteState {
+        &mut self.delete
+    }
+
+    pub fn template_state(&self) -> &TemplateState {
+        &self.template
+    }
+
+    pub fn template_state_mut(&mut self) -> &mut TemplateState {
+        &mut self.template
+    }
+
+    pub fn set_quick_find_origin(&mut self, idx: usize) {
+        self.quick_find_origin = Some(idx);
+    }
+
+    pub fn quick_find_origin(&self) -> Option<usize> {
+        self.quick_find_origin
+    }
+
+    pub fn set_navigate_filter(&mut self, filter: String) {
+        self.navigate_filter = Some(filter);
+    }
+
+    pub fn navigate_filter(&self) -> Option<&str> {
+        self.navigate_filter.as_deref()
+    }
+
+    pub fn find_history(&self) -> &FindHistory {
+        &self.history
+    }
+
+    pub fn find_history_mut(&mut self) -> &mut FindHistory {
+        &mut self.history
+    }
+
+    pub fn set_find_history(&mut self, history: FindHistory) {
+        self.history = history;
+    }
+
     // Find functions
 
     pub fn find_state_mut(&mut self) -> &mut FindState {
@@ -124,10 +261,27 @@ impl ActionContext {
         self.find.set_results(results)
     }
 
+    pub fn apply_find_results(&mut self, results: Vec<FindResult>, complete: bool) {
+        let request_id = self.find.request_id();
+        self.find.apply_results(results, request_id, complete)
+    }
+
+    pub fn is_find_searching(&self) -> bool {
+        self.find.is_searching()
+    }
+
     pub fn clear_find_results(&mut self) {
         self.find.clear_results()
     }
 
+    pub fn find_kind(&self) -> FindKind {
+        self.find.kind()
+    }
+
+    pub fn cycle_find_kind(&mut self) {
+        self.find.cycle_kind()
+    }
+
     pub fn find_request_id(&self) -> u64 {
         self.find.request_id()
     }
@@ -162,35 +316,129 @@ impl ActionContext {
         self.mode = mode;
         self.input_buffer = initial_value;
         self.input_cursor_pos = self.input_buffer.len();
+        self.input_anchor = None;
     }
 
     pub fn exit_mode(&mut self) {
         self.mode = ActionMode::Normal;
         self.input_buffer.clear();
+        self.input_anchor = None;
+        self.resume_targets.clear();
+        self.conflict_targets.clear();
+        self.busy_retry = None;
+        self.delete.reset();
+        self.template = TemplateState::default();
+        self.quick_find_origin = None;
+        self.navigate_filter = None;
         self.find.reset();
+        self.history.stop_browsing();
+    }
+
+    /// Replaces the input buffer and moves the cursor to its end.
+    ///
+    /// # Arguments
+    /// * `value` - The new input.
+    pub fn set_input(&mut self, value: String) {
+        self.input_buffer = value;
+        self.input_cursor_pos = self.input_buffer.len();
+        self.input_anchor = None;
+    }
+
+    /// Selects a part of the input buffer and moves the cursor to its end.
+    ///
+    /// # Arguments
+    /// * `range` - The byte range to select, an empty range only places the cursor.
+    pub fn select_input(&mut self, range: Range<usize>) {
+        let end = range.end.min(self.input_buffer.len());
+        let start = range.start.min(end);
+        if !self.input_buffer.is_char_boundary(start) || !self.input_buffer.is_char_boundary(end) {
+            return;
+        }
+        self.input_cursor_pos = end;
+        self.input_anchor = (start < end).then_some(start);
+    }
+
+    /// Replaces the prompt of the current input mode, keeping the input buffer intact.
+    ///
+    /// # Arguments
+    /// * `new_prompt` - The new prompt string.
+    pub fn set_prompt(&mut self, new_prompt: String) {
+        if let ActionMode::Input { prompt, .. } = &mut self.mode {
+            *prompt = new_prompt;
+        }
     }
 
     // Actions functions
 
-    /// Deletes the currently marked files or the selected file if no markers exist.
+    /// Deletes the targets of the delete confirmation which were not excluded.
     ///
     /// Sends a delete task to the worker thread via the provided channel.
+    /// The targets are kept until the operation finished, see [Self::take_finished_op].
     ///
     /// # Arguments
     /// * `nav` - Mutable reference to the current navigation state.
     /// * `worker_tx` - Sender channel to dispatch worker tasks.
-    pub fn action_delete(&mut self, nav: &mut NavState, worker_tx: &Sender<WorkerTask>) {
-        let targets = nav.get_action_targets();
+    pub fn action_delete(&mut self, nav: &mut NavState, worker_tx: &TaskSender) {
+        let targets = self.delete.included();
         if targets.is_empty() {
             return;
         }
 
+        // Keep the cursor in place: on the next remaining sibling, or the previous one if last
+        let focus = nav.focus_after_removal(&targets);
+        let request_id = nav.prepare_new_request();
+        self.pending_ops.push((request_id, targets.clone(), focus));
         let _ = worker_tx.send(WorkerTask::FileOp {
-            op: FileOperation::Delete(targets.into_iter().collect()),
-            request_id: nav.prepare_new_request(),
+            op: FileOperation::Delete(targets),
+            request_id,
         });
+    }
 
-        nav.clear_markers();
+    /// Keeps the part of an operation which failed as the files were in use, for a retry.
+    ///
+    /// # Arguments
+    /// * `op` - The delete or rename to retry.
+    pub fn set_busy_retry(&mut self, op: FileOperation) {
+        self.busy_retry = Some(op);
+    }
+
+    /// Sends the operation kept by [ActionContext::set_busy_retry] to the worker again.
+    ///
+    /// # Arguments
+    /// * `nav` - Mutable reference to the current navigation state.
+    /// * `worker_tx` - Sender channel to dispatch worker tasks.
+    pub fn action_retry_busy(&mut self, nav: &mut NavState, worker_tx: &TaskSender) {
+        let Some(op) = self.busy_retry.take() else {
+            return;
+        };
+        let request_id = nav.prepare_new_request();
+        if let FileOperation::Delete(targets) = &op {
+            let focus = nav.focus_after_removal(targets);
+            self.pending_ops.push((request_id, targets.clone(), focus));
+        }
+        let _ = worker_tx.send(WorkerTask::FileOp { op, request_id });
+    }
+
+    /// Takes the targets of a finished delete or paste.
+    ///
+    /// The file operation worker runs one operation after another, so pending operations
+    /// with an older request id failed and are dropped as well.
+    ///
+    /// # Arguments
+    /// * `request_id` - The request id of the finished operation.
+    ///
+    /// # Returns
+    /// The targets of the operation, empty if it was not a delete or paste,
+    /// and the entry to focus after the reload, if the operation chose one.
+    pub fn take_finished_op(&mut self, request_id: u64) -> (Vec<PathBuf>, Option<OsString>) {
+        let mut finished = (Vec::new(), None);
+        self.pending_ops.retain_mut(|(id, paths, focus)| {
+            if *id == request_id {
+                finished = (std::mem::take(paths), focus.take());
+            }
+            *id > request_id
+        });
+        finished
     }
 
     /// Currently, cut/move is not implemented yet. Only copy/yank is used.
@@ -217,6 +465,66 @@ impl ActionContext {
         }
     }
 
+    /// Collects the clipboard files which left an interrupted copy in the current directory.
+    ///
+    /// A copy is considered interrupted if the destination is a smaller file whose tail matches
+    /// the source (see [resumable_copy_offset]). Cut operations are never resumed.
+    ///
+    /// # Arguments
+    /// * `nav` - Reference to the current navigation state.
+    ///
+    /// # Returns
+    /// The number of resumable copies found.
+    pub fn collect_resume_targets(&mut self, nav: &NavState) -> usize {
+        self.resume_targets.clear();
+        if self.is_cut {
+            return 0;
+        }
+        if let Some(source) = &self.clipboard {
+            let mut targets: Vec<PathBuf> = source
+                .iter()
+                .filter_map(|src| {
+                    let dest = nav.current_dir().join(src.file_name()?);
+                    resumable_copy_offset(src, &dest).map(|_| dest)
+                })
+                .collect();
+            targets.sort();
+            self.resume_targets = targets;
+        }
+        self.resume_targets.len()
+    }
+
+    /// Collects the entries of the current directory a paste of the clipboard would collide with.
+    ///
+    /// Pastes into the directory of the source and interrupted copies about to be resumed
+    /// are no collisions, see [FileOperation::Copy].
+    ///
+    /// # Arguments
+    /// * `nav` - The current navigation state, the destination is its directory.
+    /// * `resume` - Whether the paste resumes interrupted copies.
+    ///
+    /// # Returns
+    /// The number of collisions found.
+    pub fn collect_paste_conflicts(&mut self, nav: &NavState, resume: bool) -> usize {
+        self.conflict_targets.clear();
+        self.resume_paste = resume;
+        if let Some(source) = &self.clipboard {
+            let mut targets: Vec<PathBuf> = source
+                .iter()
+                .filter_map(|src| {
+                    let dest = nav.current_dir().join(src.file_name()?);
+                    let resumed =
+                        resume && !self.is_cut && resumable_copy_offset(src, &dest).is_some();
+                    (dest.symlink_metadata().is_ok() && !src.starts_with(&dest) && !resumed)
+                        .then_some(dest)
+                })
+                .collect();
+            targets.sort();
+            self.conflict_targets = targets;
+        }
+        self.conflict_targets.len()
+    }
+
     /// Pastes the files from the clipboard into the current directory.
     ///
     /// Sends a copy task to the worker thread via the provided channel.
@@ -224,7 +532,17 @@ impl ActionContext {
     /// # Arguments
     /// * `nav` - Mutable reference to the current navigation state.
     /// * `worker_tx` - Sender channel to dispatch worker tasks.
-    pub fn action_paste(&mut self, nav: &mut NavState, worker_tx: &Sender<WorkerTask>) {
+    /// * `resume` - Whether interrupted copies should be resumed instead of copied anew.
+    /// * `max_bytes_per_sec` - Optional bandwidth limit for the copy.
+    /// * `conflict` - What happens to entries whose name is already taken.
+    pub fn action_paste(
+        &mut self,
+        nav: &mut NavState,
+        worker_tx: &TaskSender,
+        resume: bool,
+        max_bytes_per_sec: Option<u64>,
+        conflict: PasteConflict,
+    ) {
         if let Some(source) = &self.clipboard {
             let first_file_name = source
                 .iter()
@@ -232,19 +550,24 @@ impl ActionContext {
                 .and_then(|p| p.file_name())
                 .map(|n| n.to_os_string());
 
+            let src: Vec<PathBuf> = source.iter().cloned().collect();
+            let request_id = nav.prepare_new_request();
+            self.pending_ops.push((request_id, src.clone(), None));
             let _ = worker_tx.send(WorkerTask::FileOp {
                 op: FileOperation::Copy {
-                    src: source.iter().cloned().collect(),
+                    src,
                     dest: nav.current_dir().to_path_buf(),
                     cut: self.is_cut,
                     focus: first_file_name,
+                    resume,
+                    max_bytes_per_sec,
+                    conflict,
                 },
-                request_id: nav.prepare_new_request(),
+                request_id,
             });
             if self.is_cut {
                 self.clipboard = None;
             }
-            nav.clear_markers();
         }
     }
 
@@ -266,7 +589,7 @@ impl ActionContext {
     /// * `worker_tx` - Sender channel to dispatch worker tasks.
     ///
     /// Exits input mode after performing the action.
-    pub fn action_rename(&mut self, nav: &mut NavState, worker_tx: &Sender<WorkerTask>) {
+    pub fn action_rename(&mut self, nav: &mut NavState, worker_tx: &TaskSender) {
         if self.input_buffer.is_empty() {
             return;
         }
@@ -285,29 +608,49 @@ impl ActionContext {
         self.exit_mode();
     }
 
-    /// Creates a new file or directory with the name in the input buffer.
+    /// Creates new files or directories with the names in the input buffer.
     ///
-    /// Sends a create task to the worker thread via the provided channel.
+    /// Braces are expanded (see [expand_create_input]) and a name with a trailing `/`
+    /// creates a directory. All entries are sent as one create task to the worker thread.
     ///
     /// # Arguments
     /// * `nav` - Mutable reference to the current navigation state.
     /// * `is_dir` - Boolean indicating if creating a directory (true) or file (false).
     /// * `worker_tx` - Sender channel to dispatch worker tasks.
+    /// * `file_mode` - Permissions of new files, `None` for the platform default.
+    /// * `dir_mode` - Permissions of new directories, `None` for the platform default.
+    /// * `template` - The template filling the new files it matches, if one was chosen.
     ///
     /// Exits input mode after performing the action.
     pub fn action_create(
         &mut self,
         nav: &mut NavState,
         is_dir: bool,
-        worker_tx: &Sender<WorkerTask>,
+        worker_tx: &TaskSender,
+        file_mode: Option<u32>,
+        dir_mode: Option<u32>,
+        template: Option<Template>,
     ) {
-        if self.input_buffer.is_empty() {
+        let (names, _) = expand_create_input(&self.input_buffer);
+        let entries: Vec<(PathBuf, bool)> = names
+            .iter()
+            .filter(|name| !name.trim_end_matches('/').is_empty())
+            .map(|name| {
+                let path = nav.current_dir().join(name.trim_end_matches('/'));
+                (path, is_dir || name.ends_with('/'))
+            })
+            .collect();
+        if entries.is_empty() {
             return;
         }
 
-        let path = nav.current_dir().join(&self.input_buffer);
         let _ = worker_tx.send(WorkerTask::FileOp {
-            op: FileOperation::Create { path, is_dir },
+            op: FileOperation::Create {
+                entries,
+                file_mode,
+                dir_mode,
+                template,
+            },
             request_id: nav.prepare_new_request(),
         });
         self.exit_mode();
@@ -315,33 +658,66 @@ impl ActionContext {
 
     // Cursor actions
 
-    /// Moves the input cursor one position to the left, if possible.
+    /// Moves the input cursor one character to the left, if possible.
+    ///
+    /// With a selection the cursor moves to its start instead.
     pub fn action_move_cursor_left(&mut self) {
-        if self.input_cursor_pos > 0 {
-            self.input_cursor_pos -= 1;
+        if let Some(selection) = self.input_selection() {
+            self.input_anchor = None;
+            self.input_cursor_pos = selection.start;
+        } else if let Some((previous, _)) = self.input_buffer[..self.input_cursor_pos]
+            .char_indices()
+            .next_back()
+        {
+            self.input_cursor_pos = previous;
         }
     }
 
-    /// Moves the input cursor one position to the right, if possible.
+    /// Moves the input cursor one character to the right, if possible.
+    ///
+    /// With a selection the cursor moves to its end instead.
     pub fn action_move_cursor_right(&mut self) {
-        if self.input_cursor_pos < self.input_buffer.len() {
-            self.input_cursor_pos += 1;
+        if let Some(selection) = self.input_selection() {
+            self.input_anchor = None;
+            self.input_cursor_pos = selection.end;
+        } else if let Some(ch) = self.input_buffer[self.input_cursor_pos..].chars().next() {
+            self.input_cursor_pos += ch.len_utf8();
         }
     }
 
-    /// Inserts a character at the current cursor position in the input buffer.
+    /// Removes the selected input, if any, leaving the cursor where it was.
+    ///
+    /// # Returns
+    /// Whether a selection was removed.
+    fn delete_selection(&mut self) -> bool {
+        let Some(selection) = self.input_selection() else {
+            return false;
+        };
+        self.input_anchor = None;
+        self.input_cursor_pos = selection.start;
+        self.input_buffer.replace_range(selection, "");
+        true
+    }
+
+    /// Inserts a character at the current cursor position in the input buffer,
+    /// replacing the selected input.
     ///
     /// # Arguments
     /// * `ch` - The character to insert.
     pub fn action_insert_at_cursor(&mut self, ch: char) {
+        self.delete_selection();
         self.input_buffer.insert(self.input_cursor_pos, ch);
         self.input_cursor_pos += ch.len_utf8();
     }
 
-    /// Deletes the character before the current cursor position in the input buffer.
+    /// Deletes the character before the current cursor position in the input buffer,
+    /// or the selected input.
     ///
     /// Moves the cursor back accordingly
     pub fn action_backspace_at_cursor(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
         if self.input_cursor_pos > 0
             && let Some((previous, _)) = self.input_buffer[..self.input_cursor_pos]
                 .char_indices()
@@ -352,8 +728,12 @@ impl ActionContext {
         }
     }
 
-    /// Deletes the character at the current cursor position in the input buffer.
+    /// Deletes the character at the current cursor position in the input buffer,
+    /// or the selected input.
     pub fn action_delete_at_cursor(&mut self) {
+        if self.delete_selection() {
+            return;
+        }
         if self.input_cursor_pos < self.input_buffer.len() {
             let off = self.input_cursor_pos;
             let mut ch_iter = self.input_buffer[off..].char_indices();
@@ -365,11 +745,13 @@ impl ActionContext {
 
     /// Moves the input cursor to the start of the input buffer.
     pub fn action_cursor_home(&mut self) {
+        self.input_anchor = None;
         self.input_cursor_pos = 0;
     }
 
     /// Moves the input cursor to the end of the input buffer.
     pub fn action_cursor_end(&mut self) {
+        self.input_anchor = None;
         self.input_cursor_pos = self.input_buffer.len();
     }
 }
@@ -380,10 +762,306 @@ impl Default for ActionContext {
             mode: ActionMode::Normal,
             input_buffer: String::new(),
             input_cursor_pos: 0,
+            input_anchor: None,
             clipboard: None,
             is_cut: false,
+            resume_targets: Vec::new(),
+            conflict_targets: Vec::new(),
+            resume_paste: false,
+            busy_retry: None,
+            pending_ops: Vec::new(),
+            delete: DeleteState::default(),
+            template: TemplateState::default(),
+            quick_find_origin: None,
+            navigate_filter: None,
             find: FindState::default(),
+            history: FindHistory::default(),
+        }
+    }
+}
+
+/// Tracks the targets listed in the delete confirmation dialog.
+///
+/// Targets can be excluded one by one before confirming, so a single mis-marked file
+/// does not force cancelling the whole batch.
+///
+/// # Fields
+/// * `targets` - Sorted list of paths about to be deleted.
+/// * `excluded` - Indices of the targets which were deselected.
+/// * `selected` - Index of the currently selected target.
+#[derive(Default)]
+pub struct DeleteState {
+    targets: Vec<PathBuf>,
+    excluded: HashSet<usize>,
+    selected: usize,
+}
+
+impl DeleteState {
+    // Getters / Accessors
+
+    pub fn targets(&self) -> &[PathBuf] {
+        &self.targets
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn is_excluded(&self, idx: usize) -> bool {
+        self.excluded.contains(&idx)
+    }
+
+    pub fn included_count(&self) -> usize {
+        self.targets.len() - self.excluded.len()
+    }
+
+    // Delete functions
+
+    /// Sets the targets of the confirmation, sorted by path, with all of them included.
+    ///
+    /// # Arguments
+    /// * `targets` - The paths about to be deleted.
+    pub fn set_targets(&mut self, targets: impl IntoIterator<Item = PathBuf>) {
+        self.targets = targets.into_iter().collect();
+        self.targets.sort();
+        self.excluded.clear();
+        self.selected = 0;
+    }
+
+    /// Returns the targets which were not excluded.
+    pub fn included(&self) -> Vec<PathBuf> {
+        self.targets
+            .iter()
+            .enumerate()
+            .filter(|(idx, _)| !self.excluded.contains(idx))
+            .map(|(_, path)| path.clone())
+            .collect()
+    }
+
+    /// Toggles the exclusion of the currently selected target.
+    pub fn toggle_selected(&mut self) {
+        if self.selected >= self.targets.len() {
+            return;
+        }
+        if !self.excluded.remove(&self.selected) {
+            self.excluded.insert(self.selected);
+        }
+    }
+
+    /// Moves the selection to the next target.
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.targets.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Moves the selection to the previous target.
+    pub fn select_prev(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+        }
+    }
+
+    /// Clears the targets, exclusions and selection.
+    fn reset(&mut self) {
+        self.targets.clear();
+        self.excluded.clear();
+        self.selected = 0;
+    }
+}
+
+/// State of the template prompt, shown after confirming the new-file prompt when
+/// templates match the new files.
+///
+/// # Fields
+/// * `input` - The input of the new-file prompt, created once a template is chosen.
+/// * `templates` - Names of the matching templates.
+/// * `selected` - Index of the selected option, 0 creates empty files.
+#[derive(Default)]
+pub struct TemplateState {
+    input: String,
+    templates: Vec<String>,
+    selected: usize,
+}
+
+impl TemplateState {
+    pub fn templates(&self) -> &[String] {
+        &self.templates
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    /// The name of the selected template, `None` for empty files.
+    pub fn selected_template(&self) -> Option<&str> {
+        self.selected
+            .checked_sub(1)
+            .and_then(|idx| self.templates.get(idx))
+            .map(String::as_str)
+    }
+
+    /// Offers templates for the new files, with the first template selected.
+    ///
+    /// # Arguments
+    /// * `input` - The input of the new-file prompt.
+    /// * `templates` - Names of the matching templates.
+    pub fn set(&mut self, input: String, templates: Vec<String>) {
+        self.input = input;
+        self.templates = templates;
+        self.selected = 1.min(self.templates.len());
+    }
+
+    /// Takes the input of the new-file prompt.
+    pub fn take_input(&mut self) -> String {
+        std::mem::take(&mut self.input)
+    }
+
+    /// Moves the selection to the next option.
+    pub fn select_next(&mut self) {
+        if self.selected < self.templates.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Moves the selection to the previous option.
+    pub fn select_prev(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+}
+
+/// Remembers the queries accepted in the find and grep dialogs.
+///
+/// Queries can be recalled with Up/Down. While browsing, the text typed before
+/// the first recall is kept as draft and restored when browsing past the newest entry.
+///
+/// # Fields
+/// * `entries` - The remembered queries, oldest first.
+/// * `cursor` - Index of the recalled entry while browsing.
+/// * `draft` - The input before browsing started.
+/// * `capacity` - Maximum number of remembered queries.
+pub struct FindHistory {
+    entries: VecDeque<String>,
+    cursor: Option<usize>,
+    draft: String,
+    capacity: usize,
+}
+
+impl FindHistory {
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            entries: VecDeque::new(),
+            cursor: None,
+            draft: String::new(),
+            capacity,
+        }
+    }
+
+    /// Loads the history from a file with one query per line.
+    ///
+    /// # Arguments
+    /// * `path` - The history file.
+    /// * `capacity` - Maximum number of remembered queries, the newest ones are kept.
+    ///
+    /// # Errors
+    /// Returns an std::io::Error if the file cannot be read.
+    pub fn load(path: &Path, capacity: usize) -> io::Result<Self> {
+        let mut history = Self::new(capacity);
+        for line in fs::read_to_string(path)?.lines() {
+            history.push(line);
         }
+        Ok(history)
+    }
+
+    /// Writes the history to a file with one query per line, creating its directory.
+    ///
+    /// # Errors
+    /// Returns an std::io::Error if the file cannot be written.
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut content = String::new();
+        for entry in &self.entries {
+            content.push_str(entry);
+            content.push('\n');
+        }
+        fs::write(path, content)
+    }
+
+    // Getters / Accessors
+
+    pub fn entries(&self) -> &VecDeque<String> {
+        &self.entries
+    }
+
+    pub fn is_browsing(&self) -> bool {
+        self.cursor.is_some()
+    }
+
+    // History functions
+
+    /// Remembers a query as the newest entry and stops browsing.
+    /// Empty queries are ignored, a repeated query moves to the newest position.
+    pub fn push(&mut self, query: &str) {
+        self.stop_browsing();
+        let query = query.trim();
+        if query.is_empty() || self.capacity == 0 {
+            return;
+        }
+        self.entries.retain(|e| e != query);
+        self.entries.push_back(query.to_string());
+        while self.entries.len() > self.capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    /// Recalls the next older query.
+    ///
+    /// # Arguments
+    /// * `current` - The current input, kept as draft when browsing starts.
+    ///
+    /// # Returns
+    /// The recalled query, or None if there is no older entry.
+    pub fn older(&mut self, current: &str) -> Option<&str> {
+        let idx = match self.cursor {
+            None if self.entries.is_empty() => return None,
+            None => {
+                self.draft = current.to_string();
+                self.entries.len() - 1
+            }
+            Some(0) => return None,
+            Some(idx) => idx - 1,
+        };
+        self.cursor = Some(idx);
+        self.entries.get(idx).map(String::as_str)
+    }
+
+    /// Recalls the next newer query, or the draft once past the newest entry.
+    ///
+    /// # Returns
+    /// The recalled query, or None if not browsing.
+    pub fn newer(&mut self) -> Option<&str> {
+        let idx = self.cursor?;
+        if idx + 1 < self.entries.len() {
+            self.cursor = Some(idx + 1);
+            self.entries.get(idx + 1).map(String::as_str)
+        } else {
+            self.cursor = None;
+            Some(&self.draft)
+        }
+    }
+
+    /// Stops browsing, keeping the recalled query as input.
+    pub fn stop_browsing(&mut self) {
+        self.cursor = None;
+        self.draft.clear();
+    }
+}
+
+impl Default for FindHistory {
+    fn default() -> Self {
+        Self::new(100)
     }
 }
 
@@ -410,6 +1088,9 @@ pub struct FindState {
     last_query: String,
     selected: usize,
     cancel: Option<Arc<AtomicBool>>,
+    kind: FindKind,
+    shown_request: u64,
+    searching: bool,
 }
 
 impl FindState {
@@ -427,8 +1108,23 @@ impl FindState {
         self.selected
     }
 
+    fn kind(&self) -> FindKind {
+        self.kind
+    }
+
+    fn is_searching(&self) -> bool {
+        self.searching
+    }
+
     // Find functions
 
+    /// Cycles the [FindKind] restricting the results and forces the query to be re-run.
+    fn cycle_kind(&mut self) {
+        self.kind = self.kind.next();
+        self.last_query.clear();
+        self.debounce = Some(Instant::now());
+    }
+
     /// Cancels the current ongoing find operation, if any.
     ///
     /// Sets the cancellation token to true.
@@ -447,6 +1143,28 @@ impl FindState {
         self.selected = 0;
     }
 
+    /// Applies a (possibly intermediate) batch of results of the current request.
+    ///
+    /// Batches of the request already shown replace the results but keep the selection,
+    /// so the list can be navigated while the search is still running.
+    ///
+    /// # Arguments
+    /// * `results` - Vector of [FindResult] to cache.
+    /// * `request_id` - The request the results belong to.
+    /// * `complete` - Whether this is the final batch of the request.
+    fn apply_results(&mut self, results: Vec<FindResult>, request_id: u64, complete: bool) {
+        if request_id == self.shown_request {
+            self.cache = results;
+            self.selected = self.selected.min(self.cache.len().saturating_sub(1));
+        } else {
+            self.set_results(results);
+            self.shown_request = request_id;
+        }
+        if complete {
+            self.searching = false;
+        }
+    }
+
     /// Sets the cancellation token for the current find operation.
     ///
     /// # Arguments
@@ -469,6 +1187,7 @@ impl FindState {
     /// * `u64` - The new request ID.
     fn prepare_new_request(&mut self) -> u64 {
         self.request_id = self.request_id.wrapping_add(1);
+        self.searching = true;
         self.request_id
     }
 
@@ -506,6 +1225,7 @@ impl FindState {
     /// Resets the find state, clearing cache, debounce, and last query.
     fn reset(&mut self) {
         self.cancel_current();
+        self.searching = false;
         self.cache.clear();
         self.debounce = None;
         self.last_query.clear();
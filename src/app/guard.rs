@@ -0,0 +1,66 @@
+//! The root guard: the guard rail of sessions running as root, see the `root_guard` option.
+//!
+//! A guarded session starts locked, which disables the same actions as read-only mode until the
+//! unlock key is pressed, shows a banner in the header and appends every file operation and
+//! command to a log file.
+
+use std::fs::{self, OpenOptions};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::Local;
+
+/// Lock state and operation log of a root session.
+///
+/// # Fields
+/// * `locked` - Whether actions changing the filesystem are disabled.
+/// * `log` - The file operations are appended to, `None` if the home directory is unknown.
+#[derive(Debug)]
+pub struct RootGuard {
+    locked: bool,
+    log: Option<PathBuf>,
+}
+
+impl RootGuard {
+    /// Starts a locked guard logging to `log`.
+    pub fn new(log: Option<PathBuf>) -> Self {
+        Self { locked: true, log }
+    }
+
+    pub fn locked(&self) -> bool {
+        self.locked
+    }
+
+    pub fn set_locked(&mut self, locked: bool) {
+        self.locked = locked;
+    }
+
+    pub fn log_path(&self) -> Option<&Path> {
+        self.log.as_deref()
+    }
+
+    /// Appends a line with the local time and `text` to the log, creating its directory.
+    ///
+    /// # Errors
+    /// Returns an std::io::Error if the log cannot be written.
+    pub fn record(&self, text: &str) -> io::Result<()> {
+        let Some(path) = &self.log else {
+            return Ok(());
+        };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+        let time = Local::now().format("%Y-%m-%d %H:%M:%S");
+        writeln!(file, "{} [{}] {}", time, std::process::id(), text)
+    }
+}
+
+/// Joins paths for a log line, separated by spaces.
+pub(super) fn join_paths(paths: &[PathBuf]) -> String {
+    paths
+        .iter()
+        .map(|p| p.display().to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -3,18 +3,30 @@
 //! This module implements [AppState] methods that process key events, file/nav actions,
 //! and input modes (rename, filter, etc).
 
-use crate::app::NavState;
 use crate::app::actions::{ActionMode, InputMode};
-use crate::app::keymap::{FileAction, NavAction};
+use crate::app::guard::join_paths;
+use crate::app::keymap::{FileAction, GotoTarget, NavAction};
 use crate::app::state::{AppState, KeypressResult};
-use crate::core::FileInfo;
-use crate::ui::overlays::Overlay;
+use crate::app::{Jump, NavState, Severity};
+use crate::config::{Config, OpenAction, Template};
+use crate::core::worker::{FileOperation, PasteConflict};
+use crate::core::{
+    FileInfo, FileType, clear_quarantine, is_binary_file, open_with_system, platform,
+};
+use crate::ui::overlays::{MarkedState, Overlay};
+use crate::ui::render::layout_metrics;
+use crate::utils::{
+    CommandPaths, copy_to_clipboard, expand_command, expand_create_input, name_stem_len,
+    open_in_pager, open_many_in_editor, open_shell, relative_path, run_shell_command,
+    run_suspended, shorten_home_path,
+};
 
-use crossterm::event::{KeyCode::*, KeyEvent};
-use std::time::{Duration, Instant};
+use crossterm::event::{KeyCode::*, KeyEvent, KeyModifiers};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// AppState input and action handlers
-impl<'a> AppState<'a> {
+impl AppState {
     // AppState core handlers
 
     /// Handles key events when in an input mode (rename, filter, etc).
@@ -28,22 +40,103 @@ impl<'a> AppState<'a> {
         } else {
             return KeypressResult::Continue;
         };
+        let is_find = matches!(mode, InputMode::Find | InputMode::Grep | InputMode::Jump);
+
+        if matches!(mode, InputMode::Find | InputMode::Grep) && key.code == Enter {
+            let query = self.actions.input_buffer().to_string();
+            self.remember_find_query(&query);
+        }
 
         match key.code {
+            // Ctrl+Enter is not reported by every terminal, so Alt+Enter works as well
+            Enter
+                if is_find
+                    && key
+                        .modifiers
+                        .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT) =>
+            {
+                self.reveal_find_result();
+                self.exit_input_mode();
+                KeypressResult::Consumed
+            }
+
+            // Marked results are opened together, in one editor invocation
+            Enter
+                if matches!(mode, InputMode::Find | InputMode::Grep)
+                    && self.nav.markers().iter().any(|p| p.is_file()) =>
+            {
+                let result = self.open_marked_files();
+                self.exit_input_mode();
+                result
+            }
+
+            Enter if mode == InputMode::Grep => {
+                let result = self.handle_grep();
+                self.exit_input_mode();
+                result
+            }
+
+            Enter if mode == InputMode::Command => {
+                let result = self.run_command();
+                self.exit_input_mode();
+                result
+            }
+
+            Enter if mode == InputMode::Navigate => self.navigate_enter(),
+
+            // Invalid names keep the dialog open, the error is shown in its title
+            Enter
+                if self
+                    .actions
+                    .name_issue()
+                    .is_some_and(|issue| issue.is_invalid()) =>
+            {
+                KeypressResult::Consumed
+            }
+
+            // Matching templates are offered before the new files are created
+            Enter if mode == InputMode::NewFile && self.prompt_template() => {
+                KeypressResult::Consumed
+            }
+
+            // The paste may ask about name collisions next, so the resume prompt closes first
+            Enter if mode == InputMode::ConfirmResume => {
+                self.exit_input_mode();
+                self.paste_clipboard(true);
+                KeypressResult::Consumed
+            }
+
             Enter => {
                 match mode {
-                    InputMode::NewFile => self.create_file(),
+                    InputMode::NewFile => self.create_file(None),
+                    InputMode::Template => self.create_from_template(),
                     InputMode::NewFolder => self.create_folder(),
                     InputMode::Rename => self.rename_entry(),
                     InputMode::Filter => self.apply_filter(),
                     InputMode::ConfirmDelete => self.confirm_delete(),
-                    InputMode::Find => self.handle_find(),
+                    InputMode::ConfirmRetry => self.retry_busy(),
+                    InputMode::ConfirmOverwrite => self.paste_with(PasteConflict::Overwrite),
+                    InputMode::Find | InputMode::Jump => self.handle_find(),
+                    InputMode::ConfirmResume
+                    | InputMode::OpenWith
+                    | InputMode::Grep
+                    | InputMode::QuickFind
+                    | InputMode::Navigate
+                    | InputMode::Command => {}
                 }
                 self.exit_input_mode();
                 KeypressResult::Consumed
             }
 
             Esc => {
+                if mode == InputMode::QuickFind
+                    && let Some(origin) = self.actions.quick_find_origin()
+                {
+                    self.select_shown_entry(origin);
+                }
+                if mode == InputMode::Navigate {
+                    self.restore_navigate_filter();
+                }
                 self.exit_input_mode();
                 KeypressResult::Consumed
             }
@@ -53,23 +146,76 @@ impl<'a> AppState<'a> {
                 KeypressResult::Consumed
             }
 
-            Up => {
-                if matches!(mode, InputMode::Find) {
+            Up => match mode {
+                InputMode::Find | InputMode::Grep => {
+                    let at_top = self.actions.find_results().is_empty()
+                        || (self.actions.find_selected() == 0
+                            && self.actions.find_history().is_browsing());
+                    if at_top {
+                        self.recall_find_query(true);
+                    } else {
+                        self.actions.find_state_mut().select_prev();
+                    }
+                    KeypressResult::Consumed
+                }
+                InputMode::Jump => {
                     self.actions.find_state_mut().select_prev();
                     KeypressResult::Consumed
-                } else {
-                    KeypressResult::Continue
                 }
-            }
+                InputMode::ConfirmDelete => {
+                    self.actions.delete_state_mut().select_prev();
+                    KeypressResult::Consumed
+                }
+                InputMode::Template => {
+                    self.actions.template_state_mut().select_prev();
+                    KeypressResult::Consumed
+                }
+                InputMode::QuickFind => {
+                    let query = self.actions.input_buffer().to_string();
+                    self.move_nav_if_possible(|nav| nav.select_next_match(&query, false));
+                    KeypressResult::Consumed
+                }
+                InputMode::Navigate => {
+                    self.move_nav_if_possible(|nav| nav.move_up());
+                    KeypressResult::Consumed
+                }
+                _ => KeypressResult::Continue,
+            },
 
-            Down => {
-                if matches!(mode, InputMode::Find) {
+            Down => match mode {
+                InputMode::Find | InputMode::Grep => {
+                    if self.actions.find_results().is_empty()
+                        && self.actions.find_history().is_browsing()
+                    {
+                        self.recall_find_query(false);
+                    } else {
+                        self.actions.find_state_mut().select_next();
+                    }
+                    KeypressResult::Consumed
+                }
+                InputMode::Jump => {
                     self.actions.find_state_mut().select_next();
                     KeypressResult::Consumed
-                } else {
-                    KeypressResult::Continue
                 }
-            }
+                InputMode::ConfirmDelete => {
+                    self.actions.delete_state_mut().select_next();
+                    KeypressResult::Consumed
+                }
+                InputMode::Template => {
+                    self.actions.template_state_mut().select_next();
+                    KeypressResult::Consumed
+                }
+                InputMode::QuickFind => {
+                    let query = self.actions.input_buffer().to_string();
+                    self.move_nav_if_possible(|nav| nav.select_next_match(&query, true));
+                    KeypressResult::Consumed
+                }
+                InputMode::Navigate => {
+                    self.move_nav_if_possible(|nav| nav.move_down());
+                    KeypressResult::Consumed
+                }
+                _ => KeypressResult::Continue,
+            },
 
             Right => {
                 self.actions.action_move_cursor_right();
@@ -86,36 +232,119 @@ impl<'a> AppState<'a> {
                 KeypressResult::Consumed
             }
 
+            // Backspace on an empty query goes up a directory, like in broot
+            Backspace if mode == InputMode::Navigate && self.actions.input_buffer().is_empty() => {
+                self.navigate_parent();
+                KeypressResult::Consumed
+            }
+
             Backspace => {
                 self.actions.action_backspace_at_cursor();
                 if matches!(mode, InputMode::Filter) {
                     self.apply_filter();
                 }
-                if matches!(mode, InputMode::Find) {
+                if matches!(mode, InputMode::QuickFind) {
+                    self.quick_find_jump();
+                }
+                if matches!(mode, InputMode::Navigate) {
+                    self.navigate_narrow();
+                }
+                if is_find {
+                    self.actions.find_history_mut().stop_browsing();
                     self.actions.find_debounce(Duration::from_millis(90));
                 }
                 KeypressResult::Consumed
             }
 
+            // The marker key only marks results if it would not be typed into the query
+            Tab if is_find => {
+                self.mark_find_result();
+                KeypressResult::Consumed
+            }
+            _ if is_find && self.is_marker_key(key) && !is_plain_char(key) => {
+                self.mark_find_result();
+                KeypressResult::Consumed
+            }
+
+            Char('a')
+                if mode == InputMode::Rename && key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.select_rename_stem();
+                KeypressResult::Consumed
+            }
+
+            Char('t')
+                if mode == InputMode::Find && key.modifiers.contains(KeyModifiers::CONTROL) =>
+            {
+                self.actions.cycle_find_kind();
+                KeypressResult::Consumed
+            }
+
             Char(c) => match mode {
                 InputMode::ConfirmDelete => {
                     self.process_confirm_delete_char(c);
                     KeypressResult::Consumed
                 }
+                InputMode::ConfirmResume => {
+                    self.process_confirm_resume_char(c);
+                    KeypressResult::Consumed
+                }
+                InputMode::ConfirmRetry => {
+                    self.process_confirm_retry_char(c);
+                    KeypressResult::Consumed
+                }
+                InputMode::ConfirmOverwrite => {
+                    self.process_confirm_overwrite_char(c);
+                    KeypressResult::Consumed
+                }
+                InputMode::OpenWith => self.process_open_with_char(c),
+                InputMode::Template => {
+                    match c {
+                        'j' => self.actions.template_state_mut().select_next(),
+                        'k' => self.actions.template_state_mut().select_prev(),
+                        _ => {}
+                    }
+                    KeypressResult::Consumed
+                }
                 InputMode::Filter => {
                     self.actions.action_insert_at_cursor(c);
                     self.apply_filter();
                     KeypressResult::Consumed
                 }
-                InputMode::Rename | InputMode::NewFile | InputMode::NewFolder => {
+                InputMode::QuickFind => {
+                    self.actions.action_insert_at_cursor(c);
+                    self.quick_find_jump();
+                    KeypressResult::Consumed
+                }
+                InputMode::Navigate => {
+                    self.actions.action_insert_at_cursor(c);
+                    self.navigate_narrow();
+                    KeypressResult::Consumed
+                }
+                InputMode::Rename
+                | InputMode::NewFile
+                | InputMode::NewFolder
+                | InputMode::Command => {
                     self.actions.action_insert_at_cursor(c);
                     KeypressResult::Consumed
                 }
                 InputMode::Find => {
+                    self.actions.find_history_mut().stop_browsing();
                     self.actions.action_insert_at_cursor(c);
                     self.actions.find_debounce(Duration::from_millis(120));
                     KeypressResult::Consumed
                 }
+                InputMode::Grep => {
+                    self.actions.find_history_mut().stop_browsing();
+                    self.actions.action_insert_at_cursor(c);
+                    self.actions.find_debounce(Duration::from_millis(200));
+                    KeypressResult::Consumed
+                }
+                InputMode::Jump => {
+                    self.actions.action_insert_at_cursor(c);
+                    self.actions.find_debounce(Duration::from_millis(90));
+                    KeypressResult::Consumed
+                }
             },
 
             _ => KeypressResult::Consumed,
@@ -140,6 +369,13 @@ impl<'a> AppState<'a> {
                 self.move_nav_if_possible(|nav| nav.move_down());
                 self.refresh_show_info_if_open();
             }
+            NavAction::GoTop => self.select_shown_entry(0),
+            NavAction::GoBottom => {
+                let len = self.nav.shown_entries_len();
+                if len > 0 {
+                    self.select_shown_entry(len - 1);
+                }
+            }
             NavAction::GoParent => {
                 let res = self.handle_go_parent();
                 self.refresh_show_info_if_open();
@@ -156,6 +392,16 @@ impl<'a> AppState<'a> {
                 self.nav.toggle_marker_advance(clipboard, marker_jump);
                 self.request_preview();
             }
+            NavAction::MarkAll => {
+                let clipboard = self.actions.clipboard_mut();
+                self.nav.mark_all(clipboard);
+                self.request_preview();
+            }
+            NavAction::InvertMarks => {
+                let clipboard = self.actions.clipboard_mut();
+                self.nav.invert_marks(clipboard);
+                self.request_preview();
+            }
             NavAction::ClearMarker => {
                 self.nav.clear_markers();
                 self.request_preview();
@@ -164,6 +410,10 @@ impl<'a> AppState<'a> {
                 self.nav.clear_filters();
                 self.request_preview();
             }
+            NavAction::ShowMarked => self.show_marked_list(),
+            NavAction::JumpBack => self.retrace_jump(true),
+            NavAction::JumpForward => self.retrace_jump(false),
+            NavAction::Goto(target) => self.goto(target),
         }
         KeypressResult::Continue
     }
@@ -177,27 +427,59 @@ impl<'a> AppState<'a> {
     /// # Returns
     /// * [KeypressResult] indicating the result of the action.
     pub fn handle_file_action(&mut self, action: FileAction) -> KeypressResult {
+        if self.readonly()
+            && let Some(name) = action.mutation()
+        {
+            self.notify_readonly(name);
+            return KeypressResult::Consumed;
+        }
         match action {
-            FileAction::Open => return self.handle_open_file(),
+            FileAction::Open => return self.handle_open_file(false),
+            FileAction::OpenWithEditor => return self.handle_open_file(true),
+            FileAction::Shell => return self.handle_open_shell(),
             FileAction::Delete => self.prompt_delete(),
             FileAction::Copy => {
                 self.actions.action_copy(&self.nav, false);
-                self.handle_timed_message(Duration::from_secs(15));
+                if let Some(clipboard) = self.actions.clipboard() {
+                    self.notify(
+                        Severity::Info,
+                        format!("Yanked files: {}", clipboard.len()),
+                        Duration::from_secs(3),
+                    );
+                }
             }
-            FileAction::Paste => {
-                let fileop_tx = self.workers.fileop_tx();
-                self.actions.action_paste(&mut self.nav, fileop_tx);
+            FileAction::CopyPath | FileAction::CopyRelativePath | FileAction::CopyName => {
+                self.copy_selected_path(action);
             }
+            FileAction::Paste => self.prompt_paste(),
             FileAction::Rename => self.prompt_rename(),
             FileAction::Create => self.prompt_create_file(),
             FileAction::CreateDirectory => self.prompt_create_folder(),
             FileAction::Filter => self.prompt_filter(),
             FileAction::ShowInfo => self.toggle_file_info(),
+            FileAction::DirSize => self.refresh_dir_sizes(),
             FileAction::Find => self.prompt_find(),
+            FileAction::QuickFind => self.prompt_quick_find(),
+            FileAction::Navigate => self.prompt_navigate(),
+            FileAction::Grep => self.enter_input_mode(InputMode::Grep, "".to_string(), None),
+            FileAction::Jump => self.prompt_jump(),
+            FileAction::Command => self.prompt_command(),
+            FileAction::ClearQuarantine => self.clear_quarantine(),
         }
         KeypressResult::Continue
     }
 
+    /// Explains that the action `name` is disabled by read-only mode or the root guard.
+    fn notify_readonly(&mut self, name: &str) {
+        let text = match self.config.keys().unlock().first() {
+            Some(key) if !self.readonly => {
+                format!("Running as root: {name} is disabled until {key} unlocks it")
+            }
+            _ => format!("Read-only mode: {name} is disabled"),
+        };
+        self.notify(Severity::Warn, text, Duration::from_secs(3));
+    }
+
     /// Enters an input mode with the given parameters.
     ///
     /// # Arguments
@@ -223,7 +505,7 @@ impl<'a> AppState<'a> {
         F: FnOnce(&mut NavState) -> bool,
     {
         if f(&mut self.nav) {
-            if self.config.display().instant_preview() {
+            if self.instant_preview {
                 self.request_preview();
             } else {
                 self.preview.mark_pending();
@@ -302,18 +584,250 @@ impl<'a> AppState<'a> {
     /// If a file is selected, attempts to open it in the configured editor.
     /// If an error occurs, prints it to stderr.
     ///
+    /// Files with an extension in `[openers]` are opened with that command instead, with the TUI
+    /// suspended until it exits. Other binary files (images, videos, PDFs..) are opened with the
+    /// OS default application, unless `open_with_system` is disabled.
+    ///
+    /// # Arguments
+    /// * `force_editor` - Always use the editor, for the open with editor action.
+    ///
     /// # Returns
     /// * [KeypressResult] indicating the result of the action.
-    fn handle_open_file(&mut self) -> KeypressResult {
-        if let Some(entry) = self.nav.selected_shown_entry() {
-            let path = self.nav.current_dir().join(entry.name());
-            if let Err(e) = crate::utils::open_in_editor(self.config.editor(), &path) {
-                eprintln!("Error: {}", e);
+    fn handle_open_file(&mut self, force_editor: bool) -> KeypressResult {
+        let Some(entry) = self.nav.selected_shown_entry() else {
+            return KeypressResult::Continue;
+        };
+        let path = self.nav.current_dir().join(entry.name());
+        let is_dir = entry.is_dir();
+        self.stats.files_opened += 1;
+        if !is_dir {
+            self.audit(format!("open {}", path.display()));
+        }
+        if force_editor || is_dir {
+            return self.open_file_with(OpenAction::Editor, &path);
+        }
+        if let Some(opener) = self.config.openers().get(&path) {
+            let cmd = expand_command(
+                opener,
+                &CommandPaths {
+                    dir: self.nav.current_dir(),
+                    selected: Some(&path),
+                    marked: &[],
+                },
+            );
+            match run_suspended(&cmd, self.nav.current_dir()) {
+                Ok(status) if !status.success() => self.notify(
+                    Severity::Warn,
+                    format!("Opener exited with {}", status),
+                    Duration::from_secs(5),
+                ),
+                Ok(_) => {}
+                Err(e) => self.notify(
+                    Severity::Error,
+                    format!("Could not run opener: {}", e),
+                    Duration::from_secs(5),
+                ),
             }
-            KeypressResult::OpenedEditor
-        } else {
-            KeypressResult::Continue
+            return KeypressResult::OpenedEditor;
         }
+        let action = self.enter_action(&path);
+        self.open_file_with(action, &path)
+    }
+
+    /// The [OpenAction] of Enter for `path`, from the `[open.enter]` table.
+    ///
+    /// Files without a configured action open in the editor, binary files with the OS
+    /// default application if `open_with_system` is on.
+    fn enter_action(&self, path: &Path) -> OpenAction {
+        let executable = std::fs::metadata(path).is_ok_and(|meta| platform::is_executable(&meta));
+        self.config
+            .open()
+            .enter()
+            .get(path, executable, || is_binary_file(path))
+            .unwrap_or_else(|| {
+                if self.config.editor().open_with_system() && is_binary_file(path) {
+                    OpenAction::System
+                } else {
+                    OpenAction::Editor
+                }
+            })
+    }
+
+    /// Opens `path` in the editor, with the OS default application or in the pager,
+    /// or asks which of them with the open-with prompt.
+    ///
+    /// # Returns
+    /// * [KeypressResult] indicating the result of the action.
+    fn open_file_with(&mut self, action: OpenAction, path: &Path) -> KeypressResult {
+        match action {
+            OpenAction::Editor => {
+                if let Err(e) = crate::utils::open_in_editor(self.config.editor(), path) {
+                    eprintln!("Error: {}", e);
+                }
+                KeypressResult::OpenedEditor
+            }
+            OpenAction::System => {
+                if let Err(e) = open_with_system(path) {
+                    self.notify(
+                        Severity::Error,
+                        format!("Could not open with the system default: {}", e),
+                        Duration::from_secs(5),
+                    );
+                }
+                KeypressResult::Consumed
+            }
+            OpenAction::Preview => {
+                if let Err(e) = open_in_pager(path, self.nav.current_dir()) {
+                    self.notify(
+                        Severity::Error,
+                        format!("Could not run the pager: {}", e),
+                        Duration::from_secs(5),
+                    );
+                }
+                KeypressResult::OpenedEditor
+            }
+            OpenAction::Prompt => {
+                let name = path.file_name().unwrap_or_default().to_string_lossy();
+                let prompt = format!("Open {}: [E]ditor [S]ystem [P]review", name);
+                self.enter_input_mode(InputMode::OpenWith, prompt, None);
+                KeypressResult::Consumed
+            }
+        }
+    }
+
+    /// Handles the open shell action.
+    ///
+    /// Suspends the TUI for an interactive shell in the current directory and
+    /// reloads the directory once the shell exits, as it may have been changed.
+    ///
+    /// # Returns
+    /// * [KeypressResult] indicating the result of the action.
+    fn handle_open_shell(&mut self) -> KeypressResult {
+        self.audit(format!("shell in {}", self.nav.current_dir().display()));
+        if let Err(e) = open_shell(self.nav.current_dir()) {
+            eprintln!("Error: {}", e);
+        }
+        let focus = self
+            .nav
+            .selected_shown_entry()
+            .map(|entry| entry.name().to_os_string());
+        self.request_dir_load(focus);
+        self.request_parent_content();
+        KeypressResult::OpenedEditor
+    }
+
+    /// Handles the grep action.
+    ///
+    /// Opens the selected grep result in the configured editor at the matching line.
+    ///
+    /// # Returns
+    /// * [KeypressResult] indicating the result of the action.
+    fn handle_grep(&mut self) -> KeypressResult {
+        let Some(r) = self
+            .actions
+            .find_results()
+            .get(self.actions.find_selected())
+        else {
+            return KeypressResult::Consumed;
+        };
+        let line = r.content().map(|c| c.line());
+        self.stats.files_opened += 1;
+        if let Err(e) = crate::utils::open_in_editor_at(self.config.editor(), r.path(), line) {
+            eprintln!("Error: {}", e);
+        }
+        KeypressResult::OpenedEditor
+    }
+
+    /// Opens all marked files in one editor invocation, sorted by path.
+    ///
+    /// Grep results open without their line, as the editor takes only one.
+    /// The files are unmarked afterwards like the targets of an operation.
+    fn open_marked_files(&mut self) -> KeypressResult {
+        let mut files: Vec<PathBuf> = self
+            .nav
+            .markers()
+            .iter()
+            .filter(|p| p.is_file())
+            .cloned()
+            .collect();
+        files.sort();
+        self.stats.files_opened += files.len() as u64;
+        self.audit(format!("open {}", join_paths(&files)));
+        if let Err(e) = open_many_in_editor(self.config.editor(), &files, self.nav.current_dir()) {
+            self.notify(
+                Severity::Error,
+                format!("Could not run the editor: {}", e),
+                Duration::from_secs(5),
+            );
+        }
+        self.nav.finish_operation(&files);
+        KeypressResult::OpenedEditor
+    }
+
+    /// Runs the shell command in the input buffer in the current directory.
+    ///
+    /// # Returns
+    /// * [KeypressResult] indicating the result of the action.
+    fn run_command(&mut self) -> KeypressResult {
+        let template = self.actions.input_buffer().trim().to_string();
+        if template.is_empty() {
+            return KeypressResult::Consumed;
+        }
+        self.run_command_template(&template)
+    }
+
+    /// Runs a custom command of the `[commands]` config.
+    ///
+    /// # Arguments
+    /// * `idx` - Index of the command in [Commands](crate::config::Commands).
+    ///
+    /// # Returns
+    /// * [KeypressResult] indicating the result of the action.
+    pub fn handle_custom_command(&mut self, idx: usize) -> KeypressResult {
+        if self.readonly() {
+            self.notify_readonly("running commands");
+            return KeypressResult::Consumed;
+        }
+        let Some(template) = self
+            .config
+            .commands()
+            .get(idx)
+            .map(|c| c.template().to_owned())
+        else {
+            return KeypressResult::Continue;
+        };
+        self.run_command_template(&template)
+    }
+
+    /// Expands the placeholders of a command template (see [expand_command]) and runs it
+    /// in the current directory.
+    ///
+    /// The directory is reloaded afterwards, as the command may have changed it.
+    fn run_command_template(&mut self, template: &str) -> KeypressResult {
+        let dir = self.nav.current_dir();
+        let selected = self.nav.selected_shown_entry().map(|e| dir.join(e.name()));
+        let mut marked: Vec<PathBuf> = self.nav.markers().iter().cloned().collect();
+        marked.sort();
+        let cmd = expand_command(
+            template,
+            &CommandPaths {
+                dir,
+                selected: selected.as_deref(),
+                marked: &marked,
+            },
+        );
+        self.audit(format!("command in {}: {}", dir.display(), cmd));
+        if let Err(e) = run_shell_command(&cmd, dir) {
+            eprintln!("Error: {}", e);
+        }
+
+        let focus = self
+            .nav
+            .selected_shown_entry()
+            .map(|entry| entry.name().to_os_string());
+        self.request_dir_load(focus);
+        self.request_parent_content();
+        KeypressResult::OpenedEditor
     }
 
     /// Handles the find action.
@@ -329,48 +843,177 @@ impl<'a> AppState<'a> {
         else {
             return;
         };
-        let path = r.path();
+        let path = r.path().to_path_buf();
         let is_dir = path.is_dir();
 
         if is_dir {
+            self.record_jump();
             self.nav.save_position();
-            self.nav.set_path(path.to_path_buf());
+            self.nav.set_path(path);
             self.request_dir_load(None);
             self.request_parent_content();
             return;
         }
 
+        self.reveal_find_result();
+    }
+
+    /// Replaces the find input with an older (`older == true`) or newer query of the history
+    /// and searches for it right away.
+    fn recall_find_query(&mut self, older: bool) {
+        let current = self.actions.input_buffer().to_string();
+        let history = self.actions.find_history_mut();
+        let recalled = if older {
+            history.older(&current)
+        } else {
+            history.newer()
+        };
+        if let Some(query) = recalled.map(str::to_string) {
+            self.actions.set_input(query);
+            self.actions.find_debounce(Duration::ZERO);
+        }
+    }
+
+    /// Toggles the marker of the selected find or grep result and selects the next result.
+    ///
+    /// Results are marked by their full path, so results from several subdirectories
+    /// can be collected and used as targets of one copy or delete action.
+    fn mark_find_result(&mut self) {
+        let selected = self.actions.find_selected();
+        let Some(path) = self
+            .actions
+            .find_results()
+            .get(selected)
+            .map(|r| r.path().to_path_buf())
+        else {
+            return;
+        };
+        let clipboard = self.actions.clipboard_mut();
+        self.nav.toggle_marker_path(path, clipboard);
+        self.actions.find_state_mut().select_next();
+        self.request_preview();
+    }
+
+    /// Reveals the selected find or grep result in its parent directory.
+    ///
+    /// Navigates to the directory containing the result and focuses the result,
+    /// for files and directories alike.
+    fn reveal_find_result(&mut self) {
+        let Some(r) = self
+            .actions
+            .find_results()
+            .get(self.actions.find_selected())
+        else {
+            return;
+        };
+        let path = r.path();
         let Some(parent) = path.parent() else {
             return;
         };
+        let parent = parent.to_path_buf();
         let focus = path.file_name().map(|n| n.to_os_string());
 
+        self.record_jump();
         self.nav.save_position();
-        self.nav.set_path(parent.to_path_buf());
+        self.nav.set_path(parent);
         self.request_dir_load(focus);
         self.request_parent_content();
     }
 
-    /// Handles displaying a timed message overlay.
+    // Input processes
+
+    /// Processes a character input for the confirm delete input mode.
     ///
+    /// `y` confirms, space toggles the exclusion of the selected target and `j`/`k` move
+    /// the selection. Any other character cancels the deletion.
     /// # Arguments
-    /// * `duration` - The duration for which the message should be displayed.
-    pub fn handle_timed_message(&mut self, duration: Duration) {
-        self.notification_time = Some(Instant::now() + duration);
+    /// * `c` - The character input to process.
+    pub fn process_confirm_delete_char(&mut self, c: char) {
+        match c {
+            ' ' => {
+                self.actions.delete_state_mut().toggle_selected();
+                let state = self.actions.delete_state();
+                let prompt = delete_prompt(state.included_count(), state.targets().len());
+                self.actions.set_prompt(prompt);
+            }
+            'j' => self.actions.delete_state_mut().select_next(),
+            'k' => self.actions.delete_state_mut().select_prev(),
+            'y' | 'Y' => {
+                self.confirm_delete();
+                self.exit_input_mode();
+            }
+            _ => self.exit_input_mode(),
+        }
     }
 
-    // Input processes
+    /// Processes a character input for the confirm resume input mode.
+    ///
+    /// `y` resumes the interrupted copies, `n` pastes them as new copies instead.
+    /// # Arguments
+    /// * `c` - The character input to process.
+    pub fn process_confirm_resume_char(&mut self, c: char) {
+        let resume = match c {
+            'y' | 'Y' => true,
+            'n' | 'N' => false,
+            _ => return,
+        };
+        // The paste may ask about name collisions next
+        self.exit_input_mode();
+        self.paste_clipboard(resume);
+    }
 
-    /// Processes a character input for the confirm delete input mode.
+    /// Processes a character input for the prompt about names taken on paste.
+    ///
+    /// `y`/`Y` overwrites the existing entries, `r`/`R` pastes under unused names and
+    /// `s`/`S` skips the colliding entries. Other characters are ignored.
     /// # Arguments
     /// * `c` - The character input to process.
-    pub fn process_confirm_delete_char(&mut self, c: char) {
-        if matches!(c, 'y' | 'Y') {
-            self.confirm_delete();
+    pub fn process_confirm_overwrite_char(&mut self, c: char) {
+        let conflict = match c {
+            'y' | 'Y' => PasteConflict::Overwrite,
+            'r' | 'R' => PasteConflict::Rename,
+            's' | 'S' => PasteConflict::Skip,
+            _ => return,
+        };
+        self.paste_with(conflict);
+        self.exit_input_mode();
+    }
+
+    /// Processes a character input for the retry prompt of files in use.
+    ///
+    /// `y`/`Y` retries the operation, `n`/`N` gives up. Other characters are ignored.
+    /// # Arguments
+    /// * `c` - The character input to process.
+    pub fn process_confirm_retry_char(&mut self, c: char) {
+        match c {
+            'y' | 'Y' => self.retry_busy(),
+            'n' | 'N' => {}
+            _ => return,
         }
         self.exit_input_mode();
     }
 
+    /// Processes a character input for the open-with prompt.
+    ///
+    /// `e`/`E` opens the selected file in the editor, `s`/`S` with the OS default application
+    /// and `p`/`P` in the pager. Other characters are ignored.
+    /// # Arguments
+    /// * `c` - The character input to process.
+    pub fn process_open_with_char(&mut self, c: char) -> KeypressResult {
+        let action = match c {
+            'e' | 'E' => OpenAction::Editor,
+            's' | 'S' => OpenAction::System,
+            'p' | 'P' => OpenAction::Preview,
+            _ => return KeypressResult::Consumed,
+        };
+        self.exit_input_mode();
+        let Some(entry) = self.nav.selected_shown_entry() else {
+            return KeypressResult::Consumed;
+        };
+        let path = self.nav.current_dir().join(entry.name());
+        self.open_file_with(action, &path)
+    }
+
     /// Exits the current input mode.
     /// Simple wrapper around actions::exit_mode.
     pub fn exit_input_mode(&mut self) {
@@ -379,25 +1022,56 @@ impl<'a> AppState<'a> {
 
     /// Creates a new file with the name in the input buffer.
     /// Calls actions::action_create with `is_folder` set to false.
-    fn create_file(&mut self) {
-        if !self.actions.input_buffer().is_empty() {
-            let fileop_tx = self.workers.fileop_tx();
-            self.actions.action_create(&mut self.nav, false, fileop_tx);
-        }
+    fn create_file(&mut self, template: Option<Template>) {
+        self.audit_create();
+        let fileop_tx = self.workers.fileop_tx();
+        self.actions.action_create(
+            &mut self.nav,
+            false,
+            fileop_tx,
+            self.config.create_file_mode(),
+            self.config.create_dir_mode(),
+            template,
+        );
+    }
+
+    /// Creates the new files of the template prompt, filled with the selected template.
+    fn create_from_template(&mut self) {
+        let state = self.actions.template_state_mut();
+        let template = state
+            .selected_template()
+            .and_then(|name| self.config.templates().get(name))
+            .cloned();
+        let input = state.take_input();
+        self.actions.set_input(input);
+        self.create_file(template);
     }
 
     /// Creates a new folder with the name in the input buffer.
     /// Calls actions::action_create with `is_folder` set to true.
     fn create_folder(&mut self) {
-        if !self.actions.input_buffer().is_empty() {
-            let fileop_tx = self.workers.fileop_tx();
-            self.actions.action_create(&mut self.nav, true, fileop_tx);
-        }
+        self.audit_create();
+        let fileop_tx = self.workers.fileop_tx();
+        self.actions.action_create(
+            &mut self.nav,
+            true,
+            fileop_tx,
+            self.config.create_file_mode(),
+            self.config.create_dir_mode(),
+            None,
+        );
     }
 
     /// Renames the selected entry to the name in the input buffer.
     /// Calls actions::action_rename.
     fn rename_entry(&mut self) {
+        if let Some(entry) = self.nav.selected_entry()
+            && !self.actions.input_buffer().is_empty()
+        {
+            let old = self.nav.current_dir().join(entry.name());
+            let new = old.with_file_name(self.actions.input_buffer());
+            self.audit(format!("rename {} -> {}", old.display(), new.display()));
+        }
         let fileop_tx = self.workers.fileop_tx();
         self.actions.action_rename(&mut self.nav, fileop_tx);
     }
@@ -409,9 +1083,76 @@ impl<'a> AppState<'a> {
         self.request_preview();
     }
 
+    /// Pastes the clipboard into the current directory.
+    ///
+    /// With `paste_conflict = "ask"`, names already taken in the current directory are
+    /// listed for confirmation first.
+    fn paste_clipboard(&mut self, resume: bool) {
+        let conflict = self.config.paste_conflict();
+        if conflict == PasteConflict::Ask {
+            let count = self.actions.collect_paste_conflicts(&self.nav, resume);
+            if count > 0 {
+                let prompt_text = format!(
+                    "{} name{} already taken. [Y]overwrite [R]ename [S]kip",
+                    count,
+                    if count > 1 { "s" } else { "" }
+                );
+                self.enter_input_mode(InputMode::ConfirmOverwrite, prompt_text, None);
+                return;
+            }
+        }
+        self.send_paste(resume, conflict);
+    }
+
+    /// Pastes after the prompt about names taken, with the answer for all of them.
+    fn paste_with(&mut self, conflict: PasteConflict) {
+        let resume = self.actions.resume_paste();
+        self.send_paste(resume, conflict);
+    }
+
+    /// Calls actions::action_paste.
+    fn send_paste(&mut self, resume: bool, conflict: PasteConflict) {
+        if let Some(clipboard) = self.actions.clipboard() {
+            let mut src: Vec<PathBuf> = clipboard.iter().cloned().collect();
+            src.sort();
+            let verb = if self.actions.is_cut() {
+                "move"
+            } else {
+                "paste"
+            };
+            self.audit(format!(
+                "{} {} -> {}",
+                verb,
+                join_paths(&src),
+                self.nav.current_dir().display()
+            ));
+        }
+        let fileop_tx = self.workers.fileop_tx();
+        let max_bytes_per_sec = self.config.max_copy_bytes_per_sec();
+        self.actions.action_paste(
+            &mut self.nav,
+            fileop_tx,
+            resume,
+            max_bytes_per_sec,
+            conflict,
+        );
+    }
+
+    /// Retries the delete or rename of files in use by another process.
+    /// Calls actions::action_retry_busy.
+    fn retry_busy(&mut self) {
+        self.audit(format!("retry {}", join_paths(self.actions.busy_targets())));
+        let fileop_tx = self.workers.fileop_tx();
+        self.actions.action_retry_busy(&mut self.nav, fileop_tx);
+    }
+
     /// Confirms deletion of the selected items.
     /// Calls actions::action_delete.
     fn confirm_delete(&mut self) {
+        let targets = self.actions.delete_state().included();
+        if !targets.is_empty() {
+            self.audit(format!("delete {}", join_paths(&targets)));
+        }
         let fileop_tx = self.workers.fileop_tx();
         self.actions.action_delete(&mut self.nav, fileop_tx);
     }
@@ -419,27 +1160,317 @@ impl<'a> AppState<'a> {
     // Prompt functions
 
     /// Prompts the user to confirm deletion of selected items.
+    ///
+    /// The targets are kept in the [DeleteState](crate::app::actions::DeleteState),
+    /// so single items can be excluded before confirming.
     fn prompt_delete(&mut self) {
         let targets = self.nav.get_action_targets();
         if targets.is_empty() {
             return;
         }
+        let prompt_text = delete_prompt(targets.len(), targets.len());
+        self.enter_input_mode(InputMode::ConfirmDelete, prompt_text, None);
+        self.actions.delete_state_mut().set_targets(targets);
+    }
+
+    /// Offers to retry a delete or rename which failed because another process has the files open.
+    ///
+    /// While another dialog is open, the files in use are only reported in the status line.
+    pub(super) fn prompt_busy_retry(&mut self, op: FileOperation) {
+        let names: Vec<String> = match &op {
+            FileOperation::Delete(paths) => paths.iter().collect::<Vec<_>>(),
+            FileOperation::Rename { old, .. } => vec![old],
+            _ => return,
+        }
+        .into_iter()
+        .map(|p| {
+            p.file_name()
+                .unwrap_or_default()
+                .to_string_lossy()
+                .into_owned()
+        })
+        .collect();
+        let subject = match names.as_slice() {
+            [name] => format!("'{}' is", name),
+            _ => format!("{} files are", names.len()),
+        };
+        if self.actions.is_input_mode() {
+            self.notify(
+                Severity::Warn,
+                format!("{} in use by another process", subject),
+                Duration::from_secs(4),
+            );
+            return;
+        }
+        let prompt_text = format!("{} in use by another process. Retry? [Y/N]", subject);
+        self.enter_input_mode(InputMode::ConfirmRetry, prompt_text, None);
+        self.actions.set_busy_retry(op);
+    }
+
+    /// Offers the templates matching the files of the new-file prompt, instead of creating
+    /// them right away.
+    ///
+    /// # Returns
+    /// Whether the template prompt was opened.
+    fn prompt_template(&mut self) -> bool {
+        let templates = self.config.templates();
+        if templates.is_empty() {
+            return false;
+        }
+        let input = self.actions.input_buffer().to_string();
+        let (names, _) = expand_create_input(&input);
+        let matching = templates.matching(
+            names
+                .iter()
+                .filter(|name| !name.ends_with('/'))
+                .map(|name| name.rsplit('/').next().unwrap_or(name)),
+        );
+        if matching.is_empty() {
+            return false;
+        }
+        self.enter_input_mode(InputMode::Template, "Template".to_string(), None);
+        self.actions.template_state_mut().set(input, matching);
+        true
+    }
+
+    /// Opens the overview of all marked paths, across every directory.
+    fn show_marked_list(&mut self) {
+        if self.nav.markers().is_empty() {
+            self.notify(
+                Severity::Info,
+                "No marked entries".to_string(),
+                Duration::from_secs(2),
+            );
+            return;
+        }
+        let marked = MarkedState::new(self.nav.markers().iter().cloned());
+        self.overlays_mut().push(Overlay::MarkedList { marked });
+    }
+
+    /// Handles key events while the marked overview is the topmost overlay.
+    ///
+    /// Enter jumps to the selected path, Space or `u` unmarks it, Up/Down or `j`/`k` move
+    /// the selection and Esc or `q` closes the overview. Other keys are swallowed.
+    ///
+    /// # Returns
+    /// [KeypressResult::Continue] if the overview is not open, else [KeypressResult::Consumed].
+    pub fn handle_marked_list(&mut self, key: KeyEvent) -> KeypressResult {
+        let Some(marked) = self.marked_list_mut() else {
+            return KeypressResult::Continue;
+        };
+        match key.code {
+            Up | Char('k') => marked.select_prev(),
+            Down | Char('j') => marked.select_next(),
+            Char(' ') | Char('u') => self.unmark_selected_marked(),
+            Enter => {
+                let selected = marked.selected_path().map(Path::to_path_buf);
+                self.close_marked_list();
+                if let Some(path) = selected {
+                    self.jump_to_marked(&path);
+                }
+            }
+            Esc | Char('q') => self.close_marked_list(),
+            _ => {}
+        }
+        KeypressResult::Consumed
+    }
+
+    fn marked_list_mut(&mut self) -> Option<&mut MarkedState> {
+        match self.overlays_mut().top_mut() {
+            Some(Overlay::MarkedList { marked }) => Some(marked),
+            _ => None,
+        }
+    }
+
+    fn close_marked_list(&mut self) {
+        self.overlays_mut()
+            .retain(|o| !matches!(o, Overlay::MarkedList { .. }));
+    }
+
+    /// Removes the marker of the path selected in the marked overview.
+    /// Closes the overview once nothing is marked anymore.
+    fn unmark_selected_marked(&mut self) {
+        let Some(marked) = self.marked_list_mut() else {
+            return;
+        };
+        let Some(path) = marked.remove_selected() else {
+            return;
+        };
+        let remaining = marked.targets().len();
+        let clipboard = self.actions.clipboard_mut();
+        self.nav.toggle_marker_path(path, clipboard);
+        self.request_preview();
+
+        if remaining == 0 {
+            self.close_marked_list();
+        }
+    }
+
+    /// Navigates to the directory of the marked `path` and focuses it.
+    fn jump_to_marked(&mut self, path: &Path) {
+        let Some(parent) = path.parent() else {
+            return;
+        };
+        let parent = parent.to_path_buf();
+        let focus = path.file_name().map(|n| n.to_os_string());
+
+        self.record_jump();
+        self.nav.save_position();
+        self.nav.set_path(parent);
+        self.request_dir_load(focus);
+        self.request_parent_content();
+    }
+
+    /// The current directory and selected entry, as recorded in the [JumpList](crate::app::JumpList).
+    fn current_location(&self) -> Jump {
+        Jump::new(
+            self.nav.current_dir().to_path_buf(),
+            self.nav.selected_entry().map(|e| e.name().to_os_string()),
+        )
+    }
+
+    /// Records the current location before a significant jump.
+    fn record_jump(&mut self) {
+        let location = self.current_location();
+        self.jumps.push(location);
+    }
+
+    /// Goes back (`back == true`) or forward in the jump list, restoring the selected entry.
+    fn retrace_jump(&mut self, back: bool) {
+        let current = self.current_location();
+        let target = if back {
+            self.jumps.back(current)
+        } else {
+            self.jumps.forward(current)
+        };
+        let Some(target) = target else {
+            return;
+        };
+        if !target.dir().is_dir() {
+            self.notify(
+                Severity::Warn,
+                format!("{} no longer exists", shorten_home_path(target.dir())),
+                Duration::from_secs(3),
+            );
+            return;
+        }
+
+        let focus = target.name().cloned();
+        self.nav.save_position();
+        self.nav.set_path(target.dir().to_path_buf());
+        self.request_dir_load(focus);
+        self.request_parent_content();
+    }
+
+    /// Goes to the directory of a goto shortcut, recorded in the jump list like other jumps.
+    ///
+    /// The root is the root of the current directory, e.g. `C:\` on Windows.
+    fn goto(&mut self, target: GotoTarget) {
+        let dir = match target {
+            GotoTarget::Home => dirs::home_dir(),
+            GotoTarget::Root => self.nav.current_dir().ancestors().last().map(PathBuf::from),
+            GotoTarget::ConfigDir => Config::default_path().parent().map(PathBuf::from),
+            GotoTarget::Custom(idx) => self.config.goto().get(idx).map(|entry| entry.path()),
+        };
+        let Some(dir) = dir else {
+            return;
+        };
+        if !dir.is_dir() {
+            self.notify(
+                Severity::Warn,
+                format!("{} is not a directory", shorten_home_path(&dir)),
+                Duration::from_secs(3),
+            );
+            return;
+        }
+        if dir == self.nav.current_dir() {
+            return;
+        }
+
+        self.record_jump();
+        self.nav.save_position();
+        self.nav.set_path(dir);
+        self.request_dir_load(None);
+        self.request_parent_content();
+    }
+
+    /// Copies the absolute path, the relative path or the name of the selected entry
+    /// to the system clipboard and confirms it in the status line.
+    fn copy_selected_path(&mut self, action: FileAction) {
+        let Some(entry) = self.nav.selected_entry() else {
+            return;
+        };
+        let path = self.nav.current_dir().join(entry.name());
+        let text = match action {
+            FileAction::CopyRelativePath => relative_path(&path, &self.start_dir)
+                .to_string_lossy()
+                .into_owned(),
+            FileAction::CopyName => entry.name_str().to_string(),
+            _ => path.to_string_lossy().into_owned(),
+        };
+        match copy_to_clipboard(&text, self.config.clipboard()) {
+            Ok(()) => self.notify(
+                Severity::Info,
+                format!("Copied: {}", text),
+                Duration::from_secs(3),
+            ),
+            Err(e) => self.notify(
+                Severity::Error,
+                format!("Copy to clipboard failed: {}", e),
+                Duration::from_secs(5),
+            ),
+        }
+    }
+
+    /// Pastes the clipboard, asking first if interrupted copies of it can be resumed.
+    fn prompt_paste(&mut self) {
+        let count = self.actions.collect_resume_targets(&self.nav);
+        if count == 0 {
+            self.paste_clipboard(false);
+            return;
+        }
         let prompt_text = format!(
-            "Delete {} item{}? [Y/N]",
-            targets.len(),
-            if targets.len() > 1 { "s" } else { "" }
+            "Resume {} interrupted cop{}? [Y/N]",
+            count,
+            if count > 1 { "ies" } else { "y" }
         );
-        self.enter_input_mode(InputMode::ConfirmDelete, prompt_text, None);
+        self.enter_input_mode(InputMode::ConfirmResume, prompt_text, None);
     }
 
     /// Prompts the user to rename the selected entry.
+    ///
+    /// The cursor starts before the extension of files, so typing changes the stem.
     fn prompt_rename(&mut self) {
         if let Some(entry) = self.nav.selected_shown_entry() {
             let name = entry.name().to_string_lossy().to_string();
+            let stem_end = if entry.is_dir() {
+                name.len()
+            } else {
+                name_stem_len(&name)
+            };
             self.enter_input_mode(InputMode::Rename, "Rename: ".to_string(), Some(name));
+            self.actions.select_input(stem_end..stem_end);
         }
     }
 
+    /// Selects the stem of the name in the rename dialog, or the whole name if the stem
+    /// is already selected, so typing replaces it.
+    fn select_rename_stem(&mut self) {
+        let name = self.actions.input_buffer();
+        let is_dir = self.nav.selected_shown_entry().is_some_and(|e| e.is_dir());
+        let stem_end = if is_dir {
+            name.len()
+        } else {
+            name_stem_len(name)
+        };
+        let range = if self.actions.input_selection() == Some(0..stem_end) {
+            0..name.len()
+        } else {
+            0..stem_end
+        };
+        self.actions.select_input(range);
+    }
+
     /// Prompts the user to create a new file.
     fn prompt_create_file(&mut self) {
         self.enter_input_mode(InputMode::NewFile, "New File: ".to_string(), None);
@@ -460,12 +1491,153 @@ impl<'a> AppState<'a> {
         );
     }
 
+    /// Opens the quick find, which jumps to entries of the current listing as the query is typed.
+    fn prompt_quick_find(&mut self) {
+        let origin = self.nav.selected_idx();
+        self.enter_input_mode(InputMode::QuickFind, "Quick Find: ".to_string(), None);
+        self.actions.set_quick_find_origin(origin);
+    }
+
+    /// Selects the best match of the quick find query in the current listing.
+    /// Without a query the selection returns to where the quick find was opened.
+    fn quick_find_jump(&mut self) {
+        let query = self.actions.input_buffer();
+        let target = if query.is_empty() {
+            self.actions.quick_find_origin()
+        } else {
+            self.nav.best_match(query)
+        };
+        if let Some(idx) = target {
+            self.select_shown_entry(idx);
+        }
+    }
+
+    /// Opens the navigation, where typing narrows the listing with a fuzzy filter and Enter
+    /// descends into the best match, clearing the query for the next directory.
+    fn prompt_navigate(&mut self) {
+        let filter = self.nav.filter().to_string();
+        self.enter_input_mode(InputMode::Navigate, "Navigate: ".to_string(), None);
+        self.actions.set_navigate_filter(filter);
+    }
+
+    /// Narrows the listing to the fuzzy matches of the navigation query and selects the best.
+    /// Without a query the filter the directory had before is shown again.
+    fn navigate_narrow(&mut self) {
+        let query = self.actions.input_buffer();
+        let filter = if query.is_empty() {
+            self.actions
+                .navigate_filter()
+                .unwrap_or_default()
+                .to_string()
+        } else {
+            format!("/fuzzy:{}", query)
+        };
+        self.nav.set_filter(filter);
+        self.select_navigate_match();
+        self.request_preview();
+    }
+
+    /// Selects the best match of the navigation query, also once a directory entered while
+    /// typing has loaded.
+    pub(super) fn select_navigate_match(&mut self) {
+        if let ActionMode::Input {
+            mode: InputMode::Navigate,
+            ..
+        } = self.actions.mode()
+            && let Some(idx) = self.nav.best_match(self.actions.input_buffer())
+        {
+            self.select_shown_entry(idx);
+        }
+    }
+
+    /// Puts back the filter the current directory had before the navigation narrowed it.
+    fn restore_navigate_filter(&mut self) {
+        if let Some(filter) = self.actions.navigate_filter() {
+            let filter = filter.to_string();
+            self.nav.set_filter(filter);
+        }
+    }
+
+    /// Descends into the selected directory and clears the query, or opens the selected file
+    /// and ends the navigation.
+    fn navigate_enter(&mut self) -> KeypressResult {
+        let dir = self.nav.current_dir().to_path_buf();
+        let filter = self
+            .actions
+            .navigate_filter()
+            .unwrap_or_default()
+            .to_string();
+        self.handle_go_into_dir();
+        if self.nav.current_dir() == dir {
+            let result = if self.nav.selected_shown_entry().is_some() {
+                self.handle_open_file(false)
+            } else {
+                KeypressResult::Consumed
+            };
+            self.restore_navigate_filter();
+            self.exit_input_mode();
+            return result;
+        }
+        self.nav.set_saved_filter(&dir, filter);
+        self.continue_navigate();
+        KeypressResult::Consumed
+    }
+
+    /// Goes up a directory, keeping the navigation open.
+    fn navigate_parent(&mut self) {
+        let dir = self.nav.current_dir().to_path_buf();
+        self.restore_navigate_filter();
+        self.handle_go_parent();
+        if self.nav.current_dir() != dir {
+            self.continue_navigate();
+        }
+    }
+
+    /// Starts over with an empty query in the directory just entered.
+    fn continue_navigate(&mut self) {
+        let filter = self.nav.filter().to_string();
+        self.actions.set_input(String::new());
+        self.actions.set_navigate_filter(filter);
+        self.refresh_show_info_if_open();
+    }
+
+    /// Selects the shown entry at `idx`, refreshing the preview if the selection changed.
+    fn select_shown_entry(&mut self, idx: usize) {
+        self.move_nav_if_possible(|nav| {
+            let changed = nav.selected_idx() != idx;
+            nav.set_selected(idx);
+            changed
+        });
+        self.refresh_show_info_if_open();
+    }
+
+    /// Prompts the user to enter a shell command.
+    fn prompt_command(&mut self) {
+        self.enter_input_mode(InputMode::Command, "Command: ".to_string(), None);
+    }
+
+    /// Opens the jump dialog, listing the directories known to zoxide.
+    /// If `zoxide` is not found, displays a temporary overlay message.
+    fn prompt_jump(&mut self) {
+        if which::which("zoxide").is_err() {
+            self.notify(
+                Severity::Warn,
+                "Jumping requires the `zoxide` tool.".to_string(),
+                Duration::from_secs(5),
+            );
+            return;
+        }
+        self.enter_input_mode(InputMode::Jump, "".to_string(), None);
+        self.request_jump(String::new());
+    }
+
     /// Prompts the user to enter a fuzzy find query.
     /// Requires the `fd` tool to be installed.
     /// If `fd` is not found, displays a temporary overlay message.
     fn prompt_find(&mut self) {
         if which::which("fd").is_err() {
-            self.push_overlay_message(
+            self.notify(
+                Severity::Warn,
                 "Fuzzy Find requires the `fd` tool.".to_string(),
                 Duration::from_secs(5),
             );
@@ -474,6 +1646,15 @@ impl<'a> AppState<'a> {
         self.enter_input_mode(InputMode::Find, "".to_string(), None);
     }
 
+    /// Opens the find dialog with `query` already typed and searched for, used by `rn find`.
+    pub fn start_find(&mut self, query: String) {
+        self.prompt_find();
+        if self.actions.is_input_mode() && !query.is_empty() {
+            self.enter_input_mode(InputMode::Find, String::new(), Some(query));
+            self.actions.find_debounce(Duration::ZERO);
+        }
+    }
+
     // Helpers
 
     /// Refreshes the file info overlay if it is currently open.
@@ -483,26 +1664,66 @@ impl<'a> AppState<'a> {
             .find_index(|o| matches!(o, Overlay::ShowInfo { .. }));
 
         if let Some(i) = maybe_idx
-            && let Some(entry) = self.nav.selected_shown_entry()
+            && let Some(file_info) = self.selected_file_info()
+            && let Some(Overlay::ShowInfo { info }) = self.overlays_mut().get_mut(i)
         {
-            let path = self.nav.current_dir().join(entry.name());
-            if let Ok(file_info) = FileInfo::get_file_info(&path)
-                && let Some(Overlay::ShowInfo { info }) = self.overlays_mut().get_mut(i)
-            {
-                *info = file_info;
-            }
+            **info = file_info;
         }
     }
 
     /// Shows the file info overlay for the currently selected entry.
     fn show_file_info(&mut self) {
-        if let Some(entry) = self.nav.selected_shown_entry() {
-            let path = self.nav.current_dir().join(entry.name());
-            if let Ok(file_info) = FileInfo::get_file_info(&path) {
-                self.overlays_mut()
-                    .push(Overlay::ShowInfo { info: file_info });
+        if let Some(file_info) = self.selected_file_info() {
+            self.overlays_mut().push(Overlay::ShowInfo {
+                info: Box::new(file_info),
+            });
+        }
+    }
+
+    /// Reads the [FileInfo] of the selected entry for the info overlay.
+    /// Its details are requested from the info worker. The size of a directory is filled in
+    /// if it is known, otherwise it is requested.
+    fn selected_file_info(&mut self) -> Option<FileInfo> {
+        let name = self.nav.selected_shown_entry()?.name().to_os_string();
+        let path = self.nav.current_dir().join(&name);
+        let mut info = FileInfo::get_basic_info(&path).ok()?;
+        self.request_file_details(path);
+        if *info.file_type() == FileType::Directory {
+            match self.nav.dir_size(&name) {
+                Some(size) => info.set_size(size.bytes()),
+                None => self.request_dir_sizes(vec![name]),
+            }
+        }
+        Some(info)
+    }
+
+    /// Removes the macOS quarantine attribute of the marked entries, or of the selected one,
+    /// so downloads open without the Gatekeeper prompt.
+    fn clear_quarantine(&mut self) {
+        let mut targets: Vec<PathBuf> = self.nav.get_action_targets().into_iter().collect();
+        targets.sort();
+        self.audit(format!("clear quarantine {}", join_paths(&targets)));
+        let mut cleared = 0;
+        for path in &targets {
+            match clear_quarantine(path) {
+                Ok(true) => cleared += 1,
+                Ok(false) => {}
+                Err(e) => {
+                    self.notify(
+                        Severity::Error,
+                        format!("Cannot clear quarantine of {}: {}", path.display(), e),
+                        Duration::from_secs(5),
+                    );
+                    return;
+                }
             }
         }
+        self.notify(
+            Severity::Info,
+            format!("Quarantine cleared: {}", cleared),
+            Duration::from_secs(3),
+        );
+        self.refresh_show_info_if_open();
     }
 
     /// Toggles the file info overlay.
@@ -520,14 +1741,180 @@ impl<'a> AppState<'a> {
         }
     }
 
-    /// Pushes a message overlay that lasts for the specified duration.
-    pub fn push_overlay_message(&mut self, text: String, duration: Duration) {
-        self.notification_time = Some(Instant::now() + duration);
+    /// Toggles the session statistics overlay.
+    pub fn toggle_stats(&mut self) {
+        let is_open = self.overlays().iter().any(|o| matches!(o, Overlay::Stats));
+
+        if is_open {
+            self.overlays_mut().retain(|o| !matches!(o, Overlay::Stats));
+        } else {
+            self.overlays_mut().push(Overlay::Stats);
+        }
+    }
+
+    /// Toggles instant previews for the rest of the session, starting from the configured
+    /// `instant_preview`. Useful when moving between fast local disks and slow network mounts.
+    pub fn toggle_instant_preview(&mut self) {
+        self.instant_preview = !self.instant_preview;
+        if self.instant_preview {
+            self.request_preview();
+        }
+        let state = if self.instant_preview { "on" } else { "off" };
+        self.notify(
+            Severity::Info,
+            format!("Instant preview: {}", state),
+            Duration::from_secs(2),
+        );
+    }
+
+    /// Toggles hidden files for the rest of the session, starting from the configured
+    /// `show_hidden`, and reloads the listings keeping the selected entry.
+    pub fn toggle_hidden(&mut self) {
+        self.show_hidden = !self.show_hidden;
+        let focus = self
+            .nav
+            .selected_shown_entry()
+            .map(|entry| entry.name().to_os_string());
+        self.request_dir_load(focus);
+        self.parent.clear();
+        self.request_parent_content();
+        let state = if self.show_hidden { "shown" } else { "hidden" };
+        self.notify(
+            Severity::Info,
+            format!("Hidden files: {}", state),
+            Duration::from_secs(2),
+        );
+    }
+
+    /// Shows or hides the preview pane for the rest of the session, starting from the configured
+    /// `display.preview`. A preview shown again is loaded for the current selection.
+    pub fn toggle_preview_pane(&mut self) {
+        self.show_preview = !self.show_preview;
+        self.relayout();
+        if self.show_preview {
+            self.request_preview();
+        }
+    }
 
-        if matches!(self.overlays.top(), Some(Overlay::Message { .. })) {
-            self.overlays_mut().pop();
+    /// Shows or hides the parent pane for the rest of the session, starting from the configured
+    /// `display.parent`. A parent pane shown again is loaded for the current directory.
+    pub fn toggle_parent_pane(&mut self) {
+        self.show_parent = !self.show_parent;
+        self.relayout();
+        if self.show_parent {
+            self.parent.clear();
+            self.request_parent_content();
         }
+    }
 
-        self.overlays_mut().push(Overlay::Message { text });
+    /// Reads `runa.toml` again and applies it with [AppState::set_config].
+    /// A config which cannot be read or parsed is reported and the current one kept.
+    pub fn reload_config(&mut self) {
+        match Config::load_from(&Config::default_path()) {
+            Ok(config) => {
+                self.set_config(config);
+                self.notify(
+                    Severity::Info,
+                    "Config reloaded".to_string(),
+                    Duration::from_secs(2),
+                );
+            }
+            Err(e) => self.notify(Severity::Error, e, Duration::from_secs(5)),
+        }
     }
+
+    /// Locks or unlocks the [RootGuard](crate::app::RootGuard) of a root session.
+    ///
+    /// Read-only mode of the `readonly` option or `--readonly` stays on while unlocked.
+    pub fn toggle_root_lock(&mut self) {
+        let Some(guard) = self.root_guard.as_mut() else {
+            self.notify(
+                Severity::Info,
+                "Not running as root, nothing to unlock".to_string(),
+                Duration::from_secs(3),
+            );
+            return;
+        };
+        let locked = !guard.locked();
+        guard.set_locked(locked);
+        self.audit(if locked { "locked" } else { "unlocked" }.to_string());
+        let (severity, text) = if locked {
+            (Severity::Info, "Locked: read-only again".to_string())
+        } else if self.readonly {
+            (
+                Severity::Info,
+                "Unlocked, but read-only mode is still on by the config or --readonly".to_string(),
+            )
+        } else {
+            (
+                Severity::Warn,
+                "Unlocked: changes as root are enabled".to_string(),
+            )
+        };
+        self.notify(severity, text, Duration::from_secs(3));
+    }
+
+    /// Appends `text` to the log of a root session, see [RootGuard::record](crate::app::RootGuard::record).
+    ///
+    /// Does nothing outside of root sessions. A failed write was already reported on startup.
+    pub(super) fn audit(&self, text: String) {
+        if let Some(guard) = &self.root_guard {
+            let _ = guard.record(&text);
+        }
+    }
+
+    /// Logs the entries the create prompt is about to create in a root session.
+    fn audit_create(&self) {
+        if self.root_guard.is_none() {
+            return;
+        }
+        let (names, _) = expand_create_input(self.actions.input_buffer());
+        let paths: Vec<PathBuf> = names
+            .iter()
+            .filter(|name| !name.trim_end_matches('/').is_empty())
+            .map(|name| self.nav.current_dir().join(name))
+            .collect();
+        if !paths.is_empty() {
+            self.audit(format!("create {}", join_paths(&paths)));
+        }
+    }
+
+    /// Computes the layout metrics again for the shown panes, before the next frame is drawn.
+    pub(super) fn relayout(&mut self) {
+        let area = self.metrics.area;
+        if !area.is_empty() {
+            self.metrics = layout_metrics(area, self);
+        }
+    }
+
+    /// Queues a notification, shown as a toast for `ttl`.
+    pub fn notify(&mut self, severity: Severity, text: String, ttl: Duration) {
+        self.notifications.push(severity, text, ttl);
+    }
+
+    /// Queues the result or error of a worker task for `message_secs`, zero drops it.
+    pub(super) fn notify_worker(&mut self, severity: Severity, text: String) {
+        let ttl = self.config.message_duration();
+        if !ttl.is_zero() {
+            self.notify(severity, text, ttl);
+        }
+    }
+}
+
+/// Builds the delete confirmation prompt, mentioning excluded items if there are any.
+fn delete_prompt(included: usize, total: usize) -> String {
+    let plural = if included == 1 { "" } else { "s" };
+    if included == total {
+        format!("Delete {included} item{plural}? [Y/N]")
+    } else {
+        format!("Delete {included} of {total} item{plural}? [Y/N]")
+    }
+}
+
+/// Returns true if `key` is a character typed into an input field, without Ctrl or Alt.
+fn is_plain_char(key: KeyEvent) -> bool {
+    matches!(key.code, Char(_))
+        && !key
+            .modifiers
+            .intersects(KeyModifiers::CONTROL | KeyModifiers::ALT)
 }
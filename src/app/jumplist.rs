@@ -0,0 +1,107 @@
+//! Vim-style jump list of the app.
+//!
+//! Significant jumps (accepting a find, grep or zoxide result, jumping to a marked path) record
+//! the location they leave, so the jump back and jump forward keys retrace the exact positions.
+//! Plain navigation with the movement keys is not recorded.
+
+use std::ffi::OsString;
+use std::path::{Path, PathBuf};
+
+/// The maximum number of locations kept, older ones are dropped first.
+const MAX_JUMPS: usize = 100;
+
+/// A location of the jump list: a directory and the entry selected in it.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Jump {
+    dir: PathBuf,
+    name: Option<OsString>,
+}
+
+impl Jump {
+    pub fn new(dir: PathBuf, name: Option<OsString>) -> Self {
+        Self { dir, name }
+    }
+
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    pub fn name(&self) -> Option<&OsString> {
+        self.name.as_ref()
+    }
+}
+
+/// Recorded locations, oldest first, and the position while moving through them.
+///
+/// The position equals the length of the list unless [JumpList::back] was used.
+/// Every directory is listed once, at the position it was last left at.
+#[derive(Debug, Default)]
+pub struct JumpList {
+    jumps: Vec<Jump>,
+    pos: usize,
+}
+
+impl JumpList {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn len(&self) -> usize {
+        self.jumps.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.jumps.is_empty()
+    }
+
+    /// Records the location left by a jump.
+    ///
+    /// Locations ahead of the current position are dropped, like the forward history of a browser.
+    pub fn push(&mut self, jump: Jump) {
+        self.jumps.truncate(self.pos);
+        self.jumps.retain(|j| j.dir != jump.dir);
+        self.jumps.push(jump);
+        if self.jumps.len() > MAX_JUMPS {
+            self.jumps.remove(0);
+        }
+        self.pos = self.jumps.len();
+    }
+
+    /// Moves back to the previous location.
+    ///
+    /// # Arguments
+    /// * `current` - The current location, remembered so [JumpList::forward] returns to it.
+    ///
+    /// # Returns
+    /// The location to go to, or `None` at the start of the list.
+    pub fn back(&mut self, current: Jump) -> Option<Jump> {
+        if self.pos == 0 {
+            return None;
+        }
+        if self.pos == self.jumps.len() {
+            self.jumps.retain(|j| j.dir != current.dir);
+            self.pos = self.jumps.len();
+            if self.pos == 0 {
+                return None;
+            }
+            self.jumps.push(current);
+        } else {
+            self.jumps[self.pos] = current;
+        }
+        self.pos -= 1;
+        Some(self.jumps[self.pos].clone())
+    }
+
+    /// Moves forward to the location [JumpList::back] came from.
+    ///
+    /// # Returns
+    /// The location to go to, or `None` at the end of the list.
+    pub fn forward(&mut self, current: Jump) -> Option<Jump> {
+        if self.pos + 1 >= self.jumps.len() {
+            return None;
+        }
+        self.jumps[self.pos] = current;
+        self.pos += 1;
+        Some(self.jumps[self.pos].clone())
+    }
+}
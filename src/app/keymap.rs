@@ -4,14 +4,17 @@
 //! for all navigation, file and actions used by runa.
 
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
-/// Represents any action in the app: navigation, file, or system.
+/// Represents any action in the app: navigation, file, system or a user-defined command.
+///
+/// `Custom` holds the index of the command in [Commands](crate::config::Commands).
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum Action {
     Nav(NavAction),
     File(FileAction),
     System(SystemAction),
+    Custom(usize),
 }
 
 /// Navigation actions (move, into_parent, markers, etc.)
@@ -21,9 +24,28 @@ pub enum NavAction {
     GoIntoDir,
     GoUp,
     GoDown,
+    GoTop,
+    GoBottom,
     ToggleMarker,
+    MarkAll,
+    InvertMarks,
     ClearMarker,
     ClearFilter,
+    ShowMarked,
+    JumpBack,
+    JumpForward,
+    Goto(GotoTarget),
+}
+
+/// Directories of the goto shortcuts, the key sequences of the goto prefix and a second key.
+///
+/// `Custom` holds the index of the shortcut in [Goto](crate::config::Goto).
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum GotoTarget {
+    Home,
+    Root,
+    ConfigDir,
+    Custom(usize),
 }
 
 /// File actions (delete, copy, open, paste, etc.)
@@ -31,20 +53,57 @@ pub enum NavAction {
 pub enum FileAction {
     Delete,
     Copy,
+    CopyPath,
+    CopyRelativePath,
+    CopyName,
     Open,
+    OpenWithEditor,
     Paste,
     Rename,
     Create,
     CreateDirectory,
     Filter,
     ShowInfo,
+    DirSize,
     Find,
+    QuickFind,
+    Navigate,
+    Grep,
+    Jump,
+    Command,
+    Shell,
+    ClearQuarantine,
+}
+
+impl FileAction {
+    /// The name of actions changing the filesystem, which read-only mode disables.
+    /// Commands and the shell count as such, as runa cannot tell what they change.
+    pub fn mutation(self) -> Option<&'static str> {
+        match self {
+            FileAction::Delete => Some("delete"),
+            FileAction::Command => Some("running commands"),
+            FileAction::Shell => Some("the shell"),
+            FileAction::Paste => Some("paste"),
+            FileAction::Rename => Some("rename"),
+            FileAction::ClearQuarantine => Some("clear quarantine"),
+            FileAction::Create | FileAction::CreateDirectory => Some("create"),
+            _ => None,
+        }
+    }
 }
 
-/// System actions (quit)
+/// System actions (quit, statistics, runtime toggles, reloading the config)
 #[derive(Copy, Clone, Debug, PartialEq)]
 pub enum SystemAction {
     Quit,
+    Stats,
+    DumpState,
+    ToggleInstantPreview,
+    ToggleHidden,
+    TogglePreview,
+    ToggleParent,
+    ReloadConfig,
+    Unlock,
 }
 
 /// Key + modifiers as used in keybind/keymap
@@ -54,12 +113,30 @@ pub struct Key {
     pub modifiers: KeyModifiers,
 }
 
-/// Stores the mapping from Key to action, which is built in the config
+/// How the keys pressed so far match the bindings, see [Keymap::match_keys].
+///
+/// Variants:
+/// * `Action` - The keys are bound to the action.
+/// * `Pending` - The keys start a longer sequence, e.g. `g` of `g g`.
+/// * `Unbound` - No binding starts with the keys.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum KeyMatch {
+    Action(Action),
+    Pending,
+    Unbound,
+}
+
+/// Stores the mapping from keys to action, which is built in the config
+///
+/// A binding is a single key like `"Ctrl+a"`, or a sequence of keys separated by spaces like
+/// `"g g"`, which [AppState](crate::app::AppState) collects in a pending-key buffer.
 ///
 /// # Fields
-/// * `map` - HashMap mapping Key to Action
+/// * `map` - HashMap mapping single keys and key sequences to Action
+/// * `prefixes` - The starts of all key sequences, which wait for the next key
 pub struct Keymap {
-    map: HashMap<Key, Action>,
+    map: HashMap<Vec<Key>, Action>,
+    prefixes: HashSet<Vec<Key>>,
 }
 
 impl Keymap {
@@ -74,56 +151,14 @@ impl Keymap {
         let mut map = HashMap::new();
         let keys = config.keys();
 
-        let parse_key = |s: &str| -> Option<Key> {
-            let mut modifiers = KeyModifiers::NONE;
-            let mut code: Option<KeyCode> = None;
-
-            for part in s.split('+') {
-                match part {
-                    "Ctrl" | "Control" => modifiers |= KeyModifiers::CONTROL,
-                    "Shift" => modifiers |= KeyModifiers::SHIFT,
-                    "Alt" => modifiers |= KeyModifiers::ALT,
-
-                    "Up" => code = Some(KeyCode::Up),
-                    "Down" => code = Some(KeyCode::Down),
-                    "Left" => code = Some(KeyCode::Left),
-                    "Right" => code = Some(KeyCode::Right),
-                    "Enter" => code = Some(KeyCode::Enter),
-                    "Esc" => code = Some(KeyCode::Esc),
-                    "Backspace" => code = Some(KeyCode::Backspace),
-                    "Tab" => code = Some(KeyCode::Tab),
-
-                    p if p.starts_with('F') => {
-                        let n = p[1..].parse().ok()?;
-                        code = Some(KeyCode::F(n));
-                    }
-
-                    p if p.len() == 1 => {
-                        let mut char = p.chars().next()?;
-                        if modifiers.contains(KeyModifiers::SHIFT) {
-                            char = char.to_ascii_uppercase();
-                        }
-                        code = Some(KeyCode::Char(char));
-                    }
-
-                    _ => return None,
-                }
-            }
-
-            Some(Key {
-                code: code?,
-                modifiers,
-            })
-        };
-
         // Helper to bind multiple keys to the same action
         // # Arguments
         // * `key_list` - List of key strings
         // * `action` - The action to bind to
         let mut bind = |key_list: &[String], action: Action| {
             for k in key_list {
-                if let Some(key) = parse_key(k) {
-                    map.insert(key, action);
+                if let Some(keys) = parse_keys(k) {
+                    map.insert(keys, action);
                 }
             }
         };
@@ -132,10 +167,22 @@ impl Keymap {
         bind(keys.go_into_dir(), Action::Nav(NavAction::GoIntoDir));
         bind(keys.go_up(), Action::Nav(NavAction::GoUp));
         bind(keys.go_down(), Action::Nav(NavAction::GoDown));
+        bind(keys.go_top(), Action::Nav(NavAction::GoTop));
+        bind(keys.go_bottom(), Action::Nav(NavAction::GoBottom));
         bind(keys.toggle_marker(), Action::Nav(NavAction::ToggleMarker));
         bind(keys.open_file(), Action::File(FileAction::Open));
+        bind(
+            keys.open_with_editor(),
+            Action::File(FileAction::OpenWithEditor),
+        );
         bind(keys.delete(), Action::File(FileAction::Delete));
         bind(keys.copy(), Action::File(FileAction::Copy));
+        bind(keys.copy_path(), Action::File(FileAction::CopyPath));
+        bind(
+            keys.copy_relative_path(),
+            Action::File(FileAction::CopyRelativePath),
+        );
+        bind(keys.copy_name(), Action::File(FileAction::CopyName));
         bind(keys.paste(), Action::File(FileAction::Paste));
         bind(keys.rename(), Action::File(FileAction::Rename));
         bind(keys.create(), Action::File(FileAction::Create));
@@ -145,20 +192,169 @@ impl Keymap {
         );
         bind(keys.filter(), Action::File(FileAction::Filter));
         bind(keys.quit(), Action::System(SystemAction::Quit));
+        bind(keys.stats(), Action::System(SystemAction::Stats));
+        bind(keys.dump_state(), Action::System(SystemAction::DumpState));
+        bind(
+            keys.toggle_instant_preview(),
+            Action::System(SystemAction::ToggleInstantPreview),
+        );
+        bind(
+            keys.toggle_hidden(),
+            Action::System(SystemAction::ToggleHidden),
+        );
+        bind(
+            keys.toggle_preview(),
+            Action::System(SystemAction::TogglePreview),
+        );
+        bind(
+            keys.toggle_parent(),
+            Action::System(SystemAction::ToggleParent),
+        );
+        bind(
+            keys.reload_config(),
+            Action::System(SystemAction::ReloadConfig),
+        );
+        bind(keys.unlock(), Action::System(SystemAction::Unlock));
         bind(keys.show_info(), Action::File(FileAction::ShowInfo));
+        bind(
+            keys.clear_quarantine(),
+            Action::File(FileAction::ClearQuarantine),
+        );
+        bind(keys.dir_size(), Action::File(FileAction::DirSize));
         bind(keys.find(), Action::File(FileAction::Find));
+        bind(keys.quick_find(), Action::File(FileAction::QuickFind));
+        bind(keys.navigate(), Action::File(FileAction::Navigate));
+        bind(keys.grep(), Action::File(FileAction::Grep));
+        bind(keys.jump(), Action::File(FileAction::Jump));
+        bind(keys.command(), Action::File(FileAction::Command));
+        bind(keys.shell(), Action::File(FileAction::Shell));
+        bind(keys.mark_all(), Action::Nav(NavAction::MarkAll));
+        bind(keys.invert_marks(), Action::Nav(NavAction::InvertMarks));
         bind(keys.clear_markers(), Action::Nav(NavAction::ClearMarker));
         bind(keys.clear_filter(), Action::Nav(NavAction::ClearFilter));
+        bind(keys.marked(), Action::Nav(NavAction::ShowMarked));
+        bind(keys.jump_back(), Action::Nav(NavAction::JumpBack));
+        bind(keys.jump_forward(), Action::Nav(NavAction::JumpForward));
+
+        // The goto prefix followed by the key of a shortcut, the [goto] table replaces
+        // the built-in ones
+        let builtin_goto = [
+            ("h", GotoTarget::Home),
+            ("r", GotoTarget::Root),
+            ("c", GotoTarget::ConfigDir),
+        ];
+        let custom_goto = config
+            .goto()
+            .iter()
+            .enumerate()
+            .map(|(idx, entry)| (entry.key(), GotoTarget::Custom(idx)));
+        for (key, target) in builtin_goto.into_iter().chain(custom_goto) {
+            let sequences: Vec<String> = keys
+                .goto()
+                .iter()
+                .map(|prefix| format!("{} {}", prefix, key))
+                .collect();
+            bind(&sequences, Action::Nav(NavAction::Goto(target)));
+        }
 
-        Keymap { map }
+        // Bound last, so user-defined commands take precedence over the built-in keys
+        for (idx, command) in config.commands().iter().enumerate() {
+            bind(&[command.key().to_string()], Action::Custom(idx));
+        }
+
+        let prefixes = map
+            .keys()
+            .flat_map(|keys| (1..keys.len()).map(|len| keys[..len].to_vec()))
+            .collect();
+        Keymap { map, prefixes }
     }
 
     /// Looks up the action for a given key event
     pub fn lookup(&self, key: KeyEvent) -> Option<Action> {
-        let k = Key {
+        self.map.get([Key::from(key)].as_slice()).copied()
+    }
+
+    /// Matches the keys pressed so far against the bindings.
+    ///
+    /// Keys starting a longer sequence are [KeyMatch::Pending], even if they are bound on their
+    /// own, e.g. a command on `"g"` next to `"g g"`. See [Keymap::complete] for that binding.
+    pub fn match_keys(&self, keys: &[KeyEvent]) -> KeyMatch {
+        let keys: Vec<Key> = keys.iter().copied().map(Key::from).collect();
+        if self.prefixes.contains(&keys) {
+            KeyMatch::Pending
+        } else if let Some(action) = self.map.get(&keys) {
+            KeyMatch::Action(*action)
+        } else {
+            KeyMatch::Unbound
+        }
+    }
+
+    /// The action bound to exactly `keys`, for a sequence that stopped early.
+    pub fn complete(&self, keys: &[KeyEvent]) -> Option<Action> {
+        let keys: Vec<Key> = keys.iter().copied().map(Key::from).collect();
+        self.map.get(&keys).copied()
+    }
+}
+
+impl From<KeyEvent> for Key {
+    fn from(key: KeyEvent) -> Self {
+        Key {
             code: key.code,
             modifiers: key.modifiers,
-        };
-        self.map.get(&k).copied()
+        }
+    }
+}
+
+/// Parses a binding like `"Ctrl+a"` or a key sequence like `"g g"`.
+///
+/// `" "` is the space bar, in a sequence it is written `"Space"`.
+fn parse_keys(s: &str) -> Option<Vec<Key>> {
+    if s.trim().is_empty() {
+        return parse_key(s).map(|key| vec![key]);
     }
+    s.split_whitespace().map(parse_key).collect()
+}
+
+/// Parses a single key like `"Shift+x"`, `"F5"` or `"Enter"`.
+fn parse_key(s: &str) -> Option<Key> {
+    let mut modifiers = KeyModifiers::NONE;
+    let mut code: Option<KeyCode> = None;
+
+    for part in s.split('+') {
+        match part {
+            "Ctrl" | "Control" => modifiers |= KeyModifiers::CONTROL,
+            "Shift" => modifiers |= KeyModifiers::SHIFT,
+            "Alt" => modifiers |= KeyModifiers::ALT,
+
+            "Up" => code = Some(KeyCode::Up),
+            "Down" => code = Some(KeyCode::Down),
+            "Left" => code = Some(KeyCode::Left),
+            "Right" => code = Some(KeyCode::Right),
+            "Enter" => code = Some(KeyCode::Enter),
+            "Esc" => code = Some(KeyCode::Esc),
+            "Backspace" => code = Some(KeyCode::Backspace),
+            "Tab" => code = Some(KeyCode::Tab),
+            "Space" => code = Some(KeyCode::Char(' ')),
+
+            p if p.starts_with('F') => {
+                let n = p[1..].parse().ok()?;
+                code = Some(KeyCode::F(n));
+            }
+
+            p if p.len() == 1 => {
+                let mut char = p.chars().next()?;
+                if modifiers.contains(KeyModifiers::SHIFT) {
+                    char = char.to_ascii_uppercase();
+                }
+                code = Some(KeyCode::Char(char));
+            }
+
+            _ => return None,
+        }
+    }
+
+    Some(Key {
+        code: code?,
+        modifiers,
+    })
 }
@@ -3,11 +3,177 @@
 //! Manages the current directory, file entries, selection, markers and filters.
 //! Provides helpers for pane navigation, selection, filtering, and bulk actions.
 
-use crate::core::FileEntry;
+use crate::core::{DirId, EntryMeta, FileEntry, PathSize};
+
+use fuzzy_matcher::FuzzyMatcher;
+use fuzzy_matcher::skim::SkimMatcherV2;
+use regex::{Regex, RegexBuilder};
+use serde::Deserialize;
 use std::collections::{HashMap, HashSet};
-use std::ffi::OsString;
+use std::ffi::{OsStr, OsString};
 use std::path::{Path, PathBuf};
 
+/// Matching modes of the filter input.
+///
+/// The default mode is set by `filter_mode` in runa.toml and can be overridden per filter
+/// with a prefix: `/re:`, `/glob:`, `/fuzzy:` or `/substr:`.
+///
+/// Variants:
+/// * `Substring` - Case-insensitive substring match.
+/// * `Fuzzy` - Fuzzy match, like the find dialog.
+/// * `Regex` - Case-insensitive regular expression.
+/// * `Glob` - Case-insensitive glob pattern (`*`, `?` and `[...]`) matched against the whole name.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum FilterMode {
+    #[default]
+    Substring,
+    Fuzzy,
+    Regex,
+    Glob,
+}
+
+impl FilterMode {
+    /// Splits a mode prefix off the filter input.
+    ///
+    /// # Returns
+    /// The mode selected by the prefix, if any, and the remaining pattern.
+    fn split_prefix(filter: &str) -> (Option<FilterMode>, &str) {
+        const PREFIXES: &[(&str, FilterMode)] = &[
+            ("/re:", FilterMode::Regex),
+            ("/glob:", FilterMode::Glob),
+            ("/fuzzy:", FilterMode::Fuzzy),
+            ("/substr:", FilterMode::Substring),
+        ];
+        for (prefix, mode) in PREFIXES {
+            if let Some(rest) = filter.strip_prefix(prefix) {
+                return (Some(*mode), rest);
+            }
+        }
+        (None, filter)
+    }
+}
+
+/// When markers are cleared without using the clear_markers key.
+///
+/// Set by `marker_policy` in runa.toml. Markers of entries that were deleted or moved away
+/// are dropped with every policy.
+///
+/// Variants:
+/// * `Operation` - Unmark the targets of a delete or paste once it succeeded.
+/// * `Directory` - Clear all markers when changing the current directory.
+/// * `Persist` - Keep markers until they are cleared manually.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum MarkerPolicy {
+    #[default]
+    Operation,
+    Directory,
+    Persist,
+}
+
+/// What is known about the recursive size of a subdirectory.
+///
+/// Variants:
+/// * `Exact` - The size, computed by the directory size worker or taken from the cache.
+/// * `Computing` - The size was requested and is not computed yet.
+/// * `Unknown` - The size was not requested.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirSizeStatus {
+    Exact(PathSize),
+    Computing,
+    Unknown,
+}
+
+/// The compiled form of the current filter, rebuilt whenever the filter changes.
+///
+/// Invalid regex or glob patterns fall back to a substring match and keep the error
+/// for display in the filter dialog.
+enum FilterMatcher {
+    All,
+    Substring(String),
+    Fuzzy(Box<SkimMatcherV2>, String),
+    Regex(Regex),
+}
+
+impl FilterMatcher {
+    /// Compiles the filter input, honoring a mode prefix over the default mode.
+    ///
+    /// # Returns
+    /// The matcher and an error message if the pattern was invalid.
+    fn compile(filter: &str, default_mode: FilterMode) -> (Self, Option<String>) {
+        let (prefix_mode, pattern) = FilterMode::split_prefix(filter);
+        if pattern.is_empty() {
+            return (FilterMatcher::All, None);
+        }
+
+        let regex_source = match prefix_mode.unwrap_or(default_mode) {
+            FilterMode::Substring => {
+                return (FilterMatcher::Substring(pattern.to_lowercase()), None);
+            }
+            FilterMode::Fuzzy => {
+                // The matcher defaults to smart case, all filter modes ignore case
+                let matcher = SkimMatcherV2::default().ignore_case();
+                return (
+                    FilterMatcher::Fuzzy(Box::new(matcher), pattern.to_owned()),
+                    None,
+                );
+            }
+            FilterMode::Regex => pattern.to_owned(),
+            FilterMode::Glob => glob_to_regex(pattern),
+        };
+
+        match RegexBuilder::new(&regex_source)
+            .case_insensitive(true)
+            .build()
+        {
+            Ok(re) => (FilterMatcher::Regex(re), None),
+            Err(_) => (
+                FilterMatcher::Substring(pattern.to_lowercase()),
+                Some("invalid pattern".to_string()),
+            ),
+        }
+    }
+
+    fn matches(&self, entry: &FileEntry) -> bool {
+        match self {
+            FilterMatcher::All => true,
+            FilterMatcher::Substring(needle) => entry.lowercase_name().contains(needle.as_str()),
+            FilterMatcher::Fuzzy(matcher, pattern) => {
+                matcher.fuzzy_match(entry.name_str(), pattern).is_some()
+            }
+            FilterMatcher::Regex(re) => re.is_match(entry.name_str()),
+        }
+    }
+}
+
+/// Translates a glob pattern into an anchored regex.
+/// `*` matches any run of characters, `?` a single one and `[...]` is kept as a class.
+fn glob_to_regex(glob: &str) -> String {
+    let mut out = String::with_capacity(glob.len() + 8);
+    out.push('^');
+    let mut in_class = false;
+    for c in glob.chars() {
+        match c {
+            '*' if !in_class => out.push_str(".*"),
+            '?' if !in_class => out.push('.'),
+            '[' if !in_class => {
+                in_class = true;
+                out.push('[');
+            }
+            ']' if in_class => {
+                in_class = false;
+                out.push(']');
+            }
+            '!' if in_class && out.ends_with('[') => out.push('^'),
+            c if in_class => out.push(c),
+            c => out.push_str(&regex::escape(&c.to_string())),
+        }
+    }
+    out.push('$');
+    out
+}
+
 /// Holds the navigation, selection and file list state of a pane.
 ///
 /// # Fields
@@ -16,18 +182,36 @@ use std::path::{Path, PathBuf};
 /// * `selected` - Index of the currently selected entry.
 /// * `positions` - Saved cursor positions per directory.
 /// * `markers` - Set of marked file paths for bulk actions.
+/// * `marker_policy` - When the [MarkerPolicy] clears markers.
 /// * `filter` - Current filter string.
 /// * `filters` - Saved filters per directory.
+/// * `filter_mode` - Default [FilterMode] for filters without a mode prefix.
+/// * `matcher` - The compiled current filter.
+/// * `filter_error` - Error message of an invalid filter pattern.
 /// * `request_id` - ID to track async directory load requests.
+/// * `dir_id` - Identity of the loaded current directory, used to follow external renames.
+/// * `partial` - Whether a large directory is still being loaded in chunks.
+/// * `metadata` - The [EntryMeta] of the entries loaded so far, for the detail view.
+/// * `dir_sizes` - Recursive sizes of the subdirectories computed so far.
+/// * `pending_dir_sizes` - Subdirectories whose size was requested and is not computed yet.
 pub struct NavState {
     current_dir: PathBuf,
     entries: Vec<FileEntry>,
     selected: usize,
     positions: HashMap<PathBuf, usize>,
     markers: HashSet<PathBuf>,
+    marker_policy: MarkerPolicy,
     filter: String,
     filters: HashMap<PathBuf, String>,
+    filter_mode: FilterMode,
+    matcher: FilterMatcher,
+    filter_error: Option<String>,
     request_id: u64,
+    dir_id: Option<DirId>,
+    partial: bool,
+    metadata: HashMap<OsString, EntryMeta>,
+    dir_sizes: HashMap<OsString, PathSize>,
+    pending_dir_sizes: HashSet<OsString>,
 }
 
 impl NavState {
@@ -38,9 +222,18 @@ impl NavState {
             selected: 0,
             positions: HashMap::new(),
             markers: HashSet::new(),
+            marker_policy: MarkerPolicy::default(),
             filter: String::new(),
             filters: HashMap::new(),
+            filter_mode: FilterMode::default(),
+            matcher: FilterMatcher::All,
+            filter_error: None,
             request_id: 0,
+            dir_id: None,
+            partial: false,
+            metadata: HashMap::new(),
+            dir_sizes: HashMap::new(),
+            pending_dir_sizes: HashSet::new(),
         }
     }
 
@@ -62,6 +255,15 @@ impl NavState {
         &self.filter
     }
 
+    pub fn filter_mode(&self) -> FilterMode {
+        self.filter_mode
+    }
+
+    /// Error message of the current filter pattern, if it is invalid.
+    pub fn filter_error(&self) -> Option<&str> {
+        self.filter_error.as_deref()
+    }
+
     pub fn selected_idx(&self) -> usize {
         self.selected
     }
@@ -74,6 +276,14 @@ impl NavState {
         self.selected_shown_entry()
     }
 
+    pub fn dir_id(&self) -> Option<DirId> {
+        self.dir_id
+    }
+
+    pub fn set_dir_id(&mut self, dir_id: Option<DirId>) {
+        self.dir_id = dir_id;
+    }
+
     // Navigation functions
 
     /// Prepares a new request by incrementing the request ID.
@@ -130,15 +340,50 @@ impl NavState {
     /// * `path` - The new directory path to set.
     pub fn set_path(&mut self, path: PathBuf) {
         self.save_position();
+        if self.marker_policy == MarkerPolicy::Directory && path != self.current_dir {
+            self.markers.clear();
+        }
 
         self.current_dir = path;
         self.entries.clear();
         self.selected = 0;
+        self.dir_id = None;
+        self.partial = false;
+        self.metadata.clear();
+        self.dir_sizes.clear();
+        self.pending_dir_sizes.clear();
         self.restore_filter_for_current_dir();
         // instantly ends all pending messages from the previous directory.
         self.request_id = self.request_id.wrapping_add(1);
     }
 
+    /// Moves the current directory to its new path after an external rename.
+    /// Entries and selection are kept, markers, saved positions and filters
+    /// below the old path are moved along.
+    ///
+    /// # Arguments
+    /// * `new_path` - The new path of the current directory.
+    pub fn follow_rename(&mut self, new_path: PathBuf) {
+        let old = std::mem::replace(&mut self.current_dir, new_path);
+        let new = &self.current_dir;
+        let rebase = |p: PathBuf| match p.strip_prefix(&old) {
+            Ok(rest) => new.join(rest),
+            Err(_) => p,
+        };
+
+        self.markers = self.markers.drain().map(rebase).collect();
+        self.positions = self
+            .positions
+            .drain()
+            .map(|(p, pos)| (rebase(p), pos))
+            .collect();
+        self.filters = self
+            .filters
+            .drain()
+            .map(|(p, filter)| (rebase(p), filter))
+            .collect();
+    }
+
     /// Sets the selected index, clamping it to valid range.
     pub fn set_selected(&mut self, idx: usize) {
         let max = self.shown_entries_len();
@@ -153,6 +398,7 @@ impl NavState {
 
     /// Updates the navigation state from a worker thread's result.
     /// Sets the current directory, entries, and selection based on the provided focus.
+    /// If the focused entry does not exist, the selection keeps its index.
     ///
     /// # Arguments
     /// * `path` - The current directory path.
@@ -166,13 +412,15 @@ impl NavState {
     ) {
         self.current_dir = path;
         self.entries = entries;
+        self.partial = false;
 
         if let Some(f) = focus {
+            // A focus that is gone (e.g. renamed away) keeps the cursor where it was
             self.selected = self
                 .entries
                 .iter()
-                .position(|e| e.name() == &f)
-                .unwrap_or(0);
+                .position(|e| e.name() == f)
+                .unwrap_or(self.selected);
         } else {
             self.selected = self.positions.get(&self.current_dir).cloned().unwrap_or(0);
         }
@@ -180,6 +428,71 @@ impl NavState {
         self.selected = self.selected.min(self.entries.len().saturating_sub(1));
     }
 
+    /// Updates the navigation state from the first chunk of a large directory.
+    /// Like [NavState::update_from_worker], but the entries are marked as partial until the
+    /// complete directory arrives.
+    pub fn update_partial_from_worker(
+        &mut self,
+        path: PathBuf,
+        entries: Vec<FileEntry>,
+        focus: Option<OsString>,
+    ) {
+        self.update_from_worker(path, entries, focus);
+        self.partial = true;
+    }
+
+    /// Appends a further chunk of a directory that is still loading.
+    /// The selection stays on its index.
+    pub fn append_entries(&mut self, entries: Vec<FileEntry>) {
+        self.entries.extend(entries);
+    }
+
+    /// The metadata of an entry of the current directory, once the metadata worker read it.
+    pub fn metadata(&self, name: &OsStr) -> Option<&EntryMeta> {
+        self.metadata.get(name)
+    }
+
+    /// Stores a batch of entry metadata from the metadata worker.
+    /// Metadata of a previous load stays until it is replaced, so reloads do not flicker.
+    pub fn update_metadata(&mut self, metadata: Vec<(OsString, EntryMeta)>) {
+        self.metadata.extend(metadata);
+    }
+
+    /// The recursive size of a subdirectory, once the directory size worker computed it.
+    pub fn dir_size(&self, name: &OsStr) -> Option<PathSize> {
+        self.dir_sizes.get(name).copied()
+    }
+
+    pub fn set_dir_size(&mut self, name: OsString, size: PathSize) {
+        self.pending_dir_sizes.remove(&name);
+        self.dir_sizes.insert(name, size);
+    }
+
+    /// Whether the size of a subdirectory is known, being computed or not requested.
+    pub fn dir_size_status(&self, name: &OsStr) -> DirSizeStatus {
+        match self.dir_sizes.get(name) {
+            Some(size) => DirSizeStatus::Exact(*size),
+            None if self.pending_dir_sizes.contains(name) => DirSizeStatus::Computing,
+            None => DirSizeStatus::Unknown,
+        }
+    }
+
+    /// Marks the sizes of subdirectories as requested, until [NavState::set_dir_size] stores them.
+    pub fn set_dir_sizes_pending(&mut self, names: &[OsString]) {
+        self.pending_dir_sizes.extend(names.iter().cloned());
+    }
+
+    /// Forgets the computed and requested sizes, so they are computed again.
+    pub fn clear_dir_sizes(&mut self) {
+        self.dir_sizes.clear();
+        self.pending_dir_sizes.clear();
+    }
+
+    /// Whether the entries are only the first chunks of a directory that is still loading.
+    pub fn is_partial(&self) -> bool {
+        self.partial
+    }
+
     /// Toggles the marker state of the currently selected entry.
     /// If the entry is in the clipboard, it is unmarked and removed from the clipboard.
     ///
@@ -188,16 +501,25 @@ impl NavState {
     pub fn toggle_marker(&mut self, clipboard: &mut Option<HashSet<PathBuf>>) {
         if let Some(entry) = self.selected_shown_entry() {
             let path = self.current_dir().join(entry.name());
+            self.toggle_marker_path(path, clipboard);
+        }
+    }
 
-            if let Some(clip) = clipboard
-                && clip.remove(&path)
-            {
-                self.markers.insert(path);
-                return;
-            }
-            if !self.markers.remove(&path) {
-                self.markers.insert(path);
-            }
+    /// Toggles the marker state of any path, e.g. a find result outside the current directory.
+    /// If the path is in the clipboard, it is marked and removed from the clipboard.
+    ///
+    /// # Arguments
+    /// * `path` - The full path to mark or unmark.
+    /// * `clipboard` - Optional mutable reference to a set of paths in the clipboard.
+    pub fn toggle_marker_path(&mut self, path: PathBuf, clipboard: &mut Option<HashSet<PathBuf>>) {
+        if let Some(clip) = clipboard
+            && clip.remove(&path)
+        {
+            self.markers.insert(path);
+            return;
+        }
+        if !self.markers.remove(&path) {
+            self.markers.insert(path);
         }
     }
 
@@ -223,11 +545,87 @@ impl NavState {
         }
     }
 
+    /// Marks all entries shown with the current filter.
+    /// Entries in the clipboard are marked and removed from the clipboard, like [Self::toggle_marker_path].
+    ///
+    /// # Arguments
+    /// * `clipboard` - Optional mutable reference to a set of paths in the clipboard.
+    pub fn mark_all(&mut self, clipboard: &mut Option<HashSet<PathBuf>>) {
+        for path in self.shown_paths() {
+            if let Some(clip) = clipboard {
+                clip.remove(&path);
+            }
+            self.markers.insert(path);
+        }
+    }
+
+    /// Toggles the marker state of all entries shown with the current filter.
+    ///
+    /// # Arguments
+    /// * `clipboard` - Optional mutable reference to a set of paths in the clipboard.
+    pub fn invert_marks(&mut self, clipboard: &mut Option<HashSet<PathBuf>>) {
+        for path in self.shown_paths() {
+            self.toggle_marker_path(path, clipboard);
+        }
+    }
+
+    /// Full paths of the entries shown with the current filter.
+    fn shown_paths(&self) -> Vec<PathBuf> {
+        self.shown_entries()
+            .map(|e| self.current_dir.join(e.name()))
+            .collect()
+    }
+
     /// Clears all markers.
     pub fn clear_markers(&mut self) {
         self.markers.clear();
     }
 
+    pub fn marker_policy(&self) -> MarkerPolicy {
+        self.marker_policy
+    }
+
+    pub fn set_marker_policy(&mut self, policy: MarkerPolicy) {
+        self.marker_policy = policy;
+    }
+
+    /// Applies the [MarkerPolicy] after a file operation succeeded.
+    ///
+    /// With [MarkerPolicy::Operation] the targets of the operation are unmarked.
+    /// Markers of paths which no longer exist are dropped with every policy.
+    ///
+    /// # Arguments
+    /// * `targets` - The paths the operation worked on.
+    pub fn finish_operation(&mut self, targets: &[PathBuf]) {
+        if self.marker_policy == MarkerPolicy::Operation {
+            for path in targets {
+                self.markers.remove(path);
+            }
+        }
+        self.markers.retain(|path| path.symlink_metadata().is_ok());
+    }
+
+    /// The entry which takes the place of the selection once `targets` are removed.
+    ///
+    /// That is the selected entry itself if it stays, else the next shown entry which stays,
+    /// or the previous one if the selection was at the end of the listing.
+    pub fn focus_after_removal(&self, targets: &[PathBuf]) -> Option<OsString> {
+        let removed: HashSet<&OsStr> = targets
+            .iter()
+            .filter(|path| path.parent() == Some(self.current_dir.as_path()))
+            .filter_map(|path| path.file_name())
+            .collect();
+        let names: Vec<&OsStr> = self.shown_entries().map(|e| e.name()).collect();
+        let stays = |name: &&OsStr| !removed.contains(name);
+        let selected = self.selected.min(names.len());
+        names[selected..]
+            .iter()
+            .copied()
+            .find(stays)
+            .or_else(|| names[..selected].iter().rev().copied().find(stays))
+            .map(|name| name.to_os_string())
+    }
+
     /// Returns the set of action targets, either marked entries or the selected entry.
     pub fn get_action_targets(&self) -> HashSet<PathBuf> {
         if self.markers.is_empty() {
@@ -245,28 +643,21 @@ impl NavState {
     /// Returns an iterator over the entries that match the current filter.
     /// If the filter is empty, returns all entries.
     pub fn shown_entries(&self) -> Box<dyn Iterator<Item = &FileEntry> + '_> {
-        if self.filter.is_empty() {
+        if let FilterMatcher::All = self.matcher {
             Box::new(self.entries.iter())
         } else {
-            let filter_lower = self.filter.to_lowercase();
-
-            Box::new(
-                self.entries
-                    .iter()
-                    .filter(move |e| e.lowercase_name().contains(&filter_lower)),
-            )
+            Box::new(self.entries.iter().filter(|e| self.matcher.matches(e)))
         }
     }
 
     /// Returns the number of entries that match the current filter.
     pub fn shown_entries_len(&self) -> usize {
-        if self.filter.is_empty() {
+        if let FilterMatcher::All = self.matcher {
             self.entries.len()
         } else {
-            let filter_lower = self.filter.to_lowercase();
             self.entries
                 .iter()
-                .filter(|e| e.lowercase_name().contains(&filter_lower))
+                .filter(|e| self.matcher.matches(e))
                 .count()
         }
     }
@@ -276,6 +667,53 @@ impl NavState {
         self.shown_entries().nth(self.selected)
     }
 
+    /// Index of the shown entry whose name matches `query` best, used by the quick find.
+    /// Ties go to the entry listed first, so the selection does not jump around.
+    pub fn best_match(&self, query: &str) -> Option<usize> {
+        if query.is_empty() {
+            return None;
+        }
+        let matcher = SkimMatcherV2::default();
+        self.shown_entries()
+            .enumerate()
+            .filter_map(|(idx, e)| matcher.fuzzy_match(e.name_str(), query).map(|s| (s, idx)))
+            .max_by(|(a, a_idx), (b, b_idx)| a.cmp(b).then(b_idx.cmp(a_idx)))
+            .map(|(_, idx)| idx)
+    }
+
+    /// Selects the next (`forward == true`) or previous shown entry matching `query`,
+    /// wrapping around at the end of the listing.
+    ///
+    /// # Returns
+    /// true if another matching entry was selected.
+    pub fn select_next_match(&mut self, query: &str, forward: bool) -> bool {
+        let len = self.shown_entries_len();
+        if query.is_empty() || len == 0 {
+            return false;
+        }
+        let matcher = SkimMatcherV2::default();
+        let matching: Vec<bool> = self
+            .shown_entries()
+            .map(|e| matcher.fuzzy_match(e.name_str(), query).is_some())
+            .collect();
+        let next = (1..len)
+            .map(|step| {
+                if forward {
+                    (self.selected + step) % len
+                } else {
+                    (self.selected + len - step) % len
+                }
+            })
+            .find(|&idx| matching[idx]);
+        match next {
+            Some(idx) => {
+                self.selected = idx;
+                true
+            }
+            None => false,
+        }
+    }
+
     /// Sets a new filter string, preserving the selected entry if possible.
     ///
     /// # Arguments
@@ -287,6 +725,7 @@ impl NavState {
 
         let target_name = self.selected_shown_entry().map(|e| e.name().to_os_string());
         self.filter = filter;
+        self.compile_filter();
         self.save_filter_for_current_dir();
 
         let new_idx = if let Some(ref name) = target_name {
@@ -299,12 +738,43 @@ impl NavState {
         self.selected = new_idx.unwrap_or(0);
     }
 
+    /// Replaces the filter saved for a directory other than the current one, which is restored
+    /// when it is entered again.
+    ///
+    /// # Arguments
+    /// * `dir` - The directory.
+    /// * `filter` - The filter, empty to forget it.
+    pub fn set_saved_filter(&mut self, dir: &Path, filter: String) {
+        if filter.is_empty() {
+            self.filters.remove(dir);
+        } else {
+            self.filters.insert(dir.to_path_buf(), filter);
+        }
+    }
+
+    /// Sets the default [FilterMode] used for filters without a mode prefix.
+    ///
+    /// # Arguments
+    /// * `mode` - The new default filter mode.
+    pub fn set_filter_mode(&mut self, mode: FilterMode) {
+        self.filter_mode = mode;
+        self.compile_filter();
+    }
+
     /// Clears the current filter.
     pub fn clear_filters(&mut self) {
         self.filter.clear();
+        self.compile_filter();
         self.save_filter_for_current_dir();
     }
 
+    /// Rebuilds the matcher and the error message from the current filter string.
+    fn compile_filter(&mut self) {
+        let (matcher, error) = FilterMatcher::compile(&self.filter, self.filter_mode);
+        self.matcher = matcher;
+        self.filter_error = error;
+    }
+
     /// Saves the current filter for the current directory.
     fn save_filter_for_current_dir(&mut self) {
         if self.filter.is_empty() {
@@ -322,5 +792,6 @@ impl NavState {
             .get(&self.current_dir)
             .cloned()
             .unwrap_or_default();
+        self.compile_filter();
     }
 }
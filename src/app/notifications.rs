@@ -0,0 +1,100 @@
+//! Notification queue of the app.
+//!
+//! Results of file operations, errors of background tasks and warnings about the configuration
+//! are queued with a [Severity] and their own time to live, and drawn as stacked toasts.
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+/// The maximum number of notifications kept, older ones are dropped first.
+const MAX_NOTIFICATIONS: usize = 5;
+
+/// How important a notification is, shown by the title and border color of its toast.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Info,
+    Warn,
+    Error,
+}
+
+impl Severity {
+    pub fn label(self) -> &'static str {
+        match self {
+            Severity::Info => "Info",
+            Severity::Warn => "Warning",
+            Severity::Error => "Error",
+        }
+    }
+}
+
+/// A single queued message and when it expires.
+#[derive(Debug, Clone)]
+pub struct Notification {
+    text: String,
+    severity: Severity,
+    expires: Instant,
+}
+
+impl Notification {
+    pub fn text(&self) -> &str {
+        &self.text
+    }
+
+    pub fn severity(&self) -> Severity {
+        self.severity
+    }
+}
+
+/// Queued notifications, oldest first.
+#[derive(Debug, Default)]
+pub struct Notifications {
+    queue: VecDeque<Notification>,
+}
+
+impl Notifications {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.queue.is_empty()
+    }
+
+    pub fn len(&self) -> usize {
+        self.queue.len()
+    }
+
+    pub fn iter(&self) -> impl DoubleEndedIterator<Item = &Notification> {
+        self.queue.iter()
+    }
+
+    /// The most recent notification.
+    pub fn latest(&self) -> Option<&Notification> {
+        self.queue.back()
+    }
+
+    /// Queues a notification shown for `ttl`.
+    ///
+    /// A notification with the same text is moved to the end and renewed instead of shown twice.
+    pub fn push(&mut self, severity: Severity, text: String, ttl: Duration) {
+        self.queue.retain(|n| n.text != text);
+        self.queue.push_back(Notification {
+            text,
+            severity,
+            expires: Instant::now() + ttl,
+        });
+        if self.queue.len() > MAX_NOTIFICATIONS {
+            self.queue.pop_front();
+        }
+    }
+
+    /// Drops the notifications expired at `now`.
+    ///
+    /// # Returns
+    /// Whether any notification was dropped.
+    pub fn expire(&mut self, now: Instant) -> bool {
+        let len = self.queue.len();
+        self.queue.retain(|n| n.expires > now);
+        self.queue.len() != len
+    }
+}
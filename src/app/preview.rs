@@ -4,16 +4,19 @@
 //! data, debounce for background rendering, selection within the preview and request tracking
 
 use crate::core::FileEntry;
+use ratatui::text::Line;
 use std::path::PathBuf;
 use std::time::Instant;
 
 /// Preview content for the preview pane
 ///
-/// Holds loaded lines for file preview, directory entries for folder preview or empty if nothing.
+/// Holds loaded lines for file preview, syntax highlighted lines, directory entries for folder
+/// preview or empty if nothing.
 /// Used to display or render file/folder content in the preview pane
 pub enum PreviewData {
     Directory(Vec<FileEntry>),
     File(Vec<String>),
+    Highlighted(Vec<Line<'static>>),
     Empty,
 }
 
@@ -65,6 +68,7 @@ impl PreviewState {
         let len = match &self.data {
             PreviewData::Directory(entries) => entries.len(),
             PreviewData::File(lines) => lines.len(),
+            PreviewData::Highlighted(lines) => lines.len(),
             PreviewData::Empty => 0,
         };
         self.selected_idx = idx.min(len.saturating_sub(1));
@@ -97,13 +101,23 @@ impl PreviewState {
 
     /// Updates the preview content with new file lines
     /// Only applies the update if the request ID matches the latest
+    /// Highlighted lines take precedence over the plain lines if present.
     ///
     /// # Arguments
     /// * `lines` - The new file lines
+    /// * `styled` - Optional syntax highlighted version of the lines
     /// * `request_id` - The request ID of the update
-    pub fn update_content(&mut self, lines: Vec<String>, request_id: u64) {
+    pub fn update_content(
+        &mut self,
+        lines: Vec<String>,
+        styled: Option<Vec<Line<'static>>>,
+        request_id: u64,
+    ) {
         if request_id == self.request_id {
-            self.data = PreviewData::File(lines);
+            self.data = match styled {
+                Some(styled) => PreviewData::Highlighted(styled),
+                None => PreviewData::File(lines),
+            };
         }
     }
 
@@ -140,6 +154,7 @@ impl PreviewData {
         match self {
             PreviewData::Directory(v) => v.is_empty(),
             PreviewData::File(v) => v.is_empty(),
+            PreviewData::Highlighted(v) => v.is_empty(),
             PreviewData::Empty => true,
         }
     }
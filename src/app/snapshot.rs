@@ -0,0 +1,259 @@
+//! State snapshots for bug reports about runa.
+//!
+//! A snapshot is a JSON file with what is needed to tell why a load is stuck or a worker
+//! response went to the wrong pane: the directories and request ids of the panes, the tasks
+//! queued for each worker, the slow loads of the session, the input mode, the overlay stack and
+//! a digest of the config file.
+//! It is written by the `dump_state` key, and by `rn --dump-state` when runa exits.
+//!
+//! The layout is versioned by [SNAPSHOT_VERSION], which is raised whenever a field changes.
+
+use crate::app::actions::ActionMode;
+use crate::app::{AppState, Severity};
+use crate::config::Config;
+use crate::ui::overlays::Overlay;
+
+use std::fmt::Write;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+/// The version of the snapshot layout, the `version` field of every snapshot.
+pub const SNAPSHOT_VERSION: u32 = 2;
+
+impl AppState {
+    /// Serializes the state for a bug report, see the [module docs](self).
+    pub fn snapshot_json(&self) -> String {
+        let nav = JsonObject::new()
+            .string("path", &self.nav.current_dir().to_string_lossy())
+            .number("request_id", self.nav.request_id())
+            .number("entries", self.nav.entries().len() as u64)
+            .number("shown", self.nav.shown_entries_len() as u64)
+            .number("selected", self.nav.selected_idx() as u64)
+            .string("filter", self.nav.filter())
+            .bool("partial", self.nav.is_partial())
+            .bool("loading", self.is_loading)
+            .finish();
+        let parent = JsonObject::new()
+            .opt_path("path", self.parent.last_path().map(PathBuf::as_path))
+            .number("request_id", self.parent.request_id())
+            .finish();
+        let preview = JsonObject::new()
+            .opt_path("path", self.preview.current_path().map(PathBuf::as_path))
+            .number("request_id", self.preview.request_id())
+            .finish();
+        let requests = JsonObject::new()
+            .number("git", self.git_request_id)
+            .number("file_details", self.file_details_request_id)
+            .number("metadata", self.metadata_request_id)
+            .number("dir_size", self.dir_size_request_id)
+            .finish();
+        let queued = self
+            .workers
+            .queue_lengths()
+            .into_iter()
+            .fold(JsonObject::new(), |object, (name, len)| {
+                object.number(name, len as u64)
+            })
+            .finish();
+        let slow = json_list(self.stats.slow_operations().iter().map(|op| {
+            JsonObject::new()
+                .string("kind", op.kind().label())
+                .string("path", &op.path().to_string_lossy())
+                .number("ms", op.elapsed().as_millis() as u64)
+                .finish()
+        }));
+        let mode = match self.actions.mode() {
+            ActionMode::Normal => "normal".to_string(),
+            ActionMode::Input { mode, .. } => format!("{mode:?}"),
+            ActionMode::ShowInfo { .. } => "info".to_string(),
+        };
+        let overlays = json_list(self.overlays.iter().map(|overlay| {
+            let name = match overlay {
+                Overlay::ShowInfo { .. } => "info",
+                Overlay::Stats => "stats",
+                Overlay::MarkedList { .. } => "marked",
+            };
+            json_string(name)
+        }));
+        let config_path = Config::default_path();
+        let config = JsonObject::new()
+            .string("path", &config_path.to_string_lossy())
+            .opt_string("digest", config_digest(&config_path).as_deref())
+            .number("warnings", self.config.warnings().len() as u64)
+            .finish();
+
+        JsonObject::pretty()
+            .number("version", SNAPSHOT_VERSION as u64)
+            .string("runa", env!("CARGO_PKG_VERSION"))
+            .string("created", &chrono::Local::now().to_rfc3339())
+            .number("uptime_secs", self.stats.uptime().as_secs())
+            .raw("nav", nav)
+            .raw("parent", parent)
+            .raw("preview", preview)
+            .raw("requests", requests)
+            .raw("queued", queued)
+            .number("slow_count", self.stats.slow_count())
+            .raw("slow", slow)
+            .string("mode", &mode)
+            .raw("overlays", overlays)
+            .opt_string("profile", self.active_profile())
+            .bool("readonly", self.readonly)
+            .bool(
+                "root_locked",
+                self.root_guard.as_ref().is_some_and(|g| g.locked()),
+            )
+            .raw("config", config)
+            .finish()
+    }
+
+    /// Writes [snapshot_json](Self::snapshot_json) to a new file in `dir`.
+    ///
+    /// # Returns
+    /// The path of the file, named by the time of the snapshot.
+    ///
+    /// # Errors
+    /// Returns an std::io::Error if the directory cannot be created or the file not written.
+    pub fn dump_state(&self, dir: &Path) -> io::Result<PathBuf> {
+        fs::create_dir_all(dir)?;
+        let name = chrono::Local::now().format("state-%Y%m%d-%H%M%S%.3f.json");
+        let path = dir.join(name.to_string());
+        fs::write(&path, self.snapshot_json())?;
+        Ok(path)
+    }
+
+    /// Writes a snapshot to [snapshot_dir] for the `dump_state` key and shows where it went.
+    pub(super) fn dump_state_and_notify(&mut self) {
+        let result = snapshot_dir()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no home directory"))
+            .and_then(|dir| self.dump_state(&dir));
+        match result {
+            Ok(path) => self.notify(
+                Severity::Info,
+                format!("State written to {}", path.display()),
+                Duration::from_secs(5),
+            ),
+            Err(e) => self.notify(
+                Severity::Error,
+                format!("Cannot write the state: {e}"),
+                Duration::from_secs(3),
+            ),
+        }
+    }
+}
+
+/// The directory snapshots are written to, `~/.local/share/runa/snapshots`.
+pub fn snapshot_dir() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".local/share/runa/snapshots"))
+}
+
+/// A 64 bit FNV-1a hash of the config file, so reports tell whether two configs are the same
+/// without sharing them. `None` if there is no config file.
+fn config_digest(path: &Path) -> Option<String> {
+    let data = fs::read(path).ok()?;
+    let hash = data.iter().fold(0xcbf2_9ce4_8422_2325_u64, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x0100_0000_01b3)
+    });
+    Some(format!("{hash:016x}"))
+}
+
+/// Writes the fields of a JSON object in order.
+struct JsonObject {
+    out: String,
+    separator: &'static str,
+}
+
+impl JsonObject {
+    fn new() -> Self {
+        JsonObject {
+            out: String::from("{"),
+            separator: " ",
+        }
+    }
+
+    /// An object with one field per line, for the top level.
+    fn pretty() -> Self {
+        JsonObject {
+            out: String::from("{"),
+            separator: "\n  ",
+        }
+    }
+
+    fn key(mut self, key: &str) -> Self {
+        if self.out.len() > 1 {
+            self.out.push(',');
+        }
+        self.out.push_str(self.separator);
+        push_json_string(&mut self.out, key);
+        self.out.push_str(": ");
+        self
+    }
+
+    fn raw(self, key: &str, json: String) -> Self {
+        let mut object = self.key(key);
+        object.out.push_str(&json);
+        object
+    }
+
+    fn string(self, key: &str, value: &str) -> Self {
+        let mut object = self.key(key);
+        push_json_string(&mut object.out, value);
+        object
+    }
+
+    fn opt_string(self, key: &str, value: Option<&str>) -> Self {
+        match value {
+            Some(value) => self.string(key, value),
+            None => self.raw(key, "null".to_string()),
+        }
+    }
+
+    fn opt_path(self, key: &str, value: Option<&Path>) -> Self {
+        self.opt_string(key, value.map(|path| path.to_string_lossy()).as_deref())
+    }
+
+    fn number(self, key: &str, value: u64) -> Self {
+        self.raw(key, value.to_string())
+    }
+
+    fn bool(self, key: &str, value: bool) -> Self {
+        self.raw(key, value.to_string())
+    }
+
+    fn finish(mut self) -> String {
+        let end = if self.separator == " " { " }" } else { "\n}" };
+        self.out.push_str(end);
+        self.out
+    }
+}
+
+/// A JSON array of already serialized values.
+fn json_list(items: impl Iterator<Item = String>) -> String {
+    let items: Vec<String> = items.collect();
+    format!("[{}]", items.join(", "))
+}
+
+fn json_string(value: &str) -> String {
+    let mut out = String::with_capacity(value.len() + 2);
+    push_json_string(&mut out, value);
+    out
+}
+
+fn push_json_string(out: &mut String, value: &str) {
+    out.push('"');
+    for c in value.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\r' => out.push_str("\\r"),
+            '\t' => out.push_str("\\t"),
+            c if c.is_control() => {
+                let _ = write!(out, "\\u{:04x}", c as u32);
+            }
+            c => out.push(c),
+        }
+    }
+    out.push('"');
+}
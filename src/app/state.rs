@@ -14,18 +14,36 @@
 //!
 //! This is the primary context/state object passed to most UI/Terminal event logic.
 
-use crate::app::actions::{ActionContext, ActionMode, InputMode};
-use crate::app::keymap::{Action, Keymap, SystemAction};
-use crate::app::{NavState, ParentState, PreviewState};
-use crate::config::Config;
-use crate::core::worker::{WorkerResponse, WorkerTask, Workers};
+use crate::app::actions::{ActionContext, ActionMode, FindHistory, InputMode};
+use crate::app::keymap::{Action, FileAction, KeyMatch, Keymap, NavAction, SystemAction};
+use crate::app::{
+    JumpList, NavState, Notifications, ParentState, PreviewState, RootGuard, SessionStats,
+    Severity, SlowKind, Tutorial,
+};
+use crate::config::{Config, Profile};
+use crate::core::worker::{PreviewParams, WorkerResponse, WorkerStats, WorkerTask, Workers};
+use crate::core::{FileDetails, FileType, GitInfo, PathSize, TextRendering, fs_type};
+use crate::ui::animation::Animator;
 use crate::ui::overlays::{Overlay, OverlayStack};
+use crate::utils::{find_history_path, termux};
 
 use crossterm::event::KeyEvent;
+use ratatui::layout::Rect;
+use std::collections::HashMap;
 use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::sync::atomic::AtomicBool;
-use std::time::Instant;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::{Duration, Instant};
+
+/// How long warnings about the configuration file are shown after starting.
+const CONFIG_WARNING_TTL: Duration = Duration::from_secs(10);
+
+/// How long a started key sequence like `g g` waits for its next key.
+pub const KEY_SEQUENCE_TIMEOUT: Duration = Duration::from_millis(1000);
+
+/// The maximum number of computed directory sizes kept across directory changes.
+const DIR_SIZE_CACHE_LIMIT: usize = 4096;
 
 /// Enumeration for each individual keypress result processed.
 ///
@@ -44,6 +62,8 @@ pub struct LayoutMetrics {
     pub main_width: usize,
     pub preview_width: usize,
     pub preview_height: usize,
+    /// The area the panes were laid out in, empty before the first frame.
+    pub area: Rect,
 }
 
 impl Default for LayoutMetrics {
@@ -53,6 +73,7 @@ impl Default for LayoutMetrics {
             main_width: 40,
             preview_width: 40,
             preview_height: 50,
+            area: Rect::default(),
         }
     }
 }
@@ -67,13 +88,19 @@ impl Default for LayoutMetrics {
 /// - Live layout information
 /// - crossbeam channels for communication with background worker threads
 /// - Notification timing and loading indicators
+/// - The git status of the current directory, shown in the header
+/// - The [Profile] applied to the current directory and the options it overrides
+/// - The [JumpList] of locations left by find results and other jumps
 /// - UI overlay for a seamless widet rendering
+/// - The quick-start tutorial, when started with `rn --tutorial`
 ///
 /// Functions are provided for the core event loop, input handling, file navigationm
 /// worker requests and Notification management.
-pub struct AppState<'a> {
-    pub(super) config: &'a Config,
+pub struct AppState {
+    pub(super) config: Arc<Config>,
     pub(super) keymap: Keymap,
+    pub(super) pending_keys: Vec<KeyEvent>,
+    pub(super) pending_since: Instant,
 
     pub(super) metrics: LayoutMetrics,
 
@@ -84,39 +111,120 @@ pub struct AppState<'a> {
 
     pub(super) workers: Workers,
     pub(super) is_loading: bool,
+    pub(super) instant_preview: bool,
+    pub(super) show_hidden: bool,
+    pub(super) show_parent: bool,
+    pub(super) show_preview: bool,
+    pub(super) readonly: bool,
+    pub(super) root_guard: Option<RootGuard>,
+    pub(super) profile: Option<(String, Profile)>,
+    pub(super) profile_dir: Option<PathBuf>,
 
-    pub(super) notification_time: Option<Instant>,
+    pub(super) git_status: Option<(PathBuf, GitInfo)>,
+    pub(super) git_request_id: u64,
+    pub(super) file_details_request_id: u64,
+    pub(super) metadata_request_id: u64,
+    pub(super) dir_size_request_id: u64,
+    pub(super) dir_size_cancel: Option<(PathBuf, Arc<AtomicBool>)>,
+    pub(super) dir_size_cache: HashMap<PathBuf, PathSize>,
+    pub(super) jumps: JumpList,
+    pub(super) marked_sizes: HashMap<PathBuf, Option<u64>>,
+    pub(super) marked_size_cancel: Arc<AtomicBool>,
+
+    pub(super) notifications: Notifications,
+    pub(super) start_dir: PathBuf,
+    pub(super) last_refresh: Instant,
+    pub(super) slow_load_warned: Option<u64>,
+    pub(super) watched_dir: Option<PathBuf>,
+    pub(super) dir_changed: bool,
     pub(super) overlays: OverlayStack,
+    pub(super) animator: Animator,
+    pub(super) tutorial: Option<Tutorial>,
+    pub(super) stats: SessionStats,
 }
 
-impl<'a> AppState<'a> {
-    pub fn new(config: &'a Config) -> std::io::Result<Self> {
-        let workers = Workers::spawn();
+impl AppState {
+    pub fn new(config: Config) -> std::io::Result<Self> {
         let current_dir = std::env::current_dir()?;
+        Ok(Self::with_dir(config, current_dir))
+    }
+
+    /// Creates the state with the navigation starting in `dir` instead of the working directory.
+    ///
+    /// # Arguments
+    /// * `config` - The loaded [Config].
+    /// * `dir` - The directory to start in.
+    pub fn with_dir(config: Config, dir: PathBuf) -> Self {
+        let config = Arc::new(config);
+        let workers = Workers::spawn_with(config.workers());
+        let start_dir = std::env::current_dir().unwrap_or_else(|_| dir.clone());
 
         let mut app = Self {
-            config,
-            keymap: Keymap::from_config(config),
+            config: Arc::clone(&config),
+            keymap: Keymap::from_config(&config),
+            pending_keys: Vec::new(),
+            pending_since: Instant::now(),
             metrics: LayoutMetrics::default(),
-            nav: NavState::new(current_dir),
+            nav: NavState::new(dir),
             actions: ActionContext::default(),
             preview: PreviewState::default(),
             parent: ParentState::default(),
             workers,
             is_loading: false,
-            notification_time: None,
+            instant_preview: config.display().instant_preview(),
+            show_hidden: config.show_hidden(),
+            show_parent: config.display().parent(),
+            show_preview: config.display().preview(),
+            readonly: config.readonly(),
+            root_guard: None,
+            profile: None,
+            profile_dir: None,
+            git_status: None,
+            git_request_id: 0,
+            file_details_request_id: 0,
+            metadata_request_id: 0,
+            dir_size_request_id: 0,
+            dir_size_cancel: None,
+            dir_size_cache: HashMap::new(),
+            jumps: JumpList::new(),
+            marked_sizes: HashMap::new(),
+            marked_size_cancel: Arc::new(AtomicBool::new(false)),
+            notifications: Notifications::new(),
+            start_dir,
+            last_refresh: Instant::now(),
+            slow_load_warned: None,
+            watched_dir: None,
+            dir_changed: false,
             overlays: OverlayStack::new(),
+            animator: Animator::default(),
+            tutorial: None,
+            stats: SessionStats::default(),
         };
 
+        for warning in config.warnings() {
+            app.notify(Severity::Warn, warning.clone(), CONFIG_WARNING_TTL);
+        }
+        if config.termux()
+            && let Some(hint) = termux::install_hint()
+        {
+            app.notify(Severity::Warn, hint, CONFIG_WARNING_TTL);
+        }
+        app.nav.set_filter_mode(config.filter_mode());
+        app.nav.set_marker_policy(config.marker_policy());
+        app.load_find_history();
         app.request_dir_load(None);
         app.request_parent_content();
-        Ok(app)
+        app
     }
 
     // Getters/ accessors
 
+    pub fn jump_list(&self) -> &JumpList {
+        &self.jumps
+    }
+
     pub fn config(&self) -> &Config {
-        self.config
+        &self.config
     }
 
     pub fn metrics(&self) -> &LayoutMetrics {
@@ -143,8 +251,171 @@ impl<'a> AppState<'a> {
         &self.parent
     }
 
-    pub fn notification_time(&self) -> &Option<Instant> {
-        &self.notification_time
+    /// The git branch and dirty state of the current directory, if it is inside a repository.
+    pub fn git_info(&self) -> Option<&GitInfo> {
+        self.git_status
+            .as_ref()
+            .filter(|(path, _)| path == self.nav.current_dir())
+            .map(|(_, info)| info)
+    }
+
+    /// Counters of the current session, see also [AppState::worker_stats].
+    pub fn session_stats(&self) -> &SessionStats {
+        &self.stats
+    }
+
+    pub fn worker_stats(&self) -> &WorkerStats {
+        self.workers.stats()
+    }
+
+    /// Whether the preview follows every selection change, see [AppState::toggle_instant_preview].
+    pub fn instant_preview(&self) -> bool {
+        self.instant_preview
+    }
+
+    /// Whether hidden files are listed, see [AppState::toggle_hidden].
+    pub fn show_hidden(&self) -> bool {
+        self.show_hidden
+    }
+
+    /// Whether the parent pane is shown, see [AppState::toggle_parent_pane].
+    pub fn show_parent(&self) -> bool {
+        self.show_parent
+    }
+
+    /// Whether the preview pane is shown, see [AppState::toggle_preview_pane].
+    pub fn show_preview(&self) -> bool {
+        self.show_preview
+    }
+
+    /// Whether actions changing the filesystem are disabled, see [FileAction::mutation].
+    /// Also while the [RootGuard] of a root session is locked.
+    pub fn readonly(&self) -> bool {
+        self.readonly || self.root_guard.as_ref().is_some_and(RootGuard::locked)
+    }
+
+    pub fn set_readonly(&mut self, readonly: bool) {
+        self.readonly = readonly;
+    }
+
+    /// The guard of a session running as root, if the `root_guard` option is on.
+    pub fn root_guard(&self) -> Option<&RootGuard> {
+        self.root_guard.as_ref()
+    }
+
+    /// Starts the root guard: the session is locked until [AppState::toggle_root_lock] and
+    /// file operations and commands are appended to `log`.
+    ///
+    /// Called on startup when running as root with the `root_guard` option on.
+    /// A log which cannot be written is reported once.
+    pub fn enable_root_guard(&mut self, log: Option<PathBuf>) {
+        let guard = RootGuard::new(log);
+        let started = format!("session started in {}", self.nav.current_dir().display());
+        if let Err(e) = guard.record(&started) {
+            self.notify(
+                Severity::Warn,
+                format!("Cannot write the root log: {}", e),
+                CONFIG_WARNING_TTL,
+            );
+        }
+        self.root_guard = Some(guard);
+        let unlock = self.config.keys().unlock().first().cloned();
+        self.notify(
+            Severity::Warn,
+            match unlock {
+                Some(key) => format!("Running as root: read-only until {} unlocks it", key),
+                None => "Running as root: read-only".to_string(),
+            },
+            CONFIG_WARNING_TTL,
+        );
+    }
+
+    /// The instant_preview of the active profile, or of the config.
+    /// [AppState::toggle_instant_preview] overrides it until the profile changes.
+    pub fn default_instant_preview(&self) -> bool {
+        self.profile
+            .as_ref()
+            .and_then(|(_, p)| p.instant_preview())
+            .unwrap_or(self.config.display().instant_preview())
+    }
+
+    /// The name of the profile applied to the current directory, if any.
+    pub fn active_profile(&self) -> Option<&str> {
+        self.profile.as_ref().map(|(name, _)| name.as_str())
+    }
+
+    /// Whether the main pane shows the detail column, see [Display::details](crate::config::Display::details).
+    pub fn show_details(&self) -> bool {
+        self.profile
+            .as_ref()
+            .and_then(|(_, p)| p.details())
+            .unwrap_or(self.config.display().details())
+    }
+
+    /// Whether a rename is typed into the selected row of the main pane instead of the dialog.
+    pub fn is_inline_rename(&self) -> bool {
+        self.config.display().inline_rename()
+            && matches!(
+                self.actions.mode(),
+                ActionMode::Input {
+                    mode: InputMode::Rename,
+                    ..
+                }
+            )
+    }
+
+    /// Whether Finder tags are shown next to the entries, only on macOS.
+    pub fn show_finder_tags(&self) -> bool {
+        cfg!(target_os = "macos") && self.config.display().finder_tags()
+    }
+
+    /// Whether entries with another SELinux type than the current directory are colored and
+    /// the info overlay shows the context, only on Linux.
+    pub fn show_security_context(&self) -> bool {
+        cfg!(target_os = "linux") && self.config.display().security_context()
+    }
+
+    /// Whether the git status is shown, see [Display::git_status](crate::config::Display::git_status).
+    pub fn show_git_status(&self) -> bool {
+        self.profile
+            .as_ref()
+            .and_then(|(_, p)| p.git_status())
+            .unwrap_or(self.config.display().git_status())
+    }
+
+    fn watch_enabled(&self) -> bool {
+        self.profile
+            .as_ref()
+            .and_then(|(_, p)| p.watch())
+            .unwrap_or(self.config.watch())
+    }
+
+    fn dir_sizes_enabled(&self) -> bool {
+        self.profile
+            .as_ref()
+            .and_then(|(_, p)| p.dir_sizes())
+            .unwrap_or(self.config.display().dir_sizes())
+    }
+
+    fn auto_refresh_interval(&self) -> Option<Duration> {
+        match self
+            .profile
+            .as_ref()
+            .and_then(|(_, p)| p.auto_refresh_secs())
+        {
+            Some(secs) => (secs > 0).then(|| Duration::from_secs(secs)),
+            None => self.config.auto_refresh_interval(),
+        }
+    }
+
+    /// Queued notifications, drawn as toasts until they expire.
+    pub fn notifications(&self) -> &Notifications {
+        &self.notifications
+    }
+
+    /// The working directory runa was started in, the base of relative paths.
+    pub fn start_dir(&self) -> &Path {
+        &self.start_dir
     }
 
     pub fn overlays(&self) -> &OverlayStack {
@@ -155,8 +426,12 @@ impl<'a> AppState<'a> {
         &mut self.overlays
     }
 
-    pub fn set_notification_time(&mut self, t: Option<Instant>) {
-        self.notification_time = t;
+    pub fn animator(&self) -> &Animator {
+        &self.animator
+    }
+
+    pub fn animator_mut(&mut self) -> &mut Animator {
+        &mut self.animator
     }
 
     // Entry functions
@@ -180,14 +455,40 @@ impl<'a> AppState<'a> {
     pub fn tick(&mut self) -> bool {
         let mut changed = false;
 
-        if let Some(expiry) = self.notification_time
-            && Instant::now() >= expiry
+        // Tasks which did not fit into a full worker queue
+        self.workers.flush();
+
+        if self.notifications.expire(Instant::now()) {
+            changed = true;
+        }
+
+        // Reload after the watcher reported external changes, once no dialog is open
+        if self.dir_changed && !self.is_loading && !self.actions.is_input_mode() {
+            self.dir_changed = false;
+            let focus = self
+                .nav
+                .selected_shown_entry()
+                .map(|entry| entry.name().to_os_string());
+            self.request_dir_load(focus);
+        }
+
+        // Periodic refresh, for filesystems where changes are not noticed otherwise
+        if let Some(interval) = self.auto_refresh_interval()
+            && !self.is_loading
+            && !self.actions.is_input_mode()
+            && self.last_refresh.elapsed() >= interval
         {
-            self.notification_time = None;
+            let focus = self
+                .nav
+                .selected_shown_entry()
+                .map(|entry| entry.name().to_os_string());
+            self.request_dir_load(focus);
+        }
 
-            self.overlays_mut()
-                .retain(|o| !matches!(o, Overlay::Message { .. }));
+        self.warn_slow_load();
 
+        // Keep redrawing while a dialog or overlay transition is running
+        if self.animator.is_running() {
             changed = true;
         }
 
@@ -202,19 +503,27 @@ impl<'a> AppState<'a> {
             .selected_entry()
             .map(|entry| self.nav.current_dir().join(entry.name()));
 
-        // Find handling with debounce
+        // Find and grep handling with debounce
         if let ActionMode::Input {
-            mode: InputMode::Find,
+            mode: mode @ (InputMode::Find | InputMode::Grep | InputMode::Jump),
             ..
         } = self.actions.mode()
-            && let Some(query) = self.actions.take_query()
         {
-            if query.is_empty() {
-                self.actions.clear_find_results();
-            } else {
-                self.request_find(query);
+            let mode = *mode;
+            if let Some(query) = self.actions.take_query() {
+                if mode == InputMode::Jump {
+                    // An empty jump query lists all directories known to zoxide
+                    self.request_jump(query);
+                } else if query.is_empty() {
+                    self.actions.cancel_find();
+                    self.actions.clear_find_results();
+                } else if mode == InputMode::Grep {
+                    self.request_grep(query);
+                } else {
+                    self.request_find(query);
+                }
+                changed = true;
             }
-            changed = true;
         }
 
         // Process worker response
@@ -225,12 +534,55 @@ impl<'a> AppState<'a> {
                     path,
                     entries,
                     focus,
+                    dir_id,
                     request_id,
+                    complete,
+                    requested,
                 } => {
                     // only update nav if BOTH the ID and path match.
                     if request_id == self.nav.request_id() && path == self.nav.current_dir() {
+                        // The id is only unset on the first load after entering a directory
+                        if self.nav.dir_id().is_none() {
+                            self.stats.dirs_visited += 1;
+                            if focus.is_none() {
+                                self.stats.position_lookups += 1;
+                                if self.nav.get_position().contains_key(&path) {
+                                    self.stats.position_hits += 1;
+                                }
+                            }
+                            if self.config.zoxide() {
+                                let _ = self
+                                    .workers
+                                    .io_tx()
+                                    .send(WorkerTask::ZoxideAdd { path: path.clone() });
+                            }
+                        }
+                        if !complete {
+                            self.nav.update_partial_from_worker(path, entries, focus);
+                            self.nav.set_dir_id(dir_id);
+                            self.request_preview();
+                            continue;
+                        }
+
+                        self.report_slow(SlowKind::Directory, &path, requested);
+
+                        // The sorted directory replaces the chunks, keep the entry the user is on
+                        let focus = match focus {
+                            None if self.nav.is_partial() => self
+                                .nav
+                                .selected_shown_entry()
+                                .map(|e| e.name().to_os_string()),
+                            focus => focus,
+                        };
                         self.nav.update_from_worker(path, entries, focus);
+                        self.nav.set_dir_id(dir_id);
+                        self.select_navigate_match();
                         self.is_loading = false;
+                        self.sync_profile();
+                        self.watch_current_dir();
+                        self.request_git_status();
+                        self.request_metadata();
+                        self.sync_dir_sizes();
                         self.request_preview();
                         self.request_parent_content();
                         self.refresh_show_info_if_open();
@@ -238,6 +590,7 @@ impl<'a> AppState<'a> {
                     // PREVIEW CHECK: Must match the current preview request
                     else if request_id == self.preview.request_id() {
                         if current_selection_path.as_ref() == Some(&path) {
+                            self.report_slow(SlowKind::Preview, &path, requested);
                             self.preview.update_from_entries(entries, request_id);
 
                             let pos = current_selection_path
@@ -260,21 +613,71 @@ impl<'a> AppState<'a> {
 
                         self.parent
                             .update_from_entries(entries, &current_name, request_id, &path);
+                    } else {
+                        self.stats.stale_responses += 1;
                     }
                 }
-                WorkerResponse::PreviewLoaded { lines, request_id } => {
+                WorkerResponse::DirectoryChunk {
+                    path,
+                    entries,
+                    request_id,
+                } => {
+                    // Chunks for the preview and parent pane are skipped, they wait for the whole directory
+                    if request_id == self.nav.request_id() && path == self.nav.current_dir() {
+                        self.nav.append_entries(entries);
+                    }
+                }
+                WorkerResponse::PreviewLoaded {
+                    lines,
+                    styled,
+                    request_id,
+                    requested,
+                } => {
                     if request_id == self.preview.request_id() {
-                        self.preview.update_content(lines, request_id);
+                        if let Some(path) = self.preview.current_path().cloned() {
+                            self.report_slow(SlowKind::Preview, &path, requested);
+                        }
+                        self.preview.update_content(lines, styled, request_id);
+                    } else {
+                        self.stats.stale_responses += 1;
                     }
                 }
 
                 WorkerResponse::OperationComplete {
-                    message: _,
-                    request_id: _,
+                    message,
+                    request_id,
                     need_reload,
                     focus,
+                    focus_dir,
                 } => {
-                    if need_reload {
+                    let (targets, keep_focus) = self.actions.take_finished_op(request_id);
+                    self.notify_worker(Severity::Info, message);
+                    self.nav.finish_operation(&targets);
+                    // Marked paths may have been changed or removed by the operation
+                    self.marked_sizes.clear();
+                    self.sync_marked_sizes();
+
+                    // An entry created in a subdirectory is shown in it
+                    if let Some(dir) = focus_dir
+                        && self.config.focus_new_items()
+                        && dir != self.nav.current_dir()
+                    {
+                        self.nav.save_position();
+                        self.nav.set_path(dir);
+                        self.request_dir_load(focus);
+                        self.request_parent_content();
+                    } else if need_reload {
+                        // Without focus on new items the cursor stays on the selected entry,
+                        // a delete moves it to the entry taking the place of the deleted one
+                        let focus = if keep_focus.is_some() {
+                            keep_focus
+                        } else if self.config.focus_new_items() {
+                            focus
+                        } else {
+                            self.nav
+                                .selected_shown_entry()
+                                .map(|e| e.name().to_os_string())
+                        };
                         self.request_dir_load(focus);
                         self.request_parent_content();
                     }
@@ -282,41 +685,372 @@ impl<'a> AppState<'a> {
 
                 WorkerResponse::FindResults {
                     base_dir,
-                    results,
+                    mut results,
                     request_id,
+                    complete,
                 } => {
                     if base_dir == self.nav.current_dir()
                         && request_id == self.actions.find_request_id()
                     {
-                        self.actions.set_find_results(results);
+                        // A stable sort keeps the score order within both groups
+                        if self.config.find().dirs_first() {
+                            results.sort_by_key(|r| !r.is_dir());
+                        }
+                        self.actions.apply_find_results(results, complete);
+                    } else {
+                        self.stats.stale_responses += 1;
+                    }
+                }
+
+                WorkerResponse::DirectoryMissing {
+                    path,
+                    renamed,
+                    request_id,
+                } => {
+                    if request_id == self.nav.request_id() && path == self.nav.current_dir() {
+                        match renamed {
+                            Some(renamed) => self.follow_renamed_dir(renamed),
+                            None => self.leave_missing_dir(&path),
+                        }
+                    } else if request_id == self.preview.request_id() {
+                        self.preview
+                            .set_error(format!("{} no longer exists", path.display()));
+                    }
+                }
+
+                WorkerResponse::GitStatus {
+                    path,
+                    info,
+                    request_id,
+                } => {
+                    if request_id == self.git_request_id {
+                        self.git_status = info.map(|info| (path, info));
+                    }
+                }
+
+                WorkerResponse::FileDetailsLoaded {
+                    path,
+                    details,
+                    request_id,
+                } => {
+                    if request_id == self.file_details_request_id
+                        && path.parent() == Some(self.nav.current_dir())
+                    {
+                        self.set_file_details(&path, details);
+                    }
+                }
+
+                WorkerResponse::MetadataLoaded {
+                    path,
+                    metadata,
+                    request_id,
+                } => {
+                    if request_id == self.metadata_request_id && path == self.nav.current_dir() {
+                        self.nav.update_metadata(metadata);
+                    }
+                }
+
+                WorkerResponse::DirSizeLoaded {
+                    path,
+                    name,
+                    size,
+                    request_id,
+                } => {
+                    if request_id == self.dir_size_request_id && path == self.nav.current_dir() {
+                        self.set_dir_size(name, size);
+                    }
+                }
+
+                WorkerResponse::MarkedSizeLoaded { path, size } => {
+                    if let Some(known) = self.marked_sizes.get_mut(&path) {
+                        *known = Some(size);
                     }
                 }
 
+                WorkerResponse::DirectoryChanged { path } => {
+                    if path == self.nav.current_dir() {
+                        self.dir_changed = true;
+                    } else if Some(path.as_path()) == self.nav.current_dir().parent() {
+                        self.request_parent_content();
+                    }
+                }
+
+                WorkerResponse::FileBusy { op } => {
+                    self.prompt_busy_retry(op);
+                }
+                WorkerResponse::Warning(text) => {
+                    self.notify_worker(Severity::Warn, text);
+                }
                 WorkerResponse::Error(e) => {
-                    self.preview.set_error(e);
+                    self.notify_worker(Severity::Error, e);
                 }
             }
         }
         changed
     }
 
+    /// Replaces the config, e.g. after `runa.toml` was edited, without restarting.
+    ///
+    /// Rebuilds the keymap, resets the options toggled at runtime to the new config and reloads
+    /// the panes, so theme and display changes show on the next frame. Read-only mode and the
+    /// root guard stay on if they were on. The worker threads keep the queue sizes they were started with.
+    pub fn set_config(&mut self, config: Config) {
+        for warning in config.warnings() {
+            self.notify(Severity::Warn, warning.clone(), CONFIG_WARNING_TTL);
+        }
+        self.keymap = Keymap::from_config(&config);
+        self.pending_keys.clear();
+        self.show_hidden = config.show_hidden();
+        self.show_parent = config.display().parent();
+        self.show_preview = config.display().preview();
+        self.readonly |= config.readonly();
+        self.nav.set_filter_mode(config.filter_mode());
+        self.nav.set_marker_policy(config.marker_policy());
+        self.config = Arc::new(config);
+
+        // Profiles and the watcher are applied again once the directory is loaded
+        self.profile = None;
+        self.profile_dir = None;
+        self.instant_preview = self.default_instant_preview();
+        if self.watched_dir.take().is_some() {
+            let _ = self
+                .workers
+                .watch_tx()
+                .send(WorkerTask::Watch { paths: Vec::new() });
+        }
+
+        self.relayout();
+        let focus = self
+            .nav
+            .selected_shown_entry()
+            .map(|entry| entry.name().to_os_string());
+        self.request_dir_load(focus);
+        self.parent.clear();
+        self.request_parent_content();
+    }
+
+    /// Applies the first [Profile] matching the current directory, by path prefix or by the
+    /// filesystem type of its mount. Only looked up when the current directory changed.
+    fn sync_profile(&mut self) {
+        let config = Arc::clone(&self.config);
+        let profiles = config.profiles();
+        if profiles.is_empty() || self.profile_dir.as_deref() == Some(self.nav.current_dir()) {
+            return;
+        }
+        let dir = self.nav.current_dir().to_path_buf();
+        let fs_type = if profiles.uses_fs_types() {
+            fs_type(&dir)
+        } else {
+            None
+        };
+        let profile = profiles.find(&dir, fs_type.as_deref());
+        self.profile_dir = Some(dir);
+        if profile.map(|(name, _)| name) == self.active_profile() {
+            return;
+        }
+
+        self.profile = profile.map(|(name, profile)| (name.to_string(), profile.clone()));
+        self.instant_preview = self.default_instant_preview();
+        if !self.watch_enabled() && self.watched_dir.take().is_some() {
+            let _ = self
+                .workers
+                .watch_tx()
+                .send(WorkerTask::Watch { paths: Vec::new() });
+        }
+    }
+
+    /// Asks the watcher to watch the current and parent directory, if watching is enabled
+    /// and the current directory changed since the last call.
+    fn watch_current_dir(&mut self) {
+        if !self.watch_enabled() || self.watched_dir.as_deref() == Some(self.nav.current_dir()) {
+            return;
+        }
+        let current = self.nav.current_dir().to_path_buf();
+        let mut paths = vec![current.clone()];
+        paths.extend(current.parent().map(Path::to_path_buf));
+        let _ = self.workers.watch_tx().send(WorkerTask::Watch { paths });
+        self.watched_dir = Some(current);
+    }
+
+    /// Follows a current directory that was renamed within its parent,
+    /// found by the io worker through the identity recorded when it was last loaded.
+    fn follow_renamed_dir(&mut self, renamed: PathBuf) {
+        let focus = self
+            .nav
+            .selected_shown_entry()
+            .map(|entry| entry.name().to_os_string());
+        self.nav.follow_rename(renamed);
+        self.request_dir_load(focus);
+        self.request_parent_content();
+    }
+
+    /// Moves to the nearest existing ancestor of a current directory that was deleted
+    /// or unmounted, and explains the jump with a notification.
+    fn leave_missing_dir(&mut self, missing: &Path) {
+        let Some(ancestor) = missing.ancestors().skip(1).find(|p| p.is_dir()) else {
+            self.is_loading = false;
+            self.preview
+                .set_error(format!("{} no longer exists", missing.display()));
+            return;
+        };
+        let ancestor = ancestor.to_path_buf();
+
+        self.notify(
+            Severity::Warn,
+            format!(
+                "{} no longer exists, moved to {}",
+                missing.display(),
+                ancestor.display()
+            ),
+            Duration::from_secs(5),
+        );
+        self.nav.set_path(ancestor);
+        self.request_dir_load(None);
+        self.request_parent_content();
+    }
+
+    /// Loads the persisted find history, if enabled.
+    /// A missing or unreadable history file starts an empty history.
+    fn load_find_history(&mut self) {
+        let find_cfg = self.config.find();
+        let history = find_history_path()
+            .filter(|_| find_cfg.persist_history())
+            .and_then(|path| FindHistory::load(&path, find_cfg.history_size()).ok())
+            .unwrap_or_else(|| FindHistory::new(find_cfg.history_size()));
+        self.actions.set_find_history(history);
+    }
+
+    /// Remembers a find or grep query and persists the history, if enabled.
+    pub fn remember_find_query(&mut self, query: &str) {
+        self.actions.find_history_mut().push(query);
+        if self.config.find().persist_history()
+            && let Some(path) = find_history_path()
+        {
+            let _ = self.actions.find_history().save(&path);
+        }
+    }
+
+    /// Returns true if `key` is bound to the open action.
+    pub fn is_open_key(&self, key: KeyEvent) -> bool {
+        matches!(
+            self.keymap.lookup(key),
+            Some(Action::File(FileAction::Open))
+        )
+    }
+
+    /// Returns true if `key` is bound to the toggle marker action.
+    pub fn is_marker_key(&self, key: KeyEvent) -> bool {
+        matches!(
+            self.keymap.lookup(key),
+            Some(Action::Nav(NavAction::ToggleMarker))
+        )
+    }
+
     /// Central key handlers
     ///
     /// Coordinates the action and handler module functions.
     pub fn handle_keypress(&mut self, key: KeyEvent) -> KeypressResult {
+        let result = if self.tutorial.is_some() {
+            let action = (!self.actions.is_input_mode())
+                .then(|| self.keymap.lookup(key))
+                .flatten();
+            self.handle_tutorial_keypress(action, |app| app.dispatch_keypress(key))
+        } else {
+            self.dispatch_keypress(key)
+        };
+        self.sync_marked_sizes();
+        result
+    }
+
+    fn dispatch_keypress(&mut self, key: KeyEvent) -> KeypressResult {
         if self.actions.is_input_mode() {
+            self.pending_keys.clear();
             return self.handle_input_mode(key);
         }
+        if matches!(self.overlays.top(), Some(Overlay::MarkedList { .. })) {
+            self.pending_keys.clear();
+            return self.handle_marked_list(key);
+        }
+        if let Some(result) = self.expire_key_sequence()
+            && matches!(result, KeypressResult::Quit)
+        {
+            return result;
+        }
 
-        if let Some(action) = self.keymap.lookup(key) {
-            match action {
-                Action::System(SystemAction::Quit) => return KeypressResult::Quit,
-                Action::Nav(nav_act) => return self.handle_nav_action(nav_act),
-                Action::File(file_act) => return self.handle_file_action(file_act),
+        self.pending_keys.push(key);
+        match self.keymap.match_keys(&self.pending_keys) {
+            KeyMatch::Pending => {
+                self.pending_since = Instant::now();
+                KeypressResult::Consumed
+            }
+            KeyMatch::Action(action) => {
+                self.pending_keys.clear();
+                self.run_action(action)
+            }
+            KeyMatch::Unbound => {
+                let keys = std::mem::take(&mut self.pending_keys);
+                let Some((last, started)) = keys.split_last() else {
+                    return KeypressResult::Continue;
+                };
+                if started.is_empty() {
+                    return KeypressResult::Continue;
+                }
+                // The keys so far may be bound on their own, e.g. a command on "g" next to "g g",
+                // otherwise the key which does not continue the sequence cancels it
+                let Some(action) = self.keymap.complete(started) else {
+                    return KeypressResult::Consumed;
+                };
+                match self.run_action(action) {
+                    KeypressResult::Continue | KeypressResult::Consumed => {
+                        self.dispatch_keypress(*last)
+                    }
+                    result => result,
+                }
             }
         }
+    }
+
+    /// Ends a key sequence which waited longer than [KEY_SEQUENCE_TIMEOUT] for its next key.
+    ///
+    /// Called by the event loop while no key is pressed. Runs the action bound to the keys
+    /// so far, e.g. a command on `"g"` next to `"g g"`.
+    ///
+    /// # Returns
+    /// The result of the action, [KeypressResult::Consumed] if the keys are not bound on their own
+    /// and `None` if no sequence expired.
+    pub fn expire_key_sequence(&mut self) -> Option<KeypressResult> {
+        if self.pending_keys.is_empty() || self.pending_since.elapsed() < KEY_SEQUENCE_TIMEOUT {
+            return None;
+        }
+        let keys = std::mem::take(&mut self.pending_keys);
+        Some(match self.keymap.complete(&keys) {
+            Some(action) => self.run_action(action),
+            None => KeypressResult::Consumed,
+        })
+    }
 
-        KeypressResult::Continue
+    /// The keys of a started key sequence, shown in the status line.
+    pub fn pending_keys(&self) -> &[KeyEvent] {
+        &self.pending_keys
+    }
+
+    /// Runs the action of a key or key sequence.
+    fn run_action(&mut self, action: Action) -> KeypressResult {
+        match action {
+            Action::System(SystemAction::Quit) => return KeypressResult::Quit,
+            Action::System(SystemAction::Stats) => self.toggle_stats(),
+            Action::System(SystemAction::DumpState) => self.dump_state_and_notify(),
+            Action::System(SystemAction::ToggleInstantPreview) => self.toggle_instant_preview(),
+            Action::System(SystemAction::ToggleHidden) => self.toggle_hidden(),
+            Action::System(SystemAction::TogglePreview) => self.toggle_preview_pane(),
+            Action::System(SystemAction::ToggleParent) => self.toggle_parent_pane(),
+            Action::System(SystemAction::ReloadConfig) => self.reload_config(),
+            Action::System(SystemAction::Unlock) => self.toggle_root_lock(),
+            Action::Nav(nav_act) => return self.handle_nav_action(nav_act),
+            Action::File(file_act) => return self.handle_file_action(file_act),
+            Action::Custom(idx) => return self.handle_custom_command(idx),
+        }
+        KeypressResult::Consumed
     }
 
     // Worker requests functions for directory loading, preview and parent pane content
@@ -327,22 +1061,80 @@ impl<'a> AppState<'a> {
     /// * `focus` - Optional OsString to focus on a specific entry after loading
     pub fn request_dir_load(&mut self, focus: Option<std::ffi::OsString>) {
         self.is_loading = true;
+        self.last_refresh = Instant::now();
         let request_id = self.nav.prepare_new_request();
         let _ = self.workers.io_tx().send(WorkerTask::LoadDirectory {
             path: self.nav.current_dir().to_path_buf(),
             focus,
+            dir_id: self.nav.dir_id(),
             dirs_first: self.config.dirs_first(),
-            show_hidden: self.config.show_hidden(),
+            sort_expr: self.config.sort_expr().cloned(),
+            show_hidden: self.show_hidden,
             show_system: self.config.show_system(),
             case_insensitive: self.config.case_insensitive(),
             always_show: Arc::clone(self.config.always_show()),
             pane_width: self.metrics.main_width,
+            ascii_only: self.config.display().ascii_only(),
             request_id,
+            requested: self.last_refresh,
         });
     }
 
-    /// Requests a preview load for the currently selected entry in the navigation pane
+    /// Hints once per request that the directory load is still running after `slow_warning_ms`.
+    fn warn_slow_load(&mut self) {
+        let Some(limit) = self.config.workers().slow_warning() else {
+            return;
+        };
+        let elapsed = self.last_refresh.elapsed();
+        if !self.is_loading
+            || elapsed < limit
+            || self.slow_load_warned == Some(self.nav.request_id())
+        {
+            return;
+        }
+        self.slow_load_warned = Some(self.nav.request_id());
+        let text = format!(
+            "Slow filesystem: loading {} for {:.1}s",
+            self.nav.current_dir().display(),
+            elapsed.as_secs_f64()
+        );
+        self.notify_worker(Severity::Warn, text);
+    }
+
+    /// Logs a directory load or preview which took longer than `slow_warning_ms`, and hints at it
+    /// unless [warn_slow_load](Self::warn_slow_load) already did.
+    ///
+    /// # Arguments
+    /// * `kind` - What was loaded.
+    /// * `path` - The loaded path.
+    /// * `requested` - When the load was requested, as handed back by the worker.
+    fn report_slow(&mut self, kind: SlowKind, path: &Path, requested: Instant) {
+        let Some(limit) = self.config.workers().slow_warning() else {
+            return;
+        };
+        let elapsed = requested.elapsed();
+        if elapsed < limit {
+            return;
+        }
+        self.stats.record_slow(kind, path.to_path_buf(), elapsed);
+        if kind == SlowKind::Directory && self.slow_load_warned == Some(self.nav.request_id()) {
+            return;
+        }
+        let text = format!(
+            "Slow filesystem: {} {} took {:.1}s",
+            kind.label(),
+            path.display(),
+            elapsed.as_secs_f64()
+        );
+        self.notify_worker(Severity::Warn, text);
+    }
+
+    /// Requests a preview load for the currently selected entry in the navigation pane.
+    /// Nothing is loaded while the preview pane is hidden.
     pub fn request_preview(&mut self) {
+        if !self.show_preview {
+            return;
+        }
         if let Some(entry) = self.nav.selected_shown_entry() {
             let path = self.nav.current_dir().join(entry.name());
             let req_id = self.preview.prepare_new_request(path.clone());
@@ -352,13 +1144,17 @@ impl<'a> AppState<'a> {
                 let _ = self.workers.io_tx().send(WorkerTask::LoadDirectory {
                     path,
                     focus: None,
+                    dir_id: None,
                     dirs_first: self.config.dirs_first(),
-                    show_hidden: self.config.show_hidden(),
+                    sort_expr: self.config.sort_expr().cloned(),
+                    show_hidden: self.show_hidden,
                     show_system: self.config.show_system(),
                     case_insensitive: self.config.case_insensitive(),
                     always_show: Arc::clone(self.config.always_show()),
                     pane_width: self.metrics.preview_width,
+                    ascii_only: self.config.display().ascii_only(),
                     request_id: req_id,
+                    requested: Instant::now(),
                 });
             } else {
                 let preview_options = self.config.display().preview_options();
@@ -375,7 +1171,17 @@ impl<'a> AppState<'a> {
                     pane_width: self.metrics.preview_width,
                     preview_method,
                     args: bat_args,
+                    params: PreviewParams {
+                        syntax_theme: preview_options
+                            .syntax_highlighting()
+                            .then(|| self.config.theme().syntax_theme_name().to_owned()),
+                        text: TextRendering {
+                            tab_width: preview_options.tab_width(),
+                            show_control_chars: preview_options.show_control_chars(),
+                        },
+                    },
                     request_id: req_id,
+                    requested: Instant::now(),
                 });
             }
         } else {
@@ -383,8 +1189,12 @@ impl<'a> AppState<'a> {
         }
     }
 
-    /// Requests loading of the parent directory content for the parent pane
+    /// Requests loading of the parent directory content for the parent pane.
+    /// Nothing is loaded while the parent pane is hidden.
     pub fn request_parent_content(&mut self) {
+        if !self.show_parent {
+            return;
+        }
         if let Some(parent_path) = self.nav.current_dir().parent() {
             let parent_path_buf = parent_path.to_path_buf();
 
@@ -394,13 +1204,17 @@ impl<'a> AppState<'a> {
                 let _ = self.workers.io_tx().send(WorkerTask::LoadDirectory {
                     path: parent_path_buf,
                     focus: None,
+                    dir_id: None,
                     dirs_first: self.config.dirs_first(),
-                    show_hidden: self.config.show_hidden(),
+                    sort_expr: self.config.sort_expr().cloned(),
+                    show_hidden: self.show_hidden,
                     show_system: self.config.show_system(),
                     case_insensitive: self.config.case_insensitive(),
                     always_show: Arc::clone(self.config.always_show()),
                     pane_width: self.metrics.parent_width,
+                    ascii_only: self.config.display().ascii_only(),
                     request_id: req_id,
+                    requested: Instant::now(),
                 });
             }
         } else {
@@ -422,9 +1236,263 @@ impl<'a> AppState<'a> {
         let _ = self.workers.find_tx().send(WorkerTask::FindRecursive {
             base_dir: self.nav.current_dir().to_path_buf(),
             query,
+            kind: self.actions.find_kind(),
+            filters: self.config.find().search_filters(),
+            max_results: self.config().max_find_results(),
+            request_id,
+            cancel: cancel_token,
+        });
+    }
+
+    /// Requests a recursive content search (grep) for the current navigation directory
+    pub fn request_grep(&mut self, query: String) {
+        self.actions.cancel_find();
+
+        let request_id = self.actions.prepare_new_find_request();
+        let cancel_token = Arc::new(AtomicBool::new(false));
+
+        self.actions
+            .set_cancel_find_token(Arc::clone(&cancel_token));
+
+        let _ = self.workers.find_tx().send(WorkerTask::GrepRecursive {
+            base_dir: self.nav.current_dir().to_path_buf(),
+            query,
+            filters: self.config.find().search_filters(),
             max_results: self.config().max_find_results(),
             request_id,
             cancel: cancel_token,
         });
     }
+
+    /// Requests the git status of the current directory, if shown in the header
+    /// Requests the metadata of the current entries for the detail view, if it is enabled.
+    pub fn request_metadata(&mut self) {
+        if !self.show_details()
+            && !self.show_finder_tags()
+            && !self.show_security_context()
+            && !self.config.file_styles().colors_executables()
+        {
+            return;
+        }
+        self.metadata_request_id = self.metadata_request_id.wrapping_add(1);
+        let names = self
+            .nav
+            .entries()
+            .iter()
+            .map(|e| e.name().to_os_string())
+            .collect();
+        let _ = self.workers.meta_tx().send(WorkerTask::LoadMetadata {
+            path: self.nav.current_dir().to_path_buf(),
+            names,
+            git: self.show_git_status(),
+            tags: self.show_finder_tags(),
+            context: self.show_security_context(),
+            request_id: self.metadata_request_id,
+        });
+    }
+
+    /// Requests the recursive sizes of the named subdirectories of the current directory.
+    ///
+    /// Requests for the same directory are queued behind each other, a request for another
+    /// directory cancels the previous ones.
+    ///
+    /// # Arguments
+    /// * `names` - The names of the subdirectories.
+    pub fn request_dir_sizes(&mut self, names: Vec<OsString>) {
+        if names.is_empty() {
+            return;
+        }
+        self.nav.set_dir_sizes_pending(&names);
+        let dir = self.nav.current_dir().to_path_buf();
+        let cancel = match &self.dir_size_cancel {
+            Some((path, cancel)) if *path == dir => Arc::clone(cancel),
+            _ => {
+                self.cancel_dir_sizes();
+                self.dir_size_request_id = self.dir_size_request_id.wrapping_add(1);
+                let cancel = Arc::new(AtomicBool::new(false));
+                self.dir_size_cancel = Some((dir.clone(), Arc::clone(&cancel)));
+                cancel
+            }
+        };
+        let _ = self.workers.size_tx().send(WorkerTask::DirSize {
+            path: dir,
+            names,
+            excludes: Arc::clone(self.config.scan_excludes()),
+            cancel,
+            request_id: self.dir_size_request_id,
+        });
+    }
+
+    /// Requests the sizes of all subdirectories of the current directory which are not known yet.
+    pub fn request_all_dir_sizes(&mut self) {
+        let names = self
+            .nav
+            .entries()
+            .iter()
+            .filter(|e| e.is_dir() && self.nav.dir_size(e.name()).is_none())
+            .map(|e| e.name().to_os_string())
+            .collect();
+        self.request_dir_sizes(names);
+    }
+
+    /// Computes the sizes of all subdirectories of the current directory again, also the known
+    /// ones, e.g. after their contents changed.
+    pub fn refresh_dir_sizes(&mut self) {
+        let dir = self.nav.current_dir().to_path_buf();
+        self.dir_size_cache
+            .retain(|path, _| path.parent() != Some(dir.as_path()));
+        self.nav.clear_dir_sizes();
+        self.request_all_dir_sizes();
+    }
+
+    /// Cancels the size requests of a directory that was left, fills in the sizes computed on
+    /// an earlier visit and requests the others if `dir_sizes` is enabled.
+    fn sync_dir_sizes(&mut self) {
+        if self
+            .dir_size_cancel
+            .as_ref()
+            .is_some_and(|(path, _)| path != self.nav.current_dir())
+        {
+            self.cancel_dir_sizes();
+        }
+        let dir = self.nav.current_dir();
+        let cached: Vec<_> = self
+            .nav
+            .entries()
+            .iter()
+            .filter(|e| e.is_dir())
+            .filter_map(|e| {
+                let size = self.dir_size_cache.get(&dir.join(e.name()))?;
+                Some((e.name().to_os_string(), *size))
+            })
+            .collect();
+        for (name, size) in cached {
+            self.nav.set_dir_size(name, size);
+        }
+        if self.dir_sizes_enabled() {
+            self.request_all_dir_sizes();
+        }
+    }
+
+    fn cancel_dir_sizes(&mut self) {
+        if let Some((_, cancel)) = self.dir_size_cancel.take() {
+            cancel.store(true, Ordering::Relaxed);
+        }
+    }
+
+    /// Stores a computed directory size and shows it in the info overlay, if it is open on it.
+    /// The size is also cached for later visits of the directory.
+    fn set_dir_size(&mut self, name: OsString, size: PathSize) {
+        if self.dir_size_cache.len() >= DIR_SIZE_CACHE_LIMIT {
+            self.dir_size_cache.clear();
+        }
+        self.dir_size_cache
+            .insert(self.nav.current_dir().join(&name), size);
+        let idx = self.overlays.find_index(|o| {
+            matches!(o, Overlay::ShowInfo { info }
+                if *info.file_type() == FileType::Directory && *info.name() == name)
+        });
+        if let Some(Overlay::ShowInfo { info }) = idx.and_then(|i| self.overlays.get_mut(i)) {
+            info.set_size(size.bytes());
+        }
+        self.nav.set_dir_size(name, size);
+    }
+
+    /// Fills the [FileDetails] read by the info worker into the file info overlay of `path`.
+    fn set_file_details(&mut self, path: &Path, details: FileDetails) {
+        let name = path.file_name().unwrap_or_default();
+        let idx = self
+            .overlays
+            .find_index(|o| matches!(o, Overlay::ShowInfo { info } if info.name() == name));
+        if let Some(Overlay::ShowInfo { info }) = idx.and_then(|i| self.overlays.get_mut(i)) {
+            info.set_details(details);
+        }
+    }
+
+    /// Size of the marked `path`, or `None` while the size worker is still walking it.
+    pub fn marked_size(&self, path: &Path) -> Option<u64> {
+        self.marked_sizes.get(path).copied().flatten()
+    }
+
+    /// Number of marked paths, their combined size so far and whether all sizes are known.
+    pub fn marked_summary(&self) -> (usize, u64, bool) {
+        let markers = self.nav.markers();
+        let mut total = 0;
+        let mut complete = true;
+        for path in markers {
+            match self.marked_sizes.get(path).copied().flatten() {
+                Some(size) => total += size,
+                None => complete = false,
+            }
+        }
+        (markers.len(), total, complete)
+    }
+
+    /// Requests the sizes of newly marked paths and forgets the sizes of unmarked ones.
+    ///
+    /// Clearing all markers cancels the sizes still being computed.
+    fn sync_marked_sizes(&mut self) {
+        let markers = self.nav.markers();
+        if markers.is_empty() {
+            if !self.marked_sizes.is_empty() {
+                self.marked_size_cancel.store(true, Ordering::Relaxed);
+                self.marked_size_cancel = Arc::new(AtomicBool::new(false));
+                self.marked_sizes.clear();
+            }
+            return;
+        }
+
+        self.marked_sizes.retain(|path, _| markers.contains(path));
+        let mut paths: Vec<PathBuf> = markers
+            .iter()
+            .filter(|path| !self.marked_sizes.contains_key(*path))
+            .cloned()
+            .collect();
+        if paths.is_empty() {
+            return;
+        }
+        paths.sort();
+        for path in &paths {
+            self.marked_sizes.insert(path.clone(), None);
+        }
+        let _ = self.workers.size_tx().send(WorkerTask::MarkedSize {
+            paths,
+            excludes: Arc::clone(self.config.scan_excludes()),
+            cancel: Arc::clone(&self.marked_size_cancel),
+        });
+    }
+
+    pub fn request_git_status(&mut self) {
+        if !self.show_git_status() {
+            return;
+        }
+        self.git_request_id = self.git_request_id.wrapping_add(1);
+        let _ = self.workers.info_tx().send(WorkerTask::GitStatus {
+            path: self.nav.current_dir().to_path_buf(),
+            request_id: self.git_request_id,
+        });
+    }
+
+    /// Requests the [FileDetails] of `path` for the file info overlay from the info worker.
+    pub(super) fn request_file_details(&mut self, path: PathBuf) {
+        self.file_details_request_id = self.file_details_request_id.wrapping_add(1);
+        let _ = self.workers.info_tx().send(WorkerTask::FileDetails {
+            path,
+            excludes: Arc::clone(self.config.scan_excludes()),
+            request_id: self.file_details_request_id,
+        });
+    }
+
+    /// Requests the directories matching `query` from zoxide for the jump dialog
+    pub fn request_jump(&mut self, query: String) {
+        self.actions.cancel_find();
+
+        let request_id = self.actions.prepare_new_find_request();
+        let _ = self.workers.find_tx().send(WorkerTask::ZoxideQuery {
+            base_dir: self.nav.current_dir().to_path_buf(),
+            query,
+            max_results: self.config().max_find_results(),
+            request_id,
+        });
+    }
 }
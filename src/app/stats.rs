@@ -0,0 +1,138 @@
+//! Session statistics of the app, shown in the statistics overlay together with the
+//! [WorkerStats](crate::core::worker::WorkerStats) of the worker threads.
+//!
+//! Also keeps the log of slow directory loads and previews, for hangs on network mounts.
+
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+/// The number of slow operations kept in [SessionStats::slow_operations].
+pub const SLOW_LOG_LIMIT: usize = 16;
+
+/// What took longer than `slow_warning_ms`.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SlowKind {
+    Directory,
+    Preview,
+}
+
+impl SlowKind {
+    pub fn label(self) -> &'static str {
+        match self {
+            SlowKind::Directory => "directory",
+            SlowKind::Preview => "preview",
+        }
+    }
+}
+
+/// A directory load or preview which took longer than `slow_warning_ms`.
+///
+/// # Fields
+/// * `kind` - Whether a directory or a preview was loaded.
+/// * `path` - The loaded path.
+/// * `elapsed` - The time from the request to the result, including the wait in the queue.
+#[derive(Debug, Clone)]
+pub struct SlowOperation {
+    kind: SlowKind,
+    path: PathBuf,
+    elapsed: Duration,
+}
+
+impl SlowOperation {
+    pub fn kind(&self) -> SlowKind {
+        self.kind
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+}
+
+/// Counters of the current session, updated by [AppState](crate::app::AppState).
+///
+/// # Fields
+/// * `started` - When the session started.
+/// * `dirs_visited` - Directories entered in the main pane.
+/// * `files_opened` - Files opened in the editor, an opener or the OS default application.
+/// * `position_lookups` - Directory loads that restored a remembered cursor position.
+/// * `position_hits` - Lookups that found a remembered position.
+/// * `stale_responses` - Worker responses discarded because a newer request superseded them.
+/// * `slow_count` - Directory loads and previews slower than `slow_warning_ms`.
+/// * `slow_operations` - The last [SLOW_LOG_LIMIT] of them, oldest first.
+#[derive(Debug)]
+pub struct SessionStats {
+    started: Instant,
+    pub(super) dirs_visited: u64,
+    pub(super) files_opened: u64,
+    pub(super) position_lookups: u64,
+    pub(super) position_hits: u64,
+    pub(super) stale_responses: u64,
+    slow_count: u64,
+    slow_operations: VecDeque<SlowOperation>,
+}
+
+impl SessionStats {
+    pub fn uptime(&self) -> Duration {
+        self.started.elapsed()
+    }
+
+    pub fn dirs_visited(&self) -> u64 {
+        self.dirs_visited
+    }
+
+    pub fn files_opened(&self) -> u64 {
+        self.files_opened
+    }
+
+    pub fn position_lookups(&self) -> u64 {
+        self.position_lookups
+    }
+
+    pub fn position_hits(&self) -> u64 {
+        self.position_hits
+    }
+
+    pub fn stale_responses(&self) -> u64 {
+        self.stale_responses
+    }
+
+    pub fn slow_count(&self) -> u64 {
+        self.slow_count
+    }
+
+    pub fn slow_operations(&self) -> &VecDeque<SlowOperation> {
+        &self.slow_operations
+    }
+
+    pub(super) fn record_slow(&mut self, kind: SlowKind, path: PathBuf, elapsed: Duration) {
+        self.slow_count += 1;
+        if self.slow_operations.len() == SLOW_LOG_LIMIT {
+            self.slow_operations.pop_front();
+        }
+        self.slow_operations.push_back(SlowOperation {
+            kind,
+            path,
+            elapsed,
+        });
+    }
+}
+
+impl Default for SessionStats {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            dirs_visited: 0,
+            files_opened: 0,
+            position_lookups: 0,
+            position_hits: 0,
+            stale_responses: 0,
+            slow_count: 0,
+            slow_operations: VecDeque::new(),
+        }
+    }
+}
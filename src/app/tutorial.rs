@@ -0,0 +1,267 @@
+//! Quick-start tutorial for runa (`rn --tutorial`).
+//!
+//! The tutorial is a small scripted scenario: a list of [TutorialStep]s, each explaining one
+//! feature, naming the configured keys for it and waiting for a [StepGoal].
+//! After every keypress [AppState] checks the goal of the current step and moves on once it is met.
+//!
+//! It runs inside a playground directory with sample files (see [create_playground]),
+//! so yanking, pasting and filtering never touch real files.
+
+use crate::app::actions::{ActionMode, InputMode};
+use crate::app::keymap::{Action, FileAction, NavAction, SystemAction};
+use crate::app::{AppState, KeypressResult};
+use crate::config::Keys;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// What the user has to do to complete a [TutorialStep].
+///
+/// Variants:
+/// * `Action` - Trigger this keymap action.
+/// * `InputMode` - Open this dialog.
+/// * `Marked` - Have at least one marked entry.
+/// * `Yanked` - Have entries in the clipboard.
+/// * `Filtered` - Apply a non-empty filter.
+/// * `Done` - Last step, completed by quitting.
+#[derive(Clone, Copy, PartialEq)]
+pub enum StepGoal {
+    Action(Action),
+    InputMode(InputMode),
+    Marked,
+    Yanked,
+    Filtered,
+    Done,
+}
+
+/// A single step of the tutorial.
+pub struct TutorialStep {
+    title: &'static str,
+    text: &'static str,
+    keys: fn(&Keys) -> &Vec<String>,
+    goal: StepGoal,
+}
+
+impl TutorialStep {
+    pub fn title(&self) -> &'static str {
+        self.title
+    }
+
+    pub fn text(&self) -> &'static str {
+        self.text
+    }
+
+    /// The configured keys which complete this step.
+    pub fn keys<'k>(&self, keys: &'k Keys) -> &'k [String] {
+        (self.keys)(keys)
+    }
+
+    pub fn goal(&self) -> StepGoal {
+        self.goal
+    }
+}
+
+/// The scenario of the tutorial, in order.
+const STEPS: &[TutorialStep] = &[
+    TutorialStep {
+        title: "Moving around",
+        text: "Move the selection down.",
+        keys: Keys::go_down,
+        goal: StepGoal::Action(Action::Nav(NavAction::GoDown)),
+    },
+    TutorialStep {
+        title: "Moving around",
+        text: "And back up again.",
+        keys: Keys::go_up,
+        goal: StepGoal::Action(Action::Nav(NavAction::GoUp)),
+    },
+    TutorialStep {
+        title: "Directories",
+        text: "Select a directory and open it.",
+        keys: Keys::go_into_dir,
+        goal: StepGoal::Action(Action::Nav(NavAction::GoIntoDir)),
+    },
+    TutorialStep {
+        title: "Directories",
+        text: "Go back to the parent directory.",
+        keys: Keys::go_parent,
+        goal: StepGoal::Action(Action::Nav(NavAction::GoParent)),
+    },
+    TutorialStep {
+        title: "Marking",
+        text: "Mark one or more entries.\nYank, paste and delete work on all marked entries.",
+        keys: Keys::toggle_marker,
+        goal: StepGoal::Marked,
+    },
+    TutorialStep {
+        title: "Yank",
+        text: "Yank (copy) the marked entries.",
+        keys: Keys::copy,
+        goal: StepGoal::Yanked,
+    },
+    TutorialStep {
+        title: "Paste",
+        text: "Open the backup directory and paste the yanked entries.",
+        keys: Keys::paste,
+        goal: StepGoal::Action(Action::File(FileAction::Paste)),
+    },
+    TutorialStep {
+        title: "Filter",
+        text: "Filter the current directory.\nType a few letters and press Enter.",
+        keys: Keys::filter,
+        goal: StepGoal::Filtered,
+    },
+    TutorialStep {
+        title: "Filter",
+        text: "Clear the filter again.",
+        keys: Keys::clear_filter,
+        goal: StepGoal::Action(Action::Nav(NavAction::ClearFilter)),
+    },
+    TutorialStep {
+        title: "Find",
+        text: "Find files in all subdirectories (needs fd).\nEsc closes the dialog.",
+        keys: Keys::find,
+        goal: StepGoal::InputMode(InputMode::Find),
+    },
+    TutorialStep {
+        title: "Done",
+        text: "That's it! See `rn --config-help` for all options\nand key bindings.",
+        keys: Keys::quit,
+        goal: StepGoal::Done,
+    },
+];
+
+/// Progress through the tutorial [STEPS].
+#[derive(Debug, Default)]
+pub struct Tutorial {
+    step: usize,
+}
+
+impl Tutorial {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn current(&self) -> &'static TutorialStep {
+        &STEPS[self.step.min(STEPS.len() - 1)]
+    }
+
+    /// Index of the current step, starting at 0.
+    pub fn step(&self) -> usize {
+        self.step
+    }
+
+    pub fn len(&self) -> usize {
+        STEPS.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        STEPS.is_empty()
+    }
+
+    pub fn is_last(&self) -> bool {
+        self.step + 1 >= STEPS.len()
+    }
+
+    fn advance(&mut self) {
+        if !self.is_last() {
+            self.step += 1;
+        }
+    }
+}
+
+impl AppState {
+    /// Starts the tutorial at its first step.
+    pub fn start_tutorial(&mut self) {
+        self.tutorial = Some(Tutorial::new());
+    }
+
+    pub fn tutorial(&self) -> Option<&Tutorial> {
+        self.tutorial.as_ref()
+    }
+
+    /// Handles a keypress while the tutorial is shown.
+    ///
+    /// Quitting before the last step only closes the tutorial. Otherwise the key is handled as
+    /// usual and the tutorial advances if the goal of the current step was met.
+    pub(super) fn handle_tutorial_keypress(
+        &mut self,
+        action: Option<Action>,
+        result: impl FnOnce(&mut Self) -> KeypressResult,
+    ) -> KeypressResult {
+        let is_last = self.tutorial.as_ref().is_some_and(Tutorial::is_last);
+        if action == Some(Action::System(SystemAction::Quit)) && !is_last {
+            self.tutorial = None;
+            return KeypressResult::Consumed;
+        }
+
+        let result = result(self);
+        let goal = self.tutorial.as_ref().map(|t| t.current().goal());
+        if goal.is_some_and(|goal| self.tutorial_goal_met(goal, action))
+            && let Some(tutorial) = self.tutorial.as_mut()
+        {
+            tutorial.advance();
+        }
+        result
+    }
+
+    fn tutorial_goal_met(&self, goal: StepGoal, action: Option<Action>) -> bool {
+        match goal {
+            StepGoal::Action(expected) => action == Some(expected),
+            StepGoal::InputMode(expected) => {
+                matches!(self.actions.mode(), ActionMode::Input { mode, .. } if *mode == expected)
+            }
+            StepGoal::Marked => !self.nav.markers().is_empty(),
+            StepGoal::Yanked => self.actions.clipboard().is_some(),
+            StepGoal::Filtered => !self.nav.filter().is_empty() && !self.actions.is_input_mode(),
+            StepGoal::Done => false,
+        }
+    }
+}
+
+/// Creates a playground directory for the tutorial in the temp directory.
+///
+/// Contains a few directories and small text files to navigate, mark, paste and filter.
+/// The caller is responsible for removing it again.
+///
+/// # Errors
+/// Returns an std::io::Error if the directory or files cannot be created.
+pub fn create_playground() -> io::Result<PathBuf> {
+    let root = std::env::temp_dir().join(format!("runa-tutorial-{}", std::process::id()));
+    let files: &[(&str, &str)] = &[
+        (
+            "docs/readme.md",
+            "# Welcome to runa\n\nThis is a playground for the tutorial.\n",
+        ),
+        (
+            "docs/notes.txt",
+            "Marked entries are yanked, pasted and deleted together.\n",
+        ),
+        (
+            "projects/crab/Cargo.toml",
+            "[package]\nname = \"crab\"\nversion = \"0.1.0\"\n",
+        ),
+        (
+            "projects/crab/src/main.rs",
+            "fn main() {\n    println!(\"Hello, crab!\");\n}\n",
+        ),
+        (
+            "todo.txt",
+            "- finish the runa tutorial\n- water the plants\n",
+        ),
+        ("shopping_list.txt", "apples\nbread\ncoffee\n"),
+        (
+            "hello.sh",
+            "#!/bin/sh\necho \"Hello from the playground\"\n",
+        ),
+    ];
+    for (name, contents) in files {
+        let path = root.join(name);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(path, contents)?;
+    }
+    fs::create_dir_all(root.join("backup"))?;
+    Ok(root)
+}
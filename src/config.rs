@@ -4,11 +4,23 @@
 //! configuration options for runa, including display settings, input keybindings,
 
 pub mod display;
+pub mod find;
+pub mod icons;
 pub mod input;
 pub mod load;
+pub mod profiles;
+pub mod templates;
 pub mod theme;
+pub mod workers;
 
 pub use display::Display;
-pub use input::{Editor, Keys};
+pub use find::FindOptions;
+pub use icons::IconOptions;
+pub use input::{
+    Commands, CustomCommand, Editor, EnterActions, Goto, GotoEntry, Keys, Open, OpenAction, Openers,
+};
 pub use load::Config;
+pub use profiles::{Profile, Profiles, expand_path};
+pub use templates::{Template, Templates};
 pub use theme::Theme;
+pub use workers::WorkerOptions;
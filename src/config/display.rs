@@ -3,6 +3,7 @@
 //! This module defines the display configuration options which are read from the runa.toml
 //! configuration file.
 
+use crate::core::{DEFAULT_DATE_FORMAT, SizeFormat, is_valid_date_format};
 use crate::ui::widgets::DialogPosition;
 use ratatui::widgets::BorderType;
 use serde::Deserialize;
@@ -26,6 +27,7 @@ pub struct Display {
     border_shape: BorderShape,
     titles: bool,
     icons: bool,
+    ascii_only: bool,
     separators: bool,
     parent: bool,
     preview: bool,
@@ -35,6 +37,17 @@ pub struct Display {
     scroll_padding: usize,
     toggle_marker_jump: bool,
     instant_preview: bool,
+    git_status: bool,
+    finder_tags: bool,
+    security_context: bool,
+    details: bool,
+    dir_sizes: bool,
+    inline_rename: bool,
+    file_colors: FileColors,
+    #[serde(deserialize_with = "deserialize_date_format")]
+    date_format: String,
+    size_format: SizeFormat,
+    force_ltr: bool,
     preview_options: PreviewOptions,
     layout: LayoutConfig,
     info: ShowInfoOptions,
@@ -70,6 +83,12 @@ impl Display {
         self.icons
     }
 
+    /// Whether icons, the truncation mark and the find marker are plain ASCII, for terminals
+    /// without a Nerd Font.
+    pub fn ascii_only(&self) -> bool {
+        self.ascii_only
+    }
+
     pub fn separators(&self) -> bool {
         self.separators
     }
@@ -118,6 +137,56 @@ impl Display {
         self.instant_preview
     }
 
+    pub fn git_status(&self) -> bool {
+        self.git_status
+    }
+
+    /// Whether the colors of Finder tags are shown next to the entries, on macOS.
+    pub fn finder_tags(&self) -> bool {
+        self.finder_tags
+    }
+
+    /// Whether the SELinux context is shown in the info overlay and entries labeled with another
+    /// type than their directory are colored, on Linux.
+    pub fn security_context(&self) -> bool {
+        self.security_context
+    }
+
+    /// Whether the main pane shows the size, modification time and git status of each entry.
+    pub fn details(&self) -> bool {
+        self.details
+    }
+
+    /// Whether the recursive sizes of all subdirectories are computed in the background.
+    pub fn dir_sizes(&self) -> bool {
+        self.dir_sizes
+    }
+
+    /// Whether the selected row of the main pane is edited in place when renaming, instead of
+    /// opening the rename dialog.
+    pub fn inline_rename(&self) -> bool {
+        self.inline_rename
+    }
+
+    /// Where the per-file-type and per-extension entry colors come from, see [FileColors].
+    pub fn file_colors(&self) -> FileColors {
+        self.file_colors
+    }
+
+    /// The strftime format of dates in the info overlay, e.g. `"%d.%m.%Y %H:%M"`.
+    pub fn date_format(&self) -> &str {
+        &self.date_format
+    }
+
+    /// Whether sizes are shown in decimal (kB) or binary (KiB) units.
+    pub fn size_format(&self) -> SizeFormat {
+        self.size_format
+    }
+
+    pub fn force_ltr(&self) -> bool {
+        self.force_ltr
+    }
+
     pub fn preview_options(&self) -> &PreviewOptions {
         &self.preview_options
     }
@@ -149,6 +218,7 @@ impl Default for Display {
             border_shape: BorderShape::Square,
             titles: true,
             icons: false,
+            ascii_only: false,
             separators: true,
             parent: true,
             preview: true,
@@ -163,12 +233,38 @@ impl Default for Display {
             scroll_padding: 5,
             toggle_marker_jump: false,
             instant_preview: false,
+            git_status: true,
+            finder_tags: true,
+            security_context: false,
+            details: false,
+            dir_sizes: false,
+            inline_rename: false,
+            file_colors: FileColors::None,
+            date_format: DEFAULT_DATE_FORMAT.to_string(),
+            size_format: SizeFormat::Decimal,
+            force_ltr: false,
             preview_options: PreviewOptions::default(),
             info: ShowInfoOptions::default(),
         }
     }
 }
 
+// Helper function to deserialize the date format, unknown specifiers are an error
+fn deserialize_date_format<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let format = String::deserialize(deserializer)?;
+    if is_valid_date_format(&format) {
+        Ok(format)
+    } else {
+        Err(serde::de::Error::custom(format!(
+            "invalid date_format \"{}\"",
+            format
+        )))
+    }
+}
+
 /// Layout configuration for the display panes
 /// This struct holds the ratio settings for the parent, main, and preview panes
 #[derive(Deserialize, Debug)]
@@ -206,6 +302,7 @@ pub struct ShowInfoOptions {
     size: bool,
     modified: bool,
     perms: bool,
+    items: bool,
     position: Option<DialogPosition>,
 }
 
@@ -231,6 +328,10 @@ impl ShowInfoOptions {
         self.perms
     }
 
+    pub fn items(&self) -> bool {
+        self.items
+    }
+
     pub fn position(&self) -> &Option<DialogPosition> {
         &self.position
     }
@@ -245,6 +346,7 @@ impl Default for ShowInfoOptions {
             size: true,
             modified: true,
             perms: false,
+            items: true,
             position: None,
         }
     }
@@ -277,7 +379,8 @@ pub enum BatStyle {
 
 /// Preview configuration options
 /// This struct holds various options related to file previewing,
-/// including the preview method, bat style, and text wrapping.
+/// including the preview method, bat style, text wrapping, and the syntax highlighting,
+/// tab width and control character rendering of the internal preview.
 /// These options can be customized by the user in the configuration file.
 #[derive(Deserialize, Debug, Clone)]
 pub struct PreviewOptions {
@@ -289,6 +392,12 @@ pub struct PreviewOptions {
     theme: Option<String>,
     #[serde(default = "PreviewOptions::default_wrap")]
     wrap: bool,
+    #[serde(default = "PreviewOptions::default_syntax_highlighting")]
+    syntax_highlighting: bool,
+    #[serde(default = "PreviewOptions::default_tab_width")]
+    tab_width: usize,
+    #[serde(default)]
+    show_control_chars: bool,
 }
 
 /// Public methods for accessing preview configuration options
@@ -299,6 +408,9 @@ impl PreviewOptions {
             style: BatStyle::Plain,
             theme: None,
             wrap: true,
+            syntax_highlighting: true,
+            tab_width: 4,
+            show_control_chars: false,
         }
     }
 
@@ -310,6 +422,14 @@ impl PreviewOptions {
         true
     }
 
+    fn default_syntax_highlighting() -> bool {
+        true
+    }
+
+    fn default_tab_width() -> usize {
+        4
+    }
+
     pub fn method(&self) -> &PreviewMethod {
         &self.method
     }
@@ -322,6 +442,18 @@ impl PreviewOptions {
         self.wrap
     }
 
+    pub fn syntax_highlighting(&self) -> bool {
+        self.syntax_highlighting
+    }
+
+    pub fn tab_width(&self) -> usize {
+        self.tab_width
+    }
+
+    pub fn show_control_chars(&self) -> bool {
+        self.show_control_chars
+    }
+
     /// Generate command-line arguments for the 'bat' command based on the preview options
     /// and the given theme name and pane width.
     ///
@@ -364,6 +496,20 @@ pub enum BorderStyle {
     Split,
 }
 
+/// Entry color options
+/// This enum defines where entries get colors beyond the directory color of the theme
+/// - None: Only directories are colored
+/// - Builtin: Symlinks, executables, archives, images and media get built-in colors
+/// - LsColors: The colors of the `LS_COLORS` variable, the built-in ones if it is not set
+#[derive(Deserialize, Debug, Clone, Copy, Default, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum FileColors {
+    #[default]
+    None,
+    Builtin,
+    LsColors,
+}
+
 /// Border shape options
 /// This enum defines the different border shapes that can be used in the UI
 #[derive(Deserialize, Debug, Clone, PartialEq)]
@@ -0,0 +1,73 @@
+//! Find configuration options for runa
+//!
+//! This module defines the `[find]` section of the runa.toml configuration file,
+//! which configures the find and grep dialogs.
+
+use crate::core::{DEFAULT_EXCLUDES, SearchFilters};
+use serde::Deserialize;
+
+/// Find dialog configuration options
+///
+/// # Fields
+/// * `persist_history` - Save the find history to `~/.local/share/runa/find_history`.
+/// * `history_size` - Maximum number of remembered queries.
+/// * `include` - If set, only files matching one of these globs are found.
+/// * `exclude` - Globs of files and directories to skip, replacing the built-in list.
+/// * `gitignore` - Respect .gitignore rules.
+/// * `dirs_first` - List matching directories before files, each group by score.
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct FindOptions {
+    persist_history: bool,
+    history_size: usize,
+    include: Vec<String>,
+    exclude: Vec<String>,
+    gitignore: bool,
+    dirs_first: bool,
+}
+
+/// Public methods for accessing find configuration options
+impl FindOptions {
+    pub fn persist_history(&self) -> bool {
+        self.persist_history
+    }
+
+    pub fn history_size(&self) -> usize {
+        self.history_size
+    }
+
+    pub fn include(&self) -> &[String] {
+        &self.include
+    }
+
+    pub fn exclude(&self) -> &[String] {
+        &self.exclude
+    }
+
+    pub fn gitignore(&self) -> bool {
+        self.gitignore
+    }
+
+    pub fn dirs_first(&self) -> bool {
+        self.dirs_first
+    }
+
+    /// The [SearchFilters] passed to the find and grep workers.
+    pub fn search_filters(&self) -> SearchFilters {
+        SearchFilters::new(self.include.clone(), self.exclude.clone(), self.gitignore)
+    }
+}
+
+/// Default find configuration options
+impl Default for FindOptions {
+    fn default() -> Self {
+        FindOptions {
+            persist_history: false,
+            history_size: 100,
+            include: Vec::new(),
+            exclude: DEFAULT_EXCLUDES.iter().map(|s| s.to_string()).collect(),
+            gitignore: true,
+            dirs_first: false,
+        }
+    }
+}
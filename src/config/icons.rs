@@ -0,0 +1,89 @@
+//! Icon mappings for runa
+//!
+//! This module defines the `[icons]` table of the runa.toml configuration file.
+//! Its tables map extensions, file names and directory names to Nerd Font icons and are merged
+//! over the built-in icons, see [Icons](crate::ui::icons::Icons). An icon is either a string or
+//! a table with an `icon` and a `fg` color, e.g. `rs = { icon = "", fg = "#dea584" }`.
+//! A table without `icon` only colors the built-in icon.
+
+use crate::utils::parse_color;
+use ratatui::style::Color;
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// The `[icons]` table.
+///
+/// # Fields
+/// * `default` - The icon of files without a matching name or extension.
+/// * `extensions` - Icons by extension, case-insensitive.
+/// * `files` - Icons by exact file name.
+/// * `dirs` - Icons by directory name, case-insensitive.
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct IconOptions {
+    default: Option<IconSpec>,
+    extensions: HashMap<String, IconSpec>,
+    files: HashMap<String, IconSpec>,
+    dirs: HashMap<String, IconSpec>,
+}
+
+/// A single icon of the `[icons]` tables: a glyph, a color for it, or both.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(from = "RawIconSpec")]
+pub struct IconSpec {
+    icon: Option<String>,
+    fg: Option<Color>,
+}
+
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawIconSpec {
+    Glyph(String),
+    Styled {
+        icon: Option<String>,
+        fg: Option<String>,
+    },
+}
+
+impl From<RawIconSpec> for IconSpec {
+    fn from(raw: RawIconSpec) -> Self {
+        match raw {
+            RawIconSpec::Glyph(icon) => IconSpec {
+                icon: Some(icon),
+                fg: None,
+            },
+            RawIconSpec::Styled { icon, fg } => IconSpec {
+                icon,
+                fg: fg.as_deref().map(parse_color),
+            },
+        }
+    }
+}
+
+impl IconOptions {
+    pub fn default_icon(&self) -> Option<&IconSpec> {
+        self.default.as_ref()
+    }
+
+    pub fn extensions(&self) -> &HashMap<String, IconSpec> {
+        &self.extensions
+    }
+
+    pub fn files(&self) -> &HashMap<String, IconSpec> {
+        &self.files
+    }
+
+    pub fn dirs(&self) -> &HashMap<String, IconSpec> {
+        &self.dirs
+    }
+}
+
+impl IconSpec {
+    pub fn icon(&self) -> Option<&str> {
+        self.icon.as_deref()
+    }
+
+    pub fn fg(&self) -> Option<Color> {
+        self.fg
+    }
+}
@@ -3,21 +3,34 @@
 //! This module defines the input configuration options which are read from the runa.toml
 //! configuration file.
 
+use crate::config::expand_path;
+
 use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::vec;
 
 /// Input configuration options of all actions
+///
+/// Each action has a list of bindings, a binding is a key like `"Ctrl+a"` or a sequence of
+/// keys separated by spaces like `"g g"`.
 #[derive(Deserialize, Debug)]
 #[serde(default)]
 pub struct Keys {
     open_file: Vec<String>,
+    open_with_editor: Vec<String>,
     go_up: Vec<String>,
     go_down: Vec<String>,
+    go_top: Vec<String>,
+    go_bottom: Vec<String>,
     go_parent: Vec<String>,
     go_into_dir: Vec<String>,
     quit: Vec<String>,
     delete: Vec<String>,
     copy: Vec<String>,
+    copy_path: Vec<String>,
+    copy_relative_path: Vec<String>,
+    copy_name: Vec<String>,
     paste: Vec<String>,
     rename: Vec<String>,
     create: Vec<String>,
@@ -25,16 +38,132 @@ pub struct Keys {
     filter: Vec<String>,
     toggle_marker: Vec<String>,
     show_info: Vec<String>,
+    clear_quarantine: Vec<String>,
+    dir_size: Vec<String>,
+    stats: Vec<String>,
+    dump_state: Vec<String>,
+    toggle_instant_preview: Vec<String>,
+    toggle_hidden: Vec<String>,
+    toggle_preview: Vec<String>,
+    toggle_parent: Vec<String>,
+    reload_config: Vec<String>,
+    unlock: Vec<String>,
     find: Vec<String>,
+    quick_find: Vec<String>,
+    navigate: Vec<String>,
+    grep: Vec<String>,
+    jump: Vec<String>,
+    command: Vec<String>,
+    shell: Vec<String>,
+    mark_all: Vec<String>,
+    invert_marks: Vec<String>,
     clear_markers: Vec<String>,
+    marked: Vec<String>,
+    jump_back: Vec<String>,
+    jump_forward: Vec<String>,
     clear_filter: Vec<String>,
+    goto: Vec<String>,
+}
+
+/// User-defined commands of the `[commands]` table, e.g. `"Ctrl+e" = "tar -xf %f"`.
+///
+/// Each key binding runs its shell command template (see [expand_command](crate::utils::expand_command)).
+/// Commands are ordered by their key, which keeps the indices used by the keymap stable.
+#[derive(Deserialize, Debug, Default)]
+#[serde(from = "BTreeMap<String, String>")]
+pub struct Commands {
+    commands: Vec<CustomCommand>,
+}
+
+/// A single user-defined command: the key binding and the shell command template.
+#[derive(Debug, Clone)]
+pub struct CustomCommand {
+    key: String,
+    template: String,
+}
+
+/// User-defined goto shortcuts of the `[goto]` table, e.g. `"d" = "~/Downloads"`.
+///
+/// Each entry is the key pressed after the `goto` prefix and the directory it goes to,
+/// `~` and `$PREFIX` are expanded (see [expand_path](crate::config::expand_path)).
+/// Entries are ordered by their key, like [Commands], and override the built-in shortcuts.
+#[derive(Deserialize, Debug, Default)]
+#[serde(from = "BTreeMap<String, String>")]
+pub struct Goto {
+    entries: Vec<GotoEntry>,
+}
+
+/// A single goto shortcut: the key after the prefix and the directory.
+#[derive(Debug, Clone)]
+pub struct GotoEntry {
+    key: String,
+    path: String,
+}
+
+/// Opener commands of the `[openers]` table by file extension, e.g. `"pdf" = "zathura %f"`.
+///
+/// Extensions are matched case-insensitively, without the leading dot.
+/// Multi-part extensions like `"tar.gz"` are supported, the longest match wins.
+#[derive(Deserialize, Debug, Default)]
+#[serde(from = "HashMap<String, String>")]
+pub struct Openers {
+    openers: HashMap<String, String>,
+}
+
+/// What the `open_file` key (Enter) does with a file, see [EnterActions].
+///
+/// Variants:
+/// * `Editor` - Open the file in the configured editor.
+/// * `System` - Open the file with the OS default application.
+/// * `Preview` - Show the file full-screen in the pager, `$PAGER` or `less`.
+/// * `Prompt` - Ask which of the above.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum OpenAction {
+    Editor,
+    System,
+    Preview,
+    Prompt,
+}
+
+/// The `[open]` table.
+///
+/// # Fields
+/// * `enter` - What Enter does per file class, the `[open.enter]` table.
+#[derive(Deserialize, Debug, Default)]
+#[serde(default)]
+pub struct Open {
+    enter: EnterActions,
+}
+
+/// The `[open.enter]` table: the [OpenAction] of Enter by file class, e.g. `binary = "system"`.
+///
+/// The classes are `text`, `binary` and `executable`, any other key is an extension like
+/// in [Openers], e.g. `".md" = "preview"`. A leading dot always makes the key an extension.
+/// An extension takes precedence over `executable`, which takes precedence over `text` and `binary`.
+#[derive(Deserialize, Debug, Default)]
+#[serde(from = "HashMap<String, OpenAction>")]
+pub struct EnterActions {
+    text: Option<OpenAction>,
+    binary: Option<OpenAction>,
+    executable: Option<OpenAction>,
+    extensions: HashMap<String, OpenAction>,
 }
 
 /// Editor configuration options
+///
+/// # Fields
+/// * `cmd` - The editor command.
+/// * `open_with_system` - Open binary files with the OS default application instead.
+/// * `open_many` - Shell command template opening several files at once, see
+///   [expand_command](crate::utils::expand_command), e.g. `"nvim -p %m"`.
+///   Without it the files are passed to `cmd` one argument each.
 #[derive(Deserialize, Debug)]
 #[serde(default)]
 pub struct Editor {
     cmd: String,
+    open_with_system: bool,
+    open_many: Option<String>,
 }
 
 /// Public methods for accessing input configuration options
@@ -43,6 +172,10 @@ impl Keys {
         &self.open_file
     }
 
+    pub fn open_with_editor(&self) -> &Vec<String> {
+        &self.open_with_editor
+    }
+
     pub fn go_up(&self) -> &Vec<String> {
         &self.go_up
     }
@@ -51,6 +184,14 @@ impl Keys {
         &self.go_down
     }
 
+    pub fn go_top(&self) -> &Vec<String> {
+        &self.go_top
+    }
+
+    pub fn go_bottom(&self) -> &Vec<String> {
+        &self.go_bottom
+    }
+
     pub fn go_parent(&self) -> &Vec<String> {
         &self.go_parent
     }
@@ -71,6 +212,18 @@ impl Keys {
         &self.copy
     }
 
+    pub fn copy_path(&self) -> &Vec<String> {
+        &self.copy_path
+    }
+
+    pub fn copy_relative_path(&self) -> &Vec<String> {
+        &self.copy_relative_path
+    }
+
+    pub fn copy_name(&self) -> &Vec<String> {
+        &self.copy_name
+    }
+
     pub fn paste(&self) -> &Vec<String> {
         &self.paste
     }
@@ -99,10 +252,82 @@ impl Keys {
         &self.show_info
     }
 
+    pub fn clear_quarantine(&self) -> &Vec<String> {
+        &self.clear_quarantine
+    }
+
+    pub fn dir_size(&self) -> &Vec<String> {
+        &self.dir_size
+    }
+
+    pub fn stats(&self) -> &Vec<String> {
+        &self.stats
+    }
+
+    pub fn dump_state(&self) -> &Vec<String> {
+        &self.dump_state
+    }
+
+    pub fn toggle_instant_preview(&self) -> &Vec<String> {
+        &self.toggle_instant_preview
+    }
+
+    pub fn toggle_hidden(&self) -> &Vec<String> {
+        &self.toggle_hidden
+    }
+
+    pub fn toggle_preview(&self) -> &Vec<String> {
+        &self.toggle_preview
+    }
+
+    pub fn toggle_parent(&self) -> &Vec<String> {
+        &self.toggle_parent
+    }
+
+    pub fn reload_config(&self) -> &Vec<String> {
+        &self.reload_config
+    }
+
+    pub fn unlock(&self) -> &Vec<String> {
+        &self.unlock
+    }
+
     pub fn find(&self) -> &Vec<String> {
         &self.find
     }
 
+    pub fn quick_find(&self) -> &Vec<String> {
+        &self.quick_find
+    }
+
+    pub fn navigate(&self) -> &Vec<String> {
+        &self.navigate
+    }
+
+    pub fn grep(&self) -> &Vec<String> {
+        &self.grep
+    }
+
+    pub fn jump(&self) -> &Vec<String> {
+        &self.jump
+    }
+
+    pub fn command(&self) -> &Vec<String> {
+        &self.command
+    }
+
+    pub fn shell(&self) -> &Vec<String> {
+        &self.shell
+    }
+
+    pub fn mark_all(&self) -> &Vec<String> {
+        &self.mark_all
+    }
+
+    pub fn invert_marks(&self) -> &Vec<String> {
+        &self.invert_marks
+    }
+
     pub fn clear_markers(&self) -> &Vec<String> {
         &self.clear_markers
     }
@@ -110,6 +335,22 @@ impl Keys {
     pub fn clear_filter(&self) -> &Vec<String> {
         &self.clear_filter
     }
+
+    pub fn marked(&self) -> &Vec<String> {
+        &self.marked
+    }
+
+    pub fn jump_back(&self) -> &Vec<String> {
+        &self.jump_back
+    }
+
+    pub fn jump_forward(&self) -> &Vec<String> {
+        &self.jump_forward
+    }
+
+    pub fn goto(&self) -> &Vec<String> {
+        &self.goto
+    }
 }
 
 /// Default input configuration options
@@ -117,14 +358,20 @@ impl Default for Keys {
     fn default() -> Self {
         Keys {
             open_file: vec!["Enter".into()],
+            open_with_editor: vec!["o".into()],
             go_up: vec!["k".into(), "Up".into()],
             go_down: vec!["j".into(), "Down".into()],
+            go_top: vec!["g g".into()],
+            go_bottom: vec!["Shift+g".into()],
             go_parent: vec!["h".into(), "Left".into(), "Backspace".into()],
             go_into_dir: vec!["l".into(), "Right".into()],
             quit: vec!["q".into(), "Esc".into()],
 
             delete: vec!["d".into()],
             copy: vec!["y".into()],
+            copy_path: vec!["Shift+y".into()],
+            copy_relative_path: vec!["Ctrl+y".into()],
+            copy_name: vec!["Alt+y".into()],
             paste: vec!["p".into()],
             rename: vec!["r".into()],
             create: vec!["n".into()],
@@ -132,24 +379,226 @@ impl Default for Keys {
             filter: vec!["f".into()],
             toggle_marker: vec![" ".into()],
             show_info: vec!["i".into()],
+            clear_quarantine: vec!["Shift+x".into()],
+            dir_size: vec!["Shift+d".into()],
+            stats: vec!["Shift+i".into()],
+            dump_state: vec!["F12".into()],
+            toggle_instant_preview: vec!["Ctrl+p".into()],
+            toggle_hidden: vec![".".into()],
+            toggle_preview: vec!["Shift+p".into()],
+            toggle_parent: vec!["Shift+h".into()],
+            reload_config: vec!["Shift+r".into()],
+            unlock: vec!["Shift+u".into()],
             find: vec!["s".into()],
+            quick_find: vec!["/".into()],
+            navigate: vec!["Shift+f".into()],
+            grep: vec!["Shift+s".into()],
+            jump: vec!["z".into()],
+            command: vec![":".into(), "!".into()],
+            shell: vec!["w".into()],
 
+            mark_all: vec!["Ctrl+a".into()],
+            invert_marks: vec!["Ctrl+r".into()],
             clear_markers: vec!["Ctrl+c".into()],
             clear_filter: vec!["Ctrl+f".into()],
+            marked: vec!["m".into()],
+            jump_back: vec!["Ctrl+o".into()],
+            jump_forward: vec!["Ctrl+i".into(), "Tab".into()],
+            goto: vec!["g".into()],
+        }
+    }
+}
+
+impl From<BTreeMap<String, String>> for Commands {
+    fn from(map: BTreeMap<String, String>) -> Self {
+        Commands {
+            commands: map
+                .into_iter()
+                .map(|(key, template)| CustomCommand { key, template })
+                .collect(),
         }
     }
 }
 
+impl From<BTreeMap<String, String>> for Goto {
+    fn from(map: BTreeMap<String, String>) -> Self {
+        Goto {
+            entries: map
+                .into_iter()
+                .map(|(key, path)| GotoEntry { key, path })
+                .collect(),
+        }
+    }
+}
+
+impl From<HashMap<String, String>> for Openers {
+    fn from(map: HashMap<String, String>) -> Self {
+        Openers {
+            openers: map
+                .into_iter()
+                .map(|(ext, cmd)| (ext.trim_start_matches('.').to_lowercase(), cmd))
+                .collect(),
+        }
+    }
+}
+
+/// Looks up the extension of the file name of `path` in `map`, the longest extension first.
+///
+/// The keys of `map` are lowercase extensions without the leading dot.
+fn get_by_extension<'a, T>(map: &'a HashMap<String, T>, path: &Path) -> Option<&'a T> {
+    if map.is_empty() {
+        return None;
+    }
+    let name = path.file_name()?.to_string_lossy().to_lowercase();
+    // Dots after the first character, so ".bashrc" has no extension
+    name.char_indices()
+        .skip(1)
+        .filter(|(_, c)| *c == '.')
+        .find_map(|(idx, _)| map.get(&name[idx + 1..]))
+}
+
+/// Public methods for looking up openers
+impl Openers {
+    /// Returns the opener command template for the file name of `path`, if one is configured.
+    pub fn get(&self, path: &Path) -> Option<&str> {
+        get_by_extension(&self.openers, path).map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.openers.is_empty()
+    }
+}
+
+impl From<HashMap<String, OpenAction>> for EnterActions {
+    fn from(map: HashMap<String, OpenAction>) -> Self {
+        let mut actions = EnterActions::default();
+        for (key, action) in map {
+            match key.as_str() {
+                "text" => actions.text = Some(action),
+                "binary" => actions.binary = Some(action),
+                "executable" => actions.executable = Some(action),
+                ext => {
+                    let ext = ext.trim_start_matches('.').to_lowercase();
+                    actions.extensions.insert(ext, action);
+                }
+            }
+        }
+        actions
+    }
+}
+
+impl Open {
+    pub fn enter(&self) -> &EnterActions {
+        &self.enter
+    }
+}
+
+impl EnterActions {
+    /// Returns the configured action for `path`, if its extension or class has one.
+    ///
+    /// # Arguments
+    /// * `path` - The file to open.
+    /// * `executable` - Whether the file has an executable bit.
+    /// * `binary` - Tells whether the file is binary, only called if `text` or `binary` is set.
+    pub fn get(
+        &self,
+        path: &Path,
+        executable: bool,
+        binary: impl FnOnce() -> bool,
+    ) -> Option<OpenAction> {
+        if let Some(action) = get_by_extension(&self.extensions, path) {
+            return Some(*action);
+        }
+        if executable && self.executable.is_some() {
+            return self.executable;
+        }
+        if self.text.is_none() && self.binary.is_none() {
+            return None;
+        }
+        if binary() { self.binary } else { self.text }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.text.is_none()
+            && self.binary.is_none()
+            && self.executable.is_none()
+            && self.extensions.is_empty()
+    }
+}
+
+/// Public methods for accessing the user-defined commands
+impl Commands {
+    pub fn iter(&self) -> std::slice::Iter<'_, CustomCommand> {
+        self.commands.iter()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&CustomCommand> {
+        self.commands.get(idx)
+    }
+
+    pub fn len(&self) -> usize {
+        self.commands.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.commands.is_empty()
+    }
+}
+
+impl CustomCommand {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    pub fn template(&self) -> &str {
+        &self.template
+    }
+}
+
+/// Public methods for accessing the user-defined goto shortcuts
+impl Goto {
+    pub fn iter(&self) -> std::slice::Iter<'_, GotoEntry> {
+        self.entries.iter()
+    }
+
+    pub fn get(&self, idx: usize) -> Option<&GotoEntry> {
+        self.entries.get(idx)
+    }
+}
+
+impl GotoEntry {
+    pub fn key(&self) -> &str {
+        &self.key
+    }
+
+    /// The directory with `~` and `$PREFIX` expanded.
+    pub fn path(&self) -> PathBuf {
+        expand_path(&self.path)
+    }
+}
+
 /// Public methods for accessing editor configuration options
 impl Editor {
     pub fn cmd(&self) -> &str {
         &self.cmd
     }
+
+    pub fn open_with_system(&self) -> bool {
+        self.open_with_system
+    }
+
+    pub fn open_many(&self) -> Option<&str> {
+        self.open_many.as_deref()
+    }
 }
 
 /// Default editor configuration options
 impl Default for Editor {
     fn default() -> Self {
-        Editor { cmd: "nvim".into() }
+        Editor {
+            cmd: "nvim".into(),
+            open_with_system: true,
+            open_many: None,
+        }
     }
 }
@@ -6,17 +6,28 @@
 //!
 //! Also implements default config initialization when `runa.toml` is not present.
 
+use crate::app::{FilterMode, MarkerPolicy};
 use crate::config::Display;
+use crate::config::FindOptions;
+use crate::config::IconOptions;
 use crate::config::Theme;
-use crate::config::{Editor, Keys};
-use crate::utils::DEFAULT_FIND_RESULTS;
+use crate::config::WorkerOptions;
+use crate::config::{Commands, Editor, Goto, Keys, Open, Openers, Profiles, Templates};
+use crate::core::SortExpr;
+use crate::core::worker::PasteConflict;
+use crate::ui::filestyle::FileStyles;
+use crate::ui::icons::Icons;
 use crate::utils::helpers::clamp_find_results;
+use crate::utils::termux;
+use crate::utils::{ClipboardMode, DEFAULT_FIND_RESULTS};
 
 use serde::Deserialize;
 use std::collections::HashSet;
 use std::ffi::OsString;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
-use std::{fs, io, path::PathBuf};
+use std::time::Duration;
+use std::{fs, io};
 
 /// Raw configuration as read from the toml file
 /// This struct is deserialized directly from the toml file.
@@ -25,16 +36,44 @@ use std::{fs, io, path::PathBuf};
 #[serde(default)]
 pub struct RawConfig {
     dirs_first: bool,
+    #[serde(deserialize_with = "deserialize_sort_expr")]
+    sort_expr: Option<Arc<SortExpr>>,
     show_hidden: bool,
     show_system: bool,
     case_insensitive: bool,
     always_show: Vec<String>,
+    scan_excludes: Vec<String>,
     #[serde(default = "default_find_results")]
     max_find_results: usize,
+    max_copy_mbps: u32,
+    create_file_mode: Option<u32>,
+    create_dir_mode: Option<u32>,
+    auto_refresh_secs: u64,
+    watch: bool,
+    focus_new_items: bool,
+    message_secs: u64,
+    filter_mode: FilterMode,
+    marker_policy: MarkerPolicy,
+    clipboard: ClipboardMode,
+    paste_conflict: PasteConflict,
+    readonly: bool,
+    root_guard: bool,
+    termux: Option<bool>,
+    animations: bool,
+    zoxide: bool,
+    find: FindOptions,
+    workers: WorkerOptions,
     display: Display,
     theme: Theme,
+    icons: IconOptions,
     editor: Editor,
     keys: Keys,
+    commands: Commands,
+    goto: Goto,
+    openers: Openers,
+    open: Open,
+    profiles: Profiles,
+    templates: Templates,
 }
 
 /// Default values for RawConfig
@@ -43,15 +82,42 @@ impl Default for RawConfig {
     fn default() -> Self {
         RawConfig {
             dirs_first: true,
+            sort_expr: None,
             show_hidden: true,
             show_system: false,
             case_insensitive: true,
             always_show: Vec::new(),
+            scan_excludes: default_scan_excludes(),
             max_find_results: default_find_results(),
+            max_copy_mbps: 0,
+            create_file_mode: None,
+            create_dir_mode: None,
+            auto_refresh_secs: 0,
+            watch: true,
+            focus_new_items: true,
+            message_secs: 3,
+            filter_mode: FilterMode::default(),
+            marker_policy: MarkerPolicy::default(),
+            clipboard: ClipboardMode::default(),
+            paste_conflict: PasteConflict::default(),
+            readonly: false,
+            root_guard: true,
+            termux: None,
+            animations: false,
+            zoxide: false,
+            find: FindOptions::default(),
+            workers: WorkerOptions::default(),
             display: Display::default(),
             theme: Theme::default(),
+            icons: IconOptions::default(),
             editor: Editor::default(),
             keys: Keys::default(),
+            commands: Commands::default(),
+            goto: Goto::default(),
+            openers: Openers::default(),
+            open: Open::default(),
+            profiles: Profiles::default(),
+            templates: Templates::default(),
         }
     }
 }
@@ -61,15 +127,44 @@ impl Default for RawConfig {
 #[derive(Debug)]
 pub struct Config {
     dirs_first: bool,
+    sort_expr: Option<Arc<SortExpr>>,
     show_hidden: bool,
     show_system: bool,
     case_insensitive: bool,
     always_show: Arc<HashSet<OsString>>,
+    scan_excludes: Arc<HashSet<OsString>>,
     max_find_results: usize,
+    max_copy_mbps: u32,
+    create_file_mode: Option<u32>,
+    create_dir_mode: Option<u32>,
+    auto_refresh_secs: u64,
+    watch: bool,
+    focus_new_items: bool,
+    message_secs: u64,
+    filter_mode: FilterMode,
+    marker_policy: MarkerPolicy,
+    clipboard: ClipboardMode,
+    paste_conflict: PasteConflict,
+    readonly: bool,
+    root_guard: bool,
+    termux: bool,
+    animations: bool,
+    zoxide: bool,
+    find: FindOptions,
+    workers: WorkerOptions,
     display: Display,
     theme: Theme,
+    file_styles: FileStyles,
+    icons: Icons,
     editor: Editor,
     keys: Keys,
+    commands: Commands,
+    goto: Goto,
+    openers: Openers,
+    open: Open,
+    profiles: Profiles,
+    templates: Templates,
+    warnings: Vec<String>,
 }
 
 /// Conversion from RawConfig to Config
@@ -78,6 +173,7 @@ impl From<RawConfig> for Config {
     fn from(raw: RawConfig) -> Self {
         Self {
             dirs_first: raw.dirs_first,
+            sort_expr: raw.sort_expr,
             show_hidden: raw.show_hidden,
             show_system: raw.show_system,
             case_insensitive: raw.case_insensitive,
@@ -87,11 +183,44 @@ impl From<RawConfig> for Config {
                     .map(OsString::from)
                     .collect::<HashSet<_>>(),
             ),
+            scan_excludes: Arc::new(
+                raw.scan_excludes
+                    .into_iter()
+                    .map(OsString::from)
+                    .collect::<HashSet<_>>(),
+            ),
             max_find_results: clamp_find_results(raw.max_find_results),
+            max_copy_mbps: raw.max_copy_mbps,
+            create_file_mode: raw.create_file_mode.map(|mode| mode & 0o7777),
+            create_dir_mode: raw.create_dir_mode.map(|mode| mode & 0o7777),
+            auto_refresh_secs: raw.auto_refresh_secs,
+            watch: raw.watch,
+            focus_new_items: raw.focus_new_items,
+            message_secs: raw.message_secs,
+            filter_mode: raw.filter_mode,
+            marker_policy: raw.marker_policy,
+            clipboard: raw.clipboard,
+            paste_conflict: raw.paste_conflict,
+            readonly: raw.readonly,
+            root_guard: raw.root_guard,
+            termux: raw.termux.unwrap_or_else(termux::is_termux),
+            animations: raw.animations,
+            zoxide: raw.zoxide,
+            find: raw.find,
+            workers: raw.workers,
+            file_styles: FileStyles::from_mode(raw.display.file_colors()),
+            icons: Icons::new(&raw.icons, raw.display.ascii_only()),
             display: raw.display,
             theme: raw.theme,
             editor: raw.editor,
             keys: raw.keys,
+            commands: raw.commands,
+            goto: raw.goto,
+            openers: raw.openers,
+            open: raw.open,
+            profiles: raw.profiles,
+            templates: raw.templates,
+            warnings: Vec::new(),
         }
     }
 }
@@ -100,7 +229,7 @@ impl From<RawConfig> for Config {
 impl Config {
     /// Load configuration from the default path
     /// If the file does not exist or fails to parse, returns the default configuration.
-    /// Also applies any necessary overrides to the theme after loading.
+    /// Also applies the theme preset or theme file named by `theme.name` after loading.
     ///
     /// Called by entry point to load config at startup.
     pub fn load() -> Self {
@@ -113,18 +242,43 @@ impl Config {
             return Self::default();
         }
 
-        match std::fs::read_to_string(&path) {
-            Ok(content) => match toml::from_str::<RawConfig>(&content) {
-                Ok(mut raw) => {
-                    raw.theme = raw.theme.with_overrides();
-                    raw.into()
-                }
-                Err(e) => {
-                    eprintln!("Error parsing config: {}", e);
-                    Self::default()
-                }
-            },
-            Err(_) => Self::default(),
+        Self::load_from(&path)
+            .unwrap_or_else(|e| Self::with_warning(format!("{}, using defaults", e)))
+    }
+
+    /// Reads and parses the config file at `path`, used on startup and to reload the config.
+    ///
+    /// # Errors
+    /// Returns a message naming the line of the first error if the file cannot be read or parsed.
+    pub fn load_from(path: &Path) -> Result<Self, String> {
+        let content =
+            fs::read_to_string(path).map_err(|e| format!("Could not read config: {}", e))?;
+        match toml::from_str::<RawConfig>(&content) {
+            Ok(mut raw) => {
+                let themes_dir = path.parent().unwrap_or(Path::new(".")).join("themes");
+                let (theme, warning) = raw.theme.with_overrides(&themes_dir);
+                raw.theme = theme;
+                let mut config = Config::from(raw);
+                config.warnings.extend(warning);
+                Ok(config)
+            }
+            Err(e) => {
+                let line = e
+                    .span()
+                    .map(|span| content[..span.start].matches('\n').count() + 1);
+                Err(match line {
+                    Some(line) => format!("Error parsing config at line {}: {}", line, e.message()),
+                    None => format!("Error parsing config: {}", e.message()),
+                })
+            }
+        }
+    }
+
+    /// The defaults, with a warning shown once the app has started.
+    fn with_warning(warning: String) -> Self {
+        Self {
+            warnings: vec![warning],
+            ..Self::default()
         }
     }
 
@@ -134,6 +288,11 @@ impl Config {
         self.dirs_first
     }
 
+    /// The custom sort order, which replaces `dirs_first` and the order by name.
+    pub fn sort_expr(&self) -> Option<&Arc<SortExpr>> {
+        self.sort_expr.as_ref()
+    }
+
     pub fn show_hidden(&self) -> bool {
         self.show_hidden
     }
@@ -150,10 +309,102 @@ impl Config {
         &self.always_show
     }
 
+    pub fn scan_excludes(&self) -> &Arc<HashSet<OsString>> {
+        &self.scan_excludes
+    }
+
     pub fn max_find_results(&self) -> usize {
         self.max_find_results
     }
 
+    pub fn max_copy_mbps(&self) -> u32 {
+        self.max_copy_mbps
+    }
+
+    /// The interval of the periodic directory refresh, or `None` if it is disabled.
+    pub fn auto_refresh_interval(&self) -> Option<Duration> {
+        (self.auto_refresh_secs > 0).then(|| Duration::from_secs(self.auto_refresh_secs))
+    }
+
+    /// How long operation results and errors stay in the status line, zero hides them.
+    pub fn message_duration(&self) -> Duration {
+        Duration::from_secs(self.message_secs)
+    }
+
+    /// Whether the current and parent directory are watched for external changes.
+    pub fn watch(&self) -> bool {
+        self.watch
+    }
+
+    /// Whether the cursor moves to entries created, pasted or renamed by runa.
+    pub fn focus_new_items(&self) -> bool {
+        self.focus_new_items
+    }
+
+    pub fn filter_mode(&self) -> FilterMode {
+        self.filter_mode
+    }
+
+    pub fn marker_policy(&self) -> MarkerPolicy {
+        self.marker_policy
+    }
+
+    pub fn clipboard(&self) -> ClipboardMode {
+        self.clipboard
+    }
+
+    pub fn paste_conflict(&self) -> PasteConflict {
+        self.paste_conflict
+    }
+
+    /// Whether delete, rename, paste and create are disabled, also set by `--readonly`.
+    pub fn readonly(&self) -> bool {
+        self.readonly
+    }
+
+    /// Whether a session running as root starts read-only with a banner and an operation log.
+    pub fn root_guard(&self) -> bool {
+        self.root_guard
+    }
+
+    /// Whether the Termux compatibility mode is on, by default when running inside Termux.
+    pub fn termux(&self) -> bool {
+        self.termux
+    }
+
+    pub fn animations(&self) -> bool {
+        self.animations
+    }
+
+    pub fn zoxide(&self) -> bool {
+        self.zoxide
+    }
+
+    /// The copy bandwidth limit in bytes per second, or `None` if copies are unthrottled.
+    pub fn max_copy_bytes_per_sec(&self) -> Option<u64> {
+        (self.max_copy_mbps > 0).then(|| u64::from(self.max_copy_mbps) * 1024 * 1024)
+    }
+
+    /// Permissions of files created by runa, before the umask is applied.
+    /// `None` keeps the platform default.
+    pub fn create_file_mode(&self) -> Option<u32> {
+        self.create_file_mode
+    }
+
+    /// Permissions of directories created by runa, before the umask is applied.
+    /// `None` keeps the platform default.
+    pub fn create_dir_mode(&self) -> Option<u32> {
+        self.create_dir_mode
+    }
+
+    pub fn find(&self) -> &FindOptions {
+        &self.find
+    }
+
+    pub fn workers(&self) -> &WorkerOptions {
+        &self.workers
+    }
+
     pub fn display(&self) -> &Display {
         &self.display
     }
@@ -162,6 +413,16 @@ impl Config {
         &self.theme
     }
 
+    /// The entry colors selected by `display.file_colors`.
+    pub fn file_styles(&self) -> &FileStyles {
+        &self.file_styles
+    }
+
+    /// The built-in icons merged with the `[icons]` tables.
+    pub fn icons(&self) -> &Icons {
+        &self.icons
+    }
+
     pub fn editor(&self) -> &Editor {
         &self.editor
     }
@@ -170,6 +431,37 @@ impl Config {
         &self.keys
     }
 
+    pub fn commands(&self) -> &Commands {
+        &self.commands
+    }
+
+    /// The user-defined goto shortcuts, next to the built-in home, root and config dir shortcuts.
+    pub fn goto(&self) -> &Goto {
+        &self.goto
+    }
+
+    pub fn openers(&self) -> &Openers {
+        &self.openers
+    }
+
+    /// What Enter does with files, the `[open]` table.
+    pub fn open(&self) -> &Open {
+        &self.open
+    }
+
+    pub fn profiles(&self) -> &Profiles {
+        &self.profiles
+    }
+
+    pub fn templates(&self) -> &Templates {
+        &self.templates
+    }
+
+    /// Problems found while loading the config file, shown as notifications.
+    pub fn warnings(&self) -> &[String] {
+        &self.warnings
+    }
+
     pub fn bat_args_for_preview(&self, pane_width: usize) -> Vec<String> {
         self.display
             .preview_options()
@@ -210,11 +502,47 @@ impl Config {
 
 # General behavior
 dirs_first = true
+# sort_expr = "is_dir desc, ext, name"
 show_hidden = true
 # show_system = false
 case_insensitive = true
 # always_show = []
+# scan_excludes = ["node_modules", ".git"]
 # max_find_results = 2000
+# max_copy_mbps = 0
+# create_file_mode = 0o644
+# create_dir_mode = 0o755
+# auto_refresh_secs = 0
+# watch = true
+# focus_new_items = true
+# message_secs = 3
+# filter_mode = "substring"
+# marker_policy = "operation"
+# clipboard = "auto"
+# paste_conflict = "rename"
+# readonly = false
+# root_guard = true
+# termux = false
+# animations = false
+# zoxide = false
+
+[find]
+# persist_history = false
+# history_size = 100
+# include = []
+# exclude = [".git", ".hg", ".svn", ".rustup", ".cargo", "target", "node_modules", "dist",
+#            "venv", ".venv", "__pycache__", ".DS_Store", "build", "out", "bin", "obj"]
+# gitignore = true
+# dirs_first = false
+
+# [workers]
+# queue_size = 64
+# preview_queue_size = 4
+# response_queue_size = 256
+# preview_overflow = "drop_oldest"   # "backlog", "drop_oldest" or "drop_newest"
+# fileop_overflow = "backlog"       # file operations are never dropped
+# dir_chunk_size = 5000
+# slow_warning_ms = 2000             # hint about loads slower than this, 0 = off
 
 [display]
 # selection_marker = true
@@ -223,6 +551,7 @@ borders = "unified"
 border_shape = "square"
 # titles = true
 # icons = false
+# ascii_only = false         # ASCII icons and markers, for terminals without a Nerd Font
 # separators = true
 # parent = true
 # preview = true
@@ -232,9 +561,22 @@ preview_underline = true
 # scroll_padding = 5
 # toggle_marker_jump = false
 # instant_preview = false
+# git_status = true
+# finder_tags = true
+# security_context = false
+# details = false
+# dir_sizes = false
+# inline_rename = false
+# file_colors = "none"     # "none", "builtin" or "ls_colors"
+# date_format = "%Y-%m-%d %H:%M:%S"
+# size_format = "decimal"  # "decimal" (kB) or "binary" (KiB)
+# force_ltr = false
 
 [display.preview_options]
 method = "internal"
+# syntax_highlighting = true
+# tab_width = 4
+# show_control_chars = false
 # bat related options if method = "bat"
 # theme = "TwoDark"
 # style = "plain"
@@ -251,11 +593,13 @@ method = "internal"
 # size = true
 # modified = true
 # perms = false
+# items = true
 # position = "default"
 
 [theme]
-name = "default"
+name = "default"             # a preset, or a file in themes/<name>.toml next to this file
 symlink = "default"
+context_mismatch = "red"
 selection_icon = ""
 
 # [theme.selection]
@@ -273,6 +617,7 @@ selection_icon = ""
 # [theme.directory]
 # fg = "blue"
 # bg = "default"
+# modifiers = ["bold"]   # also "dim", "italic", "underlined", "reversed", "crossed_out"
 
 # [theme.separator]
 # fg = "default"
@@ -306,15 +651,22 @@ selection_icon = ""
 # clipboard.bg = "default"
 
 # [theme.widget]
-# size = "medium"           # "small", "medium", "large" or [w ,h] or { w = 30, y = 30 }.
+# size = "medium"           # "small", "medium", "large", "60x12", "40%x30%", [w ,h] or { w = 30, h = 30 }.
 # position = "center"       # "center", "top_left", "bottomright", or [x, y] (percent) or { x = 42, y = 80 }.
+#                           # or { anchor = "bottom_right", dx = -2, dy = -1 } (offset in cells)
 # confirm_size = "large"
-# find_size = "medium"
+# shadow = false
+# dim_background = false
+# transparent = false
 # color.fg = "default"
 # color.bg = "default"
 # border.fg = "default"
 # border.bg = "default"
 
+# [theme.widget.find]       # also [theme.widget.rename] and [theme.widget.confirm]
+# size = "60%x50%"          # unset keys inherit from [theme.widget]
+# position = "center"
+
 # [theme.status_line]
 # fg = "default"
 # bg = "default"
@@ -330,16 +682,24 @@ selection_icon = ""
 
 # [editor]
 # cmd = "nvim"
+# open_with_system = true
+# open_many = "nvim -p %m"
 
 # [keys]
 # open_file = ["Enter"]
+# open_with_editor = ["o"]
 # go_up = ["k", "Up"]
 # go_down = ["j", "Down"]
+# go_top = ["g g"]          # key sequences are separated by spaces
+# go_bottom = ["Shift+g"]
 # go_parent = ["h", "Left", "Backspace"]
 # go_into_dir = ["l", "Right"]
 # quit = ["q", "Esc"]
 # delete = ["d"]
 # copy = ["y"]
+# copy_path = ["Shift+y"]
+# copy_relative_path = ["Ctrl+y"]
+# copy_name = ["Alt+y"]
 # paste = ["p"]
 # rename = ["r"]
 # create = ["n"]
@@ -347,9 +707,71 @@ selection_icon = ""
 # filter = ["f"]
 # toggle_marker = [" "]     # " " - indicates space bar
 # info = ["i"]
+# dir_size = ["Shift+d"]
+# clear_quarantine = ["Shift+x"]
+# stats = ["Shift+i"]
+# dump_state = ["F12"]
+# toggle_instant_preview = ["Ctrl+p"]
+# toggle_hidden = ["."]
+# toggle_preview = ["Shift+p"]
+# toggle_parent = ["Shift+h"]
+# reload_config = ["Shift+r"]
+# unlock = ["Shift+u"]
 # find = ["s"]
+# quick_find = ["/"]
+# navigate = ["Shift+f"]
+# grep = ["Shift+s"]
+# jump = ["z"]
+# command = [":", "!"]
+# shell = ["w"]
+# mark_all = ["Ctrl+a"]
+# invert_marks = ["Ctrl+r"]
 # clear_markers = ["Ctrl+c"]
 # clear_filter = ["Ctrl+f"]
+# marked = ["m"]
+# jump_back = ["Ctrl+o"]
+# jump_forward = ["Ctrl+i", "Tab"]
+# goto = ["g"]              # then h = home, r = root, c = config dir, or a [goto] key
+
+# [icons]                   # merged over the built-in icons, an icon is a string or { icon, fg }
+# default = ""
+# [icons.extensions]
+# rs = { icon = "", fg = "#dea584" }
+# [icons.files]
+# "justfile" = ""
+# [icons.dirs]
+# src = { fg = "blue" }
+
+# [commands]                # key = shell command, %f = selected, %m = marked, %s = marked or selected,
+# "Ctrl+e" = "tar -xf %f"   # %d = current directory
+
+# [goto]                    # key after the goto prefix = directory, "gd" goes to ~/Downloads
+# "d" = "~/Downloads"
+
+# [openers]                 # extension = command, used by open_file instead of the editor
+# "png" = "feh %f"
+# "pdf" = "zathura %f"
+
+# [open.enter]              # what Enter does per file class: "editor", "system", "preview" or "prompt"
+# text = "editor"           # classes are text, binary and executable, other keys are extensions
+# binary = "system"
+# ".md" = "preview"
+
+# [profiles.network]        # overrides while the current directory is on matching storage
+# fs_types = ["nfs", "nfs4", "cifs", "smb3", "fuse.sshfs"]
+# paths = ["/mnt/nas"]
+# instant_preview = false
+# watch = false
+# auto_refresh_secs = 10
+# git_status = false
+# dir_sizes = false
+
+# [templates.rust-main]     # contents of new files with a matching name, offered after the
+# names = ["main.rs"]       # new-file dialog, "*.sh" matches by extension
+# text = "fn main() {\n}\n"
+# [templates.script]
+# names = ["*.sh"]
+# file = "~/.config/runa/templates/script.sh"
 "##;
 
         let minimal_toml = r##"# runa.toml - minimal configuration
@@ -391,15 +813,49 @@ impl Default for Config {
     fn default() -> Self {
         Config {
             dirs_first: true,
+            sort_expr: None,
             show_hidden: true,
             show_system: false,
             case_insensitive: true,
             always_show: Arc::new(HashSet::new()),
+            scan_excludes: Arc::new(
+                default_scan_excludes()
+                    .into_iter()
+                    .map(OsString::from)
+                    .collect(),
+            ),
             max_find_results: DEFAULT_FIND_RESULTS,
+            max_copy_mbps: 0,
+            create_file_mode: None,
+            create_dir_mode: None,
+            auto_refresh_secs: 0,
+            watch: true,
+            focus_new_items: true,
+            message_secs: 3,
+            filter_mode: FilterMode::default(),
+            marker_policy: MarkerPolicy::default(),
+            clipboard: ClipboardMode::default(),
+            paste_conflict: PasteConflict::default(),
+            readonly: false,
+            root_guard: true,
+            termux: termux::is_termux(),
+            animations: false,
+            zoxide: false,
+            find: FindOptions::default(),
+            workers: WorkerOptions::default(),
             display: Display::default(),
             theme: Theme::default(),
+            file_styles: FileStyles::default(),
+            icons: Icons::default(),
             editor: Editor::default(),
             keys: Keys::default(),
+            commands: Commands::default(),
+            goto: Goto::default(),
+            openers: Openers::default(),
+            open: Open::default(),
+            profiles: Profiles::default(),
+            templates: Templates::default(),
+            warnings: Vec::new(),
         }
     }
 }
@@ -408,3 +864,19 @@ impl Default for Config {
 fn default_find_results() -> usize {
     DEFAULT_FIND_RESULTS
 }
+
+/// Helper function to parse sort_expr, an invalid expression is an error
+fn deserialize_sort_expr<'de, D>(deserializer: D) -> Result<Option<Arc<SortExpr>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    let expr = String::deserialize(deserializer)?;
+    SortExpr::parse(&expr)
+        .map(|expr| Some(Arc::new(expr)))
+        .map_err(|e| serde::de::Error::custom(format!("invalid sort_expr: {}", e)))
+}
+
+/// Helper function for default scan_excludes
+fn default_scan_excludes() -> Vec<String> {
+    vec!["node_modules".into(), ".git".into()]
+}
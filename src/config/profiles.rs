@@ -0,0 +1,137 @@
+//! Per-filesystem profile options for runa
+//!
+//! This module defines the `[profiles]` tables of the runa.toml configuration file.
+//! A profile overrides a few performance related options while the current directory
+//! is on matching storage, e.g. `[profiles.network]` for NFS and SMB mounts.
+
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+/// All profiles of the `[profiles]` table, ordered by their name.
+///
+/// The first profile matching the current directory is applied, see [Profiles::find].
+#[derive(Deserialize, Debug, Default)]
+#[serde(from = "BTreeMap<String, Profile>")]
+pub struct Profiles {
+    profiles: Vec<(String, Profile)>,
+}
+
+/// A single profile: where it applies and the options it overrides.
+///
+/// # Fields
+/// * `paths` - Path prefixes the profile applies to, `~` and `$PREFIX` are expanded, see [expand_path].
+/// * `fs_types` - Filesystem types the profile applies to, e.g. `"nfs"` or `"fuse.sshfs"`.
+/// * `instant_preview` - Overrides `display.instant_preview`.
+/// * `watch` - Overrides `watch`.
+/// * `auto_refresh_secs` - Overrides `auto_refresh_secs`.
+/// * `git_status` - Overrides `display.git_status`.
+/// * `details` - Overrides `display.details`.
+/// * `dir_sizes` - Overrides `display.dir_sizes`.
+#[derive(Deserialize, Debug, Default, Clone)]
+#[serde(default)]
+pub struct Profile {
+    paths: Vec<String>,
+    fs_types: Vec<String>,
+    instant_preview: Option<bool>,
+    watch: Option<bool>,
+    auto_refresh_secs: Option<u64>,
+    git_status: Option<bool>,
+    details: Option<bool>,
+    dir_sizes: Option<bool>,
+}
+
+impl From<BTreeMap<String, Profile>> for Profiles {
+    fn from(map: BTreeMap<String, Profile>) -> Self {
+        Profiles {
+            profiles: map.into_iter().collect(),
+        }
+    }
+}
+
+impl Profiles {
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+
+    /// Whether any profile matches by filesystem type, so the mounts have to be looked up.
+    pub fn uses_fs_types(&self) -> bool {
+        self.profiles.iter().any(|(_, p)| !p.fs_types.is_empty())
+    }
+
+    /// Finds the first profile, by name, which applies to `path`.
+    ///
+    /// # Arguments
+    /// * `path` - The current directory.
+    /// * `fs_type` - The filesystem type of the mount containing `path`, if known.
+    ///
+    /// # Returns
+    /// The name and the profile, or `None` if no profile applies.
+    pub fn find(&self, path: &Path, fs_type: Option<&str>) -> Option<(&str, &Profile)> {
+        self.profiles
+            .iter()
+            .find(|(_, profile)| profile.matches(path, fs_type))
+            .map(|(name, profile)| (name.as_str(), profile))
+    }
+}
+
+impl Profile {
+    pub fn instant_preview(&self) -> Option<bool> {
+        self.instant_preview
+    }
+
+    pub fn watch(&self) -> Option<bool> {
+        self.watch
+    }
+
+    pub fn auto_refresh_secs(&self) -> Option<u64> {
+        self.auto_refresh_secs
+    }
+
+    pub fn git_status(&self) -> Option<bool> {
+        self.git_status
+    }
+
+    pub fn details(&self) -> Option<bool> {
+        self.details
+    }
+
+    pub fn dir_sizes(&self) -> Option<bool> {
+        self.dir_sizes
+    }
+
+    /// Whether `path` is below one of the path prefixes, or on one of the filesystem types.
+    fn matches(&self, path: &Path, fs_type: Option<&str>) -> bool {
+        let by_path = self
+            .paths
+            .iter()
+            .any(|prefix| path.starts_with(expand_path(prefix)));
+        let by_type = fs_type.is_some_and(|fs_type| {
+            self.fs_types
+                .iter()
+                .any(|t| t.eq_ignore_ascii_case(fs_type))
+        });
+        by_path || by_type
+    }
+}
+
+/// Expands a leading `~` to the home directory, and a leading `$PREFIX` or `${PREFIX}` to the
+/// `PREFIX` variable, the install prefix of Termux (e.g. `"$PREFIX/share/runa"`).
+pub fn expand_path(path: &str) -> PathBuf {
+    let (base, rest) = if let Some(rest) = path.strip_prefix('~') {
+        (dirs::home_dir(), rest)
+    } else if let Some(rest) = path
+        .strip_prefix("${PREFIX}")
+        .or_else(|| path.strip_prefix("$PREFIX"))
+    {
+        (std::env::var_os("PREFIX").map(PathBuf::from), rest)
+    } else {
+        return PathBuf::from(path);
+    };
+    match base {
+        Some(base) if rest.is_empty() || rest.starts_with(['/', '\\']) => {
+            base.join(rest.trim_start_matches(['/', '\\']))
+        }
+        _ => PathBuf::from(path),
+    }
+}
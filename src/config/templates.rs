@@ -0,0 +1,91 @@
+//! File templates for runa
+//!
+//! This module defines the `[templates]` tables of the runa.toml configuration file.
+//! A template pre-populates new files whose name matches, e.g. `[templates.rust-main]`
+//! for a `main.rs` skeleton. Matching templates are offered after confirming the new-file dialog.
+
+use crate::config::profiles::expand_path;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+
+/// All templates of the `[templates]` table, ordered by their name.
+#[derive(Deserialize, Debug, Default)]
+#[serde(from = "BTreeMap<String, Template>")]
+pub struct Templates {
+    templates: Vec<(String, Template)>,
+}
+
+/// A single template: the file names it applies to and the contents of new files.
+///
+/// # Fields
+/// * `names` - File names or `*.ext` patterns the template applies to, e.g. `"main.rs"` or `"*.sh"`.
+/// * `text` - The contents of new files.
+/// * `file` - A file to copy the contents from instead of `text`, `~` and `$PREFIX` are expanded.
+#[derive(Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(default)]
+pub struct Template {
+    names: Vec<String>,
+    text: Option<String>,
+    file: Option<String>,
+}
+
+impl From<BTreeMap<String, Template>> for Templates {
+    fn from(map: BTreeMap<String, Template>) -> Self {
+        Templates {
+            templates: map.into_iter().collect(),
+        }
+    }
+}
+
+impl Templates {
+    pub fn is_empty(&self) -> bool {
+        self.templates.is_empty()
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Template> {
+        self.templates
+            .iter()
+            .find(|(n, _)| n == name)
+            .map(|(_, template)| template)
+    }
+
+    /// The names of all templates which apply to one of `file_names`, ordered by name.
+    pub fn matching<'a>(&self, file_names: impl IntoIterator<Item = &'a str>) -> Vec<String> {
+        let file_names: Vec<&str> = file_names.into_iter().collect();
+        self.templates
+            .iter()
+            .filter(|(_, template)| file_names.iter().any(|name| template.matches(name)))
+            .map(|(name, _)| name.clone())
+            .collect()
+    }
+}
+
+impl Template {
+    /// Whether the template applies to a file name, by exact name or by `*.ext` suffix.
+    /// Extensions are case-insensitive.
+    pub fn matches(&self, file_name: &str) -> bool {
+        self.names
+            .iter()
+            .any(|pattern| match pattern.strip_prefix('*') {
+                Some(suffix) => file_name
+                    .len()
+                    .checked_sub(suffix.len())
+                    .and_then(|start| file_name.get(start..))
+                    .is_some_and(|end| end.eq_ignore_ascii_case(suffix)),
+                None => file_name == pattern,
+            })
+    }
+
+    /// Reads the contents for new files, from `file` if set, otherwise `text`.
+    ///
+    /// # Errors
+    /// Returns an std::io::Error if the template file cannot be read.
+    pub fn contents(&self) -> io::Result<Vec<u8>> {
+        match &self.file {
+            Some(file) => fs::read(expand_path(file)),
+            None => Ok(self.text.clone().unwrap_or_default().into_bytes()),
+        }
+    }
+}
@@ -4,12 +4,15 @@
 //! configuration file.
 //!
 //! Also holds the internal themes and the logic to apply user overrides on top of them.
+//! Themes which are not built in are read from `themes/<name>.toml` next to runa.toml.
 
 use crate::ui::widgets::{DialogPosition, DialogSize};
-use crate::utils::parse_color;
+use crate::utils::{parse_color, parse_modifier};
 use once_cell::sync::Lazy;
-use ratatui::style::{Color, Style};
+use ratatui::style::{Color, Modifier, Style};
 use serde::Deserialize;
+use std::fs;
+use std::path::Path;
 
 /// Theme configuration options
 /// Holds all color and style options for the application.
@@ -41,6 +44,8 @@ pub struct Theme {
     status_line: ColorPair,
     #[serde(deserialize_with = "deserialize_color_field")]
     symlink: Color,
+    #[serde(deserialize_with = "deserialize_color_field")]
+    context_mismatch: Color,
     marker: MarkerTheme,
     widget: WidgetTheme,
     /// info does not honor the .size field from widget.
@@ -79,6 +84,7 @@ impl Default for Theme {
             },
             status_line: ColorPair::default(),
             symlink: Color::Magenta,
+            context_mismatch: Color::Red,
             marker: MarkerTheme::default(),
             widget: WidgetTheme::default(),
             info: WidgetTheme {
@@ -158,6 +164,12 @@ impl Theme {
         self.symlink.or(Theme::internal_defaults().symlink)
     }
 
+    /// The color of entries with another SELinux type than their directory.
+    pub fn context_mismatch(&self) -> Color {
+        self.context_mismatch
+            .or(Theme::internal_defaults().context_mismatch)
+    }
+
     // Accessor methods for various theme properties
 
     pub fn selection_icon(&self) -> &str {
@@ -184,37 +196,104 @@ impl Theme {
         &self.info
     }
 
-    /// Apply user overrides on top of a preset theme if a known preset name is provided.
-    /// If no preset name is provided or the name is unknown, returns the theme as is.
-    pub fn with_overrides(self) -> Self {
-        let preset = match self.name.as_deref() {
-            Some("gruvbox-dark-hard") => Some(gruvbox_dark_hard()),
-            Some("gruvbox-dark") => Some(gruvbox_dark()),
-            Some("gruvbox-light") => Some(gruvbox_light()),
+    /// Apply user overrides on top of the theme selected by `name`.
+    ///
+    /// A file `<name>.toml` in `themes_dir` takes precedence over the preset of that name.
+    /// The file holds the same tables as `[theme]` without the `theme.` prefix, and may name a
+    /// preset it builds on. The inline `[theme]` options are applied on top of it.
+    ///
+    /// # Returns
+    /// The theme, and a warning if the theme file cannot be read or parsed, or the name is
+    /// neither a preset nor a file. The inline options are used as they are in that case.
+    pub fn with_overrides(self, themes_dir: &Path) -> (Self, Option<String>) {
+        let Some(name) = self.name.clone() else {
+            return (self, None);
+        };
+        let path = themes_dir.join(format!("{}.toml", name));
+        if !path.is_file() {
+            if name != "default" && Theme::preset(&name).is_none() {
+                let warning = format!(
+                    "Unknown theme \"{}\", no preset or {} found",
+                    name,
+                    path.display()
+                );
+                return (self, Some(warning));
+            }
+            return (self.with_preset(), None);
+        }
+
+        match Theme::load_file(&path) {
+            Ok(file) => {
+                // Syntax highlighting follows the preset the file builds on, if any
+                let base_name = file.name.clone();
+                let mut base = file.with_preset();
+                base.apply_user_overrides(self);
+                if base_name.is_some() {
+                    base.name = base_name;
+                }
+                (base, None)
+            }
+            Err(e) => (self, Some(format!("Theme \"{}\": {}", name, e))),
+        }
+    }
+
+    /// Reads a theme file.
+    ///
+    /// # Errors
+    /// Returns a message naming the line of the first error if the file cannot be read or parsed.
+    pub fn load_file(path: &Path) -> Result<Self, String> {
+        let content = fs::read_to_string(path)
+            .map_err(|e| format!("Could not read {}: {}", path.display(), e))?;
+        toml::from_str::<Theme>(&content).map_err(|e| {
+            match e
+                .span()
+                .map(|span| content[..span.start].matches('\n').count() + 1)
+            {
+                Some(line) => format!(
+                    "Error parsing {} at line {}: {}",
+                    path.display(),
+                    line,
+                    e.message()
+                ),
+                None => format!("Error parsing {}: {}", path.display(), e.message()),
+            }
+        })
+    }
 
-            Some("catppuccin-mocha") => Some(catppuccin_mocha()),
-            Some("catppuccin-frappe") => Some(catppuccin_frappe()),
-            Some("catppuccin-macchiato") => Some(catppuccin_mocha()),
-            Some("catppuccin-latte") => Some(catppuccin_latte()),
+    /// Apply the theme on top of the preset named by `name`, if it is a known preset.
+    fn with_preset(self) -> Self {
+        match self.name.as_deref().and_then(Theme::preset) {
+            Some(mut base) => {
+                base.apply_user_overrides(self);
+                base
+            }
+            None => self,
+        }
+    }
 
-            Some("nightfox") => Some(nightfox()),
-            Some("carbonfox") => Some(carbonfox()),
+    /// The built-in theme called `name`.
+    fn preset(name: &str) -> Option<Theme> {
+        match name {
+            "gruvbox-dark-hard" => Some(gruvbox_dark_hard()),
+            "gruvbox-dark" => Some(gruvbox_dark()),
+            "gruvbox-light" => Some(gruvbox_light()),
 
-            Some("tokyonight") => Some(tokyonight_night()),
-            Some("tokyonight-storm") => Some(tokyonight_storm()),
-            Some("tokyonight-day") => Some(tokyonight_day()),
+            "catppuccin-mocha" => Some(catppuccin_mocha()),
+            "catppuccin-frappe" => Some(catppuccin_frappe()),
+            "catppuccin-macchiato" => Some(catppuccin_mocha()),
+            "catppuccin-latte" => Some(catppuccin_latte()),
 
-            Some("everforest") => Some(everforest()),
-            Some("rose-pine") | Some("rose_pine") => Some(rose_pine()),
+            "nightfox" => Some(nightfox()),
+            "carbonfox" => Some(carbonfox()),
 
-            _ => None,
-        };
+            "tokyonight" => Some(tokyonight_night()),
+            "tokyonight-storm" => Some(tokyonight_storm()),
+            "tokyonight-day" => Some(tokyonight_day()),
 
-        if let Some(mut base) = preset {
-            base.apply_user_overrides(self);
-            base
-        } else {
-            self
+            "everforest" => Some(everforest()),
+            "rose-pine" | "rose_pine" => Some(rose_pine()),
+
+            _ => None,
         }
     }
 
@@ -228,6 +307,34 @@ impl Theme {
             .unwrap_or("TwoDark")
     }
 
+    /// Map internal theme name to a bundled syntect theme for the internal preview.
+    /// If no name is set, defaults to "base16-ocean.dark".
+    /// Returns a static string slice representing the syntect theme name.
+    pub fn syntax_theme_name(&self) -> &'static str {
+        self.name
+            .as_deref()
+            .map(Theme::map_to_syntax_theme)
+            .unwrap_or("base16-ocean.dark")
+    }
+
+    /// Helper function to map internal theme names to the themes bundled with syntect.
+    /// Light themes map to a light syntax theme, everything else to a dark one.
+    /// # Arguments:
+    /// * internal_theme - A string slice representing the internal theme name.
+    ///
+    /// # Returns:
+    /// * A static string slice representing the corresponding syntect theme name.
+    fn map_to_syntax_theme(internal_theme: &str) -> &'static str {
+        match internal_theme {
+            "gruvbox-dark" | "gruvbox-dark-hard" | "gruvbox" => "base16-eighties.dark",
+            "catppuccin-mocha" | "catppuccin" | "catppuccin-macchiato" | "catppuccin-frappe" => {
+                "base16-mocha.dark"
+            }
+            "gruvbox-light" | "catppuccin-latte" => "InspiredGitHub",
+            _ => "base16-ocean.dark",
+        }
+    }
+
     /// Helper function to map internal theme names to bat theme names.
     /// Used by bat for syntax highlighting.
     /// # Arguments:
@@ -267,6 +374,7 @@ impl Theme {
         override_if_changed!(self, user, defaults, path);
         override_if_changed!(self, user, defaults, status_line);
         override_if_changed!(self, user, defaults, symlink);
+        override_if_changed!(self, user, defaults, context_mismatch);
         override_if_changed!(self, user, defaults, selection_icon);
         override_if_changed!(self, user, defaults, marker);
         override_if_changed!(self, user, defaults, widget);
@@ -280,21 +388,26 @@ impl Theme {
 
 /// ColorPair struct to hold foreground and background colors.
 /// Used throughout the theme configuration.
+///
+/// `modifiers` are text attributes like `["bold", "italic"]`, see [parse_modifier].
 #[derive(Deserialize, Debug, Clone, Copy, PartialEq)]
 pub struct ColorPair {
     #[serde(default, deserialize_with = "deserialize_color_field")]
     fg: Color,
     #[serde(default, deserialize_with = "deserialize_color_field")]
     bg: Color,
+    #[serde(default, deserialize_with = "deserialize_modifiers")]
+    modifiers: Modifier,
 }
 
 /// Default implementation for ColorPair
-/// Sets both foreground and background to Color::Reset
+/// Sets both foreground and background to Color::Reset, without modifiers
 impl Default for ColorPair {
     fn default() -> Self {
         Self {
             fg: Color::Reset,
             bg: Color::Reset,
+            modifiers: Modifier::empty(),
         }
     }
 }
@@ -302,16 +415,47 @@ impl Default for ColorPair {
 /// ColorPair implementation
 /// Provides methods to convert to Style and get effective styles.
 impl ColorPair {
+    /// Returns this color pair with any `Reset` colors taken from the fallback.
+    ///
+    /// # Arguments
+    /// * `fallback` - A `ColorPair` to use for any `Reset` colors.
+    fn or_pair(&self, fallback: &ColorPair) -> ColorPair {
+        ColorPair {
+            fg: self.fg.or(fallback.fg),
+            bg: self.bg.or(fallback.bg),
+            modifiers: self.modifiers_or(fallback),
+        }
+    }
+
+    /// The own modifiers, or those of the fallback if none are set.
+    fn modifiers_or(&self, fallback: &ColorPair) -> Modifier {
+        if self.modifiers.is_empty() {
+            fallback.modifiers
+        } else {
+            self.modifiers
+        }
+    }
+
+    /// A color pair with the colors and modifiers of a style, for the `_or` fallbacks.
+    fn from_style(style: Style) -> ColorPair {
+        ColorPair {
+            fg: style.fg.unwrap_or(Color::Reset),
+            bg: style.bg.unwrap_or(Color::Reset),
+            modifiers: style.add_modifier,
+        }
+    }
+
     /// Converts a `ColorPair` to a `Style`, using the provided fallback for any `Reset` colors.
     ///
     /// If the foreground or background color is `Color::Reset`, the corresponding color from
     /// `fallback` is used. Otherwise, the `ColorPair`’s own color is used.
+    /// The modifiers of `fallback` are used if the `ColorPair` sets none.
     ///
     /// # Arguments
     /// * `fallback` - A `ColorPair` to use for any `Reset` colors.
     ///
     /// # Returns
-    /// * `Style` - A `Style` with `fg` and `bg` set to the effective colors.
+    /// * `Style` - A `Style` with `fg`, `bg` and the modifiers set to the effective values.
     ///
     /// # Example
     /// Use `ColorPair::new(fg, bg)` to create a color pair before calling `style_or`.
@@ -326,7 +470,10 @@ impl ColorPair {
         } else {
             self.bg
         };
-        Style::default().fg(fg).bg(bg)
+        Style::default()
+            .fg(fg)
+            .bg(bg)
+            .add_modifier(self.modifiers_or(fallback))
     }
 }
 
@@ -418,11 +565,11 @@ impl Default for MarkerTheme {
             icon: "*".to_string(),
             color: ColorPair {
                 fg: Color::Yellow,
-                bg: Color::Reset,
+                ..ColorPair::default()
             },
             clipboard: Some(ColorPair {
                 fg: Color::Green,
-                bg: Color::Reset,
+                ..ColorPair::default()
             }),
         }
     }
@@ -430,6 +577,9 @@ impl Default for MarkerTheme {
 
 /// WidgetTheme struct to hold colors and styles for widgets/dialogs.
 /// Used by various dialog widgets and overlay widgets.
+///
+/// The `rename`, `find` and `confirm` sections override the base widget theme for
+/// the respective dialog type, see [WidgetTheme::for_dialog].
 #[derive(Deserialize, Debug, Clone, PartialEq)]
 #[serde(default)]
 pub struct WidgetTheme {
@@ -441,9 +591,99 @@ pub struct WidgetTheme {
     confirm_size: Option<DialogSize>,
     find_visible_results: Option<usize>,
     find_width: Option<u16>,
+    shadow: bool,
+    dim_background: bool,
+    transparent: bool,
+    rename: Option<DialogTheme>,
+    find: Option<DialogTheme>,
+    confirm: Option<DialogTheme>,
+}
+
+/// The dialog types which can be themed separately inside `[theme.widget]`.
+///
+/// Variants:
+/// * `Rename` - The rename input dialog, `[theme.widget.rename]`.
+/// * `Find` - The find and grep dialog, `[theme.widget.find]`.
+/// * `Confirm` - The delete and resume confirmations, `[theme.widget.confirm]`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DialogKind {
+    Rename,
+    Find,
+    Confirm,
+}
+
+/// DialogTheme struct to hold the per dialog overrides of the [WidgetTheme].
+///
+/// Every field left unset (or set to "default" for colors) inherits from the base widget theme.
+#[derive(Deserialize, Debug, Clone, Default, PartialEq)]
+#[serde(default)]
+pub struct DialogTheme {
+    color: ColorPair,
+    border: ColorPair,
+    title: ColorPair,
+    position: Option<DialogPosition>,
+    size: Option<DialogSize>,
 }
 
 impl WidgetTheme {
+    /// Returns the widget theme for a specific dialog type.
+    ///
+    /// Starts from the base widget theme and applies the matching dialog section on top.
+    /// For [DialogKind::Confirm] the size of the section overrides `confirm_size`.
+    ///
+    /// # Arguments
+    /// * `kind` - The dialog type to resolve the theme for.
+    ///
+    /// # Returns
+    /// * `WidgetTheme` - The merged theme for the dialog.
+    pub fn for_dialog(&self, kind: DialogKind) -> WidgetTheme {
+        let mut merged = self.clone();
+        let section = match kind {
+            DialogKind::Rename => &self.rename,
+            DialogKind::Find => &self.find,
+            DialogKind::Confirm => &self.confirm,
+        };
+        let Some(section) = section else {
+            return merged;
+        };
+
+        merged.color = section.color.or_pair(&self.color);
+        merged.border = section.border.or_pair(&self.border);
+        merged.title = section.title.or_pair(&self.title);
+        if section.position.is_some() {
+            merged.position = section.position;
+        }
+        if section.size.is_some() {
+            match kind {
+                DialogKind::Confirm => merged.confirm_size = section.size,
+                _ => merged.size = section.size,
+            }
+        }
+        merged
+    }
+
+    /// Returns true if dialogs draw a drop shadow to their bottom right.
+    pub fn shadow(&self) -> bool {
+        self.shadow
+    }
+
+    /// Returns true if the panes are dimmed while a modal dialog is open.
+    pub fn dim_background(&self) -> bool {
+        self.dim_background
+    }
+
+    /// Returns true if dialogs leave the background to the terminal, so translucent
+    /// terminal backgrounds show through.
+    pub fn transparent(&self) -> bool {
+        self.transparent
+    }
+
+    /// Returns the size set in the `[theme.widget.find]` section, if any.
+    /// The find dialog is sized by `find_width` and `find_visible_results` otherwise.
+    pub fn find_size(&self) -> Option<DialogSize> {
+        self.find.as_ref().and_then(|f| f.size)
+    }
+
     /// Returns the dialog position.
     pub fn position(&self) -> &Option<DialogPosition> {
         &self.position
@@ -470,26 +710,17 @@ impl WidgetTheme {
 
     /// Returns the border style, falling back to the provided style for Reset colors.
     pub fn border_style_or(&self, fallback: Style) -> Style {
-        self.border.style_or(&ColorPair {
-            fg: fallback.fg.unwrap_or(Color::Reset),
-            bg: fallback.bg.unwrap_or(Color::Reset),
-        })
+        self.border.style_or(&ColorPair::from_style(fallback))
     }
 
     /// Returns the foreground style, falling back to the provided style if Reset.
     pub fn fg_or(&self, fallback: Style) -> Style {
-        self.color.style_or(&ColorPair {
-            fg: fallback.fg.unwrap_or(Color::Reset),
-            bg: fallback.bg.unwrap_or(Color::Reset),
-        })
+        self.color.style_or(&ColorPair::from_style(fallback))
     }
 
     /// Returns the background style, falling back to the provided style if Reset.
     pub fn bg_or(&self, fallback: Style) -> Style {
-        self.color.style_or(&ColorPair {
-            fg: fallback.fg.unwrap_or(Color::Reset),
-            bg: fallback.bg.unwrap_or(Color::Reset),
-        })
+        self.color.style_or(&ColorPair::from_style(fallback))
     }
 
     /// Returns the foreground style, falling back to the internal default theme if Reset.
@@ -504,10 +735,7 @@ impl WidgetTheme {
 
     /// Returns the title style, falling back to the provided style for Reset colors.
     pub fn title_style_or(&self, fallback: Style) -> Style {
-        self.title.style_or(&ColorPair {
-            fg: fallback.fg.unwrap_or(Color::Reset),
-            bg: fallback.bg.unwrap_or(Color::Reset),
-        })
+        self.title.style_or(&ColorPair::from_style(fallback))
     }
 
     /// Returns the title style, falling back to the internal default theme if Reset.
@@ -538,6 +766,12 @@ impl Default for WidgetTheme {
             confirm_size: Some(DialogSize::Large),
             find_visible_results: Some(5),
             find_width: Some(40),
+            shadow: false,
+            dim_background: false,
+            transparent: false,
+            rename: None,
+            find: None,
+            confirm: None,
         }
     }
 }
@@ -570,6 +804,21 @@ where
     Ok(parse_color(&s))
 }
 
+// Helper function to deserialize the modifiers of a ColorPair, unknown names are an error
+fn deserialize_modifiers<'de, D>(deserializer: D) -> Result<Modifier, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    Vec::<String>::deserialize(deserializer)?.iter().try_fold(
+        Modifier::empty(),
+        |modifiers, name| {
+            parse_modifier(name)
+                .map(|modifier| modifiers | modifier)
+                .ok_or_else(|| serde::de::Error::custom(format!("unknown modifier \"{}\"", name)))
+        },
+    )
+}
+
 /// Helper function to convert RGB tuples to [Color] instances.
 fn rgb(c: (u8, u8, u8)) -> Color {
     Color::Rgb(c.0, c.1, c.2)
@@ -627,10 +876,11 @@ pub fn make_theme(name: &str, palette: Palette, icon: &str) -> Theme {
         },
 
         status_line: ColorPair {
-            fg: Color::Reset,
             bg: base_bg,
+            ..ColorPair::default()
         },
         symlink: secondary,
+        context_mismatch: Color::Red,
         marker: MarkerTheme {
             icon: icon.to_string(),
             color: ColorPair {
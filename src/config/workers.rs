@@ -0,0 +1,97 @@
+//! Worker configuration options for runa
+//!
+//! This module defines the `[workers]` section of the runa.toml configuration file,
+//! which bounds the task and result queues of the background workers, sets how large
+//! directories are streamed to the UI and when slow loads are pointed out.
+
+use crate::core::worker::OverflowPolicy;
+use serde::Deserialize;
+use std::time::Duration;
+
+/// Worker queue configuration options
+///
+/// # Fields
+/// * `queue_size` - Capacity of the directory load, file operation and git status queues.
+/// * `preview_queue_size` - Capacity of the preview queue.
+/// * `response_queue_size` - Capacity of the queue carrying results back to the UI.
+/// * `preview_overflow` - What happens to previews requested while the queue is full.
+/// * `fileop_overflow` - What happens to file operations requested while the queue is full,
+///   only `backlog` is accepted, as dropping a queued delete or paste would lose it.
+/// * `dir_chunk_size` - Entries after which a large directory is shown while it is still loading,
+///   0 waits for the whole directory.
+/// * `slow_warning_ms` - Milliseconds after which a directory load or preview is reported as
+///   slow, 0 never reports them.
+#[derive(Deserialize, Debug)]
+#[serde(default)]
+pub struct WorkerOptions {
+    queue_size: usize,
+    preview_queue_size: usize,
+    response_queue_size: usize,
+    preview_overflow: OverflowPolicy,
+    #[serde(deserialize_with = "deserialize_lossless_overflow")]
+    fileop_overflow: OverflowPolicy,
+    dir_chunk_size: usize,
+    slow_warning_ms: u64,
+}
+
+/// Public methods for accessing worker configuration options
+///
+/// Queue sizes are at least 1.
+impl WorkerOptions {
+    pub fn queue_size(&self) -> usize {
+        self.queue_size.max(1)
+    }
+
+    pub fn preview_queue_size(&self) -> usize {
+        self.preview_queue_size.max(1)
+    }
+
+    pub fn response_queue_size(&self) -> usize {
+        self.response_queue_size.max(1)
+    }
+
+    pub fn preview_overflow(&self) -> OverflowPolicy {
+        self.preview_overflow
+    }
+
+    pub fn fileop_overflow(&self) -> OverflowPolicy {
+        self.fileop_overflow
+    }
+
+    pub fn dir_chunk_size(&self) -> usize {
+        self.dir_chunk_size
+    }
+
+    /// How long a directory load or preview may take before it is reported as slow.
+    pub fn slow_warning(&self) -> Option<Duration> {
+        (self.slow_warning_ms > 0).then(|| Duration::from_millis(self.slow_warning_ms))
+    }
+}
+
+/// Default worker configuration options
+impl Default for WorkerOptions {
+    fn default() -> Self {
+        WorkerOptions {
+            queue_size: 64,
+            preview_queue_size: 4,
+            response_queue_size: 256,
+            preview_overflow: OverflowPolicy::DropOldest,
+            fileop_overflow: OverflowPolicy::Backlog,
+            dir_chunk_size: 5000,
+            slow_warning_ms: 2000,
+        }
+    }
+}
+
+// Helper function to deserialize the file operation overflow, which must not drop tasks
+fn deserialize_lossless_overflow<'de, D>(deserializer: D) -> Result<OverflowPolicy, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    match OverflowPolicy::deserialize(deserializer)? {
+        OverflowPolicy::Backlog => Ok(OverflowPolicy::Backlog),
+        _ => Err(serde::de::Error::custom(
+            "fileop_overflow must be \"backlog\", file operations are never dropped",
+        )),
+    }
+}
@@ -2,22 +2,48 @@
 //!
 //! This module contains the non-UI “engine” pieces used by the application:
 //! - [fm]: directory traversal and file metadata (see [browse_dir], [FileEntry], [FileInfo]).
+//! - [finder]: macOS Finder tags and the quarantine attribute.
+//! - [security]: SELinux security contexts on Linux.
+//! - [streams]: alternate data streams of NTFS files and macOS resource forks.
+//! - [platform]: the OS specific parts, with fallbacks for the BSDs, illumos and others.
 //! - [formatter]: formatting helpers for displaying file attributes, sizes, times, types, and previews.
 //! - [worker]: background work and message passing back into the app state.
 //! - [terminal]: terminal setup/teardown and the main crossterm/ratatui event loop.
-//! - [proc]: process management for running external commands like `bat`, `fd`.
+//! - [highlight]: syntax highlighting for the internal preview.
+//! - [proc]: process management for running external commands like `bat`, `fd` and content search.
 //!
 //! Most callers will import [browse_dir], [FileEntry], and [FileInfo] from this module.
 
+pub mod finder;
 pub mod fm;
 pub mod formatter;
+pub mod highlight;
+pub mod platform;
 pub mod proc;
+pub mod security;
+pub mod streams;
 pub mod terminal;
 pub mod worker;
 
-pub use fm::{FileEntry, FileInfo, FileType, browse_dir};
+pub use finder::{FinderTags, Quarantine, TagColor, clear_quarantine};
+pub use fm::{
+    DirId, DirItems, EntryMeta, FileDetails, FileEntry, FileInfo, FileType, PathSize, browse_dir,
+    browse_dir_chunked, count_dir_items, create_entry, find_renamed_dir, fs_type, link_target,
+    mount_fs_type, parse_mounts, path_size, path_size_cancellable, remove_path,
+};
 pub use formatter::{
-    Formatter, format_attributes, format_file_size, format_file_time, format_file_type,
-    preview_directory, safe_read_preview, sanitize_to_exact_width, symlink_target_resolved,
+    ASCII_ELLIPSIS, DEFAULT_DATE_FORMAT, ELLIPSIS, Formatter, PARALLEL_FORMAT_THRESHOLD,
+    SizeFormat, SortExpr, SortKey, TextRendering, display_safe_name, format_attributes,
+    format_dir_items, format_file_size, format_file_time, format_file_time_short, format_file_type,
+    highlighted_preview, is_binary_file, is_valid_date_format, preview_directory,
+    render_to_exact_width, safe_read_preview, sanitize_to_exact_width, symlink_target_resolved,
+    truncate_to_width, truncate_to_width_with,
+};
+pub use proc::{
+    ContentMatch, FindKind, FindResult, GitInfo, find, find_streaming, find_with_kind,
+    git_entry_status, git_info, grep, grep_with_filters, open_with_system, preview_bat,
+    split_extensions, zoxide_add, zoxide_query,
 };
-pub use proc::{FindResult, find, preview_bat};
+pub use proc::{DEFAULT_EXCLUDES, SearchFilters};
+pub use security::{SecurityContext, read_context};
+pub use streams::{DataStream, list_streams};
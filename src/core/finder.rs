@@ -0,0 +1,318 @@
+//! macOS Finder metadata for runa.
+//!
+//! Finder stores the color tags of a file in the `com.apple.metadata:_kMDItemUserTags` extended
+//! attribute, a binary property list with one `name\ncolor` string per tag. Downloads are marked
+//! by the `com.apple.quarantine` attribute, which makes Gatekeeper ask before opening them.
+//!
+//! Both are only read on macOS, elsewhere entries have no tags and no quarantine.
+
+use std::io;
+use std::path::Path;
+use std::time::{Duration, SystemTime};
+
+#[cfg(target_os = "macos")]
+const TAGS_XATTR: &str = "com.apple.metadata:_kMDItemUserTags";
+#[cfg(target_os = "macos")]
+const QUARANTINE_XATTR: &str = "com.apple.quarantine";
+
+/// The color of a Finder tag, numbered like in the tag strings.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TagColor {
+    Gray = 1,
+    Green = 2,
+    Purple = 3,
+    Blue = 4,
+    Yellow = 5,
+    Red = 6,
+    Orange = 7,
+}
+
+impl TagColor {
+    const ALL: [TagColor; 7] = [
+        TagColor::Gray,
+        TagColor::Green,
+        TagColor::Purple,
+        TagColor::Blue,
+        TagColor::Yellow,
+        TagColor::Red,
+        TagColor::Orange,
+    ];
+}
+
+/// The colors of the Finder tags of an entry, tags without a color are left out.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct FinderTags(u8);
+
+impl FinderTags {
+    /// Collects the colors of tag strings like `"Red\n6"`.
+    pub fn from_names<S: AsRef<str>>(names: &[S]) -> Self {
+        let bits = names
+            .iter()
+            .filter_map(|name| name.as_ref().rsplit_once('\n'))
+            .filter_map(|(_, color)| color.parse::<u8>().ok())
+            .filter(|color| (1..=7).contains(color))
+            .fold(0, |bits, color| bits | 1 << color);
+        FinderTags(bits)
+    }
+
+    pub fn is_empty(self) -> bool {
+        self.0 == 0
+    }
+
+    /// The tag colors, in the order Finder numbers them.
+    pub fn colors(self) -> impl Iterator<Item = TagColor> {
+        TagColor::ALL
+            .into_iter()
+            .filter(move |color| self.0 & 1 << *color as u8 != 0)
+    }
+}
+
+/// The quarantine attribute of a downloaded file.
+///
+/// # Fields
+/// * `agent` - The application which downloaded the file, e.g. `"Safari"`.
+/// * `downloaded` - When the file was downloaded.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Quarantine {
+    agent: Option<String>,
+    downloaded: Option<SystemTime>,
+}
+
+impl Quarantine {
+    /// Parses an attribute value like `"0083;65a1b2c3;Safari;<uuid>"`: flags, the download time
+    /// in hex seconds since the epoch, the downloading agent and an event id.
+    pub fn parse(value: &[u8]) -> Self {
+        let value = String::from_utf8_lossy(value);
+        let mut fields = value.split(';').skip(1);
+        let downloaded = fields
+            .next()
+            .and_then(|secs| u64::from_str_radix(secs, 16).ok())
+            .map(|secs| SystemTime::UNIX_EPOCH + Duration::from_secs(secs));
+        let agent = fields
+            .next()
+            .map(str::trim)
+            .filter(|agent| !agent.is_empty())
+            .map(str::to_string);
+        Quarantine { agent, downloaded }
+    }
+
+    pub fn agent(&self) -> Option<&str> {
+        self.agent.as_deref()
+    }
+
+    pub fn downloaded(&self) -> Option<SystemTime> {
+        self.downloaded
+    }
+}
+
+/// Reads the tag strings of a `_kMDItemUserTags` binary property list.
+///
+/// # Returns
+/// The strings of the top level array, empty if the data is no such property list.
+pub fn parse_user_tags(data: &[u8]) -> Vec<String> {
+    string_array(data).unwrap_or_default()
+}
+
+/// Reads the colors of the Finder tags of `path`.
+pub fn read_tags(path: &Path) -> FinderTags {
+    #[cfg(target_os = "macos")]
+    {
+        xattr::read(path, TAGS_XATTR)
+            .map(|data| FinderTags::from_names(&parse_user_tags(&data)))
+            .unwrap_or_default()
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = path;
+        FinderTags::default()
+    }
+}
+
+/// Reads the quarantine attribute of `path`, `None` if it is not quarantined.
+pub fn read_quarantine(path: &Path) -> Option<Quarantine> {
+    #[cfg(target_os = "macos")]
+    {
+        xattr::read(path, QUARANTINE_XATTR).map(|value| Quarantine::parse(&value))
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// Removes the quarantine attribute of `path`, so it opens without the Gatekeeper prompt.
+///
+/// # Returns
+/// Whether the path was quarantined.
+///
+/// # Errors
+/// Returns an std::io::Error if the attribute cannot be removed, or with
+/// [io::ErrorKind::Unsupported] on other platforms than macOS.
+pub fn clear_quarantine(path: &Path) -> io::Result<bool> {
+    #[cfg(target_os = "macos")]
+    {
+        xattr::remove(path, QUARANTINE_XATTR)
+    }
+
+    #[cfg(not(target_os = "macos"))]
+    {
+        let _ = path;
+        Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "quarantine attributes only exist on macOS",
+        ))
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod xattr {
+    use std::ffi::CString;
+    use std::io;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    /// Reads the extended attribute `name` of `path`, without following symlinks.
+    pub(super) fn read(path: &Path, name: &str) -> Option<Vec<u8>> {
+        let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let name = CString::new(name).ok()?;
+        // SAFETY: both strings are NUL terminated, a null buffer only asks for the size
+        let len = unsafe {
+            libc::getxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                std::ptr::null_mut(),
+                0,
+                0,
+                libc::XATTR_NOFOLLOW,
+            )
+        };
+        if len <= 0 {
+            return None;
+        }
+        let mut buf = vec![0u8; len as usize];
+        // SAFETY: the buffer is valid for `buf.len()` bytes
+        let read = unsafe {
+            libc::getxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                buf.as_mut_ptr().cast(),
+                buf.len(),
+                0,
+                libc::XATTR_NOFOLLOW,
+            )
+        };
+        if read < 0 {
+            return None;
+        }
+        buf.truncate(read as usize);
+        Some(buf)
+    }
+
+    /// Removes the extended attribute `name` of `path`.
+    ///
+    /// # Returns
+    /// Whether the attribute existed.
+    pub(super) fn remove(path: &Path, name: &str) -> io::Result<bool> {
+        let path = CString::new(path.as_os_str().as_bytes())?;
+        let name = CString::new(name)?;
+        // SAFETY: both strings are NUL terminated
+        let result =
+            unsafe { libc::removexattr(path.as_ptr(), name.as_ptr(), libc::XATTR_NOFOLLOW) };
+        if result == 0 {
+            return Ok(true);
+        }
+        let err = io::Error::last_os_error();
+        if err.raw_os_error() == Some(libc::ENOATTR) {
+            Ok(false)
+        } else {
+            Err(err)
+        }
+    }
+}
+
+/// Reads the strings of the top level array of a binary property list (`bplist00`).
+fn string_array(data: &[u8]) -> Option<Vec<String>> {
+    if !data.starts_with(b"bplist00") || data.len() < 40 {
+        return None;
+    }
+    // The trailer: offset and reference sizes, object count, top object and offset table
+    let trailer = &data[data.len() - 32..];
+    let offset_size = trailer[6] as usize;
+    let ref_size = trailer[7] as usize;
+    let num_objects = be_uint(&trailer[8..16])? as usize;
+    let top = be_uint(&trailer[16..24])? as usize;
+    let table = be_uint(&trailer[24..32])? as usize;
+
+    let offset_of = |object: usize| -> Option<usize> {
+        if object >= num_objects {
+            return None;
+        }
+        let start = table.checked_add(object.checked_mul(offset_size)?)?;
+        be_uint(slice(data, start, offset_size)?).map(|offset| offset as usize)
+    };
+
+    let (count, refs) = object_len(data, offset_of(top)?, 0xA)?;
+    (0..count)
+        .map(|i| {
+            let start = refs.checked_add(i.checked_mul(ref_size)?)?;
+            let object = be_uint(slice(data, start, ref_size)?)? as usize;
+            string_at(data, offset_of(object)?)
+        })
+        .collect()
+}
+
+/// Reads an ASCII or UTF-16 string object.
+fn string_at(data: &[u8], offset: usize) -> Option<String> {
+    match *data.get(offset)? >> 4 {
+        0x5 => {
+            let (len, start) = object_len(data, offset, 0x5)?;
+            Some(String::from_utf8_lossy(slice(data, start, len)?).into_owned())
+        }
+        0x6 => {
+            let (len, start) = object_len(data, offset, 0x6)?;
+            let units: Vec<u16> = slice(data, start, len.checked_mul(2)?)?
+                .chunks_exact(2)
+                .map(|unit| u16::from_be_bytes([unit[0], unit[1]]))
+                .collect();
+            Some(String::from_utf16_lossy(&units))
+        }
+        _ => None,
+    }
+}
+
+/// Reads the marker of the object at `offset`, which has to be of `kind`.
+///
+/// # Returns
+/// The length of the object and the offset of its contents.
+fn object_len(data: &[u8], offset: usize, kind: u8) -> Option<(usize, usize)> {
+    let marker = *data.get(offset)?;
+    if marker >> 4 != kind {
+        return None;
+    }
+    if marker & 0xF != 0xF {
+        return Some(((marker & 0xF) as usize, offset + 1));
+    }
+    // Longer lengths follow as an integer object
+    let int_marker = *data.get(offset + 1)?;
+    if int_marker >> 4 != 0x1 {
+        return None;
+    }
+    let size = 1usize << (int_marker & 0xF);
+    let len = be_uint(slice(data, offset + 2, size)?)? as usize;
+    Some((len, offset + 2 + size))
+}
+
+fn slice(data: &[u8], start: usize, len: usize) -> Option<&[u8]> {
+    data.get(start..start.checked_add(len)?)
+}
+
+/// A big-endian unsigned integer of 1 to 8 bytes.
+fn be_uint(bytes: &[u8]) -> Option<u64> {
+    if bytes.is_empty() || bytes.len() > 8 {
+        return None;
+    }
+    Some(bytes.iter().fold(0, |n, b| n << 8 | u64::from(*b)))
+}
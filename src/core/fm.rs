@@ -3,12 +3,18 @@
 //! Provides the FileEntry struct which is used throughout runa.
 //! Also holds all the FileInfo and FileType structs used by the ShowInfo Overlay
 
+use crate::core::finder::{FinderTags, Quarantine, read_quarantine, read_tags};
 use crate::core::format_attributes;
+use crate::core::platform::{FileFlags, entry_flags, file_id, is_executable, mount_table};
+use crate::core::security::{SecurityContext, read_context};
+use crate::core::streams::{DataStream, list_streams};
 
-use std::ffi::OsString;
+use std::collections::HashSet;
+use std::ffi::{OsStr, OsString};
 use std::fs::{self, symlink_metadata};
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::time::SystemTime;
 
 /// Represents a single entry in a directory listing
@@ -16,54 +22,76 @@ use std::time::SystemTime;
 /// Used throughout runa for directory browsing and file management
 /// Created and populated by the browse_dir function.
 ///
+/// All strings of an entry share one allocation, which keeps huge listings small:
+/// the name, the lowercase name (only stored if it differs from the name) and the display name.
+///
 /// # Fields
-/// * `name` - The original OsString name of the file or directory
-/// * `name_str` - The String representation of the name
-/// * `lowercase_name` - The lowercase version of the name for case-insensitive comparisons
-/// * `display_name` - The name formatted for display (e.g., with trailing slash for directories)
+/// * `text` - The name, lowercase name and display name, back to back
+/// * `lower_start` - Byte offset of the lowercase name in `text`
+/// * `display_start` - Byte offset of the display name in `text`
+/// * `raw_name` - The original name, only kept if it is not valid UTF-8
 /// * `file_flags` - Struct holding boolean flags for is_dir, is_hidden, is_system, is_symlink
 #[derive(Debug, Clone)]
 pub struct FileEntry {
-    name: OsString,
-    name_str: String,
-    lowercase_name: String,
-    display_name: String,
+    text: Box<str>,
+    lower_start: u32,
+    display_start: u32,
+    raw_name: Option<Box<OsStr>>,
     file_flags: FileFlags,
 }
 
 impl FileEntry {
-    fn new(
-        name: OsString,
-        name_str: String,
-        lowercase_name: String,
-        display_name: String,
-        file_flags: FileFlags,
-    ) -> Self {
+    fn new(name: OsString, display_name: &str, file_flags: FileFlags) -> Self {
+        let (name_str, raw_name) = match name.into_string() {
+            Ok(name_str) => (name_str, None),
+            Err(raw) => (
+                raw.to_string_lossy().into_owned(),
+                Some(raw.into_boxed_os_str()),
+            ),
+        };
+        let lowercase = name_str.to_lowercase();
+        let lower = if lowercase == name_str {
+            ""
+        } else {
+            &lowercase
+        };
+
+        let mut text = String::with_capacity(name_str.len() + lower.len() + display_name.len());
+        text.push_str(&name_str);
+        text.push_str(lower);
+        text.push_str(display_name);
+
         FileEntry {
-            name,
-            name_str,
-            lowercase_name,
-            display_name,
+            text: text.into_boxed_str(),
+            lower_start: name_str.len() as u32,
+            display_start: (name_str.len() + lower.len()) as u32,
+            raw_name,
             file_flags,
         }
     }
 
     // Accessors
 
-    pub fn name(&self) -> &OsString {
-        &self.name
+    pub fn name(&self) -> &OsStr {
+        self.raw_name
+            .as_deref()
+            .unwrap_or_else(|| OsStr::new(self.name_str()))
     }
 
     pub fn name_str(&self) -> &str {
-        &self.name_str
+        &self.text[..self.lower_start as usize]
     }
 
     pub fn lowercase_name(&self) -> &str {
-        &self.lowercase_name
+        if self.lower_start == self.display_start {
+            self.name_str()
+        } else {
+            &self.text[self.lower_start as usize..self.display_start as usize]
+        }
     }
 
     pub fn display_name(&self) -> &str {
-        &self.display_name
+        &self.text[self.display_start as usize..]
     }
 
     pub fn is_dir(&self) -> bool {
@@ -83,33 +111,104 @@ impl FileEntry {
     }
 
     pub fn extension(&self) -> Option<String> {
-        Path::new(&self.name_str)
+        Path::new(self.name_str())
             .extension()
             .and_then(|s| s.to_str())
             .map(|s| s.to_ascii_lowercase())
     }
 
+    /// Heap bytes owned by the entry, used to measure the memory of large listings.
+    pub fn heap_size(&self) -> usize {
+        self.text.len() + self.raw_name.as_ref().map_or(0, |raw| raw.len())
+    }
+
     // Setters
 
     pub fn set_display_name(&mut self, new_name: String) {
-        self.display_name = new_name;
+        let start = self.display_start as usize;
+        let mut text = String::with_capacity(start + new_name.len());
+        text.push_str(&self.text[..start]);
+        text.push_str(&new_name);
+        self.text = text.into_boxed_str();
     }
 }
 
-/// Struct to hold file attribute flags for FileEntry
-/// Holds is_dir, is_hidden, is_system, is_symlink booleans.
-/// Used internally by FileEntry.
+/// Metadata of a directory entry for the detail view.
+///
+/// Loaded in the background after the listing, see [WorkerTask::LoadMetadata](crate::core::worker::WorkerTask::LoadMetadata),
+/// so listing a directory never waits for a stat per entry.
+///
 /// # Fields
-/// * `is_dir` - Boolean indicating if the entry is a directory
-/// * `is_hidden` - Boolean indicating if the entry is hidden
-/// * `is_system` - Boolean indicating if the entry is a system file
-/// * `is_symlink` - Boolean indicating if the entry is a symlink
-#[derive(Debug, Clone, Copy)]
-struct FileFlags {
-    is_dir: bool,
-    is_hidden: bool,
-    is_system: bool,
-    is_symlink: bool,
+/// * `size` - Size in bytes, None for directories and unreadable entries.
+/// * `modified` - Last modification time, if available.
+/// * `git` - Git status letter of the entry, if it has changes.
+/// * `tags` - The colors of the Finder tags of the entry, only read on macOS.
+/// * `executable` - Whether the entry is a file with an executable bit.
+/// * `context_differs` - Whether the SELinux type differs from the directory, only read on Linux.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct EntryMeta {
+    size: Option<u64>,
+    modified: Option<SystemTime>,
+    git: Option<char>,
+    tags: FinderTags,
+    executable: bool,
+    context_differs: bool,
+}
+
+impl EntryMeta {
+    /// Reads the metadata of `path`, following symlinks.
+    /// A broken symlink is described by the link itself.
+    /// The Finder tags are read if `tags` is set, the SELinux context is compared to
+    /// `dir_context`, the context of the directory, if it is given.
+    pub fn read(
+        path: &Path,
+        git: Option<char>,
+        tags: bool,
+        dir_context: Option<&SecurityContext>,
+    ) -> Self {
+        let meta = fs::metadata(path).or_else(|_| symlink_metadata(path)).ok();
+        EntryMeta {
+            size: meta
+                .as_ref()
+                .filter(|meta| !meta.is_dir())
+                .map(|meta| meta.len()),
+            modified: meta.as_ref().and_then(|meta| meta.modified().ok()),
+            executable: meta.as_ref().is_some_and(is_executable),
+            git,
+            tags: if tags {
+                read_tags(path)
+            } else {
+                FinderTags::default()
+            },
+            context_differs: dir_context.is_some_and(|dir| {
+                read_context(path).is_some_and(|context| context.differs_from(dir))
+            }),
+        }
+    }
+
+    pub fn size(&self) -> Option<u64> {
+        self.size
+    }
+
+    pub fn modified(&self) -> Option<SystemTime> {
+        self.modified
+    }
+
+    pub fn git(&self) -> Option<char> {
+        self.git
+    }
+
+    pub fn tags(&self) -> FinderTags {
+        self.tags
+    }
+
+    pub fn executable(&self) -> bool {
+        self.executable
+    }
+
+    pub fn context_differs(&self) -> bool {
+        self.context_differs
+    }
 }
 
 /// Enumerator for the filye types which are then shown inside [FileInfo]
@@ -128,10 +227,12 @@ pub enum FileType {
 ///
 /// # Fields
 /// * `name` - The OsString name of the file or directory
-/// * `size` - The size of the file in bytes (None for directories)
+/// * `size` - The size of the file in bytes (None for directories until their size is computed)
 /// * `modified` - The last modified time as SystemTime (None if unavailable)
 /// * `attributes` - A formatted string of file attributes
 /// * `file_type` - The FileType enum indicating if it's a file, directory, symlink, or other
+/// * `target` - The resolved target of a symlink or Windows junction (None for other types)
+/// * `details` - The slower [FileDetails], None until they are read
 #[derive(Debug, Clone, PartialEq)]
 pub struct FileInfo {
     name: OsString,
@@ -139,6 +240,378 @@ pub struct FileInfo {
     modified: Option<SystemTime>,
     attributes: String,
     file_type: FileType,
+    target: Option<PathBuf>,
+    details: Option<FileDetails>,
+}
+
+/// The parts of a [FileInfo] which walk a directory or read extended attributes,
+/// read by the info worker so the overlay opens right away.
+///
+/// # Fields
+/// * `items` - The recursive item count of a directory (None for other types)
+/// * `quarantine` - The quarantine attribute of a download on macOS
+/// * `streams` - The alternate data streams on Windows, the resource fork on macOS
+/// * `context` - The SELinux security context on Linux
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FileDetails {
+    items: Option<DirItems>,
+    quarantine: Option<Quarantine>,
+    streams: Vec<DataStream>,
+    context: Option<SecurityContext>,
+}
+
+impl FileDetails {
+    /// Reads the details of the file or directory at `path`.
+    ///
+    /// # Arguments
+    /// * `path` - Path reference to the file or directory
+    /// * `excludes` - Entry names skipped when counting the items of a directory
+    pub fn read(path: &Path, excludes: &HashSet<OsString>) -> FileDetails {
+        let Ok(metadata) = symlink_metadata(path) else {
+            return FileDetails::default();
+        };
+        FileDetails {
+            items: metadata.is_dir().then(|| count_dir_items(path, excludes)),
+            quarantine: read_quarantine(path),
+            streams: if metadata.is_file() {
+                list_streams(path)
+            } else {
+                Vec::new()
+            },
+            context: read_context(path),
+        }
+    }
+}
+
+/// Upper bound of entries visited by [count_dir_items], keeps the info dialog responsive
+/// on huge trees.
+pub const DIR_COUNT_LIMIT: usize = 10_000;
+
+/// Recursive item count of a directory, as shown by the ShowInfo overlay.
+///
+/// # Fields
+/// * `count` - Files and directories below the directory, not counting excluded ones.
+/// * `excluded` - Entries matching the scan excludes, which were skipped without descending.
+/// * `truncated` - True if the count stopped at [DIR_COUNT_LIMIT].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DirItems {
+    count: usize,
+    excluded: usize,
+    truncated: bool,
+}
+
+impl DirItems {
+    pub fn count(&self) -> usize {
+        self.count
+    }
+
+    pub fn excluded(&self) -> usize {
+        self.excluded
+    }
+
+    pub fn truncated(&self) -> bool {
+        self.truncated
+    }
+}
+
+/// Counts the files and directories below `path`.
+///
+/// Symlinks are counted but not followed. Entries whose name is in `excludes`
+/// (e.g. `node_modules`) are only tallied as excluded, so vendored trees do not dominate the count.
+///
+/// # Arguments
+/// * `path` - The directory to count.
+/// * `excludes` - Entry names to skip.
+///
+/// # Returns
+/// The [DirItems] of the directory. Unreadable subdirectories are skipped.
+pub fn count_dir_items(path: &Path, excludes: &HashSet<OsString>) -> DirItems {
+    let mut items = DirItems::default();
+    let mut stack = vec![path.to_path_buf()];
+
+    while let Some(dir) = stack.pop() {
+        let Ok(read) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read.flatten() {
+            if excludes.contains(&entry.file_name()) {
+                items.excluded += 1;
+                continue;
+            }
+            if items.count >= DIR_COUNT_LIMIT {
+                items.truncated = true;
+                return items;
+            }
+            items.count += 1;
+            if entry.file_type().is_ok_and(|t| t.is_dir()) {
+                stack.push(entry.path());
+            }
+        }
+    }
+    items
+}
+
+/// Filesystem type of the mount containing `path`, e.g. "ext4", "nfs" or "fuse.sshfs".
+///
+/// Read from the mount table of the platform, see [mount_table]. Always `None` on Windows.
+pub fn fs_type(path: &Path) -> Option<String> {
+    let mounts = parse_mounts(&mount_table()?);
+    mount_fs_type(&mounts, path).map(str::to_string)
+}
+
+/// Parses a mount table into mount points and filesystem types.
+///
+/// Understands `/proc/self/mounts` (`device mount_point fstype options ..`, spaces escaped as `\040`),
+/// `/etc/mnttab` of illumos (the same fields, separated by tabs) and the output of `mount`
+/// (`device on mount_point type fstype (options)` on Linux, NetBSD and OpenBSD,
+/// `device on mount_point (fstype, options)` on macOS and FreeBSD).
+pub fn parse_mounts(table: &str) -> Vec<(PathBuf, String)> {
+    table
+        .lines()
+        .filter_map(|line| {
+            if line.contains('\t') {
+                let mut fields = line.split('\t');
+                let _device = fields.next()?;
+                let mount_point = fields.next()?;
+                Some((PathBuf::from(mount_point), fields.next()?.to_string()))
+            } else if let Some((_, rest)) = line.split_once(" on ") {
+                let (mount_point, fs_type) = match rest.split_once(" type ") {
+                    Some((mount_point, rest)) => (mount_point, rest.split_whitespace().next()?),
+                    None => {
+                        let (mount_point, rest) = rest.rsplit_once(" (")?;
+                        (mount_point, rest.split([',', ')']).next()?.trim())
+                    }
+                };
+                Some((PathBuf::from(mount_point), fs_type.to_string()))
+            } else {
+                let mut fields = line.split_whitespace();
+                let _device = fields.next()?;
+                let mount_point = fields.next()?.replace("\\040", " ");
+                Some((PathBuf::from(mount_point), fields.next()?.to_string()))
+            }
+        })
+        .collect()
+}
+
+/// The filesystem type of the innermost mount point containing `path`.
+pub fn mount_fs_type<'a>(mounts: &'a [(PathBuf, String)], path: &Path) -> Option<&'a str> {
+    mounts
+        .iter()
+        .filter(|(mount_point, _)| path.starts_with(mount_point))
+        .max_by_key(|(mount_point, _)| mount_point.components().count())
+        .map(|(_, fs_type)| fs_type.as_str())
+}
+
+/// Total size in bytes of the file or directory at `path`.
+///
+/// Directories are walked recursively, symlinks and Windows junctions are counted by their own
+/// size and not followed.
+/// Unreadable entries are skipped.
+pub fn path_size(path: &Path) -> u64 {
+    path_size_cancellable(path, &HashSet::new(), &AtomicBool::new(false))
+        .map_or(0, |size| size.bytes())
+}
+
+/// Recursive size of a file or directory, as computed by the directory size worker.
+///
+/// # Fields
+/// * `bytes` - Total size in bytes, not counting excluded entries.
+/// * `excluded` - Entries matching the scan excludes, which were skipped without descending.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PathSize {
+    bytes: u64,
+    excluded: usize,
+}
+
+impl PathSize {
+    pub fn bytes(&self) -> u64 {
+        self.bytes
+    }
+
+    pub fn excluded(&self) -> usize {
+        self.excluded
+    }
+}
+
+/// Resolves the target of the symlink or Windows junction at `path`.
+///
+/// Relative targets are joined to the directory of the link. The `\\?\` prefix Windows reports
+/// for junction targets is dropped, so they read like the paths shown elsewhere.
+///
+/// # Returns
+/// The target, or `None` if `path` is no link or cannot be read.
+pub fn link_target(path: &Path) -> Option<PathBuf> {
+    let target = fs::read_link(path).ok()?;
+    if target.is_absolute() {
+        return Some(strip_verbatim(target));
+    }
+    Some(path.parent().unwrap_or_else(|| Path::new("")).join(target))
+}
+
+/// Drops the `\\?\` prefix of a local verbatim path, UNC paths are kept as they are.
+fn strip_verbatim(path: PathBuf) -> PathBuf {
+    #[cfg(windows)]
+    if let Some(rest) = path.to_str().and_then(|p| p.strip_prefix(r"\\?\"))
+        && !rest.starts_with(r"UNC\")
+    {
+        return PathBuf::from(rest);
+    }
+    path
+}
+
+/// Removes the file, directory or link at `path`.
+///
+/// Symlinks and Windows junctions are removed themselves and never followed, so deleting a link
+/// to a directory leaves the contents of the directory alone. Directories are removed recursively.
+///
+/// # Returns
+/// The bytes removed, counted like [path_size].
+///
+/// # Errors
+/// Returns an std::io::Error if the entry cannot be removed.
+pub fn remove_path(path: &Path) -> io::Result<u64> {
+    let meta = symlink_metadata(path)?;
+    if meta.file_type().is_symlink() {
+        // A link to a directory is a directory entry on Windows and has to go with remove_dir
+        #[cfg(windows)]
+        {
+            use std::os::windows::fs::MetadataExt;
+            if meta.file_attributes() & 0x10 != 0 {
+                return fs::remove_dir(path).map(|()| meta.len());
+            }
+        }
+        fs::remove_file(path).map(|()| meta.len())
+    } else if meta.is_dir() {
+        let mut bytes = 0;
+        for entry in fs::read_dir(path)? {
+            bytes += remove_path(&entry?.path())?;
+        }
+        fs::remove_dir(path).map(|()| bytes)
+    } else {
+        fs::remove_file(path).map(|()| meta.len())
+    }
+}
+
+/// Like [path_size], but skips entries whose name is in `excludes` and stops walking
+/// once `cancel` is set.
+///
+/// Excluded entries are only tallied, like in [count_dir_items].
+///
+/// # Arguments
+/// * `path` - The file or directory to measure.
+/// * `excludes` - Entry names to skip.
+/// * `cancel` - Set to stop the walk.
+///
+/// # Returns
+/// The [PathSize], or `None` if the walk was cancelled.
+pub fn path_size_cancellable(
+    path: &Path,
+    excludes: &HashSet<OsString>,
+    cancel: &AtomicBool,
+) -> Option<PathSize> {
+    let Ok(meta) = symlink_metadata(path) else {
+        return Some(PathSize::default());
+    };
+    if !meta.is_dir() {
+        return Some(PathSize {
+            bytes: meta.len(),
+            excluded: 0,
+        });
+    }
+
+    let mut size = PathSize::default();
+    let mut stack = vec![path.to_path_buf()];
+    while let Some(dir) = stack.pop() {
+        if cancel.load(Ordering::Relaxed) {
+            return None;
+        }
+        let Ok(read) = fs::read_dir(&dir) else {
+            continue;
+        };
+        for entry in read.flatten() {
+            if excludes.contains(&entry.file_name()) {
+                size.excluded += 1;
+                continue;
+            }
+            let Ok(meta) = entry.metadata() else {
+                continue;
+            };
+            if meta.is_dir() {
+                stack.push(entry.path());
+            } else {
+                size.bytes += meta.len();
+            }
+        }
+    }
+    Some(size)
+}
+
+/// Creates an empty file, or a directory with all missing parents, at `path`.
+///
+/// On unix `mode` is passed to the creating syscall, so the umask of the process still applies,
+/// e.g. a mode of `0o664` with the usual umask `022` creates `0o644`.
+/// Without a mode the platform defaults are used (`0o666` for files and `0o777` for directories,
+/// minus the umask). On other platforms the mode is ignored.
+///
+/// # Errors
+/// Returns an std::io::Error if the entry cannot be created or a file already exists at `path`.
+pub fn create_entry(path: &Path, is_dir: bool, mode: Option<u32>) -> io::Result<()> {
+    if is_dir {
+        let mut builder = fs::DirBuilder::new();
+        builder.recursive(true);
+        #[cfg(unix)]
+        if let Some(mode) = mode {
+            use std::os::unix::fs::DirBuilderExt;
+            builder.mode(mode);
+        }
+        #[cfg(not(unix))]
+        let _ = mode;
+        builder.create(path)
+    } else {
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        if let Some(mode) = mode {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(mode);
+        }
+        options.open(path).map(|_| ())
+    }
+}
+
+/// Identity of a directory that survives renames: the device and inode number on unix.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DirId {
+    dev: u64,
+    ino: u64,
+}
+
+impl DirId {
+    /// Reads the identity of the directory at `path`.
+    ///
+    /// # Returns
+    /// None if the path cannot be read, or on platforms without stable inode numbers.
+    pub fn of(path: &Path) -> Option<DirId> {
+        file_id(path).map(|(dev, ino)| DirId { dev, ino })
+    }
+}
+
+/// Searches `parent` for the directory with the identity `id`,
+/// i.e. the new name of a directory renamed within `parent`.
+///
+/// # Arguments
+/// * `parent` - The directory that contained the renamed directory.
+/// * `id` - The [DirId] recorded before the rename.
+///
+/// # Returns
+/// The new path of the directory, if it is still in `parent`.
+pub fn find_renamed_dir(parent: &Path, id: DirId) -> Option<PathBuf> {
+    fs::read_dir(parent)
+        .ok()?
+        .flatten()
+        .filter(|entry| entry.file_type().is_ok_and(|t| t.is_dir()))
+        .map(|entry| entry.path())
+        .find(|path| DirId::of(path) == Some(id))
 }
 
 impl FileInfo {
@@ -164,14 +637,63 @@ impl FileInfo {
         &self.file_type
     }
 
-    /// Main file info getter used by the ShowInfo overlay functions
+    pub fn items(&self) -> Option<&DirItems> {
+        self.details.as_ref()?.items.as_ref()
+    }
+
+    pub fn target(&self) -> Option<&Path> {
+        self.target.as_deref()
+    }
+
+    pub fn quarantine(&self) -> Option<&Quarantine> {
+        self.details.as_ref()?.quarantine.as_ref()
+    }
+
+    pub fn streams(&self) -> &[DataStream] {
+        self.details
+            .as_ref()
+            .map_or(&[], |details| &details.streams)
+    }
+
+    pub fn context(&self) -> Option<&SecurityContext> {
+        self.details.as_ref()?.context.as_ref()
+    }
+
+    /// Whether the [FileDetails] are still being read.
+    pub fn details_pending(&self) -> bool {
+        self.details.is_none()
+    }
+
+    /// Sets the size, e.g. the recursive size of a directory computed in the background.
+    pub fn set_size(&mut self, size: u64) {
+        self.size = Some(size);
+    }
+
+    /// Sets the [FileDetails] read in the background.
+    pub fn set_details(&mut self, details: FileDetails) {
+        self.details = Some(details);
+    }
+
+    /// Main file info getter, reading the [FileDetails] as well
     ///
     /// # Arguments
     /// * `path` - Path reference to the file or directory to get info for
+    /// * `excludes` - Entry names skipped when counting the items of a directory
     ///
     /// # Returns
     /// A FileInfo struct populated with the file's information.
-    pub fn get_file_info(path: &Path) -> io::Result<FileInfo> {
+    pub fn get_file_info(path: &Path, excludes: &HashSet<OsString>) -> io::Result<FileInfo> {
+        let mut info = FileInfo::get_basic_info(path)?;
+        info.details = Some(FileDetails::read(path, excludes));
+        Ok(info)
+    }
+
+    /// Reads the file info without the [FileDetails], which only takes a single stat.
+    /// Used by the ShowInfo overlay, which requests the details from the info worker.
+    ///
+    /// # Arguments
+    /// * `path` - Path reference to the file or directory to get info for
+    pub fn get_basic_info(path: &Path) -> io::Result<FileInfo> {
         let metadata = symlink_metadata(path)?;
         let file_type = if metadata.is_file() {
             FileType::File
@@ -192,6 +714,10 @@ impl FileInfo {
             },
             modified: metadata.modified().ok(),
             attributes: format_attributes(&metadata),
+            target: (file_type == FileType::Symlink)
+                .then(|| link_target(path))
+                .flatten(),
+            details: None,
             file_type,
         })
     }
@@ -205,7 +731,26 @@ impl FileInfo {
 /// # Returns
 /// A Result containing a vector of FileEntry structs or an std::io::Error
 pub fn browse_dir(path: &std::path::Path) -> std::io::Result<Vec<FileEntry>> {
+    browse_dir_chunked(path, 0, |_| {})
+}
+
+/// Reads the contents of a directory like [browse_dir], handing out the entries in chunks while
+/// the directory is still being read.
+///
+/// # Arguments
+/// * `path` - Path reference to the directory to browse
+/// * `chunk_size` - Number of entries per chunk, 0 reads the directory without chunks
+/// * `on_chunk` - Called with every full chunk of new entries, in directory order
+///
+/// # Returns
+/// A Result containing all entries of the directory, including the ones already handed out.
+pub fn browse_dir_chunked(
+    path: &Path,
+    chunk_size: usize,
+    mut on_chunk: impl FnMut(&[FileEntry]),
+) -> io::Result<Vec<FileEntry>> {
     let mut entries = Vec::with_capacity(256);
+    let mut chunk_start = 0;
 
     for entry in fs::read_dir(path)? {
         let entry = match entry {
@@ -214,58 +759,18 @@ pub fn browse_dir(path: &std::path::Path) -> std::io::Result<Vec<FileEntry>> {
         };
 
         let name = entry.file_name();
-        let name_lossy = name.to_string_lossy();
-
-        let (is_dir, is_symlink) = match entry.file_type() {
-            Ok(ft) => (ft.is_dir(), ft.is_symlink()),
-            Err(_) => (false, false),
-        };
-
-        let is_hidden: bool;
-        let is_system: bool;
-
-        #[cfg(unix)]
-        {
-            use std::os::unix::ffi::OsStrExt;
-            is_hidden = name.as_bytes().first() == Some(&b'.');
-            is_system = false;
-        }
+        let file_flags = entry_flags(&entry);
 
-        #[cfg(windows)]
-        {
-            use std::os::windows::fs::MetadataExt;
-            if let Ok(md) = entry.metadata() {
-                let attrs = md.file_attributes();
-                is_hidden = (attrs & 0x2 != 0) || name_lossy.starts_with('.');
-                is_system = attrs & 0x4 != 0;
-            } else {
-                is_hidden = name_lossy.starts_with('.');
-                is_system = false;
-            }
-        }
-
-        let name_str: String = name_lossy.into_owned();
-        let lowercase_name: String = name_str.to_lowercase();
-
-        let mut display_name = name_str.clone();
-        if is_dir {
+        let mut display_name = name.to_string_lossy().into_owned();
+        if file_flags.is_dir {
             display_name.push('/');
         }
 
-        let file_flags = FileFlags {
-            is_dir,
-            is_hidden,
-            is_system,
-            is_symlink,
-        };
-
-        entries.push(FileEntry::new(
-            name,
-            name_str,
-            lowercase_name,
-            display_name,
-            file_flags,
-        ));
+        entries.push(FileEntry::new(name, &display_name, file_flags));
+        if chunk_size > 0 && entries.len() - chunk_start >= chunk_size {
+            on_chunk(&entries[chunk_start..]);
+            chunk_start = entries.len();
+        }
     }
     Ok(entries)
 }
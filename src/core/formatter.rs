@@ -1,18 +1,29 @@
 //! Sorting, filtering, and display formatting for file entries in runa.
 //!
 //! The [Formatter] struct holds pane width and rules for sorting and filtering entries,
-//! based on user preferences from the runa.toml configuration.
+//! based on user preferences from the runa.toml configuration. A [SortExpr] replaces the
+//! built-in order with the comparator chain of the `sort_expr` option.
 //! Used to prepare file lists for display in each pane.
 //!
 //! Also formatts FileTypes to be used by FileInfo and ShowInfo overlay widget.
 
 use crate::core::FileType;
+use crate::core::fm::{DirItems, link_target};
+use crate::core::highlight::highlight_lines;
+use crate::core::platform;
 use crate::core::{FileEntry, browse_dir};
 
+use chrono::format::{Item, StrftimeItems};
 use chrono::{DateTime, Local};
-use humansize::{DECIMAL, format_size};
-use unicode_width::UnicodeWidthChar;
-
+use humansize::{BINARY, DECIMAL, format_size};
+use ratatui::text::Line;
+use rayon::prelude::*;
+use serde::Deserialize;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::ffi::OsString;
 use std::fs::{File, Metadata};
@@ -21,6 +32,13 @@ use std::path::{Path, PathBuf};
 use std::sync::Arc;
 use std::time::SystemTime;
 
+/// Number of entries from which [Formatter::format] sorts and formats in parallel.
+pub const PARALLEL_FORMAT_THRESHOLD: usize = 20_000;
+/// The mark at the end of cut display names.
+pub const ELLIPSIS: char = '…';
+/// The mark at the end of cut display names with `display.ascii_only`.
+pub const ASCII_ELLIPSIS: char = '~';
+
 // Minimum number of lines shown in any preview
 const MIN_PREVIEW_LINES: usize = 3;
 // Maximum file size allowed for preview (10mb)
@@ -29,6 +47,12 @@ const MAX_PREVIEW_SIZE: u64 = 10 * 1024 * 1024;
 const HEADER_PEEK_BYTES: usize = 8;
 // Bytes to peek for null bytes in binary detections
 const BINARY_PEEK_BYTES: usize = 1024;
+// Media and document extensions that are never text, detected without reading the file
+const BINARY_EXTENSIONS: &[&str] = &[
+    "png", "jpg", "jpeg", "gif", "bmp", "webp", "tif", "tiff", "ico", "heic", "avif", "mp3",
+    "flac", "wav", "ogg", "opus", "m4a", "mp4", "mkv", "webm", "avi", "mov", "pdf", "epub", "doc",
+    "docx", "xls", "xlsx", "ppt", "pptx", "odt", "ods", "odp",
+];
 
 /// Formatter struct to handle sorting, filtering, and formatting of file entries
 /// based on user preferences.
@@ -41,6 +65,8 @@ const BINARY_PEEK_BYTES: usize = 1024;
 /// * `always_show` - Set of filenames to always show, regardless of hidden/system status.
 /// * `always_show_lowercase` - Lowercase version of always_show for case insensitive checks.
 /// * `pane_width` - Width of the pane for formatting display names.
+/// * `ascii_only` - Whether cut display names end in [ASCII_ELLIPSIS] instead of [ELLIPSIS].
+/// * `sort_expr` - Custom sort order replacing `dirs_first` and the order by name.
 pub struct Formatter {
     dirs_first: bool,
     show_hidden: bool,
@@ -49,6 +75,8 @@ pub struct Formatter {
     always_show: Arc<HashSet<OsString>>,
     always_show_lowercase: Arc<HashSet<String>>,
     pane_width: usize,
+    ascii_only: bool,
+    sort_expr: Option<Arc<SortExpr>>,
 }
 
 impl Formatter {
@@ -59,6 +87,7 @@ impl Formatter {
         case_insensitive: bool,
         always_show: Arc<HashSet<OsString>>,
         pane_width: usize,
+        ascii_only: bool,
     ) -> Self {
         let always_show_lowercase = Arc::new(
             always_show
@@ -74,58 +103,79 @@ impl Formatter {
             always_show,
             always_show_lowercase,
             pane_width,
+            ascii_only,
+            sort_expr: None,
         }
     }
 
+    /// Sorts by `sort_expr` instead of `dirs_first` and the name, if one is given.
+    pub fn with_sort_expr(mut self, sort_expr: Option<Arc<SortExpr>>) -> Self {
+        self.sort_expr = sort_expr;
+        self
+    }
+
     /// Formats and sorts the given file entries in place according to the formatter's settings.
+    /// Listings of at least [PARALLEL_FORMAT_THRESHOLD] entries are sorted and formatted on all cores,
+    /// with the same order as the sequential path.
     /// # Arguments
     /// * `entries` - Mutable slice of FileEntry to format and sort.
     pub fn format(&self, entries: &mut [FileEntry]) {
-        // Sort the entries
-        entries.sort_by(|a, b| {
-            if self.dirs_first {
-                match (a.is_dir(), b.is_dir()) {
-                    (true, false) => return std::cmp::Ordering::Less,
-                    (false, true) => return std::cmp::Ordering::Greater,
-                    _ => {}
-                }
-            }
-            if self.case_insensitive {
-                a.lowercase_name().cmp(b.lowercase_name())
-            } else {
-                a.name_str().cmp(b.name_str())
+        if entries.len() >= PARALLEL_FORMAT_THRESHOLD {
+            entries.par_sort_unstable_by(|a, b| self.compare(a, b));
+            entries
+                .par_iter_mut()
+                .for_each(|entry| self.format_display_name(entry));
+        } else {
+            entries.sort_by(|a, b| self.compare(a, b));
+            for entry in entries.iter_mut() {
+                self.format_display_name(entry);
             }
-        });
-
-        for entry in entries.iter_mut() {
-            let base_name = if entry.is_dir() {
-                entry.name_str().to_owned() + "/"
-            } else {
-                entry.name_str().to_owned()
-            };
-
-            let mut out = String::with_capacity(self.pane_width);
-            let mut current_w = 0;
+        }
+    }
 
-            for c in base_name.chars() {
-                // simple truncation for the main list
-                let w = unicode_width::UnicodeWidthChar::width(c).unwrap_or(0);
-                if current_w + w > self.pane_width {
-                    if !out.is_empty() {
-                        out.pop();
-                        out.push('…');
-                    }
-                    break;
-                }
-                out.push(c);
-                current_w += w;
+    /// Orders entries by the sort settings.
+    /// Names that compare equal (e.g. case insensitive) fall back to the raw name,
+    /// so the order is total and does not depend on the sort algorithm.
+    fn compare(&self, a: &FileEntry, b: &FileEntry) -> Ordering {
+        if let Some(expr) = &self.sort_expr {
+            return expr
+                .compare(a, b, self.case_insensitive)
+                .then_with(|| a.name().cmp(b.name()));
+        }
+        if self.dirs_first {
+            match (a.is_dir(), b.is_dir()) {
+                (true, false) => return Ordering::Less,
+                (false, true) => return Ordering::Greater,
+                _ => {}
             }
+        }
+        let by_name = if self.case_insensitive {
+            a.lowercase_name().cmp(b.lowercase_name())
+        } else {
+            a.name_str().cmp(b.name_str())
+        };
+        by_name.then_with(|| a.name().cmp(b.name()))
+    }
 
-            if current_w < self.pane_width {
-                out.push_str(&" ".repeat(self.pane_width - current_w));
-            }
-            entry.set_display_name(out);
+    /// Truncates or pads the name of `entry` to the pane width and stores it as display name.
+    fn format_display_name(&self, entry: &mut FileEntry) {
+        let base_name = if entry.is_dir() {
+            entry.name_str().to_owned() + "/"
+        } else {
+            entry.name_str().to_owned()
+        };
+
+        let mark = if self.ascii_only {
+            ASCII_ELLIPSIS
+        } else {
+            ELLIPSIS
+        };
+        let (mut out, current_w) =
+            truncate_to_width_with(&display_safe_name(&base_name, false), self.pane_width, mark);
+        if current_w < self.pane_width {
+            out.push_str(&" ".repeat(self.pane_width - current_w));
         }
+        entry.set_display_name(out);
     }
 
     pub fn filter_entries(&self, entries: &mut Vec<FileEntry>) {
@@ -148,64 +198,142 @@ impl Formatter {
     }
 }
 
+/// A key of a [SortExpr].
+///
+/// Variants:
+/// * `Name` - The file name.
+/// * `Ext` - The extension after the last dot, empty for names without one.
+/// * `IsDir` - Whether the entry is a directory.
+/// * `IsHidden` - Whether the entry is hidden.
+/// * `IsSymlink` - Whether the entry is a symlink or junction.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Name,
+    Ext,
+    IsDir,
+    IsHidden,
+    IsSymlink,
+}
+
+impl SortKey {
+    fn parse(name: &str) -> Option<Self> {
+        match name {
+            "name" => Some(SortKey::Name),
+            "ext" => Some(SortKey::Ext),
+            "is_dir" => Some(SortKey::IsDir),
+            "is_hidden" => Some(SortKey::IsHidden),
+            "is_symlink" => Some(SortKey::IsSymlink),
+            _ => None,
+        }
+    }
+
+    /// Compares two entries by this key in ascending order.
+    fn compare(self, a: &FileEntry, b: &FileEntry, case_insensitive: bool) -> Ordering {
+        let name = |entry| sort_name(entry, case_insensitive);
+        match self {
+            SortKey::Name => name(a).cmp(name(b)),
+            SortKey::Ext => sort_extension(name(a)).cmp(sort_extension(name(b))),
+            SortKey::IsDir => a.is_dir().cmp(&b.is_dir()),
+            SortKey::IsHidden => a.is_hidden().cmp(&b.is_hidden()),
+            SortKey::IsSymlink => a.is_symlink().cmp(&b.is_symlink()),
+        }
+    }
+}
+
+/// The name of `entry` for sorting, lowercase if `case_insensitive`.
+fn sort_name(entry: &FileEntry, case_insensitive: bool) -> &str {
+    if case_insensitive {
+        entry.lowercase_name()
+    } else {
+        entry.name_str()
+    }
+}
+
+/// The extension of `name` for sorting, without allocating like [FileEntry::extension].
+fn sort_extension(name: &str) -> &str {
+    Path::new(name)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .unwrap_or("")
+}
+
+/// A custom sort order, the `sort_expr` option, e.g. `"is_dir desc, ext, name"`.
+///
+/// Comma separated [SortKey]s, each compared only if the ones before are equal, optionally
+/// followed by `asc` (the default) or `desc`. Booleans sort false first, so `is_dir desc`
+/// lists directories first. Names and extensions follow `case_insensitive`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SortExpr {
+    terms: Vec<(SortKey, bool)>,
+}
+
+impl SortExpr {
+    /// Parses a sort expression.
+    ///
+    /// # Errors
+    /// Returns a description of the problem for an empty expression, an unknown key or
+    /// anything but `asc` or `desc` after a key.
+    pub fn parse(expr: &str) -> Result<Self, String> {
+        let mut terms = Vec::new();
+        for term in expr.split(',') {
+            let mut words = term.split_whitespace();
+            let Some(name) = words.next() else {
+                return Err(format!("missing key in \"{}\"", expr));
+            };
+            let key = SortKey::parse(name).ok_or_else(|| format!("unknown key \"{}\"", name))?;
+            let descending = match words.next() {
+                None | Some("asc") => false,
+                Some("desc") => true,
+                Some(other) => {
+                    return Err(format!(
+                        "expected asc or desc after {}, found \"{}\"",
+                        name, other
+                    ));
+                }
+            };
+            if let Some(extra) = words.next() {
+                return Err(format!(
+                    "unexpected \"{}\" after \"{}\"",
+                    extra,
+                    term.trim()
+                ));
+            }
+            terms.push((key, descending));
+        }
+        Ok(Self { terms })
+    }
+
+    /// The keys in the order they are compared, each with whether it is descending.
+    pub fn terms(&self) -> &[(SortKey, bool)] {
+        &self.terms
+    }
+
+    /// Compares two entries key by key, until one differs.
+    pub fn compare(&self, a: &FileEntry, b: &FileEntry, case_insensitive: bool) -> Ordering {
+        self.terms
+            .iter()
+            .map(|&(key, descending)| {
+                let order = key.compare(a, b, case_insensitive);
+                if descending { order.reverse() } else { order }
+            })
+            .find(|order| order.is_ne())
+            .unwrap_or(Ordering::Equal)
+    }
+}
+
 /// Formatts the file attributes like Directory, Symlink, and permissions in a unix-like format
 ///
 /// On Unix: Returns a string like 'drwxr-xr-x' etc. for directories and files.
 /// On Windows: Returns a short string showing file type and attributes like:
 /// (`d`, `l`, `h` for hidden, `s` for system, `a` for archive, `r` for read-only). Not all flags map 1:1 to Unix.
+/// Elsewhere: Returns only the type letter. See [platform::mode_string].
 ///
 /// # Arguments
 /// * `meta` - Reference to the Metadata struct of the file.
 /// # Returns
 /// A string representing the formatted file attributes used by FileInfo
 pub fn format_attributes(meta: &Metadata) -> String {
-    #[cfg(unix)]
-    {
-        use std::os::unix::fs::PermissionsExt;
-
-        let file_type = meta.file_type();
-        let first = if file_type.is_dir() {
-            'd'
-        } else if file_type.is_symlink() {
-            'l'
-        } else {
-            '-'
-        };
-        let mode = meta.permissions().mode();
-        let mut chars = [first, '-', '-', '-', '-', '-', '-', '-', '-', '-'];
-        let shifts = [6, 3, 0];
-        for (i, &shift) in shifts.iter().enumerate() {
-            let base = 1 + i * 3;
-            if (mode >> (shift + 2)) & 1u32 != 0 {
-                chars[base] = 'r';
-            }
-            if (mode >> (shift + 1)) & 1u32 != 0 {
-                chars[base + 1] = 'w';
-            }
-            if (mode >> shift) & 1u32 != 0 {
-                chars[base + 2] = 'x';
-            }
-        }
-        chars.iter().collect()
-    }
-    #[cfg(windows)]
-    {
-        use std::os::windows::fs::MetadataExt;
-        let attr = meta.file_attributes();
-        let mut out = String::with_capacity(7);
-        out.push(if attr & 0x10 != 0 {
-            'd'
-        } else if attr & 0x400 != 0 {
-            'l'
-        } else {
-            '-'
-        });
-        out.push(if attr & 0x02 != 0 { 'h' } else { '-' });
-        out.push(if attr & 0x04 != 0 { 's' } else { '-' });
-        out.push(if attr & 0x20 != 0 { 'a' } else { '-' });
-        out.push(if attr & 0x01 != 0 { 'r' } else { '-' });
-        out
-    }
+    platform::mode_string(meta)
 }
 
 /// Formats the FileType enum into a human-readable string.
@@ -222,41 +350,101 @@ pub fn format_file_type(file_type: &FileType) -> &'static str {
     }
 }
 
+/// The default `date_format`, see [format_file_time].
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d %H:%M:%S";
+
+/// The units of file sizes, the `size_format` display option.
+///
+/// Variants:
+/// * `Decimal` - Powers of 1000, e.g. "1.50 kB", like Finder and most download tools.
+/// * `Binary` - Powers of 1024, e.g. "1.46 KiB", like `ls -lh` and Explorer.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum SizeFormat {
+    #[default]
+    Decimal,
+    Binary,
+}
+
 /// Formats the file size into a human-readable string.
 ///
 /// # Arguments
 /// * `size` - Optional file size in bytes.
 /// * `is_dir` - Boolean indicating if the entry is a directory.
+/// * `format` - Decimal or binary units.
 ///
 /// # Returns
 /// A string representing the formatted file size or "-" for directories/unknown sizes.
-pub fn format_file_size(size: Option<u64>, is_dir: bool) -> String {
+pub fn format_file_size(size: Option<u64>, is_dir: bool, format: SizeFormat) -> String {
     if is_dir {
         "-".into()
     } else if let Some(sz) = size {
-        format_size(sz, DECIMAL)
+        match format {
+            SizeFormat::Decimal => format_size(sz, DECIMAL),
+            SizeFormat::Binary => format_size(sz, BINARY),
+        }
     } else {
         "-".to_string()
     }
 }
 
+/// Formats the recursive item count of a directory.
+///
+/// # Arguments
+/// * `items` - The counted [DirItems].
+///
+/// # Returns
+/// A string like "1234", "10000+" or "56 (2 excluded)".
+pub fn format_dir_items(items: &DirItems) -> String {
+    let mut out = items.count().to_string();
+    if items.truncated() {
+        out.push('+');
+    }
+    if items.excluded() > 0 {
+        out.push_str(&format!(" ({} excluded)", items.excluded()));
+    }
+    out
+}
+
 /// Formats the file modification time into a human-readable string.
 ///
 /// # Arguments
 /// * `modified` - Optional SystemTime representing the modification time.
+/// * `format` - A strftime format like [DEFAULT_DATE_FORMAT], checked by [is_valid_date_format].
 ///
 /// # Returns
 /// A string representing the formatted modification time or "-" if unknown.
-pub fn format_file_time(modified: Option<SystemTime>) -> String {
+pub fn format_file_time(modified: Option<SystemTime>, format: &str) -> String {
     modified
         .map(|mtime| {
             let dt: DateTime<Local> = DateTime::from(mtime);
-            dt.format("%Y-%m-%d %H:%M:%S").to_string()
+            dt.format(format).to_string()
         })
         .unwrap_or_else(|| "-".to_string())
 }
 
-/// Returns Some(resolved_target) if entry is a symlink and can be resolved, otherwise None.
+/// Whether `format` is a strftime format chrono understands.
+///
+/// Formatting a date with an unknown specifier like `%Q` panics, so the `date_format` option is
+/// checked when the config is read.
+pub fn is_valid_date_format(format: &str) -> bool {
+    !StrftimeItems::new(format).any(|item| matches!(item, Item::Error))
+}
+
+/// Formats the modification time for the detail view, without seconds.
+///
+/// # Returns
+/// A string like "2024-05-01 12:00", or "-" if unknown.
+pub fn format_file_time_short(modified: Option<SystemTime>) -> String {
+    modified
+        .map(|mtime| {
+            let dt: DateTime<Local> = DateTime::from(mtime);
+            dt.format("%Y-%m-%d %H:%M").to_string()
+        })
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Returns Some(resolved_target) if entry is a symlink or junction and can be resolved, otherwise None.
 pub fn symlink_target_resolved(
     entry: &crate::core::FileEntry,
     parent_dir: &Path,
@@ -264,19 +452,90 @@ pub fn symlink_target_resolved(
     if !entry.is_symlink() {
         return None;
     }
-    let entry_path = parent_dir.join(entry.name());
-    if let Ok(target) = std::fs::read_link(&entry_path) {
-        let resolved = if target.is_absolute() {
-            target
-        } else {
-            entry_path
-                .parent()
-                .unwrap_or_else(|| Path::new(""))
-                .join(target)
-        };
-        Some(resolved)
+    link_target(&parent_dir.join(entry.name()))
+}
+
+/// Whether `c` is a bidirectional control character, which reorders the text around it.
+fn is_bidi_control(c: char) -> bool {
+    matches!(c, '\u{202a}'..='\u{202e}' | '\u{2066}'..='\u{2069}' | '\u{200e}' | '\u{200f}' | '\u{061c}')
+}
+
+/// Makes a file name safe to display.
+///
+/// Bidirectional control characters are shown as `�`, so a name like `photo\u{202e}gpj.exe`
+/// cannot pose as `photoexe.jpg`. With `force_ltr` the name is also wrapped in a left-to-right
+/// isolate, so right-to-left text in it cannot reorder the columns around it.
+/// Only the displayed text changes, file operations keep using the raw name.
+pub fn display_safe_name(name: &str, force_ltr: bool) -> Cow<'_, str> {
+    let name = if name.contains(is_bidi_control) {
+        Cow::Owned(
+            name.chars()
+                .map(|c| if is_bidi_control(c) { '\u{fffd}' } else { c })
+                .collect(),
+        )
     } else {
-        None
+        Cow::Borrowed(name)
+    };
+    if force_ltr {
+        Cow::Owned(format!("\u{2066}{}\u{2069}", name))
+    } else {
+        name
+    }
+}
+
+/// Truncates `text` to at most `max_width` columns, marking a cut with a trailing [ELLIPSIS].
+///
+/// The text is cut between grapheme clusters, so emoji sequences (ZWJ, skin tones, flags)
+/// and combining marks are never split and wide glyphs are counted with their full width.
+///
+/// # Returns
+/// The truncated text and its display width.
+pub fn truncate_to_width(text: &str, max_width: usize) -> (String, usize) {
+    truncate_to_width_with(text, max_width, ELLIPSIS)
+}
+
+/// Same as [truncate_to_width], marking a cut with `mark`, which has to be one column wide.
+pub fn truncate_to_width_with(text: &str, max_width: usize, mark: char) -> (String, usize) {
+    let full_width = text.width();
+    if full_width <= max_width {
+        return (text.to_string(), full_width);
+    }
+    if max_width == 0 {
+        return (String::new(), 0);
+    }
+
+    // Reserve one column for the ellipsis
+    let mut out = String::with_capacity(max_width);
+    let mut current_w = 0;
+    for grapheme in text.graphemes(true) {
+        let w = grapheme.width();
+        if current_w + w > max_width - 1 {
+            break;
+        }
+        out.push_str(grapheme);
+        current_w += w;
+    }
+    out.push(mark);
+    (out, current_w + 1)
+}
+
+/// How the internal preview renders text lines.
+///
+/// # Fields
+/// * `tab_width` - Column interval of the tab stops tabs are expanded to.
+/// * `show_control_chars` - Render control characters visibly, e.g. `^M`, instead of dropping them.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TextRendering {
+    pub tab_width: usize,
+    pub show_control_chars: bool,
+}
+
+impl Default for TextRendering {
+    fn default() -> Self {
+        TextRendering {
+            tab_width: 4,
+            show_control_chars: false,
+        }
     }
 }
 
@@ -291,12 +550,28 @@ pub fn symlink_target_resolved(
 /// # Returns
 /// A sanitized string that fits exactly within the specified pane width.
 pub fn sanitize_to_exact_width(line: &str, pane_width: usize) -> String {
+    render_to_exact_width(line, pane_width, TextRendering::default())
+}
+
+/// Same as [sanitize_to_exact_width], with a configurable tab width and control characters
+/// optionally rendered in caret notation instead of being dropped.
+///
+/// # Arguments
+/// * `line` - The input string to render.
+/// * `pane_width` - The width of the pane to fit the string into.
+/// * `text` - The tab width and control character handling.
+///
+/// # Returns
+/// A string that fits exactly within the specified pane width.
+pub fn render_to_exact_width(line: &str, pane_width: usize, text: TextRendering) -> String {
     let mut out = String::with_capacity(pane_width);
     let mut current_w = 0;
+    let tab_width = text.tab_width.max(1);
 
-    for char in line.chars() {
-        if char == '\t' {
-            let space_count = 4 - (current_w % 4);
+    // Whole grapheme clusters are kept or dropped, so wide emoji sequences are never split
+    for grapheme in line.graphemes(true) {
+        if grapheme == "\t" {
+            let space_count = tab_width - (current_w % tab_width);
             if current_w + space_count > pane_width {
                 break;
             }
@@ -305,16 +580,26 @@ pub fn sanitize_to_exact_width(line: &str, pane_width: usize) -> String {
             continue;
         }
 
-        if char.is_control() {
+        if grapheme.chars().any(char::is_control) {
+            if !text.show_control_chars {
+                continue;
+            }
+            let shown: String = grapheme.chars().map(control_char_notation).collect();
+            let w = shown.width();
+            if current_w + w > pane_width {
+                break;
+            }
+            out.push_str(&shown);
+            current_w += w;
             continue;
         }
 
-        let w = char.width().unwrap_or(0);
+        let w = grapheme.width();
         if current_w + w > pane_width {
             break;
         }
 
-        out.push(char);
+        out.push_str(grapheme);
         current_w += w;
     }
 
@@ -326,6 +611,17 @@ pub fn sanitize_to_exact_width(line: &str, pane_width: usize) -> String {
     out
 }
 
+/// Visible form of a control character: caret notation for ASCII controls (`^M`, `^[`, `^?`),
+/// an escape (`\u{85}`) for the others. Other characters are returned unchanged.
+fn control_char_notation(c: char) -> String {
+    match c {
+        '\0'..='\x1f' => format!("^{}", char::from(c as u8 + b'@')),
+        '\x7f' => "^?".to_owned(),
+        c if c.is_control() => c.escape_unicode().to_string(),
+        c => c.to_string(),
+    }
+}
+
 /// Loads a fixed-width preview of a directory entries
 ///
 /// # Arguments
@@ -374,6 +670,38 @@ pub fn preview_directory(path: &Path, max_lines: usize, pane_width: usize) -> Ve
     }
 }
 
+/// Returns true if the file at `path` is not a text file.
+///
+/// Known media and document extensions are binary without reading the file. Other files are
+/// binary if they start with a PDF header or contain a null byte in their first bytes,
+/// the same checks as the preview uses. Unreadable files are treated as text.
+pub fn is_binary_file(path: &Path) -> bool {
+    let known_ext = path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| {
+            BINARY_EXTENSIONS
+                .iter()
+                .any(|known| ext.eq_ignore_ascii_case(known))
+        });
+    if known_ext {
+        return true;
+    }
+
+    let Ok(file) = File::open(path) else {
+        return false;
+    };
+    let mut buffer = Vec::with_capacity(BINARY_PEEK_BYTES);
+    if file
+        .take(BINARY_PEEK_BYTES as u64)
+        .read_to_end(&mut buffer)
+        .is_err()
+    {
+        return false;
+    }
+    buffer.starts_with(b"%PDF-") || buffer.contains(&0)
+}
+
 /// Loads a preview for any path (directory or file), returning an error or a padded lines for
 /// display.
 /// large binaries/unreadable and unsupported files are replaced with a notice.
@@ -386,32 +714,77 @@ pub fn preview_directory(path: &Path, max_lines: usize, pane_width: usize) -> Ve
 /// # Returns
 /// A vector of strings, each representing a line from the file or directory preview.
 pub fn safe_read_preview(path: &Path, max_lines: usize, pane_width: usize) -> Vec<String> {
+    read_preview(path, max_lines, pane_width, TextRendering::default(), None).0
+}
+
+/// Same as [safe_read_preview], but renders file lines as configured and additionally
+/// syntax highlights readable text files.
+///
+/// Highlighting is only applied to actual file content, never to notices such as
+/// "[Binary file - preview hidden]" or directory listings.
+///
+/// # Arguments
+/// * `path` - The path to the file or directory to preview.
+/// * `max_lines` - The maximum number of lines to return.
+/// * `pane_width` - The width of the pane for formatting.
+/// * `text` - The tab width and control character handling of file lines.
+/// * `syntax_theme` - The name of the syntax theme used for highlighting, `None` disables it.
+///
+/// # Returns
+/// The plain preview lines and, if a syntax matched the file, the highlighted lines.
+pub fn highlighted_preview(
+    path: &Path,
+    max_lines: usize,
+    pane_width: usize,
+    text: TextRendering,
+    syntax_theme: Option<&str>,
+) -> (Vec<String>, Option<Vec<Line<'static>>>) {
+    read_preview(path, max_lines, pane_width, text, syntax_theme)
+}
+
+/// Shared implementation of [safe_read_preview] and [highlighted_preview].
+fn read_preview(
+    path: &Path,
+    max_lines: usize,
+    pane_width: usize,
+    text: TextRendering,
+    syntax_theme: Option<&str>,
+) -> (Vec<String>, Option<Vec<Line<'static>>>) {
     let max_lines = std::cmp::max(max_lines, MIN_PREVIEW_LINES);
 
     // Metadata check
     let Ok(meta) = std::fs::metadata(path) else {
-        return vec![sanitize_to_exact_width(
-            "[Error: Access Denied]",
-            pane_width,
-        )];
+        return (
+            vec![sanitize_to_exact_width(
+                "[Error: Access Denied]",
+                pane_width,
+            )],
+            None,
+        );
     };
 
     // Directory Check
     if meta.is_dir() {
-        return preview_directory(path, max_lines, pane_width);
+        return (preview_directory(path, max_lines, pane_width), None);
     }
 
     // Size Check
     if meta.len() > MAX_PREVIEW_SIZE {
-        return vec![sanitize_to_exact_width(
-            "[File too large for preview]",
-            pane_width,
-        )];
+        return (
+            vec![sanitize_to_exact_width(
+                "[File too large for preview]",
+                pane_width,
+            )],
+            None,
+        );
     }
 
     // Regular File Check
     if !meta.is_file() {
-        return vec![sanitize_to_exact_width("[Not a regular file]", pane_width)];
+        return (
+            vec![sanitize_to_exact_width("[Not a regular file]", pane_width)],
+            None,
+        );
     }
 
     // File Read and binary Check
@@ -421,20 +794,26 @@ pub fn safe_read_preview(path: &Path, max_lines: usize, pane_width: usize) -> Ve
             let mut header = [0u8; HEADER_PEEK_BYTES];
             let read_bytes = file.read(&mut header).unwrap_or(0);
             if read_bytes >= 5 && &header[..5] == b"%PDF-" {
-                return vec![sanitize_to_exact_width(
-                    "[Binary file - preview hidden]",
-                    pane_width,
-                )];
+                return (
+                    vec![sanitize_to_exact_width(
+                        "[Binary file - preview hidden]",
+                        pane_width,
+                    )],
+                    None,
+                );
             }
 
             // Peek for null bytes to detect binary files
             let mut buffer = [0u8; BINARY_PEEK_BYTES];
             let n = file.read(&mut buffer).unwrap_or(0);
             if buffer[..n].contains(&0) {
-                return vec![sanitize_to_exact_width(
-                    "[Binary file - preview hidden]",
-                    pane_width,
-                )];
+                return (
+                    vec![sanitize_to_exact_width(
+                        "[Binary file - preview hidden]",
+                        pane_width,
+                    )],
+                    None,
+                );
             }
 
             // Rewind to start for full read
@@ -448,7 +827,7 @@ pub fn safe_read_preview(path: &Path, max_lines: usize, pane_width: usize) -> Ve
             for line_result in reader.lines().take(max_lines) {
                 match line_result {
                     Ok(line) => {
-                        preview_lines.push(sanitize_to_exact_width(&line, pane_width));
+                        preview_lines.push(render_to_exact_width(&line, pane_width, text));
                     }
                     Err(_) => break,
                 }
@@ -457,22 +836,28 @@ pub fn safe_read_preview(path: &Path, max_lines: usize, pane_width: usize) -> Ve
             // Handle Empty File
             if preview_lines.is_empty() {
                 preview_lines.push(sanitize_to_exact_width("[Empty file]", pane_width));
+                return (preview_lines, None);
             }
 
-            preview_lines
+            let highlighted =
+                syntax_theme.and_then(|theme| highlight_lines(path, &preview_lines, theme));
+            (preview_lines, highlighted)
         }
         Err(e) => {
             let msg = match e.kind() {
                 ErrorKind::PermissionDenied => "[Error: Permission Denied]",
                 ErrorKind::NotFound => "[Error: File Not Found]",
                 _ => {
-                    return vec![sanitize_to_exact_width(
-                        &format!("[Error reading file: {}]", e),
-                        pane_width,
-                    )];
+                    return (
+                        vec![sanitize_to_exact_width(
+                            &format!("[Error reading file: {}]", e),
+                            pane_width,
+                        )],
+                        None,
+                    );
                 }
             };
-            vec![sanitize_to_exact_width(msg, pane_width)]
+            (vec![sanitize_to_exact_width(msg, pane_width)], None)
         }
     }
 }
@@ -0,0 +1,122 @@
+//! Syntax highlighting for the previews of runa.
+//!
+//! Wraps `syntect` with the bundled default syntaxes and themes for the internal preview.
+//! The syntax and theme sets are loaded lazily on the first highlighted preview,
+//! so users who disable highlighting never pay for loading them.
+//!
+//! Also parses the ANSI colored output of the `bat` preview method into styled lines.
+
+use ansi_to_tui::IntoText;
+use once_cell::sync::Lazy;
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use syntect::easy::HighlightLines;
+use syntect::highlighting::{FontStyle, ThemeSet};
+use syntect::parsing::SyntaxSet;
+
+use std::path::Path;
+
+/// Syntax theme used if the requested theme is not bundled with syntect.
+pub const DEFAULT_SYNTAX_THEME: &str = "base16-ocean.dark";
+
+static SYNTAXES: Lazy<SyntaxSet> = Lazy::new(SyntaxSet::load_defaults_nonewlines);
+static THEMES: Lazy<ThemeSet> = Lazy::new(ThemeSet::load_defaults);
+
+/// Highlights already sanitized preview lines based on the file extension or first line.
+///
+/// Only the foreground color and font style are applied, so the preview pane background
+/// of the runa theme stays intact.
+///
+/// # Arguments
+/// * `path` - Path of the previewed file, used to detect the syntax.
+/// * `lines` - The preview lines to highlight.
+/// * `theme_name` - Name of a bundled syntect theme.
+///
+/// # Returns
+/// The highlighted lines, or `None` if no syntax matched the file.
+pub fn highlight_lines(
+    path: &Path,
+    lines: &[String],
+    theme_name: &str,
+) -> Option<Vec<Line<'static>>> {
+    let syntaxes = &*SYNTAXES;
+    let syntax = path
+        .extension()
+        .and_then(|ext| syntaxes.find_syntax_by_extension(&ext.to_string_lossy()))
+        .or_else(|| {
+            path.file_name()
+                .and_then(|name| syntaxes.find_syntax_by_extension(&name.to_string_lossy()))
+        })
+        .or_else(|| {
+            lines
+                .first()
+                .and_then(|first| syntaxes.find_syntax_by_first_line(first))
+        })?;
+
+    if syntax.name == "Plain Text" {
+        return None;
+    }
+
+    let theme = THEMES
+        .themes
+        .get(theme_name)
+        .or_else(|| THEMES.themes.get(DEFAULT_SYNTAX_THEME))?;
+    let mut highlighter = HighlightLines::new(syntax, theme);
+
+    let mut out = Vec::with_capacity(lines.len());
+    for line in lines {
+        let ranges = highlighter.highlight_line(line, syntaxes).ok()?;
+        let spans: Vec<Span<'static>> = ranges
+            .into_iter()
+            .map(|(style, text)| {
+                let fg = style.foreground;
+                let mut span_style = Style::default().fg(Color::Rgb(fg.r, fg.g, fg.b));
+                if style.font_style.contains(FontStyle::BOLD) {
+                    span_style = span_style.add_modifier(Modifier::BOLD);
+                }
+                if style.font_style.contains(FontStyle::ITALIC) {
+                    span_style = span_style.add_modifier(Modifier::ITALIC);
+                }
+                if style.font_style.contains(FontStyle::UNDERLINE) {
+                    span_style = span_style.add_modifier(Modifier::UNDERLINED);
+                }
+                Span::styled(text.to_owned(), span_style)
+            })
+            .collect();
+        out.push(Line::from(spans));
+    }
+    Some(out)
+}
+
+/// Parses ANSI escape sequences, as produced by `bat --color=always`, into styled lines.
+///
+/// The lines are parsed as one text, so styles spanning wrapped lines stay intact.
+/// The returned plain lines have all escape sequences stripped, so their width matches
+/// what is actually drawn.
+///
+/// # Arguments
+/// * `lines` - The raw output lines, possibly containing escape sequences.
+///
+/// # Returns
+/// The plain lines and, if any escape sequences were found, the styled lines.
+pub fn parse_ansi_lines(lines: Vec<String>) -> (Vec<String>, Option<Vec<Line<'static>>>) {
+    if !lines.iter().any(|line| line.contains('\x1b')) {
+        return (lines, None);
+    }
+
+    let Ok(text) = lines.join("\n").into_text() else {
+        return (lines, None);
+    };
+
+    let plain = text
+        .lines
+        .iter()
+        .map(|line| {
+            line.spans
+                .iter()
+                .map(|span| span.content.as_ref())
+                .collect::<String>()
+        })
+        .collect();
+    (plain, Some(text.lines))
+}
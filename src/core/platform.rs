@@ -0,0 +1,294 @@
+//! Platform abstraction for runa.
+//!
+//! The few things runa asks the OS beyond what std offers portably are answered here, each with
+//! a fallback, so the rest of the crate builds and behaves the same on Linux, macOS, the BSDs,
+//! illumos and Windows:
+//! - [entry_flags]: directory, symlink, hidden and system flags of a directory entry.
+//! - [file_id]: device and inode number, identifying a directory across renames.
+//! - [mode_string]: the permission or attribute letters shown in the info dialog.
+//! - [is_executable]: whether a file has an executable bit, for the entry colors.
+//! - [mount_table]: the mounted filesystems, for profiles matching `fs_types`.
+//! - [opener]: the program opening a file with its default application.
+//! - [is_root]: whether runa runs as root, which turns on the root guard.
+//! - [effective_home]: the home directory of the effective user, for the log of the root guard.
+//!
+//! Finder tags and the quarantine attribute only exist on macOS and live in [finder](crate::core::finder),
+//! alternate data streams and resource forks live in [streams](crate::core::streams).
+
+use std::fs::{DirEntry, Metadata};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// Type and visibility of a directory entry.
+///
+/// # Fields
+/// * `is_dir` - Boolean indicating if the entry is a directory
+/// * `is_hidden` - Boolean indicating if the entry is hidden
+/// * `is_system` - Boolean indicating if the entry is a system file
+/// * `is_symlink` - Boolean indicating if the entry is a symlink
+#[derive(Debug, Clone, Copy)]
+pub(crate) struct FileFlags {
+    pub(crate) is_dir: bool,
+    pub(crate) is_hidden: bool,
+    pub(crate) is_system: bool,
+    pub(crate) is_symlink: bool,
+}
+
+/// Reads the flags of a directory entry, without following symlinks.
+///
+/// Dot files are hidden everywhere, on Windows also entries with the hidden attribute.
+pub(crate) fn entry_flags(entry: &DirEntry) -> FileFlags {
+    let name = entry.file_name();
+
+    // The metadata of a windows DirEntry comes from the FindNextFileW data of the enumeration
+    // handle, so type and attributes are read in one go without a stat per entry.
+    // This matters on network shares, where every extra stat is a round trip.
+    // Junctions and mount points carry a name surrogate reparse tag, which the file type
+    // reports as a symlink rather than a directory, so they are listed and entered like one.
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        if let Ok(md) = entry.metadata() {
+            let attrs = md.file_attributes();
+            let ft = md.file_type();
+            return FileFlags {
+                is_dir: ft.is_dir(),
+                is_symlink: ft.is_symlink(),
+                is_hidden: (attrs & 0x2 != 0) || name.to_string_lossy().starts_with('.'),
+                is_system: attrs & 0x4 != 0,
+            };
+        }
+    }
+
+    let (is_dir, is_symlink) = match entry.file_type() {
+        Ok(ft) => (ft.is_dir(), ft.is_symlink()),
+        Err(_) => (false, false),
+    };
+    FileFlags {
+        is_dir,
+        is_symlink,
+        is_hidden: is_dot_file(&name),
+        is_system: false,
+    }
+}
+
+#[cfg(unix)]
+fn is_dot_file(name: &std::ffi::OsStr) -> bool {
+    use std::os::unix::ffi::OsStrExt;
+    name.as_bytes().first() == Some(&b'.')
+}
+
+#[cfg(not(unix))]
+fn is_dot_file(name: &std::ffi::OsStr) -> bool {
+    name.to_string_lossy().starts_with('.')
+}
+
+/// The device and inode number of `path`, following symlinks.
+///
+/// # Returns
+/// None if the path cannot be read, or on platforms without stable inode numbers.
+pub fn file_id(path: &Path) -> Option<(u64, u64)> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        let md = std::fs::metadata(path).ok()?;
+        Some((md.dev(), md.ino()))
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+/// The permission or attribute letters of an entry.
+///
+/// Unix shows the type and the `rwx` bits like `ls -l` (`drwxr-xr-x`), Windows the type and
+/// the hidden, system, archive and read-only attributes (`dhs-r`), other platforms the type.
+pub fn mode_string(meta: &Metadata) -> String {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+
+        let first = type_letter(meta);
+        let mode = meta.permissions().mode();
+        let mut chars = [first, '-', '-', '-', '-', '-', '-', '-', '-', '-'];
+        let shifts = [6, 3, 0];
+        for (i, &shift) in shifts.iter().enumerate() {
+            let base = 1 + i * 3;
+            if (mode >> (shift + 2)) & 1u32 != 0 {
+                chars[base] = 'r';
+            }
+            if (mode >> (shift + 1)) & 1u32 != 0 {
+                chars[base + 1] = 'w';
+            }
+            if (mode >> shift) & 1u32 != 0 {
+                chars[base + 2] = 'x';
+            }
+        }
+        chars.iter().collect()
+    }
+
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        let attr = meta.file_attributes();
+        let mut out = String::with_capacity(7);
+        out.push(if attr & 0x10 != 0 {
+            'd'
+        } else if attr & 0x400 != 0 {
+            'l'
+        } else {
+            '-'
+        });
+        out.push(if attr & 0x02 != 0 { 'h' } else { '-' });
+        out.push(if attr & 0x04 != 0 { 's' } else { '-' });
+        out.push(if attr & 0x20 != 0 { 'a' } else { '-' });
+        out.push(if attr & 0x01 != 0 { 'r' } else { '-' });
+        out
+    }
+
+    #[cfg(not(any(unix, windows)))]
+    {
+        type_letter(meta).to_string()
+    }
+}
+
+/// Whether `meta` is a file with an executable bit for anyone.
+///
+/// Always false on platforms without executable bits, where executables are colored by their
+/// extension instead.
+pub fn is_executable(meta: &Metadata) -> bool {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        meta.is_file() && meta.permissions().mode() & 0o111 != 0
+    }
+
+    #[cfg(not(unix))]
+    {
+        let _ = meta;
+        false
+    }
+}
+
+#[cfg(not(windows))]
+fn type_letter(meta: &Metadata) -> char {
+    let file_type = meta.file_type();
+    if file_type.is_dir() {
+        'd'
+    } else if file_type.is_symlink() {
+        'l'
+    } else {
+        '-'
+    }
+}
+
+/// Reads the table of mounted filesystems, see [parse_mounts](crate::core::fm::parse_mounts).
+///
+/// `/proc/self/mounts` on Linux and Android, `/etc/mnttab` on illumos and Solaris, the output
+/// of `mount` on macOS and the BSDs. Always `None` on Windows.
+pub fn mount_table() -> Option<String> {
+    #[cfg(any(target_os = "linux", target_os = "android"))]
+    {
+        std::fs::read_to_string("/proc/self/mounts").ok()
+    }
+
+    #[cfg(any(target_os = "illumos", target_os = "solaris"))]
+    {
+        std::fs::read_to_string("/etc/mnttab").ok()
+    }
+
+    #[cfg(all(
+        unix,
+        not(any(
+            target_os = "linux",
+            target_os = "android",
+            target_os = "illumos",
+            target_os = "solaris"
+        ))
+    ))]
+    {
+        let output = Command::new("mount").output().ok()?;
+        output
+            .status
+            .success()
+            .then(|| String::from_utf8_lossy(&output.stdout).into_owned())
+    }
+
+    #[cfg(not(unix))]
+    {
+        None
+    }
+}
+
+/// The command opening a file with its default application, the path is appended to it.
+///
+/// `open` on macOS, `start` on Windows and `xdg-open` on Linux, the BSDs and illumos.
+pub fn opener() -> Command {
+    if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(windows) {
+        let mut cmd = Command::new("cmd");
+        // The empty argument is the window title, otherwise a quoted path would be taken as it
+        cmd.args(["/C", "start", ""]);
+        cmd
+    } else {
+        Command::new("xdg-open")
+    }
+}
+
+/// Whether the effective user is root.
+///
+/// Always false on Windows, where an elevated session is not told apart.
+pub fn is_root() -> bool {
+    #[cfg(unix)]
+    {
+        // SAFETY: geteuid has no preconditions and cannot fail
+        unsafe { libc::geteuid() == 0 }
+    }
+
+    #[cfg(not(unix))]
+    {
+        false
+    }
+}
+
+/// The home directory of the effective user.
+///
+/// `$HOME` if the effective user owns it, otherwise the home directory of the password database,
+/// as `sudo` may keep the `$HOME` of the invoking user. On Windows always `$HOME`.
+pub fn effective_home() -> Option<PathBuf> {
+    let home = dirs::home_dir();
+
+    #[cfg(unix)]
+    {
+        use std::ffi::{CStr, OsStr};
+        use std::os::unix::ffi::OsStrExt;
+        use std::os::unix::fs::MetadataExt;
+
+        // SAFETY: geteuid has no preconditions and cannot fail
+        let euid = unsafe { libc::geteuid() };
+        if let Some(home) = home
+            && std::fs::metadata(&home).is_ok_and(|meta| meta.uid() == euid)
+        {
+            return Some(home);
+        }
+        // SAFETY: getpwuid returns null or an entry in static storage,
+        // which is copied before anything else can overwrite it
+        unsafe {
+            let entry = libc::getpwuid(euid);
+            if entry.is_null() || (*entry).pw_dir.is_null() {
+                return None;
+            }
+            let dir = CStr::from_ptr((*entry).pw_dir);
+            Some(PathBuf::from(OsStr::from_bytes(dir.to_bytes())))
+        }
+    }
+
+    #[cfg(not(unix))]
+    {
+        home
+    }
+}
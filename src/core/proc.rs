@@ -12,7 +12,19 @@
 //! in the specified base directory. It then applies fuzzy matching using the
 //! fuzzy_matcher crate to filter and score the results based on the provided query.
 //! The results are returned as a vector of [FindResult] structs, sorted by their
-//! fuzzy match scores.
+//! fuzzy match scores. [find_streaming] additionally reports the best results so far
+//! while fd is still running.
+//!
+//! The [grep] function searches file contents for a literal query using the `ignore` crate walker,
+//! which respects .gitignore files. Its results are also [FindResult]s, with a [ContentMatch]
+//! holding the matching line.
+//!
+//! [git_info] reads the branch and dirty state of the repository shown in the header.
+//!
+//! [open_with_system] hands files runa cannot edit (images, videos, PDFs..) to the OS default application.
+//!
+//! [zoxide_query] and [zoxide_add] wrap the zoxide command-line tool for the jump dialog,
+//! ranking directories by frecency instead of a fuzzy score.
 //!
 //! The module also includes a [preview_bat] function that uses the bat command-line tool
 //! to preview the contents of a file, returning a specified number of lines from the file.
@@ -21,15 +33,23 @@
 
 use fuzzy_matcher::FuzzyMatcher;
 use fuzzy_matcher::skim::SkimMatcherV2;
+use ignore::WalkBuilder;
+use ignore::overrides::{Override, OverrideBuilder};
+
+use crate::core::platform;
+use crate::utils::termux;
 
 use std::borrow::Cow;
-use std::cmp::Ordering;
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BinaryHeap, HashMap, HashSet};
 use std::ffi::OsString;
-use std::io::{self, BufRead};
+use std::fs::File;
+use std::io::{self, BufRead, Read};
 use std::path::{Path, PathBuf};
 use std::process::{Command, Stdio};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
+use std::time::{Duration, Instant};
 
 /// The size of the buffer reader used to read the output of fd
 /// This value is set to 32KB to balance memory usage and performance.
@@ -39,28 +59,88 @@ const BUFREADER_SIZE: usize = 32768;
 
 /// A list of common directories and files to exclude from the search.
 /// This helps to speed up the search and avoid irrelevant results.
+///
+/// Used as the default `exclude` list of [SearchFilters], configurable with `[find] exclude`.
 #[rustfmt::skip]
-const EXCLUDES: &[&str] = &[
+pub const DEFAULT_EXCLUDES: &[&str] = &[
     ".git", ".hg", ".svn", ".rustup", ".cargo", "target", "node_modules", "dist",
     "venv", ".venv", "__pycache__", ".DS_Store", "build", "out", "bin", "obj"
 ];
 
+/// Minimum time between two batches reported by [find_streaming].
+const FIND_BATCH_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Upper bound of the memory held by the paths of the kept results of a single find.
+/// On pathological trees with very long paths the worst results are dropped earlier,
+/// so fewer than `max_results` may be returned.
+const MAX_FIND_RESULT_BYTES: usize = 32 * 1024 * 1024;
+
+/// Files larger than this are skipped by [grep].
+const GREP_MAX_FILE_SIZE: u64 = 8 * 1024 * 1024;
+
+/// Maximum number of characters of a matching line kept for display.
+const GREP_MAX_LINE_CHARS: usize = 256;
+
 /// A single result from the find function.
 /// It contains the path and the score of the fuzzy match.
+/// Results from [grep] additionally hold the matching line as [ContentMatch].
 #[derive(Debug, Clone, Eq, PartialEq)]
 pub struct FindResult {
     path: PathBuf,
     score: i64,
+    content: Option<ContentMatch>,
+    is_dir: bool,
+}
+
+/// A matching line inside a file, as found by [grep].
+///
+/// # Fields
+/// * `line` - The 1-based line number of the match.
+/// * `text` - The trimmed text of the matching line.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct ContentMatch {
+    line: usize,
+    text: String,
+}
+
+impl ContentMatch {
+    pub fn line(&self) -> usize {
+        self.line
+    }
+
+    pub fn text(&self) -> &str {
+        &self.text
+    }
 }
 
 /// Implement ordering for FindResult based on score (higher is better).
 /// This allows sorting of FindResult instances.
+///
+/// Equal scores are ordered by [rank_ties], then by line number for [grep] results,
+/// so sorted results are the same on every run.
 impl Ord for FindResult {
     fn cmp(&self, other: &Self) -> Ordering {
-        other.score.cmp(&self.score)
+        other
+            .score
+            .cmp(&self.score)
+            .then_with(|| {
+                rank_ties(
+                    self.path.as_os_str().as_encoded_bytes(),
+                    other.path.as_os_str().as_encoded_bytes(),
+                )
+            })
+            .then_with(|| {
+                let line = |r: &FindResult| r.content.as_ref().map(ContentMatch::line);
+                line(self).cmp(&line(other))
+            })
     }
 }
 
+/// Orders paths with an equal score: the shorter path first, then lexicographically.
+fn rank_ties(a: &[u8], b: &[u8]) -> Ordering {
+    a.len().cmp(&b.len()).then_with(|| a.cmp(b))
+}
+
 /// Implement partial ordering for FindResult.
 /// This is required because we implemented Ord.
 impl PartialOrd for FindResult {
@@ -76,62 +156,372 @@ impl FindResult {
     pub fn score(&self) -> i64 {
         self.score
     }
+    pub fn content(&self) -> Option<&ContentMatch> {
+        self.content.as_ref()
+    }
+    pub fn is_dir(&self) -> bool {
+        self.is_dir
+    }
     pub fn relative(&self, base: &Path) -> String {
         let rel = self.path.strip_prefix(base).unwrap_or(&self.path);
         normalize_relative_path(rel)
     }
 }
 
+/// Restricts the find results to a type of entry.
+///
+/// Variants:
+/// * `All` - Files and directories.
+/// * `Files` - Only files.
+/// * `Dirs` - Only directories.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FindKind {
+    #[default]
+    All,
+    Files,
+    Dirs,
+}
+
+impl FindKind {
+    /// Returns the next kind, cycling All -> Files -> Dirs -> All.
+    pub fn next(self) -> Self {
+        match self {
+            FindKind::All => FindKind::Files,
+            FindKind::Files => FindKind::Dirs,
+            FindKind::Dirs => FindKind::All,
+        }
+    }
+
+    /// Short label used by the find dialog.
+    pub fn label(self) -> &'static str {
+        match self {
+            FindKind::All => "all",
+            FindKind::Files => "files",
+            FindKind::Dirs => "dirs",
+        }
+    }
+}
+
+/// Splits `*.ext` tokens off a find query.
+///
+/// # Returns
+/// The extensions (without the leading `*.`) and the remaining fuzzy query.
+///
+/// # Examples
+/// let (exts, rest) = split_extensions("*.rs *.toml foo");
+/// exts = ["rs", "toml"], rest = "foo"
+pub fn split_extensions(query: &str) -> (Vec<String>, String) {
+    let mut extensions = Vec::new();
+    let mut rest = Vec::new();
+    for token in query.split_whitespace() {
+        match token.strip_prefix("*.") {
+            Some(ext) if !ext.is_empty() => extensions.push(ext.to_owned()),
+            _ => rest.push(token),
+        }
+    }
+    (extensions, rest.join(" "))
+}
+
+/// Include/exclude globs and .gitignore handling shared by [find_streaming] and [grep_with_filters].
+///
+/// Globs use gitignore syntax: a pattern without a slash matches the name at any depth.
+///
+/// # Fields
+/// * `include` - If not empty, only files matching one of these globs are found.
+/// * `exclude` - Files and directories matching these globs are skipped.
+/// * `gitignore` - Whether .gitignore rules are respected.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SearchFilters {
+    include: Vec<String>,
+    exclude: Vec<String>,
+    gitignore: bool,
+}
+
+impl SearchFilters {
+    pub fn new(include: Vec<String>, exclude: Vec<String>, gitignore: bool) -> Self {
+        Self {
+            include,
+            exclude,
+            gitignore,
+        }
+    }
+
+    pub fn include(&self) -> &[String] {
+        &self.include
+    }
+
+    pub fn exclude(&self) -> &[String] {
+        &self.exclude
+    }
+
+    pub fn gitignore(&self) -> bool {
+        self.gitignore
+    }
+
+    /// Builds the exclude globs as `ignore` overrides relative to `base_dir`.
+    /// Invalid globs are skipped.
+    fn exclude_overrides(&self, base_dir: &Path) -> Override {
+        let mut builder = OverrideBuilder::new(base_dir);
+        for glob in &self.exclude {
+            let _ = builder.add(&format!("!{glob}"));
+        }
+        builder.build().unwrap_or_else(|_| Override::empty())
+    }
+
+    /// Builds the include globs as a matcher relative to `base_dir`, or None without includes.
+    ///
+    /// Kept apart from the walker overrides, as whitelisting a file there would also
+    /// override its .gitignore rules.
+    fn include_matcher(&self, base_dir: &Path) -> Option<Override> {
+        if self.include.is_empty() {
+            return None;
+        }
+        let mut builder = OverrideBuilder::new(base_dir);
+        for glob in &self.include {
+            let _ = builder.add(glob);
+        }
+        builder.build().ok()
+    }
+}
+
+impl Default for SearchFilters {
+    fn default() -> Self {
+        Self {
+            include: Vec::new(),
+            exclude: DEFAULT_EXCLUDES.iter().map(|s| s.to_string()).collect(),
+            gitignore: true,
+        }
+    }
+}
+
 /// An internal struct to hold raw results from the fuzzy matching process.
 /// It contains the relative path and the score.
-#[derive(Debug, Clone)]
+///
+/// Ordered from worst to best by score, then by [rank_ties] reversed,
+/// so a min-heap of `Reverse<RawResult>` keeps the best results.
+#[derive(Debug, Clone, PartialEq, Eq)]
 struct RawResult {
-    relative: String,
     score: i64,
+    relative: String,
+    is_dir: bool,
+}
+
+impl Ord for RawResult {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score
+            .cmp(&other.score)
+            .then_with(|| rank_ties(other.relative.as_bytes(), self.relative.as_bytes()))
+    }
+}
+
+impl PartialOrd for RawResult {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// The best results of a find, bounded by count and by the memory of their paths.
+///
+/// A min-heap of `Reverse<RawResult>` keeps the best results, the worst is dropped first.
+/// Paths are kept once, even if several walker roots report them.
+struct TopResults {
+    heap: BinaryHeap<Reverse<RawResult>>,
+    kept: HashSet<String>,
+    bytes: usize,
+    max_results: usize,
+}
+
+impl TopResults {
+    fn new(max_results: usize) -> Self {
+        Self {
+            heap: BinaryHeap::with_capacity(max_results.min(4096) + 1),
+            kept: HashSet::new(),
+            bytes: 0,
+            max_results,
+        }
+    }
+
+    fn is_full(&self) -> bool {
+        self.heap.len() >= self.max_results
+    }
+
+    /// Offers a match, kept if there is room or it beats the worst kept result.
+    ///
+    /// # Arguments
+    /// * `is_dir` - Checks whether the match is a directory, only called for kept matches.
+    ///
+    /// # Returns
+    /// Whether the kept results changed.
+    fn offer(&mut self, score: i64, relative: Cow<str>, is_dir: impl FnOnce() -> bool) -> bool {
+        // Ties are decided like the final order, so the kept results do not depend on
+        // the order the walker reports them in
+        let beats_worst = self.heap.peek().is_none_or(|Reverse(worst)| {
+            score
+                .cmp(&worst.score)
+                .then_with(|| rank_ties(worst.relative.as_bytes(), relative.as_bytes()))
+                .is_gt()
+        });
+        if (self.is_full() && !beats_worst) || self.kept.contains(relative.as_ref()) {
+            return false;
+        }
+        let relative = relative.into_owned();
+        self.bytes += 2 * relative.len();
+        self.kept.insert(relative.clone());
+        self.heap.push(Reverse(RawResult {
+            score,
+            relative,
+            is_dir: is_dir(),
+        }));
+        while self.heap.len() > self.max_results || self.bytes > MAX_FIND_RESULT_BYTES {
+            let Some(Reverse(worst)) = self.heap.pop() else {
+                break;
+            };
+            self.bytes -= 2 * worst.relative.len();
+            self.kept.remove(&worst.relative);
+        }
+        true
+    }
+
+    /// Converts the kept results into [FindResult]s, best score first.
+    fn to_results(&self, base_dir: &Path) -> Vec<FindResult> {
+        let mut raw: Vec<&RawResult> = self.heap.iter().map(|Reverse(r)| r).collect();
+        raw.sort_unstable_by(|a, b| b.cmp(a));
+        raw.into_iter()
+            .map(|r| FindResult {
+                path: base_dir.join(&r.relative),
+                score: r.score,
+                content: None,
+                is_dir: r.is_dir,
+            })
+            .collect()
+    }
 }
 
 /// Perform a fuzzy find using the fd command-line tool and the fuzzy_matcher crate.
 ///
+/// Same as [find_with_kind] with [FindKind::All].
+pub fn find(
+    base_dir: &Path,
+    query: &str,
+    out: &mut Vec<FindResult>,
+    cancel: Arc<AtomicBool>,
+    max_results: usize,
+) -> io::Result<()> {
+    find_with_kind(base_dir, query, FindKind::All, out, cancel, max_results)
+}
+
+/// Perform a fuzzy find using the fd command-line tool and the fuzzy_matcher crate,
+/// restricted to a [FindKind] and to the `*.ext` extensions given in the query.
+///
+/// The type and extensions are passed to fd, so they are applied before fuzzy matching.
+/// A query consisting only of extensions lists all matching entries.
+///
 /// # Arguments
 /// * `base_dir` - The base directory to search in.
-/// * `query` - The fuzzy search query.
+/// * `query` - The fuzzy search query, optionally with `*.ext` tokens.
+/// * `kind` - The [FindKind] to restrict the results to.
 /// * `out` - A mutable reference to a vector to store the results.
 /// * `cancel` - An atomic boolean to signal cancellation of the search.
 /// * `max_results` - The maximum number of results to return.
 ///
 /// # Errors
 /// Returns an std::io::Error if the fd command fails to execute.
-pub fn find(
+pub fn find_with_kind(
     base_dir: &Path,
     query: &str,
+    kind: FindKind,
     out: &mut Vec<FindResult>,
     cancel: Arc<AtomicBool>,
     max_results: usize,
 ) -> io::Result<()> {
+    let filters = SearchFilters::default();
+    find_streaming(
+        base_dir,
+        query,
+        kind,
+        &filters,
+        out,
+        cancel,
+        max_results,
+        |_| {},
+    )
+}
+
+/// Same as [find_with_kind], but reports the best results found so far while fd is running.
+///
+/// Only the best `max_results` matches are kept in a bounded heap, which also caps the memory
+/// of their paths and drops duplicates. Whenever the heap changed, at most every
+/// [FIND_BATCH_INTERVAL], a snapshot of it (best score first) is passed to `on_batch`.
+/// Once no further match could change the results, fd is stopped early.
+/// The final results are written to `out` as usual and are not reported to `on_batch`.
+///
+/// The exclude globs and the .gitignore toggle of `filters` are passed to fd,
+/// the include globs are applied to fd's output.
+///
+/// # Arguments
+/// * `filters` - The [SearchFilters] to apply.
+/// * `on_batch` - Called with each intermediate snapshot of the results.
+///
+/// See [find_with_kind] for the other arguments.
+///
+/// # Errors
+/// Returns an std::io::Error if the fd command fails to execute.
+#[allow(clippy::too_many_arguments)]
+pub fn find_streaming<F>(
+    base_dir: &Path,
+    query: &str,
+    kind: FindKind,
+    filters: &SearchFilters,
+    out: &mut Vec<FindResult>,
+    cancel: Arc<AtomicBool>,
+    max_results: usize,
+    mut on_batch: F,
+) -> io::Result<()>
+where
+    F: FnMut(Vec<FindResult>),
+{
     out.clear();
-    if query.is_empty() {
+    let (extensions, query) = split_extensions(query);
+    if query.is_empty() && extensions.is_empty() {
         return Ok(());
     }
 
-    let mut args: Vec<OsString> = vec![
-        OsString::from("."),
-        OsString::from(base_dir),
-        OsString::from("--type"),
-        OsString::from("f"),
-        OsString::from("--type"),
-        OsString::from("d"),
-        OsString::from("--hidden"),
-    ];
+    let mut args: Vec<OsString> = vec![OsString::from("."), OsString::from(base_dir)];
+    if kind != FindKind::Dirs {
+        args.push(OsString::from("--type"));
+        args.push(OsString::from("f"));
+    }
+    if kind != FindKind::Files {
+        args.push(OsString::from("--type"));
+        args.push(OsString::from("d"));
+    }
+    args.push(OsString::from("--hidden"));
+
+    for ext in &extensions {
+        args.push(OsString::from("--extension"));
+        args.push(OsString::from(ext));
+    }
 
-    for excl in EXCLUDES {
+    for excl in filters.exclude() {
         args.push(OsString::from("--exclude"));
         args.push(OsString::from(excl));
     }
+    if !filters.gitignore() {
+        args.push(OsString::from("--no-ignore-vcs"));
+    }
 
     args.push(OsString::from("--color"));
     args.push(OsString::from("never"));
-    args.push(OsString::from("--max-results"));
-    args.push(OsString::from(max_results.to_string()));
+    if let Some(threads) = termux::thread_limit() {
+        args.push(OsString::from("--threads"));
+        args.push(OsString::from(threads.to_string()));
+    }
+    // With include globs, fd's output is filtered afterwards and must not be cut short
+    if filters.include().is_empty() {
+        args.push(OsString::from("--max-results"));
+        args.push(OsString::from(max_results.to_string()));
+    }
+    let includes = filters.include_matcher(base_dir);
 
     let mut cmd = Command::new("fd");
     cmd.args(&args).stdout(Stdio::piped());
@@ -150,9 +540,11 @@ pub fn find(
     };
 
     let matcher = SkimMatcherV2::default();
-    let mut raw_results: Vec<RawResult> = Vec::with_capacity(max_results * 2);
+    let mut top = TopResults::new(max_results);
+    let mut dirty = false;
+    let mut last_batch = Instant::now();
 
-    let norm_query = normalize_separators(query);
+    let norm_query = normalize_separators(&query);
     let flat_query = flatten_separators(&norm_query);
 
     if let Some(stdout) = proc.stdout.take() {
@@ -166,32 +558,182 @@ pub fn find(
             }
             let rel = line?;
             let rel = rel.trim();
+            if let Some(includes) = &includes {
+                let path = base_dir.join(rel);
+                if includes.matched(&path, path.is_dir()).is_ignore() {
+                    continue;
+                }
+            }
             let norm_rel = normalize_separators(rel);
             let flat_rel = flatten_separators(&norm_rel);
-            if let Some(score) = matcher.fuzzy_match(&flat_rel, &flat_query) {
-                raw_results.push(RawResult {
-                    relative: norm_rel.into_owned(),
-                    score,
-                });
+            // An extension only query keeps every entry fd returned
+            let score = if flat_query.is_empty() {
+                Some(0)
+            } else {
+                matcher.fuzzy_match(&flat_rel, &flat_query)
+            };
+            // Recent fd versions print directories with a trailing separator
+            let is_dir = || rel.ends_with(['/', '\\']) || base_dir.join(rel).is_dir();
+            if let Some(score) = score
+                && top.offer(score, norm_rel, is_dir)
+            {
+                dirty = true;
+            }
+
+            if dirty && last_batch.elapsed() >= FIND_BATCH_INTERVAL {
+                on_batch(top.to_results(base_dir));
+                last_batch = Instant::now();
+                dirty = false;
             }
         }
         let _ = proc.wait();
     }
 
-    raw_results.sort_unstable_by(|a, b| b.score.cmp(&a.score));
-    raw_results.truncate(max_results);
+    *out = top.to_results(base_dir);
+    Ok(())
+}
 
-    out.reserve(raw_results.len());
-    for raw in raw_results {
-        let path = base_dir.join(&raw.relative);
-        out.push(FindResult {
-            path,
-            score: raw.score,
-        });
+/// Search the contents of all text files below `base_dir` for a literal query.
+///
+/// Same as [grep_with_filters] with the default [SearchFilters].
+pub fn grep(
+    base_dir: &Path,
+    query: &str,
+    out: &mut Vec<FindResult>,
+    cancel: Arc<AtomicBool>,
+    max_results: usize,
+) -> io::Result<()> {
+    let filters = SearchFilters::default();
+    grep_with_filters(base_dir, query, &filters, out, cancel, max_results)
+}
+
+/// Search the contents of all text files below `base_dir` for a literal query,
+/// restricted by [SearchFilters].
+///
+/// Uses the `ignore` walker, so the include/exclude globs and (if enabled) .gitignore rules
+/// of the [SearchFilters] are respected.
+/// Hidden files are searched, binary files (containing null bytes) and files larger than
+/// [GREP_MAX_FILE_SIZE] are skipped. The search is case-insensitive unless the query contains
+/// an uppercase character (smart case).
+///
+/// # Arguments
+/// * `base_dir` - The base directory to search in.
+/// * `query` - The literal text to search for.
+/// * `filters` - The [SearchFilters] to apply.
+/// * `out` - A mutable reference to a vector to store the results.
+/// * `cancel` - An atomic boolean to signal cancellation of the search.
+/// * `max_results` - The maximum number of matching lines to return.
+///
+/// # Errors
+/// Returns an std::io::Error if the base directory can not be read.
+pub fn grep_with_filters(
+    base_dir: &Path,
+    query: &str,
+    filters: &SearchFilters,
+    out: &mut Vec<FindResult>,
+    cancel: Arc<AtomicBool>,
+    max_results: usize,
+) -> io::Result<()> {
+    out.clear();
+    if query.is_empty() {
+        return Ok(());
+    }
+    // Fail early if the base directory itself can not be read
+    std::fs::read_dir(base_dir)?;
+
+    let smart_case = query.chars().any(char::is_uppercase);
+    let needle = if smart_case {
+        query.to_owned()
+    } else {
+        query.to_lowercase()
+    };
+
+    let walker = WalkBuilder::new(base_dir)
+        .hidden(false)
+        // Symlinks and Windows junctions are listed but never walked through
+        .follow_links(false)
+        .git_ignore(filters.gitignore())
+        .git_global(filters.gitignore())
+        .git_exclude(filters.gitignore())
+        .overrides(filters.exclude_overrides(base_dir))
+        // Walk in a stable order, so the same results are found on every run
+        .sort_by_file_name(|a, b| a.cmp(b))
+        .build();
+    let includes = filters.include_matcher(base_dir);
+
+    for entry in walker {
+        if cancel.load(std::sync::atomic::Ordering::Relaxed) || out.len() >= max_results {
+            break;
+        }
+        let Ok(entry) = entry else {
+            continue;
+        };
+        if !entry.file_type().is_some_and(|t| t.is_file()) {
+            continue;
+        }
+        if includes
+            .as_ref()
+            .is_some_and(|inc| inc.matched(entry.path(), false).is_ignore())
+        {
+            continue;
+        }
+        if entry
+            .metadata()
+            .is_ok_and(|meta| meta.len() > GREP_MAX_FILE_SIZE)
+        {
+            continue;
+        }
+        grep_file(entry.path(), &needle, smart_case, out, max_results);
     }
     Ok(())
 }
 
+/// Helper for [grep] which searches a single file and appends the matching lines to `out`.
+/// Stops at the first null byte, treating the file as binary.
+fn grep_file(
+    path: &Path,
+    needle: &str,
+    smart_case: bool,
+    out: &mut Vec<FindResult>,
+    max_results: usize,
+) {
+    let Ok(file) = File::open(path) else {
+        return;
+    };
+    let mut bytes = Vec::new();
+    if file
+        .take(GREP_MAX_FILE_SIZE)
+        .read_to_end(&mut bytes)
+        .is_err()
+        || bytes.contains(&0)
+    {
+        return;
+    }
+    let content = String::from_utf8_lossy(&bytes);
+
+    for (idx, line) in content.lines().enumerate() {
+        if out.len() >= max_results {
+            return;
+        }
+        let found = if smart_case {
+            line.contains(needle)
+        } else {
+            line.to_lowercase().contains(needle)
+        };
+        if found {
+            out.push(FindResult {
+                path: path.to_path_buf(),
+                score: 0,
+                content: Some(ContentMatch {
+                    line: idx + 1,
+                    text: line.trim().chars().take(GREP_MAX_LINE_CHARS).collect(),
+                }),
+                is_dir: false,
+            });
+        }
+    }
+}
+
 /// Use bat to preview a file at the given path, returning up to max_lines of output
 /// Uses the provided bat_args for customization.
 ///
@@ -227,6 +769,201 @@ pub fn preview_bat(
     Ok(stdout.lines().take(max_lines).map(str::to_owned).collect())
 }
 
+/// Queries zoxide for directories matching all keywords of `query`.
+///
+/// # Arguments
+/// * `query` - Whitespace separated keywords, an empty query lists every known directory.
+/// * `max_results` - Maximum number of directories returned.
+///
+/// # Returns
+/// The directories as [FindResult]s, best ranked first. No match is an empty list.
+///
+/// # Errors
+/// Returns an error if zoxide is not installed.
+pub fn zoxide_query(query: &str, max_results: usize) -> io::Result<Vec<FindResult>> {
+    let output = Command::new("zoxide")
+        .args(["query", "--list", "--"])
+        .args(query.split_whitespace())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .map_err(|e| {
+            if e.kind() == io::ErrorKind::NotFound {
+                io::Error::other("zoxide was not found in PATH")
+            } else {
+                io::Error::other(format!("Failed to run zoxide: {}", e))
+            }
+        })?;
+
+    // zoxide exits with an error status if nothing matches
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let paths: Vec<&str> = stdout
+        .lines()
+        .filter(|line| !line.is_empty())
+        .take(max_results)
+        .collect();
+    let total = paths.len() as i64;
+    Ok(paths
+        .into_iter()
+        .enumerate()
+        .map(|(rank, path)| FindResult {
+            path: PathBuf::from(path),
+            score: total - rank as i64,
+            content: None,
+            is_dir: true,
+        })
+        .collect())
+}
+
+/// Records a visit of `path` in the zoxide database.
+///
+/// # Errors
+/// Returns an error if zoxide could not be run or rejected the path.
+pub fn zoxide_add(path: &Path) -> io::Result<()> {
+    let status = Command::new("zoxide")
+        .args(["add", "--"])
+        .arg(path)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other("zoxide add failed"))
+    }
+}
+
+/// Opens `path` with the default application of the OS.
+///
+/// Uses the opener of the platform, see [opener](crate::core::platform::opener).
+/// The opener runs in the background, it is waited on in a separate thread.
+///
+/// # Errors
+/// Returns an error if the opener could not be started.
+pub fn open_with_system(path: &Path) -> io::Result<()> {
+    let mut child = platform::opener()
+        .arg(path)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Branch and working tree state of a git repository.
+///
+/// # Fields
+/// * `branch` - The checked out branch, or `HEAD` if detached.
+/// * `dirty` - True if there are uncommitted or untracked changes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GitInfo {
+    branch: String,
+    dirty: bool,
+}
+
+impl GitInfo {
+    pub fn branch(&self) -> &str {
+        &self.branch
+    }
+
+    pub fn dirty(&self) -> bool {
+        self.dirty
+    }
+}
+
+/// Reads the git branch and dirty state of the repository containing `dir`.
+///
+/// Runs `git status` without taking the index lock, so a concurrent git command is not disturbed.
+///
+/// # Returns
+/// None outside of a repository, or if git is not installed.
+pub fn git_info(dir: &Path) -> Option<GitInfo> {
+    let output = Command::new("git")
+        .arg("--no-optional-locks")
+        .arg("-C")
+        .arg(dir)
+        .args(["status", "--porcelain=v1", "--branch"])
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    if !output.status.success() {
+        return None;
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let mut lines = stdout.lines();
+    let header = lines.next()?.strip_prefix("## ")?;
+
+    // "main...origin/main [ahead 1]", "No commits yet on main" or "HEAD (no branch)"
+    let branch = if let Some(unborn) = header.strip_prefix("No commits yet on ") {
+        unborn
+    } else {
+        header
+            .split("...")
+            .next()
+            .and_then(|b| b.split_whitespace().next())
+            .unwrap_or("HEAD")
+    };
+
+    Some(GitInfo {
+        branch: branch.to_string(),
+        dirty: lines.next().is_some(),
+    })
+}
+
+/// Reads the git status of the entries of `dir`, for the detail view.
+///
+/// A directory gets the status of the first changed path below it. Untracked directories are
+/// reported as a whole by git; if `dir` itself is untracked, the empty name stands for all entries.
+///
+/// # Returns
+/// The status letter per entry name (`M`, `A`, `D`, `R`, `?`...), or None outside of a
+/// repository or if git is not installed.
+pub fn git_entry_status(dir: &Path) -> Option<HashMap<OsString, char>> {
+    let git = |args: &[&str]| {
+        Command::new("git")
+            .arg("--no-optional-locks")
+            .arg("-C")
+            .arg(dir)
+            .args(args)
+            .stdout(Stdio::piped())
+            .stderr(Stdio::null())
+            .output()
+            .ok()
+            .filter(|output| output.status.success())
+    };
+
+    // Porcelain paths are relative to the repository root
+    let prefix = git(&["rev-parse", "--show-prefix"])?;
+    let prefix = String::from_utf8_lossy(&prefix.stdout)
+        .trim_end()
+        .to_string();
+    let status = git(&["status", "--porcelain=v1", "-z", "--", "."])?;
+
+    let mut entries = HashMap::new();
+    let mut records = status.stdout.split(|&b| b == 0);
+    while let Some(record) = records.next() {
+        if record.len() < 4 {
+            continue;
+        }
+        let (x, y) = (record[0] as char, record[1] as char);
+        if matches!(x, 'R' | 'C') {
+            // The original path of a rename or copy follows as its own record
+            records.next();
+        }
+        let path = String::from_utf8_lossy(&record[3..]);
+        let Some(rest) = path.strip_prefix(prefix.as_str()) else {
+            continue;
+        };
+        let name = rest.split('/').next().unwrap_or_default();
+        let letter = if x != ' ' && x != '?' { x } else { y };
+        entries.entry(OsString::from(name)).or_insert(letter);
+    }
+    Some(entries)
+}
+
 /// Helpers:
 ///
 /// Normalize a relative path to use forward slashes for consistency across platforms.
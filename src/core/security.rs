@@ -0,0 +1,93 @@
+//! SELinux security contexts for runa.
+//!
+//! SELinux labels every file with a context like `unconfined_u:object_r:user_home_t:s0`,
+//! stored in the `security.selinux` extended attribute. New files inherit the type of their
+//! directory, so an entry with another type than its directory was usually moved in with `mv`
+//! or labeled by hand, the usual cause of "permission denied" despite fitting permissions.
+//!
+//! Contexts are only read on Linux. AppArmor confines programs by path and stores no label
+//! on files, so there is nothing to read for it.
+
+use std::path::Path;
+
+#[cfg(target_os = "linux")]
+const SELINUX_XATTR: &str = "security.selinux";
+
+/// The SELinux context of a file, `user:role:type:level`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SecurityContext(String);
+
+impl SecurityContext {
+    /// Parses the value of the `security.selinux` attribute, which ends in a NUL byte.
+    ///
+    /// # Returns
+    /// `None` for an empty value.
+    pub fn parse(value: &[u8]) -> Option<Self> {
+        let value = value.strip_suffix(b"\0").unwrap_or(value);
+        let context = String::from_utf8_lossy(value).trim().to_string();
+        (!context.is_empty()).then_some(SecurityContext(context))
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// The type of the context, e.g. `user_home_t`, which most policy rules are written for.
+    pub fn selinux_type(&self) -> Option<&str> {
+        self.0.split(':').nth(2)
+    }
+
+    /// Whether the type differs from `dir`, the context of the directory holding the file.
+    pub fn differs_from(&self, dir: &SecurityContext) -> bool {
+        self.selinux_type() != dir.selinux_type()
+    }
+}
+
+/// Reads the SELinux context of `path`, without following symlinks.
+///
+/// `None` if SELinux is not in use, the filesystem has no labels or on other platforms than Linux.
+pub fn read_context(path: &Path) -> Option<SecurityContext> {
+    #[cfg(target_os = "linux")]
+    {
+        xattr::read(path, SELINUX_XATTR).and_then(|value| SecurityContext::parse(&value))
+    }
+
+    #[cfg(not(target_os = "linux"))]
+    {
+        let _ = path;
+        None
+    }
+}
+
+#[cfg(target_os = "linux")]
+mod xattr {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+    use std::path::Path;
+
+    /// Reads the extended attribute `name` of `path`, without following symlinks.
+    pub(super) fn read(path: &Path, name: &str) -> Option<Vec<u8>> {
+        let path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let name = CString::new(name).ok()?;
+        // SAFETY: both strings are NUL terminated, a null buffer only asks for the size
+        let len = unsafe { libc::lgetxattr(path.as_ptr(), name.as_ptr(), std::ptr::null_mut(), 0) };
+        if len <= 0 {
+            return None;
+        }
+        let mut buf = vec![0u8; len as usize];
+        // SAFETY: the buffer is valid for `buf.len()` bytes
+        let read = unsafe {
+            libc::lgetxattr(
+                path.as_ptr(),
+                name.as_ptr(),
+                buf.as_mut_ptr().cast(),
+                buf.len(),
+            )
+        };
+        if read < 0 {
+            return None;
+        }
+        buf.truncate(read as usize);
+        Some(buf)
+    }
+}
@@ -0,0 +1,183 @@
+//! Alternate data streams and resource forks for runa.
+//!
+//! NTFS files can carry named data streams next to their contents, e.g. the `Zone.Identifier`
+//! stream Windows attaches to downloads. macOS files can carry a resource fork, which older
+//! applications and some fonts still use. Both are reachable as a path: `file:name` on Windows
+//! and `file/..namedfork/rsrc` on macOS.
+//!
+//! Copies keep the streams where the destination filesystem can hold them, see [copy_streams].
+//! Elsewhere files have no streams.
+
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// A named stream of a file, next to its main data.
+///
+/// # Fields
+/// * `name` - The name of the stream, e.g. `Zone.Identifier`, or `rsrc` for a resource fork.
+/// * `size` - The size of the stream in bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DataStream {
+    name: String,
+    size: u64,
+}
+
+impl DataStream {
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    pub fn size(&self) -> u64 {
+        self.size
+    }
+}
+
+/// The name of a stream as listed by `FindNextStreamW`, e.g. `:Zone.Identifier:$DATA`.
+///
+/// # Returns
+/// The name without the type, `None` for the main data (`::$DATA`) and streams of other types.
+pub fn parse_stream_name(raw: &str) -> Option<&str> {
+    let name = raw.strip_prefix(':')?.strip_suffix(":$DATA")?;
+    (!name.is_empty()).then_some(name)
+}
+
+/// Lists the named streams of the file at `path`, the resource fork on macOS.
+///
+/// Empty for directories, files without streams and on other platforms than Windows and macOS.
+pub fn list_streams(path: &Path) -> Vec<DataStream> {
+    #[cfg(windows)]
+    {
+        ntfs::list(path)
+    }
+
+    #[cfg(target_os = "macos")]
+    {
+        match fs::metadata(stream_path(path, "rsrc")) {
+            Ok(meta) if meta.len() > 0 => vec![DataStream {
+                name: "rsrc".to_string(),
+                size: meta.len(),
+            }],
+            _ => Vec::new(),
+        }
+    }
+
+    #[cfg(not(any(windows, target_os = "macos")))]
+    {
+        let _ = path;
+        Vec::new()
+    }
+}
+
+/// The path the stream `name` of `path` is read and written through.
+fn stream_path(path: &Path, name: &str) -> PathBuf {
+    if cfg!(windows) {
+        let mut path = path.as_os_str().to_os_string();
+        path.push(":");
+        path.push(name);
+        PathBuf::from(path)
+    } else {
+        path.join("..namedfork").join(name)
+    }
+}
+
+/// Copies the streams of `src` that `dest` does not have yet.
+///
+/// Copies by the OS usually keep the streams, this fills in the ones lost to a streamed copy.
+///
+/// # Returns
+/// The streams which could not be written, because the filesystem of `dest` cannot hold them,
+/// e.g. FAT or exFAT on Windows.
+pub fn copy_streams(src: &Path, dest: &Path) -> Vec<DataStream> {
+    let streams = list_streams(src);
+    if streams.is_empty() {
+        return streams;
+    }
+    let existing = list_streams(dest);
+    streams
+        .into_iter()
+        .filter(|stream| !existing.contains(stream))
+        .filter(|stream| copy_stream(src, dest, &stream.name).is_err())
+        .collect()
+}
+
+/// Copies one stream through plain reads and writes, the copy functions of the OS expect files.
+fn copy_stream(src: &Path, dest: &Path, name: &str) -> io::Result<u64> {
+    let mut reader = fs::File::open(stream_path(src, name))?;
+    let mut writer = fs::File::create(stream_path(dest, name))?;
+    io::copy(&mut reader, &mut writer)
+}
+
+#[cfg(windows)]
+mod ntfs {
+    use super::{DataStream, parse_stream_name};
+    use std::ffi::c_void;
+    use std::os::windows::ffi::OsStrExt;
+    use std::path::Path;
+
+    const INVALID_HANDLE_VALUE: isize = -1;
+    /// `FindStreamInfoStandard`
+    const STREAM_INFO_STANDARD: i32 = 0;
+
+    /// `WIN32_FIND_STREAM_DATA`, the name has room for `MAX_PATH + 36` characters.
+    #[repr(C)]
+    struct FindStreamData {
+        size: i64,
+        name: [u16; 296],
+    }
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn FindFirstStreamW(
+            file_name: *const u16,
+            info_level: i32,
+            data: *mut c_void,
+            flags: u32,
+        ) -> isize;
+        fn FindNextStreamW(handle: isize, data: *mut c_void) -> i32;
+        fn FindClose(handle: isize) -> i32;
+    }
+
+    pub(super) fn list(path: &Path) -> Vec<DataStream> {
+        let wide: Vec<u16> = path.as_os_str().encode_wide().chain(Some(0)).collect();
+        let mut data = FindStreamData {
+            size: 0,
+            name: [0; 296],
+        };
+        // SAFETY: the path is NUL terminated and `data` has the layout of WIN32_FIND_STREAM_DATA
+        let handle = unsafe {
+            FindFirstStreamW(
+                wide.as_ptr(),
+                STREAM_INFO_STANDARD,
+                (&mut data as *mut FindStreamData).cast(),
+                0,
+            )
+        };
+        if handle == INVALID_HANDLE_VALUE {
+            return Vec::new();
+        }
+
+        let mut streams = Vec::new();
+        loop {
+            let len = data
+                .name
+                .iter()
+                .position(|c| *c == 0)
+                .unwrap_or(data.name.len());
+            let raw = String::from_utf16_lossy(&data.name[..len]);
+            if let Some(name) = parse_stream_name(&raw) {
+                streams.push(DataStream {
+                    name: name.to_string(),
+                    size: data.size.max(0) as u64,
+                });
+            }
+            // SAFETY: the handle is valid until FindClose
+            if unsafe { FindNextStreamW(handle, (&mut data as *mut FindStreamData).cast()) } == 0 {
+                break;
+            }
+        }
+        // SAFETY: the handle came from FindFirstStreamW and is closed once
+        unsafe { FindClose(handle) };
+        streams
+    }
+}
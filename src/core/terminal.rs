@@ -61,6 +61,17 @@ where
             terminal.draw(|f| ui::render(f, app))?;
         }
 
+        // A key sequence like "g g" which waited too long for its next key
+        if let Some(result) = app.expire_key_sequence() {
+            if matches!(result, KeypressResult::Quit) {
+                break;
+            }
+            if matches!(result, KeypressResult::OpenedEditor) {
+                terminal.clear()?;
+            }
+            terminal.draw(|f| ui::render(f, app))?;
+        }
+
         // Event Polling
         if event::poll(Duration::from_millis(16))? {
             match event::read()? {
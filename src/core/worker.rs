@@ -9,107 +9,411 @@
 //! Requests [WorkerTask] come in from the AppState or UI via channels, and results or errors
 //! [WorkerResponse] go back the same way. All filesystem I/O and previews happen on these threads
 //!
+//! All task queues are bounded, see [OverflowPolicy] for what happens when one is full.
+//!
 //! # Caution:
 //! This module is a central protocol boundary. Small changes (adding or editing variants, fields, or error handling)
 //! may require corresponding changes throughout state, response-handling code and UI.
 
 use crate::config::display::PreviewMethod;
+use crate::config::{Template, WorkerOptions};
+use crate::core::highlight::parse_ansi_lines;
 use crate::core::{
-    FileEntry, FindResult, Formatter, browse_dir, find, preview_bat, safe_read_preview,
+    DirId, EntryMeta, FileDetails, FileEntry, FindKind, FindResult, Formatter, GitInfo, PathSize,
+    SearchFilters, SortExpr, TextRendering, browse_dir_chunked, create_entry, find_renamed_dir,
+    find_streaming, git_entry_status, git_info, grep_with_filters, highlighted_preview,
+    path_size_cancellable, preview_bat, read_context, remove_path, zoxide_add, zoxide_query,
+};
+use crate::utils::{
+    copy_recursive, get_unused_path, is_file_in_use, resumable_copy_offset, resume_copy,
 };
-use crate::utils::{copy_recursive, get_unused_path};
 
-use crossbeam_channel::{Receiver, Sender, bounded, unbounded};
+use crossbeam_channel::{Receiver, SendError, Sender, TrySendError, bounded, select, unbounded};
+use notify::event::ModifyKind;
+use notify::{Event, EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use ratatui::text::Line;
+use serde::Deserialize;
 
-use std::collections::HashSet;
-use std::ffi::OsString;
-use std::path::PathBuf;
-use std::sync::Arc;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::ffi::{OsStr, OsString};
+use std::io;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
 use std::thread;
+use std::time::{Duration, Instant};
+
+/// Number of entries per [WorkerResponse::MetadataLoaded] batch of the metadata worker.
+pub const METADATA_BATCH: usize = 256;
+
+/// How long the watcher collects filesystem events before reporting the changed directories.
+pub const WATCH_DEBOUNCE: Duration = Duration::from_millis(200);
 
 /// Manages worker threads channels for different task types.
 pub struct Workers {
-    io_tx: Sender<WorkerTask>,
-    find_tx: Sender<WorkerTask>,
-    preview_tx: Sender<WorkerTask>,
-    fileop_tx: Sender<WorkerTask>,
+    io_tx: TaskSender,
+    find_tx: TaskSender,
+    preview_tx: TaskSender,
+    fileop_tx: TaskSender,
+    info_tx: TaskSender,
+    watch_tx: TaskSender,
+    meta_tx: TaskSender,
+    size_tx: TaskSender,
     response_rx: Receiver<WorkerResponse>,
+    stats: Arc<WorkerStats>,
+}
+
+/// Counters updated by the worker threads, shown in the statistics overlay.
+///
+/// Relaxed atomics, the numbers are informational only.
+#[derive(Debug, Default)]
+pub struct WorkerStats {
+    dirs_loaded: AtomicU64,
+    previews_loaded: AtomicU64,
+    previews_coalesced: AtomicU64,
+    searches: AtomicU64,
+    file_ops: AtomicU64,
+    bytes_copied: AtomicU64,
+    bytes_deleted: AtomicU64,
+}
+
+impl WorkerStats {
+    fn add(counter: &AtomicU64, n: u64) {
+        counter.fetch_add(n, Ordering::Relaxed);
+    }
+
+    /// Directories read by the I/O worker, for all panes.
+    pub fn dirs_loaded(&self) -> u64 {
+        self.dirs_loaded.load(Ordering::Relaxed)
+    }
+
+    pub fn previews_loaded(&self) -> u64 {
+        self.previews_loaded.load(Ordering::Relaxed)
+    }
+
+    /// Preview requests skipped, because a newer one was already queued.
+    pub fn previews_coalesced(&self) -> u64 {
+        self.previews_coalesced.load(Ordering::Relaxed)
+    }
+
+    /// Finished find, grep and jump searches.
+    pub fn searches(&self) -> u64 {
+        self.searches.load(Ordering::Relaxed)
+    }
+
+    /// Completed file operations (delete, rename, create, paste).
+    pub fn file_ops(&self) -> u64 {
+        self.file_ops.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_copied(&self) -> u64 {
+        self.bytes_copied.load(Ordering::Relaxed)
+    }
+
+    pub fn bytes_deleted(&self) -> u64 {
+        self.bytes_deleted.load(Ordering::Relaxed)
+    }
+}
+
+/// What sending a task does when the worker queue is full.
+///
+/// Sending never waits, the UI thread must not block on a busy worker.
+///
+/// Variants:
+/// * `Backlog` - Keep the task on the sending side until [TaskSender::flush] finds room,
+///   for tasks which must not be lost. Also accepted as `block`.
+/// * `DropOldest` - Discard the oldest queued task to make room, for tasks superseded by newer ones.
+/// * `DropNewest` - Discard the task being sent.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum OverflowPolicy {
+    #[serde(alias = "block")]
+    Backlog,
+    DropOldest,
+    DropNewest,
+}
+
+/// What pasting does with entries whose name is already taken in the destination.
+///
+/// Variants:
+/// * `Ask` - Ask before pasting, the worker renames like `Rename` if it was not asked.
+/// * `Rename` - Paste under an unused name, e.g. "notes_1.txt".
+/// * `Overwrite` - Replace the existing entry, directories are replaced as a whole.
+/// * `Skip` - Leave the existing entry and do not paste the colliding one.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum PasteConflict {
+    Ask,
+    #[default]
+    Rename,
+    Overwrite,
+    Skip,
+}
+
+/// Sending half of a bounded worker queue, applying its [OverflowPolicy] when the queue is full.
+///
+/// Keeps a handle to the receiving half, so the oldest task can be dropped from the sending side.
+#[derive(Clone)]
+pub struct TaskSender {
+    tx: Sender<WorkerTask>,
+    rx: Receiver<WorkerTask>,
+    overflow: OverflowPolicy,
+    backlog: Arc<Mutex<VecDeque<WorkerTask>>>,
+}
+
+impl TaskSender {
+    /// Creates a queue holding up to `capacity` tasks, at least one.
+    fn bounded(capacity: usize, overflow: OverflowPolicy) -> (Self, Receiver<WorkerTask>) {
+        let (tx, rx) = bounded::<WorkerTask>(capacity.max(1));
+        let sender = Self {
+            tx,
+            rx: rx.clone(),
+            overflow,
+            backlog: Arc::default(),
+        };
+        (sender, rx)
+    }
+
+    /// Queues a task for the worker without waiting.
+    ///
+    /// # Errors
+    /// Returns the task if the worker thread is gone.
+    /// Tasks discarded by the [OverflowPolicy] are not an error.
+    #[allow(clippy::result_large_err)]
+    pub fn send(&self, task: WorkerTask) -> Result<(), SendError<WorkerTask>> {
+        let mut task = task;
+        match self.overflow {
+            OverflowPolicy::Backlog => {
+                let mut backlog = self.backlog.lock().unwrap_or_else(PoisonError::into_inner);
+                // Queued behind earlier tasks, so the worker gets them in order
+                if !backlog.is_empty() {
+                    backlog.push_back(task);
+                    return Ok(());
+                }
+                match self.tx.try_send(task) {
+                    Ok(()) => Ok(()),
+                    Err(TrySendError::Full(t)) => {
+                        backlog.push_back(t);
+                        Ok(())
+                    }
+                    Err(TrySendError::Disconnected(t)) => Err(SendError(t)),
+                }
+            }
+            OverflowPolicy::DropNewest => match self.tx.try_send(task) {
+                Ok(()) | Err(TrySendError::Full(_)) => Ok(()),
+                Err(TrySendError::Disconnected(t)) => Err(SendError(t)),
+            },
+            OverflowPolicy::DropOldest => loop {
+                match self.tx.try_send(task) {
+                    Ok(()) => return Ok(()),
+                    Err(TrySendError::Full(t)) => {
+                        let _ = self.rx.try_recv();
+                        task = t;
+                    }
+                    Err(TrySendError::Disconnected(t)) => return Err(SendError(t)),
+                }
+            },
+        }
+    }
+
+    /// Moves backlogged tasks into the queue as far as it has room, see [OverflowPolicy::Backlog].
+    pub fn flush(&self) {
+        let mut backlog = self.backlog.lock().unwrap_or_else(PoisonError::into_inner);
+        while let Some(task) = backlog.pop_front() {
+            match self.tx.try_send(task) {
+                Ok(()) => {}
+                Err(TrySendError::Full(t)) => {
+                    backlog.push_front(t);
+                    break;
+                }
+                // The worker is gone, nothing will take the tasks anymore
+                Err(TrySendError::Disconnected(_)) => backlog.clear(),
+            }
+        }
+    }
+
+    /// Number of tasks waiting in the queue and the backlog.
+    pub fn len(&self) -> usize {
+        let backlog = self.backlog.lock().unwrap_or_else(PoisonError::into_inner);
+        self.tx.len() + backlog.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
 }
 
 /// Manages worker thread channels for different task types.
 ///
 /// Each major operation (I/O, preview, find, file-ops) has its own dedicated worker thread.
+/// The info worker runs slow status queries (git) that must not hold up directory loads.
+/// The watch worker reports external changes to the current and parent directory.
+/// The metadata worker fills in sizes, times and git states of the detail view.
 ///
-/// The find worker uses a bounded channel of size 1: this design ensures that only the
-/// latest find request will be processed, automatically skipping obsolete queued requests
+/// The find worker also handles content searches (grep) and uses a bounded channel of size 1:
+/// this design ensures that only the latest find request will be processed, automatically skipping obsolete queued requests
 /// from rapid-fire user input. This keeps search operations efficient, responsive, and
 /// guarantees only one concurrent find per application.
+///
+/// All other queues are bounded by the [WorkerOptions], so a stuck consumer cannot pile up
+/// previews or results for the rest of the session. The UI thread never waits on a full queue
+/// (see [OverflowPolicy]), so workers waiting on a full response queue cannot deadlock with it.
 impl Workers {
-    /// Create the worker set.
+    /// Create the worker set with the default [WorkerOptions].
     ///
-    /// Spawns dedicated threads for I/O, preview, find and file operations.
+    /// Spawns dedicated threads for I/O, preview, find, file operations and status info.
     pub fn spawn() -> Self {
-        let (io_tx, io_rx) = unbounded::<WorkerTask>();
-        let (preview_tx, preview_rx) = unbounded::<WorkerTask>();
-        let (find_tx, find_rx) = bounded::<WorkerTask>(1);
-        let (fileop_tx, fileop_rx) = unbounded::<WorkerTask>();
-        let (res_tx, response_rx) = unbounded::<WorkerResponse>();
+        Self::spawn_with(&WorkerOptions::default())
+    }
 
-        start_io_worker(io_rx, res_tx.clone());
-        start_preview_worker(preview_rx, res_tx.clone());
-        start_find_worker(find_rx, res_tx.clone());
-        start_fileop_worker(fileop_rx, res_tx.clone());
+    /// Create the worker set with the queue sizes and overflow policies of `options`.
+    pub fn spawn_with(options: &WorkerOptions) -> Self {
+        let queue_size = options.queue_size();
+        let (io_tx, io_rx) = TaskSender::bounded(queue_size, OverflowPolicy::Backlog);
+        let (preview_tx, preview_rx) =
+            TaskSender::bounded(options.preview_queue_size(), options.preview_overflow());
+        let (find_tx, find_rx) = TaskSender::bounded(1, OverflowPolicy::DropOldest);
+        let (fileop_tx, fileop_rx) = TaskSender::bounded(queue_size, options.fileop_overflow());
+        let (info_tx, info_rx) = TaskSender::bounded(queue_size, OverflowPolicy::DropOldest);
+        // Only the latest set of watched directories matters
+        let (watch_tx, watch_rx) = TaskSender::bounded(1, OverflowPolicy::DropOldest);
+        // Only the metadata of the latest listing matters
+        let (meta_tx, meta_rx) = TaskSender::bounded(1, OverflowPolicy::DropOldest);
+        let (size_tx, size_rx) = TaskSender::bounded(queue_size, OverflowPolicy::DropOldest);
+        let (res_tx, response_rx) = bounded::<WorkerResponse>(options.response_queue_size());
+        let stats = Arc::new(WorkerStats::default());
+
+        start_io_worker(
+            io_rx,
+            res_tx.clone(),
+            Arc::clone(&stats),
+            options.dir_chunk_size(),
+        );
+        start_preview_worker(preview_rx, res_tx.clone(), Arc::clone(&stats));
+        start_find_worker(find_rx, res_tx.clone(), Arc::clone(&stats));
+        start_fileop_worker(fileop_rx, res_tx.clone(), Arc::clone(&stats));
+        start_info_worker(info_rx, res_tx.clone());
+        start_watch_worker(watch_rx, res_tx.clone());
+        start_metadata_worker(meta_rx, res_tx.clone());
+        start_dir_size_worker(size_rx, res_tx.clone());
 
         Self {
             io_tx,
             preview_tx,
             find_tx,
             fileop_tx,
+            info_tx,
+            watch_tx,
+            meta_tx,
+            size_tx,
             response_rx,
+            stats,
+        }
+    }
+
+    /// Hands backlogged tasks to their workers, called on every tick of the UI.
+    pub fn flush(&self) {
+        for tx in [
+            &self.io_tx,
+            &self.preview_tx,
+            &self.find_tx,
+            &self.fileop_tx,
+            &self.info_tx,
+            &self.watch_tx,
+            &self.meta_tx,
+            &self.size_tx,
+        ] {
+            tx.flush();
         }
     }
 
     /// Accessor the I/O worker task sender.
-    pub fn io_tx(&self) -> &Sender<WorkerTask> {
+    pub fn io_tx(&self) -> &TaskSender {
         &self.io_tx
     }
 
     /// Accessor for the preview worker task sender.
-    pub fn preview_tx(&self) -> &Sender<WorkerTask> {
+    pub fn preview_tx(&self) -> &TaskSender {
         &self.preview_tx
     }
 
     /// Accessor for the find worker task sender.
-    pub fn find_tx(&self) -> &Sender<WorkerTask> {
+    pub fn find_tx(&self) -> &TaskSender {
         &self.find_tx
     }
 
     /// Accessor for the file operation worker task sender.
-    pub fn fileop_tx(&self) -> &Sender<WorkerTask> {
+    pub fn fileop_tx(&self) -> &TaskSender {
         &self.fileop_tx
     }
 
+    /// Accessor for the info worker task sender.
+    pub fn info_tx(&self) -> &TaskSender {
+        &self.info_tx
+    }
+
+    /// Accessor for the filesystem watcher task sender.
+    pub fn watch_tx(&self) -> &TaskSender {
+        &self.watch_tx
+    }
+
+    /// Accessor for the metadata worker task sender.
+    pub fn meta_tx(&self) -> &TaskSender {
+        &self.meta_tx
+    }
+
+    /// Accessor for the directory size worker task sender.
+    pub fn size_tx(&self) -> &TaskSender {
+        &self.size_tx
+    }
+
     /// Accessor for the worker response receiver.
     pub fn response_rx(&self) -> &Receiver<WorkerResponse> {
         &self.response_rx
     }
+
+    /// Accessor for the counters of the worker threads.
+    pub fn stats(&self) -> &WorkerStats {
+        &self.stats
+    }
+
+    /// The number of queued tasks of each worker and of responses not yet handled, by name.
+    pub fn queue_lengths(&self) -> [(&'static str, usize); 9] {
+        [
+            ("io", self.io_tx.len()),
+            ("find", self.find_tx.len()),
+            ("preview", self.preview_tx.len()),
+            ("fileop", self.fileop_tx.len()),
+            ("info", self.info_tx.len()),
+            ("watch", self.watch_tx.len()),
+            ("meta", self.meta_tx.len()),
+            ("size", self.size_tx.len()),
+            ("responses", self.response_rx.len()),
+        ]
+    }
 }
 
 /// Tasks sent to the worker thread via channel.
 ///
 /// Each variant describes a filesystem or a preview operation to perform.
+/// Directory loads and previews carry the time they were `requested`, see [WorkerResponse].
+/// A directory load may carry the [DirId] the directory had when it was last loaded,
+/// so a directory renamed in the meantime is found again in its parent.
 pub enum WorkerTask {
     LoadDirectory {
         path: PathBuf,
         focus: Option<OsString>,
+        dir_id: Option<DirId>,
         dirs_first: bool,
+        sort_expr: Option<Arc<SortExpr>>,
         show_hidden: bool,
         show_system: bool,
         case_insensitive: bool,
         always_show: Arc<HashSet<OsString>>,
         pane_width: usize,
+        ascii_only: bool,
         request_id: u64,
+        requested: Instant,
     },
     LoadPreview {
         path: PathBuf,
@@ -117,7 +421,9 @@ pub enum WorkerTask {
         pane_width: usize,
         preview_method: PreviewMethod,
         args: Vec<OsString>,
+        params: PreviewParams,
         request_id: u64,
+        requested: Instant,
     },
     FileOp {
         op: FileOperation,
@@ -126,13 +432,86 @@ pub enum WorkerTask {
     FindRecursive {
         base_dir: PathBuf,
         query: String,
+        kind: FindKind,
+        filters: SearchFilters,
         max_results: usize,
         cancel: Arc<AtomicBool>,
         request_id: u64,
     },
+    GrepRecursive {
+        base_dir: PathBuf,
+        query: String,
+        filters: SearchFilters,
+        max_results: usize,
+        cancel: Arc<AtomicBool>,
+        request_id: u64,
+    },
+    ZoxideQuery {
+        base_dir: PathBuf,
+        query: String,
+        max_results: usize,
+        request_id: u64,
+    },
+    ZoxideAdd {
+        path: PathBuf,
+    },
+    GitStatus {
+        path: PathBuf,
+        request_id: u64,
+    },
+    /// Reads the [FileDetails] of `path` for the ShowInfo overlay.
+    /// Entries named in `excludes` are not counted.
+    FileDetails {
+        path: PathBuf,
+        excludes: Arc<HashSet<OsString>>,
+        request_id: u64,
+    },
+    /// Reads the [EntryMeta] of the named entries of `path`, and their git status if `git` is set,
+    /// their Finder tags if `tags` is set and compares their SELinux context to `path` if
+    /// `context` is set.
+    LoadMetadata {
+        path: PathBuf,
+        names: Vec<OsString>,
+        git: bool,
+        tags: bool,
+        context: bool,
+        request_id: u64,
+    },
+    /// Computes the recursive size of the named directories of `path`, until `cancel` is set.
+    /// Entries named in `excludes` are skipped.
+    DirSize {
+        path: PathBuf,
+        names: Vec<OsString>,
+        excludes: Arc<HashSet<OsString>>,
+        cancel: Arc<AtomicBool>,
+        request_id: u64,
+    },
+    /// Computes the recursive size of each marked path, until `cancel` is set.
+    /// Entries named in `excludes` are skipped.
+    MarkedSize {
+        paths: Vec<PathBuf>,
+        excludes: Arc<HashSet<OsString>>,
+        cancel: Arc<AtomicBool>,
+    },
+    /// Replaces the watched directories, an empty list stops watching.
+    Watch {
+        paths: Vec<PathBuf>,
+    },
+}
+
+/// Rendering parameters for the internal preview method.
+///
+/// # Fields
+/// * `syntax_theme` - The syntax theme used for highlighting, `None` disables highlighting.
+/// * `text` - The tab width and control character handling of file lines.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct PreviewParams {
+    pub syntax_theme: Option<String>,
+    pub text: TextRendering,
 }
 
 /// Supported file system operations the worker can perform.
+#[derive(Debug)]
 pub enum FileOperation {
     Delete(Vec<PathBuf>),
     Rename {
@@ -144,39 +523,121 @@ pub enum FileOperation {
         dest: PathBuf,
         cut: bool,
         focus: Option<OsString>,
+        resume: bool,
+        max_bytes_per_sec: Option<u64>,
+        conflict: PasteConflict,
     },
+    /// `(path, is_dir)` of each entry to create, all in one batch.
+    /// The modes are the configured permissions of new files and directories, see [create_entry].
+    /// New files matching the template are filled with its contents.
     Create {
-        path: PathBuf,
-        is_dir: bool,
+        entries: Vec<(PathBuf, bool)>,
+        file_mode: Option<u32>,
+        dir_mode: Option<u32>,
+        template: Option<Template>,
     },
 }
 
 /// Responses sent form the worker thread back to the main thread via the channel
 ///
 /// Each variant delivers the result or error from a request taks.
+///
+/// A find request may answer with several `FindResults` sharing its request_id:
+/// intermediate batches with `complete == false` while the search runs, then the final one.
+///
+/// A large directory is loaded in chunks: a `DirectoryLoaded` with `complete == false` holding
+/// the first chunk, `DirectoryChunk`s with the following entries to append, and finally a
+/// `DirectoryLoaded` with `complete == true` holding all entries in sorted order.
+/// Smaller directories are answered with the complete `DirectoryLoaded` only.
+///
+/// `DirectoryLoaded` and `PreviewLoaded` hand back the `requested` time of their task, so the
+/// UI can tell how long a load took including the time it waited in the queue.
+///
+/// `DirectoryMissing` is sent instead of an error when a directory to load no longer exists.
+///
+/// `OperationComplete` names the entry to focus afterwards, and its directory in `focus_dir`
+/// when that is not the current directory, e.g. for a file created in new subdirectories.
+///
+/// `FileBusy` is sent when files could not be deleted or renamed because another process
+/// has them open, with the part of the operation which can be retried.
+///
+/// `DirectoryChanged` is sent by the watcher when entries of a watched directory were
+/// created, removed or renamed, once per directory and debounce interval.
+///
+/// `Warning` reports an operation which succeeded but lost something on the way, e.g. the
+/// alternate data streams of a file pasted to a filesystem without them.
 #[derive(Debug)]
 pub enum WorkerResponse {
     DirectoryLoaded {
         path: PathBuf,
         entries: Vec<FileEntry>,
         focus: Option<OsString>,
+        dir_id: Option<DirId>,
+        request_id: u64,
+        complete: bool,
+        requested: Instant,
+    },
+    DirectoryChunk {
+        path: PathBuf,
+        entries: Vec<FileEntry>,
         request_id: u64,
     },
     PreviewLoaded {
         lines: Vec<String>,
+        styled: Option<Vec<Line<'static>>>,
         request_id: u64,
+        requested: Instant,
     },
     OperationComplete {
         message: String,
         request_id: u64,
         need_reload: bool,
         focus: Option<OsString>,
+        focus_dir: Option<PathBuf>,
+    },
+    FileBusy {
+        op: FileOperation,
     },
     FindResults {
         base_dir: PathBuf,
         results: Vec<FindResult>,
         request_id: u64,
+        complete: bool,
+    },
+    DirectoryMissing {
+        path: PathBuf,
+        renamed: Option<PathBuf>,
+        request_id: u64,
+    },
+    GitStatus {
+        path: PathBuf,
+        info: Option<GitInfo>,
+        request_id: u64,
+    },
+    FileDetailsLoaded {
+        path: PathBuf,
+        details: FileDetails,
+        request_id: u64,
+    },
+    MetadataLoaded {
+        path: PathBuf,
+        metadata: Vec<(OsString, EntryMeta)>,
+        request_id: u64,
     },
+    DirSizeLoaded {
+        path: PathBuf,
+        name: OsString,
+        size: PathSize,
+        request_id: u64,
+    },
+    MarkedSizeLoaded {
+        path: PathBuf,
+        size: u64,
+    },
+    DirectoryChanged {
+        path: PathBuf,
+    },
+    Warning(String),
     Error(String),
 }
 
@@ -185,38 +646,97 @@ pub enum WorkerResponse {
 /// # Arguments
 /// * `task_rx` - Receiver channel for incoming tasks
 /// * `res_tx` - Sender channel for outgoing responses
-fn start_io_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResponse>) {
+/// * `stats` - Counters shared with [Workers]
+/// * `chunk_size` - Entries per chunk of a large directory, 0 sends every directory at once
+fn start_io_worker(
+    task_rx: Receiver<WorkerTask>,
+    res_tx: Sender<WorkerResponse>,
+    stats: Arc<WorkerStats>,
+    chunk_size: usize,
+) {
     thread::spawn(move || {
         while let Ok(task) = task_rx.recv() {
+            if let WorkerTask::ZoxideAdd { path } = task {
+                let _ = zoxide_add(&path);
+                continue;
+            }
             let WorkerTask::LoadDirectory {
                 path,
                 focus,
+                dir_id: known_id,
                 dirs_first,
+                sort_expr,
                 show_hidden,
                 show_system,
                 case_insensitive,
                 always_show,
                 pane_width,
+                ascii_only,
                 request_id,
+                requested,
             } = task
             else {
                 continue;
             };
-            match browse_dir(&path) {
+            let formatter = Formatter::new(
+                dirs_first,
+                show_hidden,
+                show_system,
+                case_insensitive,
+                always_show,
+                pane_width,
+                ascii_only,
+            )
+            .with_sort_expr(sort_expr);
+            let dir_id = DirId::of(&path);
+
+            // Each chunk is sorted on its own, the final response has the whole directory sorted
+            let mut chunks_sent = 0;
+            let result = browse_dir_chunked(&path, chunk_size, |chunk| {
+                let mut entries = chunk.to_vec();
+                formatter.filter_entries(&mut entries);
+                let response = if chunks_sent == 0 {
+                    WorkerResponse::DirectoryLoaded {
+                        path: path.clone(),
+                        entries,
+                        focus: focus.clone(),
+                        dir_id,
+                        request_id,
+                        complete: false,
+                        requested,
+                    }
+                } else {
+                    WorkerResponse::DirectoryChunk {
+                        path: path.clone(),
+                        entries,
+                        request_id,
+                    }
+                };
+                let _ = res_tx.send(response);
+                chunks_sent += 1;
+            });
+
+            match result {
                 Ok(mut entries) => {
-                    let formatter = Formatter::new(
-                        dirs_first,
-                        show_hidden,
-                        show_system,
-                        case_insensitive,
-                        always_show,
-                        pane_width,
-                    );
+                    WorkerStats::add(&stats.dirs_loaded, 1);
                     formatter.filter_entries(&mut entries);
                     let _ = res_tx.send(WorkerResponse::DirectoryLoaded {
                         path,
                         entries,
                         focus,
+                        dir_id,
+                        request_id,
+                        complete: true,
+                        requested,
+                    });
+                }
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    let renamed = known_id
+                        .zip(path.parent())
+                        .and_then(|(id, parent)| find_renamed_dir(parent, id));
+                    let _ = res_tx.send(WorkerResponse::DirectoryMissing {
+                        path,
+                        renamed,
                         request_id,
                     });
                 }
@@ -233,7 +753,12 @@ fn start_io_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResponse>
 /// # Arguments
 /// * `task_rx` - Receiver channel for incoming tasks
 /// * `res_tx` - Sender channel for outgoing responses
-fn start_preview_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResponse>) {
+/// * `stats` - Counters shared with [Workers]
+fn start_preview_worker(
+    task_rx: Receiver<WorkerTask>,
+    res_tx: Sender<WorkerResponse>,
+    stats: Arc<WorkerStats>,
+) {
     thread::spawn(move || {
         while let Ok(task) = task_rx.recv() {
             let WorkerTask::LoadPreview {
@@ -242,7 +767,9 @@ fn start_preview_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResp
                 mut pane_width,
                 mut preview_method,
                 mut args,
+                mut params,
                 mut request_id,
+                mut requested,
             } = task
             else {
                 continue;
@@ -256,78 +783,404 @@ fn start_preview_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResp
                     pane_width: w,
                     preview_method: pm,
                     args: a,
+                    params: pr,
                     request_id: id,
+                    requested: r,
                 } = next
                 {
+                    WorkerStats::add(&stats.previews_coalesced, 1);
                     path = p;
                     max_lines = m;
                     pane_width = w;
                     preview_method = pm;
                     args = a;
+                    params = pr;
                     request_id = id;
+                    requested = r;
                 }
             }
 
-            let lines = match preview_method {
-                // Use internal preview method
-                PreviewMethod::Internal => safe_read_preview(&path, max_lines, pane_width),
+            let (lines, styled) = match preview_method {
+                // Use internal preview method, highlighted if a syntax theme is set
+                PreviewMethod::Internal => highlighted_preview(
+                    &path,
+                    max_lines,
+                    pane_width,
+                    params.text,
+                    params.syntax_theme.as_deref(),
+                ),
                 PreviewMethod::Bat => match preview_bat(&path, max_lines, args.as_slice()) {
                     // Bat preview succeeded
                     // If bat fails, fallback to internal preview
                     // If bat is not installed or returns error, we fallback to internal preview
-                    Ok(lines) => lines,
-                    Err(_) => safe_read_preview(&path, max_lines, pane_width),
+                    Ok(lines) => parse_ansi_lines(lines),
+                    Err(_) => highlighted_preview(&path, max_lines, pane_width, params.text, None),
                 },
             };
-            let _ = res_tx.send(WorkerResponse::PreviewLoaded { lines, request_id });
+            WorkerStats::add(&stats.previews_loaded, 1);
+            let _ = res_tx.send(WorkerResponse::PreviewLoaded {
+                lines,
+                styled,
+                request_id,
+                requested,
+            });
         }
     });
 }
 
-/// Starts the find worker thread
+/// Starts the info worker thread, answering git status and file details requests
+///
+/// Only the latest queued request of each kind is answered, as the user may have moved on already.
 ///
 /// # Arguments
 /// * `task_rx` - Receiver channel for incoming tasks
 /// * `res_tx` - Sender channel for outgoing responses
-fn start_find_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResponse>) {
+fn start_info_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResponse>) {
     thread::spawn(move || {
         while let Ok(task) = task_rx.recv() {
-            let WorkerTask::FindRecursive {
-                mut base_dir,
-                mut query,
-                mut max_results,
-                mut request_id,
-                mut cancel,
+            let mut git = None;
+            let mut details = None;
+            for task in std::iter::once(task).chain(task_rx.try_iter()) {
+                match task {
+                    WorkerTask::GitStatus { .. } => git = Some(task),
+                    WorkerTask::FileDetails { .. } => details = Some(task),
+                    _ => {}
+                }
+            }
+
+            if let Some(WorkerTask::GitStatus { path, request_id }) = git {
+                let info = git_info(&path);
+                let _ = res_tx.send(WorkerResponse::GitStatus {
+                    path,
+                    info,
+                    request_id,
+                });
+            }
+            if let Some(WorkerTask::FileDetails {
+                path,
+                excludes,
+                request_id,
+            }) = details
+            {
+                let details = FileDetails::read(&path, &excludes);
+                let _ = res_tx.send(WorkerResponse::FileDetailsLoaded {
+                    path,
+                    details,
+                    request_id,
+                });
+            }
+        }
+    });
+}
+
+/// Starts the metadata worker thread
+///
+/// Stats the entries of a [WorkerTask::LoadMetadata] and sends them back in batches of
+/// [METADATA_BATCH], so the rows of the detail view fill in while a large directory is read.
+/// A newer task aborts the current one between batches.
+///
+/// # Arguments
+/// * `task_rx` - Receiver channel for incoming tasks
+/// * `res_tx` - Sender channel for outgoing responses
+fn start_metadata_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResponse>) {
+    thread::spawn(move || {
+        while let Ok(task) = task_rx.recv() {
+            let WorkerTask::LoadMetadata {
+                path,
+                names,
+                git,
+                tags,
+                context,
+                request_id,
             } = task
             else {
                 continue;
             };
 
-            while let Ok(next) = task_rx.try_recv() {
-                if let WorkerTask::FindRecursive {
-                    base_dir: base,
-                    query: q,
-                    max_results: max,
-                    request_id: id,
-                    cancel: c,
-                } = next
-                {
-                    base_dir = base;
-                    query = q;
-                    max_results = max;
-                    request_id = id;
-                    cancel = c;
+            let git_status = if git {
+                git_entry_status(&path).unwrap_or_default()
+            } else {
+                HashMap::new()
+            };
+            // An untracked current directory is reported as a whole
+            let dir_status = git_status.get(OsStr::new("")).copied();
+            let dir_context = context.then(|| read_context(&path)).flatten();
+
+            for batch in names.chunks(METADATA_BATCH) {
+                if !task_rx.is_empty() {
+                    break;
                 }
+                let metadata = batch
+                    .iter()
+                    .map(|name| {
+                        let git = git_status.get(name).copied().or(dir_status);
+                        let meta =
+                            EntryMeta::read(&path.join(name), git, tags, dir_context.as_ref());
+                        (name.clone(), meta)
+                    })
+                    .collect();
+                let _ = res_tx.send(WorkerResponse::MetadataLoaded {
+                    path: path.clone(),
+                    metadata,
+                    request_id,
+                });
             }
+        }
+    });
+}
 
-            let mut results = Vec::new();
-            let _ = find(
-                &base_dir,
-                &query,
-                &mut results,
-                Arc::clone(&cancel),
+/// Starts the directory size worker thread
+///
+/// Walks the directories of a [WorkerTask::DirSize], or the marked paths of a
+/// [WorkerTask::MarkedSize], one after another and sends each size as soon as it is known.
+/// The walk stops once the cancel token of the task is set.
+///
+/// # Arguments
+/// * `task_rx` - Receiver channel for incoming tasks
+/// * `res_tx` - Sender channel for outgoing responses
+fn start_dir_size_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResponse>) {
+    thread::spawn(move || {
+        while let Ok(task) = task_rx.recv() {
+            match task {
+                WorkerTask::DirSize {
+                    path,
+                    names,
+                    excludes,
+                    cancel,
+                    request_id,
+                } => {
+                    for name in names {
+                        let Some(size) =
+                            path_size_cancellable(&path.join(&name), &excludes, &cancel)
+                        else {
+                            break;
+                        };
+                        let _ = res_tx.send(WorkerResponse::DirSizeLoaded {
+                            path: path.clone(),
+                            name,
+                            size,
+                            request_id,
+                        });
+                    }
+                }
+                WorkerTask::MarkedSize {
+                    paths,
+                    excludes,
+                    cancel,
+                } => {
+                    for path in paths {
+                        let Some(size) = path_size_cancellable(&path, &excludes, &cancel) else {
+                            break;
+                        };
+                        let _ = res_tx.send(WorkerResponse::MarkedSizeLoaded {
+                            path,
+                            size: size.bytes(),
+                        });
+                    }
+                }
+                _ => {}
+            }
+        }
+    });
+}
+
+/// Starts the filesystem watcher thread
+///
+/// Watches the directories of the latest [WorkerTask::Watch] (not recursively) with `notify`.
+/// Events are collected for [WATCH_DEBOUNCE], so a burst like an extracted archive
+/// results in a single [WorkerResponse::DirectoryChanged] per directory.
+///
+/// The watcher is created on the first watch task, if the platform does not support
+/// it the task is ignored and only the periodic refresh remains.
+///
+/// # Arguments
+/// * `task_rx` - Receiver channel for incoming tasks
+/// * `res_tx` - Sender channel for outgoing responses
+fn start_watch_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResponse>) {
+    thread::spawn(move || {
+        let (event_tx, event_rx) = unbounded::<notify::Result<Event>>();
+        let mut watcher: Option<RecommendedWatcher> = None;
+        let mut watched: Vec<PathBuf> = Vec::new();
+        let mut changed: Vec<PathBuf> = Vec::new();
+        let mut deadline: Option<Instant> = None;
+
+        loop {
+            let timeout = deadline.map_or(Duration::from_secs(3600), |d| {
+                d.saturating_duration_since(Instant::now())
+            });
+            select! {
+                recv(task_rx) -> task => {
+                    let Ok(task) = task else {
+                        break;
+                    };
+                    let WorkerTask::Watch { paths } = task else {
+                        continue;
+                    };
+                    if watcher.is_none() {
+                        let tx = event_tx.clone();
+                        watcher = notify::recommended_watcher(move |res| {
+                            let _ = tx.send(res);
+                        })
+                        .ok();
+                    }
+                    let Some(watcher) = watcher.as_mut() else {
+                        continue;
+                    };
+                    for path in watched.drain(..) {
+                        let _ = watcher.unwatch(&path);
+                    }
+                    for path in paths {
+                        if watcher.watch(&path, RecursiveMode::NonRecursive).is_ok() {
+                            watched.push(path);
+                        }
+                    }
+                    changed.clear();
+                    deadline = None;
+                }
+                recv(event_rx) -> event => {
+                    let Ok(Ok(event)) = event else {
+                        continue;
+                    };
+                    if matches!(
+                        event.kind,
+                        EventKind::Access(_)
+                            | EventKind::Modify(ModifyKind::Data(_) | ModifyKind::Metadata(_))
+                    ) {
+                        continue;
+                    }
+                    for path in &event.paths {
+                        let dir = watched
+                            .iter()
+                            .find(|w| path.parent() == Some(w.as_path()) || path == *w);
+                        if let Some(dir) = dir
+                            && !changed.contains(dir)
+                        {
+                            changed.push(dir.clone());
+                        }
+                    }
+                    if !changed.is_empty() && deadline.is_none() {
+                        deadline = Some(Instant::now() + WATCH_DEBOUNCE);
+                    }
+                }
+                default(timeout) => {
+                    deadline = None;
+                    for path in changed.drain(..) {
+                        let _ = res_tx.send(WorkerResponse::DirectoryChanged { path });
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Starts the find worker thread
+///
+/// # Arguments
+/// * `task_rx` - Receiver channel for incoming tasks
+/// * `res_tx` - Sender channel for outgoing responses
+/// * `stats` - Counters shared with [Workers]
+fn start_find_worker(
+    task_rx: Receiver<WorkerTask>,
+    res_tx: Sender<WorkerResponse>,
+    stats: Arc<WorkerStats>,
+) {
+    thread::spawn(move || {
+        while let Ok(mut task) = task_rx.recv() {
+            // Only the latest find or grep request is relevant
+            while let Ok(next) = task_rx.try_recv() {
+                task = next;
+            }
+
+            // Jump queries are answered at once, zoxide does the matching and ranking
+            if let WorkerTask::ZoxideQuery {
+                base_dir,
+                query,
                 max_results,
-            );
+                request_id,
+            } = task
+            {
+                let results = zoxide_query(&query, max_results).unwrap_or_default();
+                WorkerStats::add(&stats.searches, 1);
+                let _ = res_tx.send(WorkerResponse::FindResults {
+                    base_dir,
+                    results,
+                    request_id,
+                    complete: true,
+                });
+                continue;
+            }
+
+            let (base_dir, query, kind, filters, max_results, cancel, request_id, is_grep) =
+                match task {
+                    WorkerTask::FindRecursive {
+                        base_dir,
+                        query,
+                        kind,
+                        filters,
+                        max_results,
+                        cancel,
+                        request_id,
+                    } => (
+                        base_dir,
+                        query,
+                        kind,
+                        filters,
+                        max_results,
+                        cancel,
+                        request_id,
+                        false,
+                    ),
+                    WorkerTask::GrepRecursive {
+                        base_dir,
+                        query,
+                        filters,
+                        max_results,
+                        cancel,
+                        request_id,
+                    } => (
+                        base_dir,
+                        query,
+                        FindKind::Files,
+                        filters,
+                        max_results,
+                        cancel,
+                        request_id,
+                        true,
+                    ),
+                    _ => continue,
+                };
+
+            let mut results = Vec::new();
+            if is_grep {
+                let _ = grep_with_filters(
+                    &base_dir,
+                    &query,
+                    &filters,
+                    &mut results,
+                    Arc::clone(&cancel),
+                    max_results,
+                );
+            } else {
+                let _ = find_streaming(
+                    &base_dir,
+                    &query,
+                    kind,
+                    &filters,
+                    &mut results,
+                    Arc::clone(&cancel),
+                    max_results,
+                    |batch| {
+                        if !cancel.load(Ordering::Acquire) {
+                            let _ = res_tx.send(WorkerResponse::FindResults {
+                                base_dir: base_dir.clone(),
+                                results: batch,
+                                request_id,
+                                complete: false,
+                            });
+                        }
+                    },
+                );
+            }
             if results.len() > max_results {
                 results.truncate(max_results);
             }
@@ -336,10 +1189,12 @@ fn start_find_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerRespons
                 continue;
             }
 
+            WorkerStats::add(&stats.searches, 1);
             let _ = res_tx.send(WorkerResponse::FindResults {
                 base_dir,
                 results,
                 request_id,
+                complete: true,
             });
         }
     });
@@ -350,25 +1205,34 @@ fn start_find_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerRespons
 /// # Arguments
 /// * `task_rx` - Receiver channel for incoming tasks
 /// * `res_tx` - Sender channel for outgoing responses
-fn start_fileop_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerResponse>) {
+/// * `stats` - Counters shared with [Workers]
+fn start_fileop_worker(
+    task_rx: Receiver<WorkerTask>,
+    res_tx: Sender<WorkerResponse>,
+    stats: Arc<WorkerStats>,
+) {
     thread::spawn(move || {
         while let Ok(task) = task_rx.recv() {
             let WorkerTask::FileOp { op, request_id } = task else {
                 continue;
             };
             let mut focus_target: Option<OsString> = None;
+            let mut focus_dir: Option<PathBuf> = None;
+            let mut busy: Option<FileOperation> = None;
+            let mut warning = None;
             let result: Result<String, String> = match op {
                 FileOperation::Delete(paths) => {
+                    let mut busy_paths = Vec::new();
                     for p in paths {
-                        let res = if p.is_dir() {
-                            std::fs::remove_dir_all(&p)
-                        } else {
-                            std::fs::remove_file(&p)
-                        };
-                        if let Err(e) = res {
-                            eprintln!("Failed to delete {}: {}", p.display(), e);
+                        match remove_path(&p) {
+                            Ok(size) => WorkerStats::add(&stats.bytes_deleted, size),
+                            Err(e) if is_file_in_use(&e) => busy_paths.push(p),
+                            Err(e) => eprintln!("Failed to delete {}: {}", p.display(), e),
                         }
                     }
+                    if !busy_paths.is_empty() {
+                        busy = Some(FileOperation::Delete(busy_paths));
+                    }
                     Ok("Items deleted".to_string())
                 }
                 FileOperation::Rename { old, new } => {
@@ -381,36 +1245,136 @@ fn start_fileop_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerRespo
                         ))
                     } else {
                         focus_target = target.file_name().map(|n| n.to_os_string());
-                        std::fs::rename(old, &target)
-                            .map(|_| "Renamed".into())
-                            .map_err(|e| e.to_string())
+                        match std::fs::rename(&old, &target) {
+                            Ok(()) => Ok("Renamed".into()),
+                            Err(e) if is_file_in_use(&e) => {
+                                let msg = format!(
+                                    "Rename failed: '{}' is in use by another process",
+                                    old.file_name().unwrap_or_default().to_string_lossy()
+                                );
+                                busy = Some(FileOperation::Rename { old, new: target });
+                                Err(msg)
+                            }
+                            Err(e) => Err(e.to_string()),
+                        }
                     }
                 }
-                FileOperation::Create { path, is_dir } => {
-                    let target = get_unused_path(&path);
-                    focus_target = target.file_name().map(|n| n.to_os_string());
-
-                    let res = if is_dir {
-                        std::fs::create_dir_all(&target)
-                    } else {
-                        std::fs::OpenOptions::new()
-                            .write(true)
-                            .create_new(true)
-                            .open(&target)
-                            .map(|_| ())
+                FileOperation::Create {
+                    entries,
+                    file_mode,
+                    dir_mode,
+                    template,
+                } => {
+                    let count = entries.len();
+                    let mut failed = None;
+                    let contents = match template.as_ref().map(Template::contents) {
+                        Some(Err(e)) => {
+                            failed = Some(format!("Template: {}", e));
+                            None
+                        }
+                        Some(Ok(contents)) => Some(contents),
+                        None => None,
                     };
-                    res.map(|_| "Created".into()).map_err(|e| e.to_string())
+                    for (path, is_dir) in entries {
+                        let fill = contents.as_ref().filter(|_| {
+                            !is_dir
+                                && template.as_ref().is_some_and(|t| {
+                                    t.matches(
+                                        &path.file_name().unwrap_or_default().to_string_lossy(),
+                                    )
+                                })
+                        });
+                        let target = get_unused_path(&path);
+                        let mode = if is_dir { dir_mode } else { file_mode };
+                        // Missing directories of nested paths like `src/bin/tool.rs` are created
+                        let parent = target.parent().map(Path::to_path_buf);
+                        let created = match &parent {
+                            Some(parent) if !is_dir && !parent.exists() => {
+                                create_entry(parent, true, dir_mode)
+                            }
+                            _ => Ok(()),
+                        }
+                        .and_then(|()| create_entry(&target, is_dir, mode))
+                        .and_then(|()| match fill {
+                            Some(contents) => std::fs::write(&target, contents),
+                            None => Ok(()),
+                        });
+                        match created {
+                            Ok(()) if focus_target.is_none() => {
+                                focus_target = target.file_name().map(|n| n.to_os_string());
+                                focus_dir = parent;
+                            }
+                            Ok(()) => {}
+                            Err(e) => {
+                                failed.get_or_insert(format!("{}: {}", target.display(), e));
+                            }
+                        }
+                    }
+                    match failed {
+                        Some(e) => Err(e),
+                        None if count > 1 => Ok(format!("Created {} entries", count)),
+                        None => Ok("Created".into()),
+                    }
                 }
                 FileOperation::Copy {
                     src,
                     dest,
                     cut,
                     focus,
+                    resume,
+                    max_bytes_per_sec,
+                    conflict,
                 } => {
                     focus_target = focus;
+                    let mut failed = None;
+                    let mut lost_streams = Vec::new();
                     for s in src {
                         if let Some(name) = s.file_name() {
-                            let target = get_unused_path(&dest.join(name));
+                            // Append to an interrupted copy instead of creating a new one
+                            let existing = dest.join(name);
+                            if resume
+                                && !cut
+                                && let Some(offset) = resumable_copy_offset(&s, &existing)
+                            {
+                                match resume_copy(&s, &existing, offset, max_bytes_per_sec) {
+                                    Ok(n) => WorkerStats::add(&stats.bytes_copied, n),
+                                    Err(e) => {
+                                        failed.get_or_insert(format!(
+                                            "{}: {}",
+                                            existing.display(),
+                                            e
+                                        ));
+                                    }
+                                }
+                                continue;
+                            }
+
+                            // Pasting into the directory of the source always makes a copy,
+                            // and an entry containing the source is never replaced
+                            let existing = dest.join(name);
+                            let target = if s.starts_with(&existing)
+                                || existing.symlink_metadata().is_err()
+                            {
+                                get_unused_path(&existing)
+                            } else {
+                                match conflict {
+                                    PasteConflict::Skip => continue,
+                                    PasteConflict::Overwrite => {
+                                        if let Err(e) = remove_path(&existing) {
+                                            failed.get_or_insert(format!(
+                                                "{}: {}",
+                                                existing.display(),
+                                                e
+                                            ));
+                                            continue;
+                                        }
+                                        existing
+                                    }
+                                    PasteConflict::Ask | PasteConflict::Rename => {
+                                        get_unused_path(&existing)
+                                    }
+                                }
+                            };
 
                             if let Some(ref ft) = focus_target
                                 && ft == name
@@ -418,32 +1382,60 @@ fn start_fileop_worker(task_rx: Receiver<WorkerTask>, res_tx: Sender<WorkerRespo
                                 focus_target = target.file_name().map(|n| n.to_os_string());
                             }
 
-                            let _ = if cut {
-                                std::fs::rename(s, &target)
-                            } else if s.is_dir() {
-                                copy_recursive(&s, &target)
-                            } else {
-                                std::fs::copy(s, &target).map(|_| ())
-                            };
+                            if cut {
+                                let _ = std::fs::rename(s, &target);
+                            } else if let Ok(n) =
+                                copy_recursive(&s, &target, max_bytes_per_sec, &mut lost_streams)
+                            {
+                                WorkerStats::add(&stats.bytes_copied, n);
+                            }
                         }
                     }
-                    Ok("Pasted".into())
+                    if let Some(first) = lost_streams.first() {
+                        let what = if cfg!(target_os = "macos") {
+                            "resource forks"
+                        } else {
+                            "alternate data streams"
+                        };
+                        let files = match lost_streams.len() {
+                            1 => first.display().to_string(),
+                            n => format!("{} and {} more", first.display(), n - 1),
+                        };
+                        warning = Some(format!(
+                            "The destination cannot hold {}, not copied for {}",
+                            what, files
+                        ));
+                    }
+                    match failed {
+                        Some(e) => Err(e),
+                        None => Ok("Pasted".into()),
+                    }
                 }
             };
 
             match result {
                 Ok(msg) => {
+                    WorkerStats::add(&stats.file_ops, 1);
                     let _ = res_tx.send(WorkerResponse::OperationComplete {
                         message: msg,
                         request_id,
                         need_reload: true,
                         focus: focus_target,
+                        focus_dir,
                     });
                 }
+                // The retry prompt reports files in use
+                Err(_) if busy.is_some() => {}
                 Err(e) => {
                     let _ = res_tx.send(WorkerResponse::Error(format!("Op Error: {}", e)));
                 }
             }
+            if let Some(op) = busy {
+                let _ = res_tx.send(WorkerResponse::FileBusy { op });
+            }
+            if let Some(warning) = warning {
+                let _ = res_tx.send(WorkerResponse::Warning(warning));
+            }
         }
     });
 }
@@ -2,17 +2,74 @@
 //! Entry point for runa
 
 use runa_tui::app;
+use runa_tui::app::tutorial::create_playground;
 use runa_tui::config::Config;
-use runa_tui::core::terminal;
-use runa_tui::utils::cli::{CliAction, handle_args};
+use runa_tui::core::{platform, terminal};
+use runa_tui::utils::cli::{Cli, CliAction, EXIT_ERROR, handle_args};
+use runa_tui::utils::{root_log_path, termux};
+use std::process::ExitCode;
 
-fn main() -> std::io::Result<()> {
-    match handle_args() {
-        CliAction::Exit => return Ok(()),
-        CliAction::RunApp => (),
+fn main() -> ExitCode {
+    let cli = handle_args();
+    if let CliAction::Exit(code) = cli.action {
+        return code;
     }
+    match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            ExitCode::from(EXIT_ERROR)
+        }
+    }
+}
 
+/// Runs the TUI for the action chosen on the command line.
+fn run(cli: Cli) -> std::io::Result<()> {
     let config = Config::load();
-    let mut app = app::AppState::new(&config)?;
-    terminal::run_terminal(&mut app)
+    if config.termux() {
+        termux::limit_threads();
+    }
+    if let CliAction::Tutorial = cli.action {
+        return run_tutorial(config);
+    }
+
+    let mut app = app::AppState::new(config)?;
+    if cli.readonly {
+        app.set_readonly(true);
+    }
+    if app.config().root_guard() && platform::is_root() {
+        app.enable_root_guard(root_log_path());
+    }
+    if let CliAction::Find(query) = cli.action {
+        app.start_find(query);
+    }
+    let result = terminal::run_terminal(&mut app);
+    if cli.dump_state {
+        dump_state(&app);
+    }
+    result
+}
+
+/// Writes a state snapshot for `--dump-state`, also after a terminal error.
+fn dump_state(app: &app::AppState) {
+    let Some(dir) = app::snapshot_dir() else {
+        eprintln!("Error: cannot write the state, no home directory");
+        return;
+    };
+    match app.dump_state(&dir) {
+        Ok(path) => eprintln!("State written to {}", path.display()),
+        Err(e) => eprintln!("Error: cannot write the state: {}", e),
+    }
+}
+
+/// Runs runa with the tutorial in a playground directory, which is removed afterwards.
+fn run_tutorial(config: Config) -> std::io::Result<()> {
+    let playground = create_playground()?;
+    let result = {
+        let mut app = app::AppState::with_dir(config, playground.clone());
+        app.start_tutorial();
+        terminal::run_terminal(&mut app)
+    };
+    let _ = std::fs::remove_dir_all(&playground);
+    result
 }
@@ -9,8 +9,14 @@
 //! - render: Main entry point for rendering the entire UI to a frame.
 //! - layout_chunks: Utility for calculating pane positions and widths based on config.
 //!
-//! See submodules [panes] and [widgets] for detailed drawing functions.
+//! See submodules [panes] and [widgets] for detailed drawing functions,
+//! and [animation] for dialog and overlay transitions.
+//! [browser] packages the panes as the embeddable [browser::RunaBrowser] widget.
+//! [filestyle] colors the entries by file type and extension.
 
+pub mod animation;
+pub mod browser;
+pub mod filestyle;
 pub mod icons;
 pub mod overlays;
 pub mod panes;
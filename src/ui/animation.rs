@@ -0,0 +1,268 @@
+//! Open/close transitions for dialogs and overlays.
+//!
+//! The [Animator] works on the rendered buffer only: the renderer snapshots the buffer
+//! before drawing the topmost layer (input dialog, info box or message toast),
+//! and the animator then reveals that layer growing from its anchor and fading in via dim steps.
+//!
+//! When a layer closes, the last drawn cells of that layer are kept and played back shrinking.
+//!
+//! Enabled with `animations = true` in the config.
+
+use crate::app::actions::InputMode;
+use ratatui::buffer::Buffer;
+use ratatui::layout::Rect;
+use ratatui::style::{Modifier, Style};
+use std::time::{Duration, Instant};
+
+/// Length of one open or close transition.
+pub const ANIMATION_DURATION: Duration = Duration::from_millis(140);
+
+/// Progress until which an animated layer is drawn dimmed.
+const FADE_STEP: f32 = 0.5;
+
+/// Identifies the topmost layer drawn above the panes.
+///
+/// Variants:
+/// * `Input` - An input, confirm or find dialog.
+/// * `Overlay` - The overlay stack, by its depth.
+#[derive(Clone, Copy, PartialEq)]
+pub enum LayerKey {
+    Input(InputMode),
+    Overlay(usize),
+}
+
+impl LayerKey {
+    fn depth(key: Option<LayerKey>) -> usize {
+        match key {
+            None => 0,
+            Some(LayerKey::Input(_)) => 1,
+            Some(LayerKey::Overlay(n)) => 1 + n,
+        }
+    }
+}
+
+/// A running open or close transition.
+#[derive(Debug, Clone, Copy)]
+struct Transition {
+    start: Instant,
+    closing: bool,
+}
+
+impl Transition {
+    /// Eased progress in the range 0.0..=1.0.
+    fn progress(&self, now: Instant) -> f32 {
+        let t = now.saturating_duration_since(self.start).as_secs_f32()
+            / ANIMATION_DURATION.as_secs_f32();
+        let t = t.clamp(0.0, 1.0);
+        1.0 - (1.0 - t).powi(3)
+    }
+}
+
+/// Tracks the topmost layer between frames and drives its transitions.
+///
+/// # Fields
+/// * `current` - The topmost layer of the last frame.
+/// * `transition` - The running transition, if any.
+/// * `last_layer` - The cells of the last drawn topmost layer, used to play the close transition.
+#[derive(Default)]
+pub struct Animator {
+    current: Option<LayerKey>,
+    transition: Option<Transition>,
+    last_layer: Option<Buffer>,
+}
+
+impl Animator {
+    /// Returns true while a transition needs further frames.
+    pub fn is_running(&self) -> bool {
+        self.transition.is_some()
+    }
+
+    /// Returns true if the frame needs a copy of the buffer before the topmost layer:
+    /// while a transition runs and until the open layer has been captured once.
+    pub fn needs_snapshot(&self) -> bool {
+        self.transition.is_some() || (self.current.is_some() && self.last_layer.is_none())
+    }
+
+    /// Returns true once the background of the opening layer may be dimmed.
+    /// The dimming is held back for the first fade step.
+    pub fn background_dimmed(&self, now: Instant) -> bool {
+        match self.transition {
+            Some(t) if !t.closing => t.progress(now) >= FADE_STEP,
+            _ => true,
+        }
+    }
+
+    /// Registers the topmost layer of the frame about to be drawn and starts a
+    /// transition if it changed.
+    ///
+    /// # Arguments
+    /// * `key` - The topmost layer, or None if only the panes are drawn.
+    /// * `now` - The frame time.
+    pub fn update(&mut self, key: Option<LayerKey>, now: Instant) {
+        if key == self.current {
+            return;
+        }
+
+        let opening = key.is_some() && LayerKey::depth(key) >= LayerKey::depth(self.current);
+        if opening {
+            self.transition = Some(Transition {
+                start: now,
+                closing: false,
+            });
+        } else if self.last_layer.is_some() {
+            self.transition = Some(Transition {
+                start: now,
+                closing: true,
+            });
+        }
+        self.current = key;
+    }
+
+    /// Animates the topmost layer in `buf`.
+    ///
+    /// # Arguments
+    /// * `buf` - The frame buffer with the topmost layer drawn.
+    /// * `before` - A copy of the frame buffer taken before the topmost layer was drawn.
+    /// * `area` - The area runa is drawn in, the layer is looked for and animated inside it only.
+    /// * `now` - The frame time.
+    pub fn apply(&mut self, buf: &mut Buffer, before: &Buffer, area: Rect, now: Instant) {
+        let area = area.intersection(buf.area);
+        let Some(transition) = self.transition else {
+            self.last_layer = self.current.and_then(|_| changed_region(buf, before, area));
+            return;
+        };
+
+        let progress = transition.progress(now);
+        if transition.closing {
+            if let Some(layer) = &self.last_layer {
+                draw_closing(buf, layer, area, 1.0 - progress);
+            }
+        } else {
+            self.last_layer = changed_region(buf, before, area);
+            if let Some(layer) = &self.last_layer {
+                draw_opening(buf, before, layer.area, area, progress);
+            }
+        }
+
+        if progress >= 1.0 {
+            self.transition = None;
+            if transition.closing {
+                self.last_layer = None;
+            }
+        }
+    }
+
+    /// Updates the kept cells of the open layer from `buf` while no transition runs,
+    /// so closing plays back the last drawn layer without a buffer snapshot.
+    pub fn refresh(&mut self, buf: &Buffer) {
+        let Some(layer) = &mut self.last_layer else {
+            return;
+        };
+        let area = layer.area.intersection(buf.area);
+        for y in area.top()..area.bottom() {
+            for x in area.left()..area.right() {
+                layer[(x, y)] = buf[(x, y)].clone();
+            }
+        }
+    }
+}
+
+/// Scales `full` by `progress`, growing from the side of `area` it is anchored to.
+/// Centered rects grow from their center, rects at an edge grow from that edge.
+///
+/// # Arguments
+/// * `full` - The rect of the fully drawn layer.
+/// * `area` - The area the layer is positioned in.
+/// * `progress` - 0.0 (collapsed) to 1.0 (full size).
+///
+/// # Returns
+/// The scaled rect, at least one cell large.
+pub fn grow_rect(full: Rect, area: Rect, progress: f32) -> Rect {
+    let progress = progress.clamp(0.0, 1.0);
+    let width = ((full.width as f32 * progress).round() as u16).clamp(1, full.width.max(1));
+    let height = ((full.height as f32 * progress).round() as u16).clamp(1, full.height.max(1));
+
+    let anchor = |pos: u16, size: u16, start: u16, total: u16| {
+        let free = total.saturating_sub(size);
+        if free == 0 {
+            0.5
+        } else {
+            (pos.saturating_sub(start) as f32 / free as f32).clamp(0.0, 1.0)
+        }
+    };
+    let fx = anchor(full.x, full.width, area.x, area.width);
+    let fy = anchor(full.y, full.height, area.y, area.height);
+
+    Rect {
+        x: full.x + ((full.width - width) as f32 * fx).round() as u16,
+        y: full.y + ((full.height - height) as f32 * fy).round() as u16,
+        width,
+        height,
+    }
+}
+
+/// Copies the bounding box of all cells in `area` that differ between `buf` and `before`.
+fn changed_region(buf: &Buffer, before: &Buffer, area: Rect) -> Option<Buffer> {
+    if buf.area != before.area {
+        return None;
+    }
+
+    let (mut min_x, mut min_y, mut max_x, mut max_y) = (u16::MAX, u16::MAX, 0, 0);
+    for y in area.top()..area.bottom() {
+        for x in area.left()..area.right() {
+            if buf[(x, y)] != before[(x, y)] {
+                min_x = min_x.min(x);
+                min_y = min_y.min(y);
+                max_x = max_x.max(x);
+                max_y = max_y.max(y);
+            }
+        }
+    }
+    if min_x > max_x || min_y > max_y {
+        return None;
+    }
+
+    let region = Rect::new(min_x, min_y, max_x - min_x + 1, max_y - min_y + 1);
+    let mut layer = Buffer::empty(region);
+    for y in region.top()..region.bottom() {
+        for x in region.left()..region.right() {
+            layer[(x, y)] = buf[(x, y)].clone();
+        }
+    }
+    Some(layer)
+}
+
+/// Restores the cells of `full` outside the grown rect and dims the rest during the fade step.
+fn draw_opening(buf: &mut Buffer, before: &Buffer, full: Rect, area: Rect, progress: f32) {
+    let shown = grow_rect(full, area, progress);
+    for y in full.top()..full.bottom() {
+        for x in full.left()..full.right() {
+            if !shown.contains((x, y).into()) {
+                buf[(x, y)] = before[(x, y)].clone();
+            }
+        }
+    }
+    if progress < FADE_STEP {
+        buf.set_style(shown, Style::default().add_modifier(Modifier::DIM));
+    }
+}
+
+/// Draws the kept cells of a closed layer, shrunk to `progress` and dimmed once faded past the step.
+fn draw_closing(buf: &mut Buffer, layer: &Buffer, area: Rect, progress: f32) {
+    if progress <= 0.0 {
+        return;
+    }
+    let full = layer.area.intersection(area);
+    if full.is_empty() {
+        return;
+    }
+    let shown = grow_rect(full, area, progress);
+    for y in shown.top()..shown.bottom() {
+        for x in shown.left()..shown.right() {
+            buf[(x, y)] = layer[(x, y)].clone();
+        }
+    }
+    if progress < FADE_STEP {
+        buf.set_style(shown, Style::default().add_modifier(Modifier::DIM));
+    }
+}
@@ -0,0 +1,170 @@
+//! Embeddable file browser widget.
+//!
+//! [RunaBrowser] draws runa's parent, main and preview panes (plus its dialogs and overlays)
+//! into an area of another ratatui application. [RunaBrowserState] owns the [AppState],
+//! including the worker threads, and translates key events into [BrowserEvent]s,
+//! so it can be used as a directory or file picker.
+//!
+//! Everything is drawn inside the area passed to [RunaBrowser::render], the rest of the frame
+//! is left to the host. Editor and shell launches (`open_with_editor`, custom commands or the `shell` key)
+//! are the exception: they take over the whole terminal, leaving raw mode and the alternate screen
+//! while they run, and restore both before [RunaBrowserState::handle_key] returns
+//! [BrowserEvent::Resumed]. The host should then clear its terminal before the next draw.
+//!
+//! Usage in a host event loop:
+//! ```no_run
+//! use runa_tui::config::Config;
+//! use runa_tui::ui::browser::{BrowserEvent, RunaBrowser, RunaBrowserState};
+//!
+//! # fn event_loop(terminal: &mut ratatui::DefaultTerminal) -> std::io::Result<()> {
+//! let config = Config::default();
+//! let mut state = RunaBrowserState::new(config)?;
+//! loop {
+//!     if state.tick() {
+//!         terminal.draw(|f| RunaBrowser::new().render(f, f.area(), &mut state))?;
+//!     }
+//!     if let crossterm::event::Event::Key(key) = crossterm::event::read()? {
+//!         match state.handle_key(key) {
+//!             BrowserEvent::Picked(path) => println!("{}", path.display()),
+//!             BrowserEvent::Cancelled => break,
+//!             BrowserEvent::Resumed => terminal.clear()?,
+//!             BrowserEvent::Continue => {}
+//!         }
+//!         terminal.draw(|f| RunaBrowser::new().render(f, f.area(), &mut state))?;
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::app::{AppState, KeypressResult};
+use crate::config::Config;
+use crate::ui::render::render_in;
+use crossterm::event::{KeyEvent, KeyEventKind};
+use ratatui::Frame;
+use ratatui::layout::Rect;
+use std::io;
+use std::path::{Path, PathBuf};
+
+/// Result of a key event handled by [RunaBrowserState::handle_key].
+///
+/// Variants:
+/// * `Continue` - The key was handled (or ignored), the browser stays open.
+/// * `Picked` - The open key was pressed on a file, holds its path.
+/// * `Cancelled` - The quit key was pressed.
+/// * `Resumed` - An editor or shell ran in the terminal and has exited, the terminal needs a full redraw.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BrowserEvent {
+    Continue,
+    Picked(PathBuf),
+    Cancelled,
+    Resumed,
+}
+
+/// State of an embedded [RunaBrowser].
+///
+/// Wraps runa's [AppState], so navigation, filtering, find and previews behave as in `rn`.
+pub struct RunaBrowserState {
+    app: AppState,
+}
+
+impl RunaBrowserState {
+    /// Creates a browser starting in the current working directory.
+    ///
+    /// # Errors
+    /// Returns an std::io::Error if the working directory cannot be determined.
+    pub fn new(config: Config) -> io::Result<Self> {
+        Ok(Self {
+            app: AppState::new(config)?,
+        })
+    }
+
+    /// Creates a browser starting in `dir`.
+    pub fn with_dir(config: Config, dir: impl Into<PathBuf>) -> Self {
+        Self {
+            app: AppState::with_dir(config, dir.into()),
+        }
+    }
+
+    // Getters / Accessors
+
+    pub fn app(&self) -> &AppState {
+        &self.app
+    }
+
+    pub fn app_mut(&mut self) -> &mut AppState {
+        &mut self.app
+    }
+
+    pub fn current_dir(&self) -> &Path {
+        self.app.nav().current_dir()
+    }
+
+    /// Returns the path of the selected entry, if any.
+    pub fn selected_path(&self) -> Option<PathBuf> {
+        self.app
+            .nav()
+            .selected_shown_entry()
+            .map(|entry| self.current_dir().join(entry.name()))
+    }
+
+    // Event handling
+
+    /// Processes worker responses and timers.
+    ///
+    /// # Returns
+    /// True if the browser needs a redraw.
+    pub fn tick(&mut self) -> bool {
+        let expired = self.app.expire_key_sequence().is_some();
+        self.app.tick() || expired
+    }
+
+    /// Handles a key event.
+    ///
+    /// The open key on a file picks it instead of opening the editor,
+    /// the quit key cancels the browser. Everything else is handled by runa's keymap.
+    ///
+    /// # Arguments
+    /// * `key` - The key event from the host application.
+    pub fn handle_key(&mut self, key: KeyEvent) -> BrowserEvent {
+        if key.kind != KeyEventKind::Press {
+            return BrowserEvent::Continue;
+        }
+
+        if !self.app.actions().is_input_mode()
+            && self.app.is_open_key(key)
+            && let Some(entry) = self.app.nav().selected_shown_entry()
+            && !entry.is_dir()
+        {
+            return BrowserEvent::Picked(self.current_dir().join(entry.name()));
+        }
+
+        match self.app.handle_keypress(key) {
+            KeypressResult::Quit => BrowserEvent::Cancelled,
+            KeypressResult::OpenedEditor => BrowserEvent::Resumed,
+            _ => BrowserEvent::Continue,
+        }
+    }
+}
+
+/// Widget drawing an embedded runa file browser.
+///
+/// Rendered through [RunaBrowser::render], as runa's panes and dialogs draw on a [Frame].
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RunaBrowser;
+
+impl RunaBrowser {
+    pub fn new() -> Self {
+        Self
+    }
+
+    /// Draws the browser into `area` of the frame.
+    ///
+    /// # Arguments
+    /// * `frame` - The frame of the host application.
+    /// * `area` - The area used for the header and panes.
+    /// * `state` - The [RunaBrowserState] to draw.
+    pub fn render(self, frame: &mut Frame, area: Rect, state: &mut RunaBrowserState) {
+        render_in(frame, area, &mut state.app);
+    }
+}
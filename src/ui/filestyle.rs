@@ -0,0 +1,228 @@
+//! Per-file-type and per-extension entry colors for runa.
+//!
+//! Entries are colored by what they are beyond the directory color of the theme: symlinks,
+//! executables, archives, images and media. The colors come either from a built-in set or from
+//! the `LS_COLORS` variable used by `ls`, selected by the `file_colors` display option.
+//!
+//! `LS_COLORS` is a `:` separated list of `key=SGR codes`, e.g. `di=01;34:ln=01;36:*.tar=01;31`.
+//! The keys `ln`, `ex` and `fi` color symlinks, executables and regular files, keys starting with
+//! `*` color names ending in the rest of the key. Other keys are ignored, directories keep the
+//! directory color of the theme.
+
+use crate::config::display::FileColors;
+use crate::core::FileEntry;
+use ratatui::style::{Color, Modifier, Style};
+use std::collections::HashMap;
+
+const ARCHIVES: &[&str] = &[
+    "7z", "bz2", "deb", "gz", "jar", "lz", "lz4", "lzma", "rar", "rpm", "tar", "tbz2", "tgz",
+    "txz", "xz", "zip", "zst",
+];
+const IMAGES: &[&str] = &[
+    "avif", "bmp", "gif", "heic", "ico", "jpeg", "jpg", "png", "svg", "tif", "tiff", "webp",
+];
+const MEDIA: &[&str] = &[
+    "aac", "avi", "flac", "m4a", "mkv", "mov", "mp3", "mp4", "ogg", "opus", "wav", "webm",
+];
+/// Extensions run directly on Windows, which has no executable bit.
+const WINDOWS_EXECUTABLES: &[&str] = &["bat", "cmd", "com", "exe", "ps1"];
+
+/// The entry colors for a `file_colors` mode.
+///
+/// # Fields
+/// * `symlink` - Style of symlinks.
+/// * `executable` - Style of files with an executable bit.
+/// * `file` - Style of regular files without a matching extension.
+/// * `extensions` - Styles by lowercase extension, for `*.ext` keys.
+/// * `suffixes` - Styles of other name suffixes like `*.tar.gz` or `*README`, longest first.
+#[derive(Debug, Clone, Default)]
+pub struct FileStyles {
+    symlink: Option<Style>,
+    executable: Option<Style>,
+    file: Option<Style>,
+    extensions: HashMap<String, Style>,
+    suffixes: Vec<(String, Style)>,
+}
+
+impl FileStyles {
+    /// The colors of a `file_colors` mode, reading `LS_COLORS` for [FileColors::LsColors].
+    /// Falls back to the built-in colors if `LS_COLORS` is not set.
+    pub fn from_mode(mode: FileColors) -> Self {
+        match mode {
+            FileColors::None => Self::default(),
+            FileColors::Builtin => Self::builtin(),
+            FileColors::LsColors => match std::env::var("LS_COLORS") {
+                Ok(value) if !value.trim().is_empty() => Self::from_ls_colors(&value),
+                _ => Self::builtin(),
+            },
+        }
+    }
+
+    /// The built-in colors, close to the defaults of `dircolors`.
+    pub fn builtin() -> Self {
+        let mut extensions = HashMap::new();
+        let groups = [
+            (
+                ARCHIVES,
+                Style::new().fg(Color::Red).add_modifier(Modifier::BOLD),
+            ),
+            (IMAGES, Style::new().fg(Color::Magenta)),
+            (MEDIA, Style::new().fg(Color::Cyan)),
+            (
+                WINDOWS_EXECUTABLES,
+                Style::new().fg(Color::Green).add_modifier(Modifier::BOLD),
+            ),
+        ];
+        for (exts, style) in groups {
+            extensions.extend(exts.iter().map(|ext| (ext.to_string(), style)));
+        }
+        FileStyles {
+            symlink: Some(Style::new().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            executable: Some(Style::new().fg(Color::Green).add_modifier(Modifier::BOLD)),
+            file: None,
+            extensions,
+            suffixes: Vec::new(),
+        }
+    }
+
+    /// Parses an `LS_COLORS` value. Unknown keys and invalid codes are skipped.
+    pub fn from_ls_colors(value: &str) -> Self {
+        let mut styles = FileStyles::default();
+        for (key, codes) in value.split(':').filter_map(|entry| entry.split_once('=')) {
+            let Some(style) = parse_sgr(codes) else {
+                continue;
+            };
+            match key {
+                "ln" => styles.symlink = Some(style),
+                "ex" => styles.executable = Some(style),
+                "fi" => styles.file = Some(style),
+                _ => {
+                    if let Some(suffix) = key.strip_prefix('*') {
+                        styles.add_suffix(suffix, style);
+                    }
+                }
+            }
+        }
+        styles
+            .suffixes
+            .sort_by_key(|(suffix, _)| std::cmp::Reverse(suffix.len()));
+        styles
+    }
+
+    fn add_suffix(&mut self, suffix: &str, style: Style) {
+        let suffix = suffix.to_lowercase();
+        match suffix.strip_prefix('.') {
+            Some(ext) if !ext.is_empty() && !ext.contains('.') => {
+                self.extensions.insert(ext.to_string(), style);
+            }
+            _ if !suffix.is_empty() => self.suffixes.push((suffix, style)),
+            _ => {}
+        }
+    }
+
+    /// Whether executables are colored, which needs the metadata of the entries.
+    pub fn colors_executables(&self) -> bool {
+        self.executable.is_some()
+    }
+
+    /// The style of a file entry, `None` to keep the pane style.
+    ///
+    /// Symlinks are colored as such, executables before their extension like `ls` does.
+    ///
+    /// # Arguments
+    /// * `entry` - The entry, directories are not colored here.
+    /// * `executable` - Whether the entry has an executable bit, if its metadata is known.
+    pub fn style(&self, entry: &FileEntry, executable: bool) -> Option<Style> {
+        if entry.is_dir() {
+            return None;
+        }
+        if entry.is_symlink() {
+            return self.symlink;
+        }
+        if executable && self.executable.is_some() {
+            return self.executable;
+        }
+        if !self.suffixes.is_empty() {
+            let name = entry.name_str().to_lowercase();
+            if let Some((_, style)) = self.suffixes.iter().find(|(s, _)| name.ends_with(s)) {
+                return Some(*style);
+            }
+        }
+        entry
+            .extension()
+            .and_then(|ext| self.extensions.get(&ext).copied())
+            .or(self.file)
+    }
+}
+
+/// The 16 colors of the SGR codes 30-37 and 90-97.
+const ANSI_COLORS: [Color; 16] = [
+    Color::Black,
+    Color::Red,
+    Color::Green,
+    Color::Yellow,
+    Color::Blue,
+    Color::Magenta,
+    Color::Cyan,
+    Color::Gray,
+    Color::DarkGray,
+    Color::LightRed,
+    Color::LightGreen,
+    Color::LightYellow,
+    Color::LightBlue,
+    Color::LightMagenta,
+    Color::LightCyan,
+    Color::White,
+];
+
+/// Converts SGR codes like `01;38;5;208` to a style.
+///
+/// # Returns
+/// None if a code is not a number.
+pub fn parse_sgr(codes: &str) -> Option<Style> {
+    let codes: Vec<u16> = codes
+        .split(';')
+        .filter(|code| !code.is_empty())
+        .map(|code| code.parse().ok())
+        .collect::<Option<_>>()?;
+    let mut style = Style::new();
+    let mut iter = codes.into_iter();
+    while let Some(code) = iter.next() {
+        style = match code {
+            0 => Style::new(),
+            1 => style.add_modifier(Modifier::BOLD),
+            2 => style.add_modifier(Modifier::DIM),
+            3 => style.add_modifier(Modifier::ITALIC),
+            4 => style.add_modifier(Modifier::UNDERLINED),
+            5 => style.add_modifier(Modifier::SLOW_BLINK),
+            7 => style.add_modifier(Modifier::REVERSED),
+            9 => style.add_modifier(Modifier::CROSSED_OUT),
+            30..=37 => style.fg(ANSI_COLORS[(code - 30) as usize]),
+            40..=47 => style.bg(ANSI_COLORS[(code - 40) as usize]),
+            90..=97 => style.fg(ANSI_COLORS[(code - 90 + 8) as usize]),
+            100..=107 => style.bg(ANSI_COLORS[(code - 100 + 8) as usize]),
+            38 | 48 => {
+                let color = match iter.next() {
+                    Some(5) => iter.next().map(|n| Color::Indexed(n as u8)),
+                    Some(2) => {
+                        let mut rgb = iter.by_ref().take(3).map(|n| n as u8);
+                        match (rgb.next(), rgb.next(), rgb.next()) {
+                            (Some(r), Some(g), Some(b)) => Some(Color::Rgb(r, g, b)),
+                            _ => None,
+                        }
+                    }
+                    _ => None,
+                };
+                match (code, color) {
+                    (38, Some(color)) => style.fg(color),
+                    (_, Some(color)) => style.bg(color),
+                    _ => style,
+                }
+            }
+            39 => style.fg(Color::Reset),
+            49 => style.bg(Color::Reset),
+            _ => style,
+        };
+    }
+    Some(style)
+}
@@ -5,11 +5,15 @@
 //! It uses `once_cell` for lazy static initialization of icon maps.
 //! The icons are represented as static string slices.
 //!
-//! The main function `nerd_font_icon` takes a `FileEntry` and returns
-//! the corresponding Nerd Font icon.
+//! The built-in maps are the defaults of [Icons], which merges the `[icons]` tables of the
+//! config over them and returns the icon of a `FileEntry`. With `display.ascii_only` the
+//! built-in maps are left out and entries show their type letter instead.
 
+use crate::config::icons::{IconOptions, IconSpec};
 use crate::core::FileEntry;
 use once_cell::sync::Lazy;
+use ratatui::style::{Color, Style};
+use std::borrow::Cow;
 use std::collections::HashMap;
 
 /// File extension to icon mapping
@@ -147,47 +151,178 @@ pub static SPECIAL_DIR_ICON_MAP: Lazy<HashMap<&'static str, &'static str>> = Laz
     m
 });
 
-/// Get the Nerd Font icon for a given file entry.
-/// This function determines the appropriate icon based on whether
-/// the entry is a directory or a file, and uses the special
-/// filename and extension mappings to find the correct icon.
+/// The generic directory icon.
+pub const DIR_ICON: &str = "";
+/// The icon of files without a matching name or extension.
+pub const FILE_ICON: &str = "";
+/// The icon of symlinks to files.
+const SYMLINK_ICON: &str = "";
+
+/// The ASCII icons of `display.ascii_only`, the type letters of `ls -l`.
+pub const ASCII_DIR_ICON: &str = "d";
+pub const ASCII_FILE_ICON: &str = "-";
+const ASCII_SYMLINK_ICON: &str = "l";
+
+/// An icon and its color.
 ///
-/// # Arguments
-/// * `entry` - A reference to a `FileEntry` representing the file or directory.
-pub fn nerd_font_icon(entry: &FileEntry) -> &'static str {
-    let lowercase_name = entry.lowercase_name();
-    let entry_name = entry.name_str();
-
-    if entry.is_dir() {
-        if let Some(dir_icon) = SPECIAL_DIR_ICON_MAP.get(lowercase_name) {
-            return dir_icon;
+/// # Fields
+/// * `glyph` - The Nerd Font glyph.
+/// * `fg` - The color of the glyph, `None` to use the style of the entry.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Icon {
+    glyph: Cow<'static, str>,
+    fg: Option<Color>,
+}
+
+impl Icon {
+    const fn builtin(glyph: &'static str) -> Self {
+        Icon {
+            glyph: Cow::Borrowed(glyph),
+            fg: None,
         }
-        return "";
     }
 
-    if entry.is_symlink() {
-        if entry.is_dir() {
-            return "";
+    pub fn glyph(&self) -> &str {
+        &self.glyph
+    }
+
+    pub fn fg(&self) -> Option<Color> {
+        self.fg
+    }
+
+    /// The style of the icon column, the entry style with the icon color.
+    pub fn style(&self, entry_style: Style) -> Style {
+        match self.fg {
+            Some(fg) => entry_style.fg(fg),
+            None => entry_style,
+        }
+    }
+
+    fn apply(&mut self, spec: &IconSpec) {
+        if let Some(glyph) = spec.icon() {
+            self.glyph = Cow::Owned(glyph.to_string());
+        }
+        if spec.fg().is_some() {
+            self.fg = spec.fg();
+        }
+    }
+}
+
+/// The icon maps in use: the built-in maps with the `[icons]` tables of the config merged
+/// over them.
+///
+/// Extensions and directory names are looked up lowercase, file names as they are.
+#[derive(Debug, Clone)]
+pub struct Icons {
+    extensions: HashMap<String, Icon>,
+    files: HashMap<String, Icon>,
+    dirs: HashMap<String, Icon>,
+    dir: Icon,
+    symlink: Icon,
+    default: Icon,
+}
+
+impl Default for Icons {
+    fn default() -> Self {
+        let builtin = |map: &HashMap<&'static str, &'static str>| {
+            map.iter()
+                .map(|(name, glyph)| (name.to_string(), Icon::builtin(glyph)))
+                .collect()
+        };
+        Icons {
+            extensions: builtin(&EXT_ICON_MAP),
+            files: builtin(&SPECIAL_FILE_ICON_MAP),
+            dirs: builtin(&SPECIAL_DIR_ICON_MAP),
+            dir: Icon::builtin(DIR_ICON),
+            symlink: Icon::builtin(SYMLINK_ICON),
+            default: Icon::builtin(FILE_ICON),
+        }
+    }
+}
+
+impl Icons {
+    /// The icons of `display.ascii_only`: the type letters of `ls -l`, without per-name icons.
+    pub fn ascii() -> Self {
+        Icons {
+            extensions: HashMap::new(),
+            files: HashMap::new(),
+            dirs: HashMap::new(),
+            dir: Icon::builtin(ASCII_DIR_ICON),
+            symlink: Icon::builtin(ASCII_SYMLINK_ICON),
+            default: Icon::builtin(ASCII_FILE_ICON),
+        }
+    }
+
+    /// The built-in icons with the `[icons]` tables merged over them.
+    ///
+    /// An entry of the tables replaces the glyph, the color or both of a built-in icon. New
+    /// entries without a glyph get the generic file or directory icon.
+    ///
+    /// # Arguments
+    /// * `options` - The `[icons]` tables.
+    /// * `ascii_only` - Start from the [ascii](Self::ascii) icons instead of the Nerd Font ones.
+    pub fn new(options: &IconOptions, ascii_only: bool) -> Self {
+        let mut icons = if ascii_only {
+            Icons::ascii()
         } else {
-            return "";
+            Icons::default()
+        };
+        let (file, dir) = (icons.default.clone(), icons.dir.clone());
+        merge(&mut icons.extensions, options.extensions(), &file, true);
+        merge(&mut icons.files, options.files(), &file, false);
+        merge(&mut icons.dirs, options.dirs(), &dir, true);
+        if let Some(spec) = options.default_icon() {
+            icons.default.apply(spec);
         }
+        icons
     }
 
-    if let Some(icon) = SPECIAL_FILE_ICON_MAP.get(entry_name) {
-        return icon;
+    /// The generic directory icon, also used for directories among find results.
+    pub fn dir(&self) -> &Icon {
+        &self.dir
     }
 
-    let ext = match entry_name.rsplit('.').next() {
-        Some(ext) if ext != entry_name => ext,
-        _ => "",
-    };
+    /// The icon of an entry.
+    ///
+    /// Directories are looked up by name, symlinks have their own icon, files are looked up by
+    /// name and then by extension.
+    pub fn icon(&self, entry: &FileEntry) -> &Icon {
+        if entry.is_dir() {
+            return self.dirs.get(entry.lowercase_name()).unwrap_or(&self.dir);
+        }
+        if entry.is_symlink() {
+            return &self.symlink;
+        }
 
-    if !ext.is_empty() {
-        let ext_lc = ext.to_ascii_lowercase();
-        if let Some(icon) = EXT_ICON_MAP.get(ext_lc.as_str()) {
+        let entry_name = entry.name_str();
+        if let Some(icon) = self.files.get(entry_name) {
             return icon;
         }
+        match entry_name.rsplit('.').next() {
+            Some(ext) if ext != entry_name && !ext.is_empty() => self
+                .extensions
+                .get(ext.to_ascii_lowercase().as_str())
+                .unwrap_or(&self.default),
+            _ => &self.default,
+        }
     }
+}
 
-    ""
+fn merge(
+    icons: &mut HashMap<String, Icon>,
+    specs: &HashMap<String, IconSpec>,
+    fallback: &Icon,
+    lowercase: bool,
+) {
+    for (name, spec) in specs {
+        let name = if lowercase {
+            name.to_lowercase()
+        } else {
+            name.clone()
+        };
+        icons
+            .entry(name)
+            .or_insert_with(|| fallback.clone())
+            .apply(spec);
+    }
 }
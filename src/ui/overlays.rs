@@ -1,17 +1,23 @@
 //! Ovelay module to seamless stack widgets, dialogs with each other.
-//! Currently handles ShowInfo as a overlay.
+//! Currently handles ShowInfo, the session statistics and the marked list as overlays.
 //!
 //! Can be expanded to hanlde more widget types for more functions.
 //!
 //! Is used throughout the ui modules and in handlers.rs.
 
 use crate::core::FileInfo;
+use std::path::{Path, PathBuf};
 use std::slice;
 
+/// `ShowInfo` is boxed, as the info of a file is far larger than the other overlays.
+///
+/// `MarkedList` takes the keys while it is on top, see
+/// [AppState::handle_marked_list](crate::app::AppState::handle_marked_list).
 #[derive(Clone)]
 pub enum Overlay {
-    ShowInfo { info: FileInfo },
-    Message { text: String },
+    ShowInfo { info: Box<FileInfo> },
+    Stats,
+    MarkedList { marked: MarkedState },
 }
 
 pub struct OverlayStack {
@@ -69,3 +75,72 @@ impl Default for OverlayStack {
         Self::new()
     }
 }
+
+/// Paths listed in the [Overlay::MarkedList].
+///
+/// Markers survive changing directories, so the overview lists every marked path with its
+/// size, to review them before running a bulk operation.
+/// The sizes come from the directory size worker, see [crate::app::AppState::marked_size].
+///
+/// # Fields
+/// * `targets` - Sorted list of the marked paths.
+/// * `selected` - Index of the currently selected path.
+#[derive(Clone, Default)]
+pub struct MarkedState {
+    targets: Vec<PathBuf>,
+    selected: usize,
+}
+
+impl MarkedState {
+    /// Lists `paths`, sorted by path, with the first one selected.
+    pub fn new(paths: impl IntoIterator<Item = PathBuf>) -> Self {
+        let mut targets: Vec<PathBuf> = paths.into_iter().collect();
+        targets.sort();
+        Self {
+            targets,
+            selected: 0,
+        }
+    }
+
+    // Getters / Accessors
+
+    pub fn targets(&self) -> &[PathBuf] {
+        &self.targets
+    }
+
+    pub fn selected(&self) -> usize {
+        self.selected
+    }
+
+    pub fn selected_path(&self) -> Option<&Path> {
+        self.targets.get(self.selected).map(PathBuf::as_path)
+    }
+
+    // Marked functions
+
+    /// Removes the selected path from the list and returns it.
+    pub fn remove_selected(&mut self) -> Option<PathBuf> {
+        if self.selected >= self.targets.len() {
+            return None;
+        }
+        let path = self.targets.remove(self.selected);
+        if self.selected >= self.targets.len() {
+            self.selected = self.targets.len().saturating_sub(1);
+        }
+        Some(path)
+    }
+
+    /// Moves the selection to the next path.
+    pub fn select_next(&mut self) {
+        if self.selected + 1 < self.targets.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Moves the selection to the previous path.
+    pub fn select_prev(&mut self) {
+        if self.selected > 0 {
+            self.selected -= 1;
+        }
+    }
+}
@@ -5,15 +5,19 @@
 //!
 //! Used internally by ui::render
 
-use crate::app::{AppState, PreviewData};
-use crate::core::{FileEntry, symlink_target_resolved};
-use crate::ui::icons::nerd_font_icon;
-use ansi_to_tui::IntoText;
+use crate::app::{AppState, DirSizeStatus, PreviewData};
+use crate::core::{
+    EntryMeta, FileEntry, SizeFormat, TagColor, display_safe_name, format_file_size,
+    format_file_time_short, sanitize_to_exact_width, symlink_target_resolved,
+};
+use crate::ui::filestyle::FileStyles;
+use crate::ui::icons::Icons;
+use crate::ui::widgets::draw_inline_rename;
 use ratatui::text::Text;
 use ratatui::widgets::BorderType;
 use ratatui::{
     Frame,
-    layout::Rect,
+    layout::{Constraint, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, List, ListItem, ListState, Paragraph},
@@ -21,20 +25,32 @@ use ratatui::{
 use std::collections::HashSet;
 use std::ffi::OsString;
 use std::path::{Path, PathBuf};
+use unicode_width::UnicodeWidthStr;
 
 /// Styles used for rendering items in a pane
-/// Includes styles for regular items, directories and selected items
-pub struct PaneStyles {
+/// Includes styles for regular items, directories and selected items,
+/// and the per-file-type and per-extension colors of [FileStyles]
+pub struct PaneStyles<'a> {
     pub item: Style,
     pub dir: Style,
     pub selection: Style,
     pub symlink: Color,
+    pub files: &'a FileStyles,
 }
 
-impl PaneStyles {
-    pub fn get_style(&self, is_dir: bool, is_selected: bool) -> Style {
-        let mut style = if is_dir && self.dir.fg != Some(Color::Reset) {
+impl PaneStyles<'_> {
+    /// The style of an entry row.
+    ///
+    /// Directories use the directory style, other entries get their [FileStyles] color on top of
+    /// the item style.
+    ///
+    /// # Arguments
+    /// * `executable` - Whether the entry has an executable bit, false if its metadata is unknown.
+    pub fn get_style(&self, entry: &FileEntry, executable: bool, is_selected: bool) -> Style {
+        let mut style = if entry.is_dir() && self.dir.fg != Some(Color::Reset) {
             self.dir
+        } else if let Some(file_style) = self.files.style(entry, executable) {
+            self.item.patch(file_style)
         } else {
             self.item
         };
@@ -51,6 +67,8 @@ impl PaneStyles {
             {
                 style = style.fg(fg);
             }
+
+            style = style.add_modifier(self.selection.add_modifier);
         }
         style
     }
@@ -65,19 +83,25 @@ pub struct PaneContext<'a> {
     pub block: Block<'a>,
     pub border_type: BorderType,
     pub accent_style: Style,
-    pub styles: PaneStyles,
+    pub styles: PaneStyles<'a>,
     pub highlight_symbol: &'a str,
     pub entry_padding: u8,
     pub padding_str: &'static str,
-    pub show_icons: bool,
+    pub icons: Option<&'a Icons>,
     pub show_marker: bool,
+    pub force_ltr: bool,
 }
 
 /// Options for preview pane rendering
+///
+/// `dir_size` is the size status of the previewed directory, shown as a header above its entries
+/// in the units of `size_format`.
 pub struct PreviewOptions {
     pub use_underline: bool,
     pub underline_match_text: bool,
     pub underline_style: Style,
+    pub dir_size: Option<DirSizeStatus>,
+    pub size_format: SizeFormat,
 }
 
 /// Marker and clipboard data for use in pane drawing functions
@@ -142,9 +166,19 @@ pub fn draw_main(frame: &mut Frame, app: &AppState, context: PaneContext) {
         return;
     }
 
+    // Room for the entry columns, next to the highlight symbol List reserves on every row
+    let details = app.show_details();
+    let show_git = app.show_git_status();
+    let show_tags = app.show_finder_tags();
+    let show_context = app.show_security_context();
+    let context_mismatch = app.config().theme().context_mismatch();
+    let size_format = app.config().display().size_format();
+    let row_width = (context.block.inner(context.area).width as usize)
+        .saturating_sub(context.highlight_symbol.width());
+
     let items = app.nav().shown_entries().enumerate().map(|(idx, entry)| {
         let is_selected = Some(idx) == selected_idx;
-        let is_marked = local_markers.contains(entry.name().as_os_str());
+        let is_marked = local_markers.contains(entry.name());
         let entry_path = current_dir.join(entry.name());
         let is_copied = clipboard
             .as_ref()
@@ -156,22 +190,38 @@ pub fn draw_main(frame: &mut Frame, app: &AppState, context: PaneContext) {
         } else {
             entry.name_str()
         };
-
-        let entry_style = context.styles.get_style(entry.is_dir(), is_selected);
+        let name_str = display_safe_name(name_str, context.force_ltr);
+
+        let executable = app
+            .nav()
+            .metadata(entry.name())
+            .is_some_and(EntryMeta::executable);
+        let entry_style = context.styles.get_style(entry, executable, is_selected);
+        // Entries labeled with another SELinux type than the directory stand out by their name
+        let name_span = if show_context
+            && app
+                .nav()
+                .metadata(entry.name())
+                .is_some_and(EntryMeta::context_differs)
+        {
+            Span::styled(name_str, entry_style.fg(context_mismatch))
+        } else {
+            Span::raw(name_str)
+        };
         let mut spans = Vec::with_capacity(8);
 
         if entry_padding == 0 {
-            if context.show_icons {
-                let icon = nerd_font_icon(entry);
-                let mut icon_col = String::with_capacity(icon.len() + 1);
-                icon_col.push_str(icon);
+            if let Some(icons) = context.icons {
+                let icon = icons.icon(entry);
+                let mut icon_col = String::with_capacity(icon.glyph().len() + 1);
+                icon_col.push_str(icon.glyph());
                 icon_col.push(' ');
                 spans.push(Span::styled(
                     icon_col,
-                    entry_style.add_modifier(Modifier::BOLD),
+                    icon.style(entry_style).add_modifier(Modifier::BOLD),
                 ));
             }
-            spans.push(Span::raw(name_str));
+            spans.push(name_span);
         } else {
             let mut marker_style = if is_copied {
                 marker_theme.clipboard_style_or_theme()
@@ -192,17 +242,17 @@ pub fn draw_main(frame: &mut Frame, app: &AppState, context: PaneContext) {
             if entry_padding > 1 {
                 spans.push(Span::raw(&padding_str));
             }
-            if context.show_icons {
-                let icon = nerd_font_icon(entry);
-                let mut icon_col = String::with_capacity(icon.len() + 1);
-                icon_col.push_str(icon);
+            if let Some(icons) = context.icons {
+                let icon = icons.icon(entry);
+                let mut icon_col = String::with_capacity(icon.glyph().len() + 1);
+                icon_col.push_str(icon.glyph());
                 icon_col.push(' ');
                 spans.push(Span::styled(
                     icon_col,
-                    entry_style.add_modifier(Modifier::BOLD),
+                    icon.style(entry_style).add_modifier(Modifier::BOLD),
                 ));
             }
-            spans.push(Span::raw(name_str));
+            spans.push(name_span);
             if entry.is_symlink()
                 && let Some(target) = symlink_target_resolved(entry, current_dir)
             {
@@ -216,6 +266,27 @@ pub fn draw_main(frame: &mut Frame, app: &AppState, context: PaneContext) {
             }
         }
 
+        if show_tags
+            && let Some(tags) = app.nav().metadata(entry.name()).map(EntryMeta::tags)
+            && !tags.is_empty()
+        {
+            spans.push(Span::raw(" "));
+            spans.extend(
+                tags.colors()
+                    .map(|color| Span::styled(TAG_DOT, entry_style.fg(tag_color(color)))),
+            );
+        }
+
+        if details {
+            let meta = app.nav().metadata(entry.name());
+            let dir_size = app.nav().dir_size_status(entry.name());
+            push_detail_column(
+                &mut spans,
+                detail_column(entry, meta, dir_size, show_git, size_format),
+                row_width,
+            );
+        }
+
         let line = Line::from(spans);
         ListItem::new(line).style(entry_style)
     });
@@ -225,6 +296,7 @@ pub fn draw_main(frame: &mut Frame, app: &AppState, context: PaneContext) {
         state.select(selected_idx);
     }
 
+    let inner = context.block.inner(context.area);
     frame.render_stateful_widget(
         List::new(items)
             .block(
@@ -239,6 +311,31 @@ pub fn draw_main(frame: &mut Frame, app: &AppState, context: PaneContext) {
         context.area,
         &mut state,
     );
+
+    if app.is_inline_rename()
+        && let Some(idx) = selected_idx
+        && let Some(entry) = app.nav().selected_shown_entry()
+    {
+        // The field starts where the name does, after the symbol, marker, padding and icon
+        let mut name_x = context.highlight_symbol.width();
+        if entry_padding > 0 {
+            name_x += marker_icon.width() + padding_str.width();
+        }
+        if let Some(icons) = context.icons {
+            name_x += icons.icon(entry).glyph().width() + 1;
+        }
+        let row = idx.saturating_sub(state.offset()) as u16;
+        if row < inner.height && (name_x as u16) < inner.width {
+            let area = Rect {
+                x: inner.x + name_x as u16,
+                y: inner.y + row,
+                width: inner.width - name_x as u16,
+                height: 1,
+            };
+            let style = context.styles.get_style(entry, false, true);
+            draw_inline_rename(frame, app, area, style);
+        }
+    }
 }
 
 /// Draws the preview pane, showing either the file content or directory listing
@@ -259,8 +356,8 @@ pub fn draw_preview(
         }
 
         PreviewData::File(lines) => {
-            let raw = lines.join("\n");
-            let text = raw.into_text().unwrap_or_else(|_| Text::from(raw));
+            // Escape sequences are already parsed by the worker, these lines are plain text
+            let text = Text::from_iter(lines.iter().map(|line| Line::raw(line.as_str())));
 
             frame.render_widget(
                 Paragraph::new(text).block(context.block.border_style(context.accent_style)),
@@ -268,19 +365,37 @@ pub fn draw_preview(
             );
         }
 
+        PreviewData::Highlighted(lines) => {
+            frame.render_widget(
+                Paragraph::new(lines.clone())
+                    .block(context.block.border_style(context.accent_style)),
+                context.area,
+            );
+        }
+
         PreviewData::Directory(entries) => {
             if entries.is_empty() {
                 let style = context.styles.item;
                 let line = Line::from(vec![Span::raw(context.padding_str), Span::raw("[Empty]")]);
 
                 let items = vec![ListItem::new(line).style(style)];
+                let block = context.block.border_style(context.accent_style);
+                let (block, area) = split_dir_size_header(
+                    frame,
+                    block,
+                    context.area,
+                    opts.dir_size,
+                    opts.size_format,
+                    context.padding_str,
+                    style,
+                );
                 let mut state = ListState::default();
                 frame.render_stateful_widget(
                     List::new(items)
-                        .block(context.block.border_style(context.accent_style))
+                        .block(block)
                         .highlight_style(Style::default())
                         .highlight_symbol(context.highlight_symbol),
-                    context.area,
+                    area,
                     &mut state,
                 );
                 return;
@@ -291,7 +406,7 @@ pub fn draw_preview(
                 .enumerate()
                 .map(|(idx, entry)| {
                     let is_selected = Some(idx) == selected_idx;
-                    let style = context.styles.get_style(entry.is_dir(), is_selected);
+                    let style = context.styles.get_style(entry, false, is_selected);
                     make_entry_row(
                         entry,
                         path,
@@ -307,23 +422,69 @@ pub fn draw_preview(
             let mut state = ListState::default();
             state.select(selected_idx.map(|idx| idx.min(entries.len().saturating_sub(1))));
 
+            let block = context
+                .block
+                .border_style(context.accent_style)
+                .border_type(context.border_type);
+            let (block, area) = split_dir_size_header(
+                frame,
+                block,
+                context.area,
+                opts.dir_size,
+                opts.size_format,
+                context.padding_str,
+                context.styles.item,
+            );
             frame.render_stateful_widget(
                 List::new(items)
-                    .block(
-                        context
-                            .block
-                            .border_style(context.accent_style)
-                            .border_type(context.border_type),
-                    )
+                    .block(block)
                     .highlight_style(Style::default())
                     .highlight_symbol(context.highlight_symbol),
-                context.area,
+                area,
                 &mut state,
             );
         }
     }
 }
 
+/// Helper: Draws the size of the previewed directory in the first row of the pane, if it is
+/// computed or being computed.
+///
+/// # Returns
+/// The block and area left for the entries: the pane block and area without a header, otherwise
+/// no block and the area below the header, as the block is already drawn.
+fn split_dir_size_header<'a>(
+    frame: &mut Frame,
+    block: Block<'a>,
+    area: Rect,
+    dir_size: Option<DirSizeStatus>,
+    size_format: SizeFormat,
+    padding_str: &str,
+    style: Style,
+) -> (Block<'a>, Rect) {
+    let header = match dir_size {
+        Some(DirSizeStatus::Exact(size)) => {
+            let bytes = format_file_size(Some(size.bytes()), false, size_format);
+            match size.excluded() {
+                0 => format!("Size: {}", bytes),
+                n => format!("Size: {} ({} excluded)", bytes, n),
+            }
+        }
+        Some(DirSizeStatus::Computing) => "Size: computing…".to_string(),
+        Some(DirSizeStatus::Unknown) | None => return (block, area),
+    };
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+    let [header_area, list_area] =
+        Layout::vertical([Constraint::Length(1), Constraint::Min(0)]).areas(inner);
+    let line = Line::from(vec![Span::raw(padding_str), Span::raw(header)]);
+    frame.render_widget(
+        Paragraph::new(line).style(style.add_modifier(Modifier::DIM)),
+        header_area,
+    );
+    (Block::default(), list_area)
+}
+
 /// Draws the parent directory of the current working directory.
 pub fn draw_parent(
     path: Option<&Path>,
@@ -343,7 +504,7 @@ pub fn draw_parent(
         .enumerate()
         .map(|(idx, entry)| {
             let is_selected = Some(idx) == selected_idx;
-            let style = context.styles.get_style(entry.is_dir(), is_selected);
+            let style = context.styles.get_style(entry, false, is_selected);
             make_entry_row(entry, path, is_selected, style, &context, markers, None)
         })
         .collect();
@@ -392,6 +553,95 @@ pub fn pane_marker_sets(
     }
 }
 
+/// Helper: The detail column of an entry: git status, size and modification time.
+/// The dot shown for each Finder tag color of an entry.
+const TAG_DOT: &str = "\u{25cf}";
+
+/// The terminal color of a Finder tag color.
+fn tag_color(color: TagColor) -> Color {
+    match color {
+        TagColor::Gray => Color::Gray,
+        TagColor::Green => Color::Green,
+        TagColor::Purple => Color::Magenta,
+        TagColor::Blue => Color::Blue,
+        TagColor::Yellow => Color::Yellow,
+        TagColor::Red => Color::Red,
+        TagColor::Orange => Color::Rgb(255, 149, 0),
+    }
+}
+
+/// Blank until the metadata worker read the entry, so the names do not shift when it arrives.
+/// Directories show their recursive size once it was computed, "…" while it is computed and "-"
+/// if it was not requested.
+/// The size column fits the widest size of the units, "999.99 kB" or "1023.99 KiB".
+fn detail_column(
+    entry: &FileEntry,
+    meta: Option<&EntryMeta>,
+    dir_size: DirSizeStatus,
+    show_git: bool,
+    size_format: SizeFormat,
+) -> String {
+    let size_width = match size_format {
+        SizeFormat::Decimal => 9,
+        SizeFormat::Binary => 11,
+    };
+    let (git, size, time) = match meta {
+        Some(meta) => (
+            meta.git().unwrap_or(' '),
+            match dir_size {
+                DirSizeStatus::Exact(size) => {
+                    format_file_size(Some(size.bytes()), false, size_format)
+                }
+                DirSizeStatus::Computing if entry.is_dir() => "…".to_string(),
+                _ => format_file_size(meta.size(), entry.is_dir(), size_format),
+            },
+            format_file_time_short(meta.modified()),
+        ),
+        None => (' ', String::new(), String::new()),
+    };
+    if show_git {
+        format!("{} {:>size_width$} {:>16}", git, size, time)
+    } else {
+        format!("{:>size_width$} {:>16}", size, time)
+    }
+}
+
+/// Helper: Appends the detail column right aligned to a row of `width` cells.
+/// The row is cut to make room, rows too narrow for the column are left as they are.
+fn push_detail_column<'a>(spans: &mut Vec<Span<'a>>, detail: String, width: usize) {
+    const MIN_NAME_WIDTH: usize = 8;
+    let detail_width = detail.width() + 1;
+    let Some(room) = width
+        .checked_sub(detail_width)
+        .filter(|&room| room >= MIN_NAME_WIDTH)
+    else {
+        return;
+    };
+
+    let mut used = 0;
+    let mut kept = Vec::with_capacity(spans.len() + 2);
+    for span in spans.drain(..) {
+        let span_width = span.width();
+        if used + span_width <= room {
+            used += span_width;
+            kept.push(span);
+        } else {
+            let rest = room - used;
+            if rest > 0 {
+                kept.push(Span::styled(
+                    sanitize_to_exact_width(&span.content, rest),
+                    span.style,
+                ));
+            }
+            used = room;
+            break;
+        }
+    }
+    kept.push(Span::raw(" ".repeat(room - used + 1)));
+    kept.push(Span::raw(detail));
+    *spans = kept;
+}
+
 /// Helper: Create a PaneMarkers struct for use in pane drawing functions.
 /// Builds marker and clipboard sets for a specific directory.
 pub fn make_pane_markers<'a>(
@@ -476,14 +726,14 @@ fn make_entry_row<'a>(
     };
 
     let mut spans = vec![pad];
-    if context.show_icons {
-        let icon = nerd_font_icon(entry);
-        let mut icon_col = String::with_capacity(icon.len() + 1);
-        icon_col.push_str(icon);
+    if let Some(icons) = context.icons {
+        let icon = icons.icon(entry);
+        let mut icon_col = String::with_capacity(icon.glyph().len() + 1);
+        icon_col.push_str(icon.glyph());
         icon_col.push(' ');
         spans.push(Span::styled(
             icon_col,
-            row_style.add_modifier(Modifier::BOLD),
+            icon.style(row_style).add_modifier(Modifier::BOLD),
         ));
     }
     let name_str = if entry.is_dir() && context.show_marker {
@@ -491,6 +741,7 @@ fn make_entry_row<'a>(
     } else {
         entry.name_str()
     };
+    let name_str = display_safe_name(name_str, context.force_ltr);
 
     spans.push(Span::raw(name_str));
 
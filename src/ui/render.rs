@@ -10,10 +10,12 @@ use crate::ui::panes;
 use crate::ui::widgets;
 use crate::{
     app::{
-        AppState,
+        AppState, LayoutMetrics,
         actions::{ActionMode, InputMode},
     },
+    core::display_safe_name,
     ui::{
+        animation::LayerKey,
         overlays::Overlay,
         panes::{PaneContext, PaneStyles, PreviewOptions},
     },
@@ -22,9 +24,11 @@ use crate::{
 use ratatui::{
     Frame,
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph},
 };
+use std::time::Instant;
 
 /// Render function which renders the entire terminal UI for runa on each frame.
 /// Handles layout, pane rendering, borders, headers and coordinates all widgets.
@@ -32,48 +36,19 @@ use ratatui::{
 /// - frame: the drawing frame from ratatui::frame
 /// - app: runa's shared state, mutated as needed to display metrics
 pub fn render(frame: &mut Frame, app: &mut AppState) {
-    let mut root_area = frame.area();
-    {
-        let chunks = layout_chunks(root_area, app);
-        let mut metrics = crate::app::LayoutMetrics::default();
-        let display_cfg = app.config().display();
-
-        let mut current_idx = 0;
-        let has_sep = display_cfg.separators() && !display_cfg.is_split();
-
-        // Helper to determine inner space available for text
-        let get_inner = |rect: ratatui::layout::Rect| {
-            let width = if display_cfg.is_split() || display_cfg.is_unified() {
-                rect.width.saturating_sub(2)
-            } else {
-                rect.width
-            };
-            let height = rect.height.saturating_sub(2);
-            (width as usize, height as usize)
-        };
-
-        if display_cfg.parent() && current_idx < chunks.len() {
-            metrics.parent_width = get_inner(chunks[current_idx]).0;
-            current_idx += if has_sep { 2 } else { 1 };
-        }
-
-        if current_idx < chunks.len() {
-            metrics.main_width = get_inner(chunks[current_idx]).0;
-            current_idx += if has_sep && display_cfg.preview() {
-                2
-            } else {
-                1
-            };
-        }
-
-        if display_cfg.preview() && current_idx < chunks.len() {
-            let (width, height) = get_inner(chunks[current_idx]);
-            metrics.preview_width = width;
-            metrics.preview_height = height;
-        }
+    render_in(frame, frame.area(), app);
+}
 
-        *app.metrics_mut() = metrics;
-    }
+/// Same as [render], but draws inside `area` only.
+/// The header, panes, status line, dialogs, overlays and toasts are positioned in and clipped to `area`,
+/// so the rest of the frame is left to the host application.
+/// # Params:
+/// - frame: the drawing frame from ratatui::frame
+/// - area: the part of the frame used for the header and panes
+/// - app: runa's shared state, mutated as needed to display metrics
+pub fn render_in(frame: &mut Frame, area: Rect, app: &mut AppState) {
+    let mut root_area = area;
+    *app.metrics_mut() = layout_metrics(root_area, app);
 
     let cfg = app.config();
     let display_cfg = cfg.display();
@@ -81,7 +56,8 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
 
     let accent_style = theme_cfg.accent_style();
     let selection_style = theme_cfg.selection_style();
-    let path_str = shorten_home_path(app.nav().current_dir());
+    let path_str =
+        display_safe_name(&shorten_home_path(app.nav().current_dir()), false).into_owned();
     let path_style = theme_cfg.path_style();
 
     let symlink_style = theme_cfg.symlink();
@@ -104,10 +80,10 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
             .border_style(accent_style)
             .border_type(border_type);
         if display_cfg.titles() {
-            outer_block = outer_block.title(Line::from(vec![Span::styled(
-                format!(" {} ", path_str),
-                path_style,
-            )]));
+            let mut title = root_spans(app);
+            title.push(Span::styled(format!(" {} ", path_str), path_style));
+            title.extend(git_spans(app));
+            outer_block = outer_block.title(Line::from(title));
         }
         frame.render_widget(outer_block, root_area);
         root_area = Block::default()
@@ -119,13 +95,10 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
             .direction(Direction::Vertical)
             .constraints([Constraint::Length(1), Constraint::Min(0)])
             .split(root_area);
-        frame.render_widget(
-            Paragraph::new(Line::from(vec![Span::styled(
-                format!("{} ", path_str),
-                path_style,
-            )])),
-            header_layout[0],
-        );
+        let mut header = root_spans(app);
+        header.push(Span::styled(format!("{} ", path_str), path_style));
+        header.extend(git_spans(app));
+        frame.render_widget(Paragraph::new(Line::from(header)), header_layout[0]);
         root_area = header_layout[1];
     }
 
@@ -135,7 +108,7 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
     let show_separators = display_cfg.separators() && !display_cfg.is_split();
 
     // PARENT PANE
-    if display_cfg.parent() && pane_idx < chunks.len() {
+    if app.show_parent() && pane_idx < chunks.len() {
         let parent_dir =
             as_path_op(app.parent().last_path()).or_else(|| app.nav().current_dir().parent());
         let parent_markers = panes::make_pane_markers(
@@ -160,12 +133,14 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
                     dir: theme_cfg.directory_style(),
                     selection: theme_cfg.parent().selection_style_or_theme(),
                     symlink: symlink_style,
+                    files: app.config().file_styles(),
                 },
                 highlight_symbol: "",
                 entry_padding: display_cfg.entry_padding(),
                 padding_str,
-                show_icons: display_cfg.icons(),
+                icons: display_cfg.icons().then(|| app.config().icons()),
                 show_marker: display_cfg.dir_marker(),
+                force_ltr: display_cfg.force_ltr(),
             },
             app.parent().entries(),
             app.parent().selected_idx(),
@@ -200,6 +175,7 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
             dir: theme_cfg.directory_style(),
             selection: selection_style,
             symlink: symlink_style,
+            files: app.config().file_styles(),
         };
 
         panes::draw_main(
@@ -214,12 +190,13 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
                 highlight_symbol: symbol,
                 entry_padding: display_cfg.entry_padding(),
                 padding_str,
-                show_icons: display_cfg.icons(),
+                icons: display_cfg.icons().then(|| app.config().icons()),
                 show_marker: display_cfg.dir_marker(),
+                force_ltr: display_cfg.force_ltr(),
             },
         );
         pane_idx += 1;
-        if show_separators && display_cfg.preview() && pane_idx < chunks.len() {
+        if show_separators && app.show_preview() && pane_idx < chunks.len() {
             widgets::draw_separator(
                 frame,
                 Rect {
@@ -235,7 +212,7 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
     }
 
     // PREVIEW PANE
-    if display_cfg.preview() && pane_idx < chunks.len() {
+    if app.show_preview() && pane_idx < chunks.len() {
         let area = chunks[pane_idx];
         let bg_filler = Block::default().style(theme_cfg.preview().effective_style_or_theme());
         frame.render_widget(bg_filler, area);
@@ -245,6 +222,11 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
             .selected_entry()
             .map(|e| e.is_dir())
             .unwrap_or(false);
+        let dir_size = app
+            .nav()
+            .selected_entry()
+            .filter(|e| e.is_dir())
+            .map(|e| app.nav().dir_size_status(e.name()));
 
         let preview_dir = as_path_op(app.preview().current_path());
         let preview_markers = panes::make_pane_markers(
@@ -269,12 +251,14 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
                     dir: theme_cfg.directory_style(),
                     selection: theme_cfg.preview().selection_style_or_theme(),
                     symlink: symlink_style,
+                    files: app.config().file_styles(),
                 },
                 highlight_symbol: "",
                 entry_padding: display_cfg.entry_padding(),
                 padding_str,
-                show_icons: display_cfg.icons(),
+                icons: display_cfg.icons().then(|| app.config().icons()),
                 show_marker: display_cfg.dir_marker(),
+                force_ltr: display_cfg.force_ltr(),
             },
             app.preview().data(),
             if is_dir {
@@ -286,6 +270,8 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
                 use_underline: display_cfg.preview_underline(),
                 underline_match_text: display_cfg.preview_underline_color(),
                 underline_style: theme_cfg.underline_style(),
+                dir_size,
+                size_format: display_cfg.size_format(),
             },
             &preview_markers,
         );
@@ -293,26 +279,167 @@ pub fn render(frame: &mut Frame, app: &mut AppState) {
 
     // Render Input / Find Dialogs
 
-    widgets::draw_status_line(frame, app);
+    widgets::draw_status_line(frame, app, area);
 
-    if let ActionMode::Input { mode, .. } = app.actions().mode() {
-        if *mode != InputMode::Find {
-            widgets::draw_input_dialog(frame, app, accent_style);
+    if let Some(tutorial) = app.tutorial() {
+        widgets::draw_tutorial_dialog(frame, app, area, accent_style, tutorial);
+    }
+
+    // An inline rename is drawn by the main pane, without a dialog layer
+    let input_mode = match app.actions().mode() {
+        ActionMode::Input { .. } if app.is_inline_rename() => None,
+        ActionMode::Input { mode, .. } => Some(*mode),
+        _ => None,
+    };
+    let overlay_count = app.overlays().iter().len();
+    let top_layer = if overlay_count > 0 {
+        Some(LayerKey::Overlay(overlay_count))
+    } else {
+        input_mode.map(LayerKey::Input)
+    };
+
+    let animate = app.config().animations();
+    let now = Instant::now();
+    let mut snapshot = false;
+    if animate {
+        app.animator_mut().update(top_layer, now);
+        snapshot = app.animator().needs_snapshot();
+    }
+
+    // Snapshot of the buffer before the topmost layer, so it can be animated on its own
+    let mut before = None;
+
+    if let Some(mode) = input_mode {
+        if app.config().theme().widget().dim_background()
+            && (!animate || app.animator().background_dimmed(now))
+        {
+            widgets::dim_background(frame, area);
+        }
+        if snapshot && overlay_count == 0 {
+            before = Some(frame.buffer_mut().clone());
+        }
+        if matches!(mode, InputMode::Find | InputMode::Grep | InputMode::Jump) {
+            widgets::draw_find_dialog(frame, app, area, accent_style);
         } else {
-            widgets::draw_find_dialog(frame, app, accent_style);
+            widgets::draw_input_dialog(frame, app, area, accent_style);
         }
     }
 
-    for overlay in app.overlays().iter() {
+    for (idx, overlay) in app.overlays().iter().enumerate() {
+        if snapshot && idx + 1 == overlay_count {
+            before = Some(frame.buffer_mut().clone());
+        }
         match overlay {
             Overlay::ShowInfo { info } => {
-                widgets::draw_show_info_dialog(frame, app, accent_style, info);
+                widgets::draw_show_info_dialog(frame, app, area, accent_style, info);
+            }
+            Overlay::Stats => {
+                widgets::draw_stats_dialog(frame, app, area, accent_style);
             }
-            Overlay::Message { text } => {
-                widgets::draw_message_overlay(frame, app, accent_style, text);
+            Overlay::MarkedList { marked } => {
+                widgets::draw_marked_dialog(frame, app, area, accent_style, marked);
             }
         }
     }
+
+    if snapshot {
+        let before = before.unwrap_or_else(|| frame.buffer_mut().clone());
+        app.animator_mut()
+            .apply(frame.buffer_mut(), &before, area, now);
+    } else if animate {
+        app.animator_mut().refresh(frame.buffer_mut());
+    }
+
+    widgets::draw_notifications(frame, app, area, accent_style);
+}
+
+/// Builds the banner shown before the path in the header of a root session.
+/// Empty unless the root guard is on, see [RootGuard](crate::app::RootGuard).
+fn root_spans(app: &AppState) -> Vec<Span<'static>> {
+    let Some(guard) = app.root_guard() else {
+        return Vec::new();
+    };
+    let label = if guard.locked() {
+        " ROOT "
+    } else {
+        " ROOT UNLOCKED "
+    };
+    vec![
+        Span::styled(
+            label,
+            Style::default()
+                .fg(Color::White)
+                .bg(Color::Red)
+                .add_modifier(Modifier::BOLD),
+        ),
+        Span::raw(" "),
+    ]
+}
+
+/// Builds the git branch and dirty marker shown after the path in the header.
+/// Empty outside of a git repository.
+fn git_spans(app: &AppState) -> Vec<Span<'static>> {
+    let Some(info) = app.git_info() else {
+        return Vec::new();
+    };
+    let mut spans = vec![Span::styled(
+        format!("({}", info.branch()),
+        Style::default().fg(Color::DarkGray),
+    )];
+    if info.dirty() {
+        spans.push(Span::styled("*", Style::default().fg(Color::Yellow)));
+    }
+    spans.push(Span::styled(") ", Style::default().fg(Color::DarkGray)));
+    spans
+}
+
+/// Computes the text space of the panes shown in `area`, used to size the worker requests.
+///
+/// The area is remembered in the metrics, so they can be computed again when a pane is toggled
+/// before the next frame is drawn.
+pub fn layout_metrics(area: Rect, app: &AppState) -> LayoutMetrics {
+    let chunks = layout_chunks(area, app);
+    // Hidden panes have no space
+    let mut metrics = LayoutMetrics {
+        parent_width: 0,
+        preview_width: 0,
+        preview_height: 0,
+        area,
+        ..LayoutMetrics::default()
+    };
+    let display_cfg = app.config().display();
+
+    let mut current_idx = 0;
+    let has_sep = display_cfg.separators() && !display_cfg.is_split();
+
+    // Helper to determine inner space available for text
+    let get_inner = |rect: ratatui::layout::Rect| {
+        let width = if display_cfg.is_split() || display_cfg.is_unified() {
+            rect.width.saturating_sub(2)
+        } else {
+            rect.width
+        };
+        let height = rect.height.saturating_sub(2);
+        (width as usize, height as usize)
+    };
+
+    if app.show_parent() && current_idx < chunks.len() {
+        metrics.parent_width = get_inner(chunks[current_idx]).0;
+        current_idx += if has_sep { 2 } else { 1 };
+    }
+
+    if current_idx < chunks.len() {
+        metrics.main_width = get_inner(chunks[current_idx]).0;
+        current_idx += if has_sep && app.show_preview() { 2 } else { 1 };
+    }
+
+    if app.show_preview() && current_idx < chunks.len() {
+        let (width, height) = get_inner(chunks[current_idx]);
+        metrics.preview_width = width;
+        metrics.preview_height = height;
+    }
+
+    metrics
 }
 
 /// Returns the rectangular areas for all active panes, given the current configuration
@@ -324,22 +451,22 @@ pub fn layout_chunks(size: Rect, app: &AppState) -> Vec<Rect> {
     let mut constraints = Vec::new();
     let show_sep = cfg.separators() && !cfg.is_split();
 
-    let parent = if cfg.parent() {
+    let parent = if app.show_parent() {
         cfg.parent_ratio() as u32
     } else {
         0
     };
     let main = cfg.main_ratio() as u32;
-    let preview = if cfg.preview() {
+    let preview = if app.show_preview() {
         cfg.preview_ratio() as u32
     } else {
         0
     };
 
     let enabled = [
-        (parent, cfg.parent()),
+        (parent, app.show_parent()),
         (main, true),
-        (preview, cfg.preview()),
+        (preview, app.show_preview()),
     ];
 
     let total: u32 = enabled
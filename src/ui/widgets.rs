@@ -12,6 +12,7 @@ pub mod dialog;
 pub mod draw;
 
 pub use dialog::{
-    DialogLayout, DialogPosition, DialogSize, DialogStyle, dialog_area, draw_dialog, get_pane_block,
+    DialogAnchor, DialogLayout, DialogPosition, DialogSize, DialogStyle, dialog_area,
+    dim_background, draw_dialog, get_pane_block,
 };
 pub use draw::*;
@@ -8,7 +8,7 @@ use ratatui::widgets::BorderType;
 use ratatui::{
     Frame,
     layout::{Alignment, Rect},
-    style::{Color, Style},
+    style::{Color, Modifier, Style},
     text::{Span, Text},
     widgets::{Block, Borders, Clear, Paragraph},
 };
@@ -39,6 +39,58 @@ pub enum DialogPosition {
     BottomLeft,
     BottomRight,
     Custom(u16, u16),
+    Anchored(DialogAnchor, i16, i16),
+}
+
+/// Anchor points for [DialogPosition::Anchored] positions.
+///
+/// The dialog is first placed at the anchor, then nudged by the given offset in cells.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DialogAnchor {
+    Center,
+    Top,
+    Bottom,
+    Left,
+    Right,
+    TopLeft,
+    TopRight,
+    BottomLeft,
+    BottomRight,
+}
+
+impl DialogAnchor {
+    /// Parses an anchor name, accepting both "top_left" and "topleft" styles.
+    fn from_name(name: &str) -> Option<Self> {
+        let name = name.to_ascii_lowercase().replace('_', "");
+        match name.as_str() {
+            "center" => Some(DialogAnchor::Center),
+            "top" => Some(DialogAnchor::Top),
+            "bottom" => Some(DialogAnchor::Bottom),
+            "left" => Some(DialogAnchor::Left),
+            "right" => Some(DialogAnchor::Right),
+            "topleft" => Some(DialogAnchor::TopLeft),
+            "topright" => Some(DialogAnchor::TopRight),
+            "bottomleft" => Some(DialogAnchor::BottomLeft),
+            "bottomright" => Some(DialogAnchor::BottomRight),
+            _ => None,
+        }
+    }
+}
+
+impl From<DialogAnchor> for DialogPosition {
+    fn from(anchor: DialogAnchor) -> Self {
+        match anchor {
+            DialogAnchor::Center => DialogPosition::Center,
+            DialogAnchor::Top => DialogPosition::Top,
+            DialogAnchor::Bottom => DialogPosition::Bottom,
+            DialogAnchor::Left => DialogPosition::Left,
+            DialogAnchor::Right => DialogPosition::Right,
+            DialogAnchor::TopLeft => DialogPosition::TopLeft,
+            DialogAnchor::TopRight => DialogPosition::TopRight,
+            DialogAnchor::BottomLeft => DialogPosition::BottomLeft,
+            DialogAnchor::BottomRight => DialogPosition::BottomRight,
+        }
+    }
 }
 
 /// Deserialize so that the runa.toml custom position and size can be made simpler instead of just
@@ -47,6 +99,7 @@ pub enum DialogPosition {
 /// position = "bottomright"
 /// position = [25, 60]
 /// position = { x = 42, y = 80 }
+/// position = { anchor = "bottom_right", dx = -2, dy = -1 }
 impl<'de> Deserialize<'de> for DialogPosition {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -57,7 +110,17 @@ impl<'de> Deserialize<'de> for DialogPosition {
         enum Helper {
             Str(String),
             Arr([u16; 2]),
-            XY { x: u16, y: u16 },
+            XY {
+                x: u16,
+                y: u16,
+            },
+            Anchored {
+                anchor: String,
+                #[serde(default)]
+                dx: i16,
+                #[serde(default)]
+                dy: i16,
+            },
         }
 
         match Helper::deserialize(deserializer)? {
@@ -91,25 +154,33 @@ impl<'de> Deserialize<'de> for DialogPosition {
             Helper::Str(s) => Err(D::Error::custom(format!("invalid DialogPosition: '{}'", s))),
             Helper::Arr([x, y]) => Ok(DialogPosition::Custom(x, y)),
             Helper::XY { x, y } => Ok(DialogPosition::Custom(x, y)),
+            Helper::Anchored { anchor, dx, dy } => DialogAnchor::from_name(&anchor)
+                .map(|a| DialogPosition::Anchored(a, dx, dy))
+                .ok_or_else(|| D::Error::custom(format!("invalid dialog anchor: '{}'", anchor))),
         }
     }
 }
 
 /// Preset for all dialogs/widgets sizes as well as a customized size via the runa.toml
+///
+/// `Cells` is an exact size in columns and rows, `Percent` is relative to the frame.
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub enum DialogSize {
     Small,
     Medium,
     Large,
-    Custom(u16, u16),
+    Cells(u16, u16),
+    Percent(u16, u16),
 }
 
 /// Deserializer so that the runa.toml configuration can be made simpler to configure the size of
 /// dialogs/widgets
 ///
 /// size = "small"
-/// size = [10, 10]
-/// size = { w = 10, h = 20 }
+/// size = "40x10"      (cells)
+/// size = "40%x10%"    (percent of the screen)
+/// size = [10, 10]     (cells)
+/// size = { w = 10, h = 20 } (cells)
 impl<'de> Deserialize<'de> for DialogSize {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
@@ -127,9 +198,10 @@ impl<'de> Deserialize<'de> for DialogSize {
             Helper::Str(ref s) if s.eq_ignore_ascii_case("small") => Ok(DialogSize::Small),
             Helper::Str(ref s) if s.eq_ignore_ascii_case("medium") => Ok(DialogSize::Medium),
             Helper::Str(ref s) if s.eq_ignore_ascii_case("large") => Ok(DialogSize::Large),
-            Helper::Str(s) => Err(D::Error::custom(format!("invalid DialogSize: '{}'", s))),
-            Helper::Arr([w, h]) => Ok(DialogSize::Custom(w, h)),
-            Helper::Obj { w, h } => Ok(DialogSize::Custom(w, h)),
+            Helper::Str(s) => DialogSize::parse_dimensions(&s)
+                .ok_or_else(|| D::Error::custom(format!("invalid DialogSize: '{}'", s))),
+            Helper::Arr([w, h]) => Ok(DialogSize::Cells(w, h)),
+            Helper::Obj { w, h } => Ok(DialogSize::Cells(w, h)),
         }
     }
 }
@@ -137,26 +209,50 @@ impl<'de> Deserialize<'de> for DialogSize {
 impl DialogSize {
     /// preset for dialog size percentages
     ///
-    /// Returns the (width, height) -percentages of the dialog
-    pub fn percentages(&self) -> (u16, u16) {
+    /// Returns the (width, height) -percentages of the dialog, or None for cell based sizes
+    pub fn percentages(&self) -> Option<(u16, u16)> {
         match self {
-            DialogSize::Small => (24, 7),
-            DialogSize::Medium => (26, 14),
-            DialogSize::Large => (32, 40),
-            DialogSize::Custom(w, h) => (*w, *h),
+            DialogSize::Small => Some((24, 7)),
+            DialogSize::Medium => Some((26, 14)),
+            DialogSize::Large => Some((32, 40)),
+            DialogSize::Percent(w, h) => Some((*w, *h)),
+            DialogSize::Cells(..) => None,
+        }
+    }
+
+    /// Parses a "WxH" size string, either in cells ("40x10") or in percent ("40%x10%").
+    ///
+    /// Mixing cells and percent is not allowed and percentages above 100 are rejected.
+    fn parse_dimensions(s: &str) -> Option<DialogSize> {
+        let (w, h) = s
+            .trim()
+            .to_ascii_lowercase()
+            .split_once('x')
+            .map(|(w, h)| (w.trim().to_owned(), h.trim().to_owned()))?;
+
+        match (w.strip_suffix('%'), h.strip_suffix('%')) {
+            (Some(w), Some(h)) => {
+                let (w, h) = (w.trim().parse::<u16>().ok()?, h.trim().parse::<u16>().ok()?);
+                (w <= 100 && h <= 100).then_some(DialogSize::Percent(w, h))
+            }
+            (None, None) => Some(DialogSize::Cells(w.parse().ok()?, h.parse().ok()?)),
+            _ => None,
         }
     }
 }
 
 /// Struct to hold the dialog style.
 ///
-/// Includes the dialog border, border_style, the background/foreground and the title.
+/// Includes the dialog border, border_style, the background/foreground and the title,
+/// as well as the optional drop shadow and transparent background.
 pub struct DialogStyle {
     pub border: Borders,
     pub border_style: Style,
     pub bg: Style,
     pub fg: Style,
     pub title: Option<Span<'static>>,
+    pub shadow: bool,
+    pub transparent: bool,
 }
 
 impl Default for DialogStyle {
@@ -167,6 +263,8 @@ impl Default for DialogStyle {
             bg: Style::default().bg(Color::Black),
             fg: Style::default().fg(Color::Reset),
             title: None,
+            shadow: false,
+            transparent: false,
         }
     }
 }
@@ -186,22 +284,17 @@ pub fn dialog_area(area: Rect, size: DialogSize, pos: DialogPosition) -> Rect {
     let min_h = 3;
 
     let (w, h) = match size {
-        DialogSize::Small => (
-            (area.width * 24 / 100).max(min_w).min(area.width),
-            (area.height * 7 / 100).max(min_h).min(area.height),
-        ),
-        DialogSize::Medium => (
-            (area.width * 26 / 100).max(min_w).min(area.width),
-            (area.height * 14 / 100).max(min_h).min(area.height),
-        ),
-        DialogSize::Large => (
-            (area.width * 32 / 100).max(min_w).min(area.width),
-            (area.height * 40 / 100).max(min_h).min(area.height),
-        ),
-        DialogSize::Custom(w_cells, h_cells) => (
+        DialogSize::Cells(w_cells, h_cells) => (
             w_cells.max(min_w).min(area.width),
             h_cells.max(min_h).min(area.height),
         ),
+        _ => {
+            let (w_pct, h_pct) = size.percentages().unwrap_or((100, 100));
+            (
+                percent_of(area.width, w_pct).max(min_w).min(area.width),
+                percent_of(area.height, h_pct).max(min_h).min(area.height),
+            )
+        }
     };
 
     match pos {
@@ -259,6 +352,15 @@ pub fn dialog_area(area: Rect, size: DialogSize, pos: DialogPosition) -> Rect {
             width: w,
             height: h,
         },
+        DialogPosition::Anchored(anchor, dx, dy) => {
+            let base = dialog_area(area, DialogSize::Cells(w, h), anchor.into());
+            Rect {
+                x: nudge(base.x, dx, area.x, area.x + area.width - w),
+                y: nudge(base.y, dy, area.y, area.y + area.height - h),
+                width: w,
+                height: h,
+            }
+        }
         DialogPosition::Custom(xp, yp) => {
             let x = area.x + ((area.width - w) * xp / 100).min(area.width - w);
             let y = area.y + ((area.height - h) * yp / 100).min(area.height - h);
@@ -272,6 +374,16 @@ pub fn dialog_area(area: Rect, size: DialogSize, pos: DialogPosition) -> Rect {
     }
 }
 
+/// Helper to compute `pct` percent of `total` cells without overflowing for large terminals.
+fn percent_of(total: u16, pct: u16) -> u16 {
+    (u32::from(total) * u32::from(pct.min(100)) / 100) as u16
+}
+
+/// Helper to move a coordinate by a signed offset, keeping it within `min..=max`.
+fn nudge(pos: u16, offset: i16, min: u16, max: u16) -> u16 {
+    pos.saturating_add_signed(offset).clamp(min, max.max(min))
+}
+
 /// Draws the dialog widgets
 /// Takes the frame area as a rect, sets the position of the dialog and the overall style.
 pub fn draw_dialog<'a, T>(
@@ -286,13 +398,29 @@ pub fn draw_dialog<'a, T>(
 {
     let dialog = dialog_area(layout.area, layout.size, layout.position);
 
+    if style.shadow {
+        draw_shadow(frame, dialog, layout.area);
+    }
     frame.render_widget(Clear, dialog);
 
+    // A transparent dialog leaves the background color to the terminal
+    let (bg, fg) = if style.transparent {
+        (
+            Style::default(),
+            Style {
+                bg: None,
+                ..style.fg
+            },
+        )
+    } else {
+        (style.bg, style.fg)
+    };
+
     let mut block = Block::default()
         .borders(style.border)
         .border_style(style.border_style)
         .border_type(border)
-        .style(style.bg);
+        .style(bg);
 
     if let Some(title) = &style.title {
         block = block.title(title.clone());
@@ -302,11 +430,45 @@ pub fn draw_dialog<'a, T>(
     let para = Paragraph::new(content.into())
         .block(block)
         .alignment(alignment.unwrap_or(Alignment::Left))
-        .style(style.fg);
+        .style(fg);
 
     frame.render_widget(para, dialog);
 }
 
+/// Draws a one cell drop shadow along the right and bottom edge of the dialog.
+/// The cells under the shadow keep their symbols, but are darkened.
+/// The shadow is clipped to `area`, the area the dialog is positioned in.
+fn draw_shadow(frame: &mut Frame, dialog: Rect, area: Rect) {
+    let shadow_style = Style::default().fg(Color::DarkGray).bg(Color::Black);
+    let right = Rect {
+        x: dialog.right(),
+        y: dialog.y.saturating_add(1),
+        width: 1,
+        height: dialog.height,
+    }
+    .intersection(area);
+    let bottom = Rect {
+        x: dialog.x.saturating_add(1),
+        y: dialog.bottom(),
+        width: dialog.width,
+        height: 1,
+    }
+    .intersection(area);
+
+    let buf = frame.buffer_mut();
+    for edge in [right, bottom] {
+        buf.set_style(edge, shadow_style);
+    }
+}
+
+/// Dims everything drawn so far, used to push the panes into the background
+/// while a modal dialog is open. Only `area` is dimmed.
+pub fn dim_background(frame: &mut Frame, area: Rect) {
+    frame
+        .buffer_mut()
+        .set_style(area, Style::default().add_modifier(Modifier::DIM));
+}
+
 /// Getter for the overall pane block,
 pub fn get_pane_block(title: &str, app: &AppState) -> Block<'static> {
     let mut block = Block::default();
@@ -5,20 +5,26 @@
 //! All draw functions are then used by ui::rende] to then draw widgets such a input dialog,
 //! which is used by file action functions like rename and more..
 
-use crate::app::AppState;
 use crate::app::actions::{ActionMode, InputMode};
-use crate::core::{FileInfo, FileType, format_file_size, format_file_time, format_file_type};
+use crate::app::{AppState, Severity, Tutorial};
+use crate::config::theme::DialogKind;
+use crate::core::{
+    FileInfo, FileType, FindKind, display_safe_name, format_dir_items, format_file_size,
+    format_file_time, format_file_type,
+};
+use crate::ui::overlays::MarkedState;
 use crate::ui::widgets::{
     DialogLayout, DialogPosition, DialogSize, DialogStyle, dialog_area, draw_dialog,
 };
+use crate::utils::{expand_create_input, shorten_home_path};
+use crossterm::event::KeyCode;
 use ratatui::{
     Frame,
     layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span, Text},
-    widgets::{Block, Borders, Paragraph},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
 };
-use std::time::Instant;
 use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
 
 /// Draws the seperator line when enabled inside runa.toml
@@ -29,18 +35,63 @@ pub fn draw_separator(frame: &mut Frame, area: Rect, style: Style) {
     );
 }
 
-/// Either for ConfirmDelete or for anything else that requires input.
-/// For other than ConfirmDelete, calculates the exact input field.
-pub fn draw_input_dialog(frame: &mut Frame, app: &AppState, accent_style: Style) {
+/// Either for the confirm prompts (ConfirmDelete, ConfirmResume, ConfirmRetry, ConfirmOverwrite, OpenWith) or for anything else that requires input.
+/// For other than the confirm prompts, calculates the exact input field.
+pub fn draw_input_dialog(frame: &mut Frame, app: &AppState, area: Rect, accent_style: Style) {
     if let ActionMode::Input { mode, prompt } = &app.actions().mode() {
-        let widget = app.config().theme().widget();
+        if *mode == InputMode::Template {
+            draw_template_dialog(frame, app, area, accent_style, prompt);
+            return;
+        }
+        let base_widget = app.config().theme().widget();
+        let widget = &match mode {
+            InputMode::ConfirmDelete
+            | InputMode::ConfirmResume
+            | InputMode::ConfirmRetry
+            | InputMode::ConfirmOverwrite
+            | InputMode::OpenWith => base_widget.for_dialog(DialogKind::Confirm),
+            InputMode::Rename => base_widget.for_dialog(DialogKind::Rename),
+            _ => base_widget.clone(),
+        };
         let position = dialog_position_unified(widget.position(), app, DialogPosition::Center);
         let size = widget.size().unwrap_or(DialogSize::Small);
         let confirm_size = widget.confirm_size_or(DialogSize::Large);
         let border_type = app.config().display().border_shape().as_border_type();
 
-        if *mode == InputMode::ConfirmDelete {
-            let action_targets = app.nav().get_action_targets();
+        if matches!(
+            mode,
+            InputMode::ConfirmDelete
+                | InputMode::ConfirmResume
+                | InputMode::ConfirmRetry
+                | InputMode::ConfirmOverwrite
+                | InputMode::OpenWith
+        ) {
+            let is_delete = *mode == InputMode::ConfirmDelete;
+            let delete = app.actions().delete_state();
+            let action_targets = match mode {
+                InputMode::ConfirmDelete => delete.targets(),
+                InputMode::ConfirmRetry => app.actions().busy_targets(),
+                InputMode::ConfirmOverwrite => app.actions().conflict_targets(),
+                InputMode::OpenWith => &[],
+                _ => app.actions().resume_targets(),
+            };
+            let (title, verb, title_style) = match mode {
+                InputMode::ConfirmDelete => (
+                    " Confirm Delete ",
+                    "delete",
+                    Style::default().fg(Color::Red),
+                ),
+                InputMode::ConfirmRetry => {
+                    (" File In Use ", "retry", widget.title_style_or_theme())
+                }
+                InputMode::ConfirmOverwrite => (
+                    " Name Taken ",
+                    "overwrite",
+                    Style::default().fg(Color::Yellow),
+                ),
+                InputMode::OpenWith => (" Open With ", "open", widget.title_style_or_theme()),
+                _ => (" Resume Copy ", "resume", widget.title_style_or_theme()),
+            };
             let targets: Vec<String> = action_targets
                 .iter()
                 .map(|p| {
@@ -49,37 +100,93 @@ pub fn draw_input_dialog(frame: &mut Frame, app: &AppState, accent_style: Style)
                         .unwrap_or_default()
                 })
                 .collect();
-            let preview = if targets.len() == 1 {
-                format!("\nFile to delete: {}", targets[0])
-            } else if targets.len() > 1 {
-                format!(
-                    "\nFiles to delete ({}):\n{}",
-                    targets.len(),
-                    targets
-                        .iter()
-                        .map(|n| format!("  - {}", n))
-                        .collect::<Vec<_>>()
-                        .join("\n")
-                )
-            } else {
-                String::new()
-            };
+
+            // The configured confirm size is the maximum, the dialog shrinks to its content
+            let max_area = dialog_area(area, confirm_size, position);
+            let max_rows = max_area.height.saturating_sub(2) as usize;
+
+            let mut lines = vec![Line::from(prompt.as_str())];
+            if targets.len() == 1 && !is_delete {
+                lines.push(Line::from(format!("File to {}: {}", verb, targets[0])));
+            } else if !targets.is_empty() {
+                lines.push(Line::from(format!(
+                    "Files to {} ({}):",
+                    verb,
+                    targets.len()
+                )));
+
+                // Keep the selected target in view and reserve a row for the overflow notice
+                let mut rows = max_rows.saturating_sub(lines.len()).max(1);
+                if targets.len() > rows {
+                    rows = rows.saturating_sub(1).max(1);
+                }
+                let selected = if is_delete { delete.selected() } else { 0 };
+                let offset = (selected + 1).saturating_sub(rows);
+
+                for (idx, name) in targets.iter().enumerate().skip(offset).take(rows) {
+                    if !is_delete {
+                        lines.push(Line::from(format!("  - {}", name)));
+                        continue;
+                    }
+                    let check = if delete.is_excluded(idx) {
+                        "[ ]"
+                    } else {
+                        "[x]"
+                    };
+                    let mut style = Style::default();
+                    if delete.is_excluded(idx) {
+                        style = style.add_modifier(Modifier::DIM | Modifier::CROSSED_OUT);
+                    }
+                    if idx == selected {
+                        style = style.add_modifier(Modifier::REVERSED);
+                    }
+                    lines.push(Line::from(Span::styled(
+                        format!("  {} {}", check, name),
+                        style,
+                    )));
+                }
+
+                let hidden_above = offset;
+                let hidden_below = targets.len().saturating_sub(offset + rows);
+                if hidden_above + hidden_below > 0 {
+                    let notice = match (hidden_above, hidden_below) {
+                        (0, below) => format!("  \u{2026}and {} more", below),
+                        (above, 0) => format!("  \u{2026}{} above", above),
+                        (above, below) => {
+                            format!("  \u{2026}{} above, {} more below", above, below)
+                        }
+                    };
+                    lines.push(Line::from(Span::styled(
+                        notice,
+                        Style::default().add_modifier(Modifier::DIM),
+                    )));
+                }
+            }
+
+            let content_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+            let content_size = DialogSize::Cells(
+                content_width.saturating_add(4).min(max_area.width),
+                (lines.len() as u16).saturating_add(2).min(max_area.height),
+            );
 
             let dialog_style = DialogStyle {
                 border: Borders::ALL,
-                border_style: widget.border_style_or(Style::default().fg(Color::Red)),
+                border_style: if is_delete {
+                    widget.border_style_or(Style::default().fg(Color::Red))
+                } else {
+                    widget.border_style_or(accent_style)
+                },
                 bg: widget.bg_or_theme(),
                 fg: widget.fg_or_theme(),
-                title: Some(Span::styled(
-                    " Confirm Delete ",
-                    Style::default().fg(Color::Red),
-                )),
+                shadow: widget.shadow(),
+                transparent: widget.transparent(),
+                title: Some(Span::styled(title, title_style)),
             };
 
             let dialog_layout = DialogLayout {
-                area: frame.area(),
+                area,
                 position,
-                size: confirm_size,
+                size: content_size,
             };
 
             draw_dialog(
@@ -87,70 +194,373 @@ pub fn draw_input_dialog(frame: &mut Frame, app: &AppState, accent_style: Style)
                 dialog_layout,
                 border_type,
                 &dialog_style,
-                format!("{prompt}{preview}"),
+                lines,
                 Some(Alignment::Left),
             );
         } else {
+            // The filter is applied live, so flag invalid regex/glob patterns in the title.
+            // File names are checked as they are typed, non-portable names only warn.
+            let input_error = match mode {
+                InputMode::Filter => app
+                    .nav()
+                    .filter_error()
+                    .map(|err| (err.to_string(), Color::Red)),
+                InputMode::NewFile | InputMode::NewFolder | InputMode::Rename => {
+                    app.actions().name_issue().map(|issue| {
+                        let color = if issue.is_invalid() {
+                            Color::Red
+                        } else {
+                            Color::Yellow
+                        };
+                        (issue.message().to_string(), color)
+                    })
+                }
+                _ => None,
+            };
+            let title = match input_error {
+                Some((err, color)) => Span::styled(
+                    format!(" {} ({}) ", prompt, err),
+                    Style::default().fg(color),
+                ),
+                None => Span::styled(format!(" {} ", prompt), widget.title_style_or_theme()),
+            };
+
             let dialog_style = DialogStyle {
                 border: Borders::ALL,
                 border_style: widget.border_style_or(accent_style),
                 bg: widget.bg_or_theme(),
                 fg: widget.fg_or_theme(),
-                title: Some(Span::styled(
-                    format!(" {} ", prompt),
-                    widget.title_style_or_theme(),
-                )),
+                shadow: widget.shadow(),
+                transparent: widget.transparent(),
+                title: Some(title),
             };
 
             let dialog_layout = DialogLayout {
-                area: frame.area(),
+                area,
                 position,
                 size,
             };
 
             let input_text = app.actions().input_buffer();
             let cursor_pos = app.actions().input_cursor_pos();
-            let dialog_area = dialog_area(frame.area(), size, position);
+            let dialog_area = dialog_area(area, size, position);
             let visible_width = dialog_area.width.saturating_sub(2) as usize;
 
             let (display_input, cursor_offset) =
                 input_field_view(input_text, cursor_pos, visible_width);
 
+            let hidden = input_text.len() - display_input.len();
+            let content = input_line(app, display_input, hidden);
+
             draw_dialog(
                 frame,
                 dialog_layout,
                 border_type,
                 &dialog_style,
-                display_input,
+                content,
                 Some(Alignment::Left),
             );
 
             frame
                 .set_cursor_position((dialog_area.x + 1 + cursor_offset as u16, dialog_area.y + 1));
+
+            if matches!(mode, InputMode::NewFile | InputMode::NewFolder) {
+                draw_create_preview(frame, app, area, dialog_area, border_type, &dialog_style);
+            }
         }
     }
 }
 
-/// Draw the status line at the top right
-/// Used for indication of number of copied/yanked files and the current applied filter
-pub fn draw_status_line(frame: &mut Frame, app: &AppState) {
-    let area = frame.area();
+/// Lists every marked path across directories with its size and the total size.
+/// The selected path is kept in view, like in the delete confirmation.
+/// Sizes still being computed by the size worker are shown as `~`.
+pub fn draw_marked_dialog(
+    frame: &mut Frame,
+    app: &AppState,
+    area: Rect,
+    accent_style: Style,
+    marked: &MarkedState,
+) {
+    let widget = app.config().theme().widget();
+    let position = dialog_position_unified(widget.position(), app, DialogPosition::Center);
+    let border_type = app.config().display().border_shape().as_border_type();
+    let targets = marked.targets();
+    let force_ltr = app.config().display().force_ltr();
+    let size_format = app.config().display().size_format();
+
+    let max_area = dialog_area(area, widget.confirm_size_or(DialogSize::Large), position);
+    let max_rows = max_area.height.saturating_sub(2) as usize;
+
+    let (_, total, sizes_complete) = app.marked_summary();
+    let pending = if sizes_complete { "" } else { "~" };
+    let mut lines = vec![Line::from(format!(
+        "Total size: {pending}{}",
+        format_file_size(Some(total), false, size_format)
+    ))];
+
+    // Reserve a row for the overflow notice and one for the key hints
+    let mut rows = max_rows.saturating_sub(lines.len() + 1).max(1);
+    if targets.len() > rows {
+        rows = rows.saturating_sub(1).max(1);
+    }
+    let selected = marked.selected();
+    let offset = (selected + 1).saturating_sub(rows);
+
+    for (idx, path) in targets.iter().enumerate().skip(offset).take(rows) {
+        let style = if idx == selected {
+            Style::default().add_modifier(Modifier::REVERSED)
+        } else {
+            Style::default()
+        };
+        lines.push(Line::from(vec![
+            Span::styled(
+                format!(
+                    "  {}",
+                    display_safe_name(&shorten_home_path(path), force_ltr)
+                ),
+                style,
+            ),
+            Span::styled(
+                format!(
+                    "  {}",
+                    app.marked_size(path).map_or_else(
+                        || "~".to_string(),
+                        |size| format_file_size(Some(size), false, size_format)
+                    )
+                ),
+                Style::default().add_modifier(Modifier::DIM),
+            ),
+        ]));
+    }
+
+    let hidden = targets.len().saturating_sub(rows);
+    if hidden > 0 {
+        lines.push(Line::from(Span::styled(
+            format!("  \u{2026}{} of {} shown", rows, targets.len()),
+            Style::default().add_modifier(Modifier::DIM),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        "Enter: jump  Space/u: unmark  Esc: close",
+        Style::default().add_modifier(Modifier::DIM),
+    )));
+
+    let content_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+    let content_size = DialogSize::Cells(
+        content_width.saturating_add(4).min(max_area.width),
+        (lines.len() as u16).saturating_add(2).min(max_area.height),
+    );
+
+    let dialog_style = DialogStyle {
+        border: Borders::ALL,
+        border_style: widget.border_style_or(accent_style),
+        bg: widget.bg_or_theme(),
+        fg: widget.fg_or_theme(),
+        shadow: widget.shadow(),
+        transparent: widget.transparent(),
+        title: Some(Span::styled(
+            format!(
+                " {} marked item{} ",
+                targets.len(),
+                if targets.len() == 1 { "" } else { "s" }
+            ),
+            widget.title_style_or_theme(),
+        )),
+    };
+
+    let dialog_layout = DialogLayout {
+        area,
+        position,
+        size: content_size,
+    };
+
+    draw_dialog(
+        frame,
+        dialog_layout,
+        border_type,
+        &dialog_style,
+        lines,
+        Some(Alignment::Left),
+    );
+}
+
+/// Lists the templates matching the new files, below the option to create them empty.
+fn draw_template_dialog(
+    frame: &mut Frame,
+    app: &AppState,
+    area: Rect,
+    accent_style: Style,
+    prompt: &str,
+) {
+    let widget = app.config().theme().widget();
+    let position = dialog_position_unified(widget.position(), app, DialogPosition::Center);
+    let border_type = app.config().display().border_shape().as_border_type();
+    let state = app.actions().template_state();
+
+    let options = std::iter::once("Empty file").chain(state.templates().iter().map(String::as_str));
+    let mut lines: Vec<Line> = options
+        .enumerate()
+        .map(|(idx, name)| {
+            let style = if idx == state.selected() {
+                Style::default().add_modifier(Modifier::REVERSED)
+            } else {
+                Style::default()
+            };
+            Line::from(Span::styled(format!("  {}", name), style))
+        })
+        .collect();
+    lines.push(Line::from(Span::styled(
+        "Enter: create  Esc: cancel",
+        Style::default().add_modifier(Modifier::DIM),
+    )));
+
+    let max_area = dialog_area(area, widget.confirm_size_or(DialogSize::Large), position);
+    let content_width = lines.iter().map(Line::width).max().unwrap_or(0) as u16;
+    let content_size = DialogSize::Cells(
+        content_width.saturating_add(4).min(max_area.width),
+        (lines.len() as u16).saturating_add(2).min(max_area.height),
+    );
+
+    let dialog_style = DialogStyle {
+        border: Borders::ALL,
+        border_style: widget.border_style_or(accent_style),
+        bg: widget.bg_or_theme(),
+        fg: widget.fg_or_theme(),
+        shadow: widget.shadow(),
+        transparent: widget.transparent(),
+        title: Some(Span::styled(
+            format!(" {} ", prompt),
+            widget.title_style_or_theme(),
+        )),
+    };
+
+    let dialog_layout = DialogLayout {
+        area,
+        position,
+        size: content_size,
+    };
+
+    draw_dialog(
+        frame,
+        dialog_layout,
+        border_type,
+        &dialog_style,
+        lines,
+        Some(Alignment::Left),
+    );
+}
+
+/// Lists the entries a brace expanded create prompt will create, right below the input dialog.
+/// Nothing is drawn for a single name.
+fn draw_create_preview(
+    frame: &mut Frame,
+    app: &AppState,
+    area: Rect,
+    input_area: Rect,
+    border_type: BorderType,
+    input_style: &DialogStyle,
+) {
+    let (names, _) = expand_create_input(app.actions().input_buffer());
+    if names.len() < 2 {
+        return;
+    }
+
+    let below = Rect {
+        x: input_area.x,
+        y: input_area.bottom(),
+        width: input_area.width,
+        height: area.bottom().saturating_sub(input_area.bottom()),
+    };
+    if below.height < 3 {
+        return;
+    }
 
-    let count = match app.actions().clipboard() {
-        Some(set) => set.len(),
-        None => 0,
+    // Reserve a row for the overflow notice if not all names fit
+    let max_rows = below.height.saturating_sub(2) as usize;
+    let mut rows = names.len().min(max_rows);
+    if names.len() > rows {
+        rows = rows.saturating_sub(1);
+    }
+    let mut lines: Vec<Line> = names[..rows]
+        .iter()
+        .map(|name| Line::from(name.as_str()))
+        .collect();
+    if names.len() > rows {
+        lines.push(Line::from(format!("... and {} more", names.len() - rows)));
+    }
+
+    let style = DialogStyle {
+        border: Borders::ALL,
+        border_style: input_style.border_style,
+        bg: input_style.bg,
+        fg: input_style.fg,
+        shadow: input_style.shadow,
+        transparent: input_style.transparent,
+        title: Some(Span::styled(
+            format!(" Creates {} entries ", names.len()),
+            input_style.border_style,
+        )),
     };
+    let layout = DialogLayout {
+        area: below,
+        position: DialogPosition::Top,
+        size: DialogSize::Cells(below.width, lines.len() as u16 + 2),
+    };
+    draw_dialog(
+        frame,
+        layout,
+        border_type,
+        &style,
+        lines,
+        Some(Alignment::Left),
+    );
+}
+
+/// Draw the status line at the top right
+/// Used for indication of marked entries, the current applied filter and the active profile
+pub fn draw_status_line(frame: &mut Frame, app: &AppState, area: Rect) {
     let filter = app.nav().filter();
-    let now = Instant::now();
 
     let mut parts = Vec::new();
-    if count > 0 && (app.notification_time().is_some_and(|until| until > now)) {
-        let yank_msg = { format!("Yanked files: {count}") };
-        parts.push(yank_msg);
+    if app.readonly() {
+        parts.push("Read-only".to_string());
+    }
+    let (marked, marked_size, sizes_complete) = app.marked_summary();
+    if marked > 0 {
+        let size = format_file_size(
+            Some(marked_size),
+            false,
+            app.config().display().size_format(),
+        );
+        let pending = if sizes_complete { "" } else { "\u{2026}" };
+        parts.push(format!("Marked: {marked} ({size}{pending})"));
     }
     if !filter.is_empty() {
         parts.push(format!("Filter: \"{filter}\""));
     }
+    if let Some(profile) = app.active_profile() {
+        parts.push(format!("Profile: {profile}"));
+    }
+    // Only a runtime override of instant_preview is shown, the configured state is the norm
+    if app.instant_preview() != app.default_instant_preview() {
+        let state = if app.instant_preview() { "on" } else { "off" };
+        parts.push(format!("Instant preview: {state}"));
+    }
+    if app.show_hidden() != app.config().show_hidden() {
+        let state = if app.show_hidden() { "shown" } else { "hidden" };
+        parts.push(format!("Hidden: {state}"));
+    }
+    if !app.pending_keys().is_empty() {
+        let keys: Vec<String> = app
+            .pending_keys()
+            .iter()
+            .map(|key| match key.code {
+                KeyCode::Char(' ') => "Space".to_string(),
+                code => code.to_string(),
+            })
+            .collect();
+        parts.push(format!("Keys: {}", keys.join(" ")));
+    }
 
     let msg = parts.join(" | ");
     if !msg.is_empty() {
@@ -169,6 +579,82 @@ pub fn draw_status_line(frame: &mut Frame, app: &AppState) {
     }
 }
 
+/// Draws the rename input over the name of the selected row in the main pane, so the sibling
+/// names stay in view while typing.
+///
+/// # Arguments
+/// * `area` - The part of the row from the name to the end of the row.
+/// * `style` - The style of the selected row.
+pub fn draw_inline_rename(frame: &mut Frame, app: &AppState, area: Rect, style: Style) {
+    // Name issues are shown after the field, as long as they leave room for typing
+    let issue = app.actions().name_issue().map(|issue| {
+        let color = if issue.is_invalid() {
+            Color::Red
+        } else {
+            Color::Yellow
+        };
+        (format!(" {}", issue.message()), color)
+    });
+    let issue_width = issue
+        .as_ref()
+        .map(|(message, _)| message.width() as u16)
+        .filter(|width| *width <= area.width / 2)
+        .unwrap_or(0);
+
+    let field = Rect {
+        width: area.width.saturating_sub(issue_width),
+        ..area
+    };
+    let input_text = app.actions().input_buffer();
+    let visible_width = field.width.saturating_sub(1) as usize;
+    let (display_input, cursor_offset) =
+        input_field_view(input_text, app.actions().input_cursor_pos(), visible_width);
+    let hidden = input_text.len() - display_input.len();
+
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(input_line(app, display_input, hidden))
+            .style(style.add_modifier(Modifier::UNDERLINED)),
+        field,
+    );
+    if let Some((message, color)) = issue
+        && issue_width > 0
+    {
+        frame.render_widget(
+            Paragraph::new(message).style(style.fg(color)),
+            Rect {
+                x: field.x + field.width,
+                width: issue_width,
+                ..area
+            },
+        );
+    }
+    frame.set_cursor_position((field.x + cursor_offset as u16, field.y));
+}
+
+/// Helper: The visible part of the input, with the selected input shown reversed.
+///
+/// # Arguments
+/// * `display_input` - The visible part of the input buffer, see [input_field_view].
+/// * `hidden` - The length of the input cut off at the start.
+fn input_line<'a>(app: &AppState, display_input: &'a str, hidden: usize) -> Line<'a> {
+    match app.actions().input_selection() {
+        Some(selection) if selection.end > hidden => {
+            let start = selection.start.saturating_sub(hidden);
+            let end = selection.end - hidden;
+            Line::from(vec![
+                Span::raw(&display_input[..start]),
+                Span::styled(
+                    &display_input[start..end],
+                    Style::default().add_modifier(Modifier::REVERSED),
+                ),
+                Span::raw(&display_input[end..]),
+            ])
+        }
+        _ => Line::from(display_input),
+    }
+}
+
 /// Helper function to calculate cursor offset for cursor moving
 /// Handles horizontal truncation, variable width with unicode_width and clamps cursor to buffer.
 /// Is used for draw widgets/dialogs with input fields.
@@ -210,12 +696,15 @@ fn input_field_view(input_text: &str, cursor_pos: usize, visible_width: usize) -
 pub fn draw_show_info_dialog(
     frame: &mut Frame,
     app: &AppState,
+    area: Rect,
     accent_style: Style,
     info: &FileInfo,
 ) {
     let theme = app.config().theme();
     let widget_info = theme.info();
     let info_cfg = &app.config().display().info();
+    let size_format = app.config().display().size_format();
+    let date_format = app.config().display().date_format();
 
     let label_style = theme.directory_style();
     let value_style = theme.entry_style();
@@ -233,23 +722,71 @@ pub fn draw_show_info_dialog(
     };
 
     if info_cfg.name() {
-        add_line("Name:", info.name().to_string_lossy().into_owned());
+        add_line(
+            "Name:",
+            display_safe_name(&info.name().to_string_lossy(), false).into_owned(),
+        );
     }
     if info_cfg.file_type() {
         add_line("Type:", format_file_type(info.file_type()).into());
+        if let Some(target) = info.target() {
+            add_line(
+                "Target:",
+                display_safe_name(&target.to_string_lossy(), false).into_owned(),
+            );
+        }
     }
     if info_cfg.size() {
-        add_line(
-            "Size:",
-            format_file_size(*info.size(), info.file_type() == &FileType::Directory),
-        );
+        add_line("Size:", format_file_size(*info.size(), false, size_format));
     }
     if info_cfg.modified() {
-        add_line("Modified:", format_file_time(*info.modified()));
+        add_line("Modified:", format_file_time(*info.modified(), date_format));
     }
     if info_cfg.perms() {
         add_line("Perms:", info.attributes().to_string());
     }
+    if let Some(quarantine) = info.quarantine() {
+        let mut text = String::from("quarantined");
+        if let Some(agent) = quarantine.agent() {
+            text.push_str(" by ");
+            text.push_str(agent);
+        }
+        if let Some(downloaded) = quarantine.downloaded() {
+            text.push_str(", ");
+            text.push_str(&format_file_time(Some(downloaded), date_format));
+        }
+        add_line("Download:", text);
+    }
+    if !info.streams().is_empty() {
+        let streams: Vec<String> = info
+            .streams()
+            .iter()
+            .map(|stream| {
+                let name = match stream.name() {
+                    "rsrc" if cfg!(target_os = "macos") => "resource fork",
+                    name => name,
+                };
+                format!(
+                    "{} ({})",
+                    display_safe_name(name, false),
+                    format_file_size(Some(stream.size()), false, size_format)
+                )
+            })
+            .collect();
+        add_line("Streams:", streams.join(", "));
+    }
+    if app.show_security_context()
+        && let Some(context) = info.context()
+    {
+        add_line("Context:", context.as_str().to_string());
+    }
+    if info_cfg.items() {
+        if let Some(items) = info.items() {
+            add_line("Items:", format_dir_items(items));
+        } else if info.details_pending() && *info.file_type() == FileType::Directory {
+            add_line("Items:", "counting…".to_string());
+        }
+    }
 
     if lines.is_empty() {
         return;
@@ -260,7 +797,6 @@ pub fn draw_show_info_dialog(
     let min_width = 27;
     let border_pad = 2;
     let right_pad = 2;
-    let area = frame.area();
 
     let raw_width = (max_width + right_pad).max(min_width) + border_pad;
     let width = raw_width.min(area.width as usize) as u16;
@@ -271,6 +807,8 @@ pub fn draw_show_info_dialog(
         border_style: widget_info.border_style_or(accent_style),
         bg: widget_info.bg_or_theme(),
         fg: widget_info.fg_or_theme(),
+        shadow: widget_info.shadow(),
+        transparent: widget_info.transparent(),
         title: Some(Span::styled(
             " File Info ",
             widget_info.title_style_or_theme(),
@@ -280,7 +818,7 @@ pub fn draw_show_info_dialog(
     let dialog_layout = DialogLayout {
         area,
         position,
-        size: DialogSize::Custom(width, height),
+        size: DialogSize::Cells(width, height),
     };
 
     draw_dialog(
@@ -293,35 +831,66 @@ pub fn draw_show_info_dialog(
     );
 }
 
-/// Draws the fuzzy find dialog widget
+/// Draws the fuzzy find dialog widget, also used for the content search (grep) results.
 ///
 /// Draws the input field and the result field as one widget.
 /// Sets a find result indicator in the input line to the right.
 /// Find result indicator being on the input line makes the actual input line smaller.
-pub fn draw_find_dialog(frame: &mut Frame, app: &AppState, accent_style: Style) {
+pub fn draw_find_dialog(frame: &mut Frame, app: &AppState, area: Rect, accent_style: Style) {
     let actions = app.actions();
-    let widget = app.config().theme().widget();
+    let widget = &app.config().theme().widget().for_dialog(DialogKind::Find);
     let base_dir = app.nav().current_dir();
-    let area = frame.area();
+    let force_ltr = app.config().display().force_ltr();
 
     let position = dialog_position_unified(widget.position(), app, DialogPosition::Center);
-    let columns = widget
-        .find_width_or(area.width.saturating_sub(8).clamp(20, 80))
-        .min(area.width)
-        .max(20);
-
-    let max_visible = widget.find_visible_or(5);
-    let rows = max_visible as u16 + 4;
 
-    let size = DialogSize::Custom(columns, rows);
+    // An explicit [theme.widget.find] size wins, the visible results then fill its height
+    let (size, max_visible) = match widget.find_size() {
+        Some(size) => {
+            let height = dialog_area(area, size, position).height as usize;
+            (size, height.saturating_sub(4).max(1))
+        }
+        None => {
+            let columns = widget
+                .find_width_or(area.width.saturating_sub(8).clamp(20, 80))
+                .min(area.width)
+                .max(20);
+            let max_visible = widget.find_visible_or(5);
+            (
+                DialogSize::Cells(columns, max_visible as u16 + 4),
+                max_visible,
+            )
+        }
+    };
     let border_type = app.config().display().border_shape().as_border_type();
 
     let input_text = actions.input_buffer();
     let cursor_pos = actions.input_cursor_pos();
     let results = actions.find_results();
     let selected = actions.find_selected();
-    let area = frame.area();
     let dialog_rect = dialog_area(area, size, position);
+    let title = match actions.mode() {
+        ActionMode::Input {
+            mode: InputMode::Grep,
+            ..
+        } => " Grep ".to_string(),
+        ActionMode::Input {
+            mode: InputMode::Jump,
+            ..
+        } => " Jump ".to_string(),
+        _ => match actions.find_kind() {
+            FindKind::All => " Find ".to_string(),
+            kind => format!(" Find [{}] ", kind.label()),
+        },
+    };
+
+    let is_jump = matches!(
+        actions.mode(),
+        ActionMode::Input {
+            mode: InputMode::Jump,
+            ..
+        }
+    );
 
     let total = results.len();
     let selected = selected.min(total.saturating_sub(1));
@@ -334,11 +903,25 @@ pub fn draw_find_dialog(frame: &mut Frame, app: &AppState, accent_style: Style)
     }
 
     let mut display_lines = Vec::with_capacity(max_visible + 2);
+    let markers = app.nav().markers();
+    let marker_theme = app.config().theme().marker();
+    let marker_icon = marker_theme.icon();
+    let marker_pad = " ".repeat(marker_icon.width());
+    let show_icons = app.config().display().icons();
+    let dir_icon = app.config().icons().dir().glyph();
+    let ascii_only = app.config().display().ascii_only();
+    let dir_style = app.config().theme().directory_style();
 
+    // A trailing ellipsis marks results still streaming in
     let indicator = format!(
-        "[{} / {}]",
+        "[{} / {}{}]",
         if total == 0 { 0 } else { selected + 1 },
-        total
+        total,
+        match (actions.is_find_searching(), ascii_only) {
+            (false, _) => "",
+            (true, false) => "…",
+            (true, true) => "...",
+        }
     );
     let field_width = dialog_rect.width.saturating_sub(2) as usize;
     let indicator_width = indicator.width() + 2;
@@ -393,16 +976,48 @@ pub fn draw_find_dialog(frame: &mut Frame, app: &AppState, accent_style: Style)
         }
     } else {
         for (idx, r) in results.iter().enumerate().skip(scroll).take(max_visible) {
-            let marker = if idx == selected { "› " } else { "  " };
+            let marker = match (idx == selected, ascii_only) {
+                (false, _) => "  ",
+                (true, false) => "› ",
+                (true, true) => "> ",
+            };
             let marker_style = if idx == selected {
                 accent_style
             } else {
                 Style::default()
             };
-            display_lines.push(Line::from(vec![
-                Span::styled(marker, marker_style),
-                Span::raw(r.relative(base_dir)),
-            ]));
+            // Marked results carry the marker icon, like entries in the panes
+            let mark = if markers.contains(r.path()) {
+                Span::styled(marker_icon, marker_theme.style_or_theme())
+            } else {
+                Span::raw(marker_pad.as_str())
+            };
+            // Jump targets are mostly outside the current directory, so they are shown in full
+            let label = if is_jump {
+                r.path().to_string_lossy().into_owned()
+            } else {
+                r.relative(base_dir)
+            };
+            let label = display_safe_name(&label, force_ltr).into_owned();
+            let mut spans = vec![Span::styled(marker, marker_style), mark];
+            // Jump targets are all directories, so only find results point them out
+            if r.is_dir() && !is_jump {
+                if show_icons {
+                    spans.push(Span::styled(format!("{dir_icon} "), dir_style));
+                }
+                spans.push(Span::styled(format!("{label}/"), dir_style));
+            } else {
+                spans.push(Span::raw(label));
+            }
+            // Grep results show the matching line as path:line: match
+            if let Some(content) = r.content() {
+                spans.push(Span::styled(
+                    format!(":{}: ", content.line()),
+                    Style::default().fg(Color::DarkGray),
+                ));
+                spans.push(Span::raw(content.text().to_string()));
+            }
+            display_lines.push(Line::from(spans));
         }
         let lines_drawn = results
             .iter()
@@ -420,7 +1035,9 @@ pub fn draw_find_dialog(frame: &mut Frame, app: &AppState, accent_style: Style)
         border_style: widget.border_style_or(accent_style),
         bg: widget.bg_or_theme(),
         fg: widget.fg_or_theme(),
-        title: Some(Span::styled(" Find ", widget.title_style_or_theme())),
+        shadow: widget.shadow(),
+        transparent: widget.transparent(),
+        title: Some(Span::styled(title, widget.title_style_or_theme())),
     };
 
     draw_dialog(
@@ -438,43 +1055,259 @@ pub fn draw_find_dialog(frame: &mut Frame, app: &AppState, accent_style: Style)
     frame.set_cursor_position((dialog_rect.x + 1 + cursor_x as u16, dialog_rect.y + 1));
 }
 
-/// Draws a simple message overlay dialog at the bottom right
-/// Used for notifications such as "fd is not available" etc.
-pub fn draw_message_overlay(frame: &mut Frame, app: &AppState, accent_style: Style, text: &str) {
+/// Draws the queued notifications as toasts stacked up from the bottom right corner,
+/// the newest at the bottom. Toasts which do not fit above it are left out.
+pub fn draw_notifications(frame: &mut Frame, app: &AppState, mut area: Rect, accent_style: Style) {
     let widget = app.config().theme().widget();
-    let position = DialogPosition::BottomRight;
     let border_type = app.config().display().border_shape().as_border_type();
+    let min_width = 27;
+    let border_pad = 2;
+    let right_pad = 2;
 
-    let mut max_line_width = 0;
-    let mut line_count = 0;
-    for line in text.lines() {
-        max_line_width = max_line_width.max(line.len());
-        line_count += 1;
+    for notification in app.notifications().iter().rev() {
+        let text = notification.text();
+        let max_line_width = text.lines().map(UnicodeWidthStr::width).max().unwrap_or(0);
+        let height = text.lines().count().max(1) + border_pad;
+        if height > area.height as usize {
+            break;
+        }
+
+        // Cell sizes are clamped to the area by dialog_area
+        let width = (max_line_width + right_pad).max(min_width) + border_pad;
+        let dialog_size = DialogSize::Cells(width.try_into().unwrap_or(u16::MAX), height as u16);
+        let border_style = match notification.severity() {
+            Severity::Info => widget.border_style_or(accent_style),
+            Severity::Warn => Style::default().fg(Color::Yellow),
+            Severity::Error => Style::default().fg(Color::Red),
+        };
+        let dialog_style = DialogStyle {
+            border: Borders::ALL,
+            border_style,
+            bg: widget.bg_or_theme(),
+            fg: widget.fg_or_theme(),
+            shadow: false,
+            transparent: widget.transparent(),
+            title: Some(Span::styled(
+                format!(" {} ", notification.severity().label()),
+                border_style,
+            )),
+        };
+        let dialog_layout = DialogLayout {
+            area,
+            position: DialogPosition::BottomRight,
+            size: dialog_size,
+        };
+
+        draw_dialog(
+            frame,
+            dialog_layout,
+            border_type,
+            &dialog_style,
+            text,
+            Some(Alignment::Left),
+        );
+        area.height -= height as u16;
     }
+}
 
-    let min_width = 27;
+/// Draws the session statistics dialog in the center.
+///
+/// Combines the [SessionStats](crate::app::SessionStats) of the app with the counters of the
+/// worker threads. Rates are shown as the share of hits in all lookups.
+/// The latest slow directory loads and previews are listed below the counters.
+pub fn draw_stats_dialog(frame: &mut Frame, app: &AppState, area: Rect, accent_style: Style) {
+    const SLOW_ROWS: usize = 3;
+
+    let theme = app.config().theme();
+    let widget = theme.widget();
+    let session = app.session_stats();
+    let workers = app.worker_stats();
+    let size_format = app.config().display().size_format();
+    let border_type = app.config().display().border_shape().as_border_type();
+
+    let label_style = theme.directory_style();
+    let value_style = theme.entry_style();
+
+    let rate = |hits: u64, total: u64| {
+        (hits * 100)
+            .checked_div(total)
+            .map_or_else(|| "-".to_string(), |pct| format!("{}%", pct))
+    };
+    let uptime = session.uptime().as_secs();
+    let requested_previews = workers.previews_loaded() + workers.previews_coalesced();
+
+    let mut rows = vec![
+        (
+            "Session",
+            format!(
+                "{}h {:02}m {:02}s",
+                uptime / 3600,
+                uptime / 60 % 60,
+                uptime % 60
+            ),
+        ),
+        ("Dirs visited", session.dirs_visited().to_string()),
+        ("Files opened", session.files_opened().to_string()),
+        ("Dirs loaded", workers.dirs_loaded().to_string()),
+        (
+            "Previews",
+            format!(
+                "{} ({} skipped, {})",
+                workers.previews_loaded(),
+                workers.previews_coalesced(),
+                rate(workers.previews_coalesced(), requested_previews)
+            ),
+        ),
+        ("Searches", workers.searches().to_string()),
+        ("File ops", workers.file_ops().to_string()),
+        (
+            "Copied",
+            format_file_size(Some(workers.bytes_copied()), false, size_format),
+        ),
+        (
+            "Deleted",
+            format_file_size(Some(workers.bytes_deleted()), false, size_format),
+        ),
+        (
+            "Positions",
+            format!(
+                "{}/{} remembered ({})",
+                session.position_hits(),
+                session.position_lookups(),
+                rate(session.position_hits(), session.position_lookups())
+            ),
+        ),
+        (
+            "Stale",
+            format!("{} responses dropped", session.stale_responses()),
+        ),
+        ("Slow loads", session.slow_count().to_string()),
+    ];
+    // The latest slow loads, newest first
+    rows.extend(
+        session
+            .slow_operations()
+            .iter()
+            .rev()
+            .take(SLOW_ROWS)
+            .map(|op| {
+                let path = display_safe_name(&shorten_home_path(op.path()), false).into_owned();
+                ("", format!("{:.1}s {}", op.elapsed().as_secs_f64(), path))
+            }),
+    );
+
+    let lines: Vec<Line> = rows
+        .into_iter()
+        .map(|(label, value)| {
+            Line::from(vec![
+                Span::styled(format!("{:<14}", label), label_style),
+                Span::styled(value, value_style),
+            ])
+        })
+        .collect();
+
+    let max_width = lines.iter().map(|l| l.width()).max().unwrap_or(0);
     let border_pad = 2;
     let right_pad = 2;
-    let area = frame.area();
+    let width = (max_width + right_pad + border_pad).min(area.width as usize) as u16;
+    let height = (lines.len() + border_pad).min(area.height as usize) as u16;
 
-    let width =
-        ((max_line_width + right_pad).max(min_width) + border_pad).min(area.width as usize) as u16;
-    let height = ((line_count + border_pad).min(area.height as usize)) as u16;
+    let dialog_style = DialogStyle {
+        border: Borders::ALL,
+        border_style: widget.border_style_or(accent_style),
+        bg: widget.bg_or_theme(),
+        fg: widget.fg_or_theme(),
+        shadow: widget.shadow(),
+        transparent: widget.transparent(),
+        title: Some(Span::styled(" Statistics ", widget.title_style_or_theme())),
+    };
 
-    let dialog_size = DialogSize::Custom(width, height);
+    let dialog_layout = DialogLayout {
+        area,
+        position: DialogPosition::Center,
+        size: DialogSize::Cells(width, height),
+    };
+
+    draw_dialog(
+        frame,
+        dialog_layout,
+        border_type,
+        &dialog_style,
+        Text::from(lines),
+        Some(Alignment::Left),
+    );
+}
+
+/// Draws the tutorial dialog at the bottom left.
+///
+/// Shows the progress, the explanation of the current step and the configured keys
+/// for it, highlighted with the selection style.
+pub fn draw_tutorial_dialog(
+    frame: &mut Frame,
+    app: &AppState,
+    area: Rect,
+    accent_style: Style,
+    tutorial: &Tutorial,
+) {
+    let theme = app.config().theme();
+    let widget = theme.widget();
+    let step = tutorial.current();
+    let position = DialogPosition::BottomLeft;
+    let border_type = app.config().display().border_shape().as_border_type();
+
+    let mut lines: Vec<Line> = step.text().lines().map(Line::from).collect();
+    lines.push(Line::default());
+
+    let mut keys = vec![Span::styled("Keys: ", theme.directory_style())];
+    for key in step.keys(app.config().keys()) {
+        let label = if key == " " { "Space" } else { key.as_str() };
+        keys.push(Span::styled(
+            format!(" {} ", label),
+            theme.selection_style(),
+        ));
+        keys.push(Span::raw(" "));
+    }
+    lines.push(Line::from(keys));
+    if tutorial.is_last() {
+        lines.push(Line::from("Quit runa to finish the tutorial."));
+    } else {
+        lines.push(Line::from("Quit to close the tutorial."));
+    }
+
+    let title = format!(
+        " Tutorial {}/{}: {} ",
+        tutorial.step() + 1,
+        tutorial.len(),
+        step.title()
+    );
+
+    let min_width = 36;
+    let border_pad = 2;
+    let right_pad = 2;
+
+    let max_width = lines
+        .iter()
+        .map(|l| l.width())
+        .chain(std::iter::once(title.width()))
+        .max()
+        .unwrap_or(0);
+    let width = ((max_width + right_pad).max(min_width) + border_pad).min(area.width as usize);
+    let height = (lines.len() + border_pad).min(area.height as usize);
 
     let dialog_style = DialogStyle {
         border: Borders::ALL,
         border_style: widget.border_style_or(accent_style),
         bg: widget.bg_or_theme(),
         fg: widget.fg_or_theme(),
-        title: Some(Span::styled(" Message ", widget.title_style_or_theme())),
+        shadow: widget.shadow(),
+        transparent: widget.transparent(),
+        title: Some(Span::styled(title, widget.title_style_or_theme())),
     };
 
     let dialog_layout = DialogLayout {
         area,
         position,
-        size: dialog_size,
+        size: DialogSize::Cells(width as u16, height as u16),
     };
 
     draw_dialog(
@@ -482,7 +1315,7 @@ pub fn draw_message_overlay(frame: &mut Frame, app: &AppState, accent_style: Sty
         dialog_layout,
         border_type,
         &dialog_style,
-        text,
+        Text::from(lines),
         Some(Alignment::Left),
     );
 }
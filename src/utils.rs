@@ -2,16 +2,34 @@
 //!
 //! This modules hodl the [helpers] submodule, which provides commonly used utilities such as:
 //! - Color parsing
-//! - Opening a file/path in the chosen editor
+//! - Opening a file/path in the chosen editor or the pager
 //! - Computing an unused path for core/workers
+//! - Validating and brace expanding file names typed in the create and rename dialogs
 //! - Shortening the home directory path to "~"
+//! - Resuming interrupted copies
+//! - Running shell commands from the command prompt and openers, and opening a shell
 //!
 //! All of these utilities are used throughout runa for convenience and code clarity.
+//!
+//! The [clipboard] submodule copies paths to the system clipboard, or over OSC 52 in SSH sessions.
+//!
+//! The [termux] submodule detects Termux on Android, for its compatibility mode.
+//!
+//! The [update] submodule implements `rn --update`, if built with the `self-update` feature.
 
 pub mod cli;
+pub mod clipboard;
 pub mod helpers;
+pub mod termux;
+#[cfg(feature = "self-update")]
+pub mod update;
 
+pub use clipboard::{ClipboardMode, copy_to_clipboard, osc52_sequence};
 pub use helpers::{
-    DEFAULT_FIND_RESULTS, as_path_op, copy_recursive, get_unused_path, open_in_editor, parse_color,
-    shorten_home_path,
+    CommandPaths, DEFAULT_FIND_RESULTS, MAX_BRACE_EXPANSION, NameIssue, as_path_op, check_filename,
+    copy_file_limited, copy_recursive, editor_command, expand_braces, expand_command,
+    expand_create_input, find_history_path, get_unused_path, is_file_in_use, name_stem_len,
+    open_in_editor, open_in_editor_at, open_in_pager, open_many_in_editor, open_shell, parse_color,
+    parse_modifier, relative_path, resumable_copy_offset, resume_copy, root_log_path,
+    run_shell_command, run_suspended, shell_quote, shorten_home_path,
 };
@@ -1,51 +1,136 @@
 //! Command-line argument parsing and help for runa.
 //!
 //! This module handles all CLI flag parsing used for config initialization and help.
-//! It recognizes args/flags such: --help, --init, --init-full and --config-help
+//! It recognizes args/flags such: --help, --init, --init-full, --config-help, --update and --tutorial
+//! and the `find` subcommand. `--readonly` and `--dump-state` can be combined with launching the TUI.
 //!
-//! When invoked with no args/flags (rn), runa simply launches the TUI
+//! When invoked with no args/flags (rn), runa simply launches the TUI.
+//! `rn find <query>` launches it with the find dialog open and the query searched for.
+//!
+//! runa exits with 0 on success and [EXIT_ERROR] on errors, so scripts can branch on it.
+//! On the first run it offers to start the tutorial instead.
 
 use crate::config::Config;
+use std::fs;
+use std::io::{self, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+/// Exit status for errors: unknown arguments, a failed `--init` or `--update`,
+/// or a terminal error while running.
+pub const EXIT_ERROR: u8 = 2;
 
 pub enum CliAction {
     RunApp,
-    Exit,
+    Tutorial,
+    Find(String),
+    Exit(ExitCode),
+}
+
+/// The parsed command line: what to do and the flags applying to the TUI.
+///
+/// # Fields
+/// * `action` - The [CliAction] to run.
+/// * `readonly` - Whether `--readonly` was given, see the `readonly` config option.
+/// * `dump_state` - Whether `--dump-state` was given, writing a state snapshot on exit.
+pub struct Cli {
+    pub action: CliAction,
+    pub readonly: bool,
+    pub dump_state: bool,
+}
+
+/// The exit status of a finished command line action.
+fn exit_with<E: std::fmt::Display>(result: Result<(), E>) -> CliAction {
+    match result {
+        Ok(()) => CliAction::Exit(ExitCode::SUCCESS),
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            CliAction::Exit(ExitCode::from(EXIT_ERROR))
+        }
+    }
+}
+
+pub fn handle_args() -> Cli {
+    let mut args: Vec<String> = std::env::args().collect();
+    let readonly = args.iter().any(|arg| arg == "--readonly");
+    let dump_state = args.iter().any(|arg| arg == "--dump-state");
+    args.retain(|arg| arg != "--readonly" && arg != "--dump-state");
+    Cli {
+        action: parse_action(&args),
+        readonly,
+        dump_state,
+    }
 }
 
-pub fn handle_args() -> CliAction {
-    let args: Vec<String> = std::env::args().collect();
+fn parse_action(args: &[String]) -> CliAction {
     let config_path = Config::default_path();
 
     if args.len() <= 1 {
+        if offer_tutorial(&config_path) {
+            return CliAction::Tutorial;
+        }
         return CliAction::RunApp;
     }
 
     match args[1].as_str() {
         "-h" | "--help" => {
             print_help();
-            CliAction::Exit
+            CliAction::Exit(ExitCode::SUCCESS)
         }
         "--config-help" => {
             print_config_help();
-            CliAction::Exit
-        }
-        "--init" => {
-            if let Err(e) = Config::generate_default(&config_path, true) {
-                eprintln!("Error: {}", e);
-            }
-            CliAction::Exit
-        }
-        "--init-full" => {
-            if let Err(e) = Config::generate_default(&config_path, false) {
-                eprintln!("Error: {}", e);
-            }
-            CliAction::Exit
-        }
-        arg => {
-            eprintln!("Unknown argument: {}", arg);
-            CliAction::Exit
+            CliAction::Exit(ExitCode::SUCCESS)
         }
+        "--init" => exit_with(Config::generate_default(&config_path, true)),
+        "--init-full" => exit_with(Config::generate_default(&config_path, false)),
+        "--update" => exit_with(run_update()),
+        "--tutorial" => CliAction::Tutorial,
+        "find" => CliAction::Find(args[2..].join(" ")),
+        arg => exit_with(Err(format!("Unknown argument: {}", arg))),
+    }
+}
+
+/// Asks a new user once whether to start the tutorial.
+///
+/// Only asked if there is no config file yet and stdin is a terminal.
+/// Once the question was answered, a marker file in the data directory remembers it, whatever the answer.
+fn offer_tutorial(config_path: &Path) -> bool {
+    let Some(marker) = tutorial_marker_path() else {
+        return false;
+    };
+    if config_path.exists() || marker.exists() || !io::stdin().is_terminal() {
+        return false;
     }
+
+    print!("Welcome to runa! Start the quick tutorial? [y/N] ");
+    let _ = io::stdout().flush();
+    let mut answer = String::new();
+    // Asked again next time if stdin was closed before an answer
+    if !matches!(io::stdin().read_line(&mut answer), Ok(n) if n > 0) {
+        return false;
+    }
+
+    if let Some(parent) = marker.parent() {
+        let _ = fs::create_dir_all(parent);
+    }
+    let _ = fs::write(&marker, "");
+    answer.trim().eq_ignore_ascii_case("y")
+}
+
+/// The marker of the tutorial question, in the data directory of the platform,
+/// e.g. `~/.local/share/runa` on Linux or `~/Library/Application Support/runa` on macOS.
+fn tutorial_marker_path() -> Option<PathBuf> {
+    dirs::data_dir().map(|dir| dir.join("runa").join("tutorial_offered"))
+}
+
+#[cfg(feature = "self-update")]
+fn run_update() -> Result<(), String> {
+    crate::utils::update::self_update().map_err(|e| format!("Update failed: {}", e))
+}
+
+#[cfg(not(feature = "self-update"))]
+fn run_update() -> Result<(), String> {
+    Err("Self-update is disabled in this build, update runa with your package manager.".into())
 }
 
 fn print_help() {
@@ -54,15 +139,24 @@ fn print_help() {
 
 USAGE:
     rn [OPTIONS]
+    rn find [QUERY]       Start with the find dialog open, searching for QUERY
 
 OPTIONS:
     --help, -h            Print help information
     --init                Generate a default config at ~/.config/runa/runa.toml
     --init-full           Generate the full config
     --config-help         Display all the configuration options
+    --update              Update to the latest release (pre-compiled binaries only)
+    --tutorial            Start a guided tour in a playground directory
+    --readonly            Disable file operations, commands and the shell for this session
+    --dump-state          Write a state snapshot for bug reports on exit, see the dump_state key
 
 ENVIRONMENT:
     RUNA_CONFIG         Override the default config path
+
+EXIT STATUS:
+    0                   Success
+    2                   Unknown argument, failed --init or --update, or a terminal error
 "#
     );
 }
@@ -75,11 +169,53 @@ runa - Full Configuration Guide (runa.toml)
  General Settings
 =========================
   dirs_first              (bool)    Sort directories before files [default: true]
-  show_hidden             (bool)    Show hidden files (dotfiles)
+  sort_expr               (str)     Custom sort order, e.g. "is_dir desc, ext, name", replaces dirs_first
+                                    Keys: name, ext, is_dir, is_hidden, is_symlink, each asc or desc
+  show_hidden             (bool)    Show hidden files (dotfiles), toggle_hidden flips it
   show_system             (bool)    Show system/protected files (mainly Windows)
   case_insensitive        (bool)    Ignore case sensitivity in search/sort [default: true]
   always_show             (list)    Hidden entries always shown, e.g. [".config", "Downloads"]
+  scan_excludes           (list)    Names skipped by directory scans [default: ["node_modules", ".git"]]
   max_find_results        (usize)   Max results for find (default: 2000, min: 15, max: 1_000_000)
+  max_copy_mbps           (u32)     Limit copy speed in MB/s, 0 = unlimited [default: 0]
+  create_file_mode        (octal)   Permissions of created files, e.g. 0o644, minus umask [default: system]
+  create_dir_mode         (octal)   Permissions of created directories, e.g. 0o755, minus umask [default: system]
+  auto_refresh_secs       (u64)     Reload the current directory every N seconds, 0 = off [default: 0]
+  watch                   (bool)    Reload on external changes to the current/parent directory [default: true]
+  focus_new_items         (bool)    Move the cursor to created/pasted/renamed entries [default: true]
+  message_secs            (u64)     Show operation results and worker errors as notifications for N seconds, 0 = off [default: 3]
+  filter_mode             (str)     "substring", "fuzzy", "regex" or "glob" [default: "substring"]
+  marker_policy           (str)     Clear markers after an "operation", on "directory" change or "persist" [default: "operation"]
+  clipboard               (str)     "auto", "system" (clipboard tool) or "osc52" (terminal, for SSH) [default: "auto"]
+  readonly                (bool)    Disable file operations, commands and the shell, like --readonly [default: false]
+  root_guard              (bool)    As root: ROOT banner, read-only until unlock, log to root.log [default: true]
+  termux                  (bool)    Termux mode: fewer threads, warn about missing fd/bat [default: inside Termux]
+  paste_conflict          (str)     Name collisions on paste: "ask", "rename", "overwrite" or "skip" [default: "rename"]
+  animations              (bool)    Animate opening/closing dialogs and messages [default: false]
+  zoxide                  (bool)    Record visited directories with `zoxide add` [default: false]
+
+=========================
+ Find Settings
+=========================
+[find]
+  persist_history         (bool)    Save find/grep queries to ~/.local/share/runa/find_history [default: false]
+  history_size            (usize)   Number of remembered queries [default: 100]
+  include                 (list)    Only find files matching these globs, e.g. ["*.rs"] [default: []]
+  exclude                 (list)    Globs to skip, replaces the built-in list (".git", "target", "node_modules", ...)
+  gitignore               (bool)    Respect .gitignore files [default: true]
+  dirs_first              (bool)    List matching directories before files [default: false]
+
+=========================
+ Worker Settings
+=========================
+[workers]
+  queue_size              (usize)   Capacity of the directory/file operation queues [default: 64]
+  preview_queue_size      (usize)   Capacity of the preview queue [default: 4]
+  response_queue_size     (usize)   Capacity of the result queue to the UI [default: 256]
+  preview_overflow        (str)     "backlog", "drop_oldest" or "drop_newest" [default: "drop_oldest"]
+  fileop_overflow         (str)     "backlog", file operations are never dropped [default: "backlog"]
+  dir_chunk_size          (usize)   Show huge directories after this many entries, 0 = off [default: 5000]
+  slow_warning_ms         (u64)     Hint about loads and previews slower than this, 0 = off [default: 2000]
 
 =========================
  Display Settings
@@ -91,15 +227,32 @@ runa - Full Configuration Guide (runa.toml)
   border_shape            (str)     "square", "rounded", or "double"
   titles                  (bool)    Show pane titles at the top
   icons                   (bool)    Show Nerd Font icons
+  ascii_only              (bool)    ASCII icons, truncation mark and find marker
   separators              (bool)    Show vertical lines between panes
-  parent                  (bool)    Show parent (left) pane [default: true]
-  preview                 (bool)    Show preview (right) pane [default: true]
+  parent                  (bool)    Show parent (left) pane, toggle_parent flips it [default: true]
+  preview                 (bool)    Show preview (right) pane, toggle_preview flips it [default: true]
   preview_underline       (bool)    Underline preview selection instead of highlight
   preview_underline_color (bool)    Distinct color for preview underline
   entry_padding           (usize)   Padding (# chars) left/right (0–4)
   scroll_padding          (usize)   Reserved rows when scrolling
   toggle_marker_jump      (bool)    Toggle marker jumping to first entry
   instant_preview         (bool)    Toggle instant previews on every selection change
+  git_status              (bool)    Show the git branch and dirty marker in the header [default: true]
+  finder_tags             (bool)    Show Finder color tags as dots next to the entries, macOS [default: true]
+  security_context        (bool)    Show SELinux contexts in info, color entries with another type, Linux [default: false]
+  details                 (bool)    Show size, modified time and git status in the main pane [default: false]
+  dir_sizes               (bool)    Compute recursive directory sizes in the background [default: false]
+  inline_rename           (bool)    Rename in the selected row of the main pane instead of a dialog [default: false]
+  file_colors             (str)     Entry colors by type and extension: "none", "builtin" or "ls_colors" [default: "none"]
+  date_format             (str)     Format of dates in the info overlay, strftime [default: "%Y-%m-%d %H:%M:%S"]
+  size_format             (str)     Units of sizes: "decimal" (kB) or "binary" (KiB) [default: "decimal"]
+  force_ltr               (bool)    Keep right-to-left file names from reordering the listing [default: false]
+
+[display.preview_options]
+  method                  (str)     "internal" or "bat"
+  syntax_highlighting     (bool)    Syntax highlight the internal preview [default: true]
+  tab_width               (usize)   Tab stop width of the internal preview [default: 4]
+  show_control_chars      (bool)    Show control characters as e.g. ^M in the preview [default: false]
 
 [display.layout]
   parent                  (u16)     Width % for parent pane
@@ -112,15 +265,18 @@ runa - Full Configuration Guide (runa.toml)
   size                    (bool)
   modified                (bool)
   perms                   (bool)
+  items                   (bool)    Recursive item count of directories, skips scan_excludes [default: true]
 
 =========================
  Theme Configuration
 =========================
 [theme]
-  name                    (str)     Theme name, e.g. "gruvbox-dark"
+  name                    (str)     Preset name, e.g. "gruvbox-dark", or a file themes/<name>.toml next to runa.toml
   selection_icon          (str)     Symbol for selection (">" or " ")
+  context_mismatch        (str)     Color of entries with another SELinux type than their directory [default: "red"]
 
-# Each sub-table supports fg/bg colors ("Red", "Blue", hex "#RRGGBB", or "default"):
+# Each sub-table supports fg/bg colors ("Red", "Blue", hex "#RRGGBB", or "default")
+# and modifiers, e.g. modifiers = ["bold", "italic"] (also dim, underlined, reversed, crossed_out):
 [theme.selection]                  Selection bar (fg, bg)
 [theme.accent]                     Borders/titles (fg, bg)
 [theme.entry]                      Normal entries (fg, bg)
@@ -138,48 +294,122 @@ runa - Full Configuration Guide (runa.toml)
 
 [theme.widget]                     Dialog/widgets config (see docs):
   position                (str/list/table)  "center", [x, y], {x = 38, y = 32}
-  size                    (str/list/table)  "small", [w, h], {w = 33, h = 15}
-  confirm_size            (str/list/table)  Override size for confirmation popups
+                                            {anchor = "bottom_right", dx = -2, dy = -1}
+  size                    (str/list/table)  "small", "40x10", "40%x10%", [w, h], {w = 33, h = 15}
+  confirm_size            (str/list/table)  Max size for confirmation popups
+  shadow                  (bool)            Drop shadow behind dialogs [default: false]
+  dim_background          (bool)            Dim panes while a modal dialog is open [default: false]
+  transparent             (bool)            Terminal background shows through dialogs [default: false]
   color.fg/bg             (str)             Text/background color
   border.fg/bg            (str)
   title.fg/bg             (str)
 
+[theme.widget.rename]              Overrides for the rename dialog
+[theme.widget.find]                Overrides for the find/grep dialog
+[theme.widget.confirm]             Overrides for the confirmations
+  position, size, color, border, title    Unset keys inherit from [theme.widget]
+
 [theme.info]              File info overlay
  color.fg/bg,             (str)
  border.fg/bg,            (str)
  title.fg/bg,             (str)
  position                 (str/list/table) "center", "top_left", [x, y], { x, y }
 
+=========================
+ Icons
+=========================
+[icons]                   Merged over the built-in icons, shown with display.icons
+  default                 (icon)   Files without a matching name or extension
+  extensions              (table)  Extension = icon, e.g. rs = ""
+  files                   (table)  File name = icon, e.g. "Cargo.toml" = ""
+  dirs                    (table)  Directory name = icon
+  An icon is a string or { icon, fg }, fg alone recolors the built-in icon
+
 =========================
  Editor
 =========================
 [editor]
   cmd                     (str)    Command to open files (e.g., "nvim", "code")
+  open_with_system        (bool)   Open binary files with xdg-open/open/start [default: true]
+  open_many               (str)    Shell command opening marked find results at once, e.g. "nvim -p %m"
 
 =========================
  Key Bindings
 =========================
 [keys]
   open_file               (list)   e.g. ["Enter"]
+  open_with_editor        (list)   ["o"]     (open in the editor, also binary files)
   go_up                   (list)   ["k", "Up"]
   go_down                 (list)   ["j", "Down"]
+  go_top                  (list)   ["g g"]   (first entry)
+  go_bottom               (list)   ["Shift+g"] (last entry)
   go_parent               (list)   ["h", "Left", "Backspace"]
   go_into_dir             (list)   ["l", "Right"]
   quit                    (list)   ["q", "Esc"]
   delete                  (list)   ["d"]
   copy                    (list)   ["y"]
+  copy_path               (list)   ["Shift+y"] (absolute path to the system clipboard)
+  copy_relative_path      (list)   ["Ctrl+y"] (path relative to the start directory)
+  copy_name               (list)   ["Alt+y"] (file name to the system clipboard)
   paste                   (list)   ["p"]
   rename                  (list)   ["r"]
-  create                  (list)   ["n"]
+  create                  (list)   ["n"]     (braces expand: "src/{lib,main}.rs", "ch{1..5}/")
   create_directory        (list)   ["Shift+n"]
   filter                  (list)   ["f"]
   toggle_marker           (list)   [" "]     (space bar)
   info                    (list)   ["i"]
+  dir_size                (list)   ["Shift+d"] (recompute the recursive sizes of the subdirectories)
+  clear_quarantine        (list)   ["Shift+x"] (macOS: clear the quarantine of downloads)
+  stats                   (list)   ["Shift+i"] (session statistics)
+  dump_state              (list)   ["F12"]   (state snapshot for bug reports)
+  toggle_instant_preview  (list)   ["Ctrl+p"] (flip instant_preview for this session)
+  toggle_hidden           (list)   ["."]     (flip show_hidden for this session)
+  toggle_preview          (list)   ["Shift+p"] (show/hide the preview pane for this session)
+  toggle_parent           (list)   ["Shift+h"] (show/hide the parent pane for this session)
+  reload_config           (list)   ["Shift+r"] (apply changes to runa.toml without restarting)
+  unlock                  (list)   ["Shift+u"] (lift/restore read-only mode of a root session)
   find                    (list)   ["s"]
+  quick_find              (list)   ["/"]     (fuzzy jump in the current directory only)
+  navigate                (list)   ["Shift+f"] (type to narrow, Enter descends into the best match)
+  grep                    (list)   ["Shift+s"]
+  jump                    (list)   ["z"]     (requires zoxide)
+  command                 (list)   [":", "!"] (shell command, %s = marked/selected paths)
+  shell                   (list)   ["w"]     (open $SHELL in the current directory)
+  mark_all                (list)   ["Ctrl+a"] (mark all shown entries)
+  invert_marks            (list)   ["Ctrl+r"] (invert the markers of the shown entries)
   clear_markers           (list)   ["Ctrl+c]
   clear_filter            (list)   ["Ctrl+f]
+  marked                  (list)   ["m"]     (marked paths across directories, total size)
+  jump_back               (list)   ["Ctrl+o"] (back to the location before a find/jump)
+  jump_forward            (list)   ["Ctrl+i", "Tab"]
+  goto                    (list)   ["g"]     (then h = home, r = root, c = config dir, or a [goto] key)
 
     (Use "Shift+x", "Ctrl+x" as needed. " " means space bar. Omit a binding to use the default.)
+    (Key sequences are separated by spaces, e.g. "d d", with "Space" for the space bar.)
+
+[commands]                         (key = shell command, overrides built-in keys)
+  "Ctrl+e" = "tar -xf %f"
+    (%f = selected, %m = marked, %s = marked or selected, %d = current dir, %% = literal %)
+
+[goto]                             (key after the goto prefix = directory)
+  "d" = "~/Downloads"
+
+[openers]                          (extension = command, used instead of the editor)
+  "pdf" = "zathura %f"
+
+[open.enter]                       (class or extension = "editor", "system", "preview" or "prompt")
+  text, binary, executable          File classes, an extension like ".md" takes precedence
+
+[profiles.<name>]                  (overrides on matching storage, first match by name)
+  paths                   (list)   Path prefixes, e.g. ["/mnt/nas", "~/remote"]
+  fs_types                (list)   Filesystem types, e.g. ["nfs", "cifs", "fuse.sshfs"]
+  instant_preview, watch, auto_refresh_secs, git_status, details, dir_sizes
+                                   (same as above, left out options keep their value)
+
+[templates.<name>]                 (contents of new files, offered after the new-file dialog)
+  names                   (list)   File names or extensions, e.g. ["main.rs", "*.sh"]
+  text                    (str)    Contents of the new files
+  file                    (str)    File to copy the contents from instead, e.g. "~/templates/script.sh"
 
 =========================
  Examples
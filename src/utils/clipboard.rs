@@ -0,0 +1,133 @@
+//! Copying text to the system clipboard.
+//!
+//! Like the other integrations (fd, bat, zoxide), the clipboard is written by an external tool:
+//! `pbcopy` on macOS, `clip` on Windows, `wl-copy` on Wayland, `xclip` or `xsel` on X11
+//! and `termux-clipboard-set` on Termux.
+//!
+//! Over SSH these tools would write the clipboard of the remote machine, so the text is sent to
+//! the local terminal with an OSC 52 escape sequence instead (see [ClipboardMode]).
+
+use serde::Deserialize;
+use std::io::{self, Write};
+use std::process::{Command, Stdio};
+
+/// How text is copied to the clipboard, set by `clipboard` in runa.toml.
+///
+/// Variants:
+/// * `Auto` - OSC 52 in SSH sessions or if no clipboard tool is installed, else the tool.
+/// * `System` - Only the clipboard tool.
+/// * `Osc52` - Only the OSC 52 escape sequence, the terminal has to support it.
+#[derive(Deserialize, Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum ClipboardMode {
+    #[default]
+    Auto,
+    System,
+    Osc52,
+}
+
+/// The clipboard tools, in order of preference, with the arguments to read from stdin.
+/// Each tool is only used if the environment variable, if any, is set.
+const CLIPBOARD_TOOLS: &[(&str, &[&str], Option<&str>)] = &[
+    ("pbcopy", &[], None),
+    ("clip", &[], None),
+    ("termux-clipboard-set", &[], None),
+    ("wl-copy", &[], Some("WAYLAND_DISPLAY")),
+    ("xclip", &["-selection", "clipboard"], Some("DISPLAY")),
+    ("xsel", &["--clipboard", "--input"], Some("DISPLAY")),
+];
+
+/// The first available clipboard tool and its arguments.
+fn clipboard_tool() -> Option<(&'static str, &'static [&'static str])> {
+    CLIPBOARD_TOOLS
+        .iter()
+        .filter(|(_, _, env)| env.is_none_or(|var| std::env::var_os(var).is_some()))
+        .find(|(tool, _, _)| which::which(tool).is_ok())
+        .map(|(tool, args, _)| (*tool, *args))
+}
+
+/// Copies `text` to the clipboard, with a tool or OSC 52 depending on `mode`.
+///
+/// # Errors
+/// Returns an std::io::Error if the clipboard tool is missing or failed,
+/// or the escape sequence could not be written.
+pub fn copy_to_clipboard(text: &str, mode: ClipboardMode) -> io::Result<()> {
+    let use_osc52 = match mode {
+        ClipboardMode::Osc52 => true,
+        ClipboardMode::System => false,
+        ClipboardMode::Auto => is_ssh_session() || clipboard_tool().is_none(),
+    };
+    if use_osc52 {
+        let mut stdout = io::stdout();
+        stdout.write_all(osc52_sequence(text, std::env::var_os("TMUX").is_some()).as_bytes())?;
+        return stdout.flush();
+    }
+    copy_with_tool(text)
+}
+
+/// Whether runa runs in an SSH session, where the clipboard tools are not the local ones.
+fn is_ssh_session() -> bool {
+    ["SSH_TTY", "SSH_CONNECTION", "SSH_CLIENT"]
+        .iter()
+        .any(|var| std::env::var_os(var).is_some())
+}
+
+/// The OSC 52 escape sequence setting the clipboard to `text`.
+///
+/// Inside tmux the sequence is wrapped in a passthrough, so it reaches the outer terminal
+/// (this needs `set -g allow-passthrough on` in tmux 3.3 and newer).
+pub fn osc52_sequence(text: &str, tmux: bool) -> String {
+    let osc = format!("\x1b]52;c;{}\x07", base64_encode(text.as_bytes()));
+    if tmux {
+        format!("\x1bPtmux;\x1b{}\x1b\\", osc)
+    } else {
+        osc
+    }
+}
+
+/// Standard base64 with padding, as expected by OSC 52.
+fn base64_encode(bytes: &[u8]) -> String {
+    const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+    let mut out = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let n = chunk
+            .iter()
+            .enumerate()
+            .fold(0u32, |n, (i, b)| n | (u32::from(*b) << (16 - 8 * i)));
+        for i in 0..4 {
+            if i <= chunk.len() {
+                out.push(ALPHABET[(n >> (18 - 6 * i) & 0x3f) as usize] as char);
+            } else {
+                out.push('=');
+            }
+        }
+    }
+    out
+}
+
+/// Copies `text` with the first available clipboard tool.
+fn copy_with_tool(text: &str) -> io::Result<()> {
+    let (tool, args) = clipboard_tool().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            "No clipboard tool found (pbcopy, wl-copy, xclip or xsel)",
+        )
+    })?;
+
+    // The output is discarded, it would garble the TUI
+    let mut child = Command::new(tool)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(text.as_bytes())?;
+    }
+    let status = child.wait()?;
+    if status.success() {
+        Ok(())
+    } else {
+        Err(io::Error::other(format!("{} failed with {}", tool, status)))
+    }
+}
@@ -2,18 +2,26 @@
 //!
 //! This module defines constants for the minimum, default, and maximum find result limits used throughout runa.
 //! It also provides utility functions:
-//! - Color parsing from strings or hex codes
+//! - Color parsing from strings or hex codes, and text modifiers from their names
 //! - Opening paths/files in the user's chosen editor
 //! - Generating unused filenames to prevent accidental overwrite
 //! - Displaying home directories as "~" in file paths
 //! - Clamping the find result count to safe values
+//! - Detecting and resuming interrupted file copies
+//! - Copying files with an optional bandwidth limit
+//! - Running shell commands typed into the command prompt and opening a shell
+//! - Validating and brace expanding the names typed into the create and rename dialogs
 //!
 //! These helpers are used throughout runa.
 
 use crate::config::Editor;
-use ratatui::style::Color;
+use crate::core::platform::effective_home;
+use crate::core::streams::copy_streams;
+use ratatui::style::{Color, Modifier};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{MAIN_SEPARATOR, Path, PathBuf};
-use std::{fs, io};
+use std::time::{Duration, Instant};
+use std::{fs, io, thread};
 
 /// The minimum results which is set to if the maximum is overset in the runa.toml.
 pub const MIN_FIND_RESULTS: usize = 15;
@@ -22,6 +30,11 @@ pub const DEFAULT_FIND_RESULTS: usize = 2000;
 /// The maximum find result limit which is possible.
 /// Can be set higher, but better to set it to a big limit instead of usize::MAX
 pub const MAX_FIND_RESULTS_LIMIT: usize = 1000000;
+/// The number of bytes before the resume offset which must match before an interrupted copy is
+/// resumed instead of started over.
+pub const RESUME_VERIFY_WINDOW: u64 = 64 * 1024;
+/// The largest chunk written at once by a throttled copy.
+const THROTTLE_CHUNK_SIZE: u64 = 256 * 1024;
 
 /// Parses a string (color name or hex) into a ratatui::style::color
 ///
@@ -73,27 +86,261 @@ pub fn parse_color(s: &str) -> Color {
     }
 }
 
+/// Parses the name of a text modifier into a ratatui::style::Modifier
+///
+/// Supports bold, dim, italic, underline(d), reverse(d), crossed_out (or strikethrough),
+/// blink (or slow_blink), rapid_blink and hidden, case-insensitive.
+pub fn parse_modifier(s: &str) -> Option<Modifier> {
+    match s.to_lowercase().replace('-', "_").as_str() {
+        "bold" => Some(Modifier::BOLD),
+        "dim" => Some(Modifier::DIM),
+        "italic" => Some(Modifier::ITALIC),
+        "underline" | "underlined" => Some(Modifier::UNDERLINED),
+        "reverse" | "reversed" => Some(Modifier::REVERSED),
+        "crossed_out" | "strikethrough" => Some(Modifier::CROSSED_OUT),
+        "blink" | "slow_blink" => Some(Modifier::SLOW_BLINK),
+        "rapid_blink" => Some(Modifier::RAPID_BLINK),
+        "hidden" => Some(Modifier::HIDDEN),
+        _ => None,
+    }
+}
+
 /// Opens a specified path/file in the configured editor ("nvim" or "vim" etc.).
 ///
 /// Temporary disables raw mode and exits alternate sceen while the editor runs.
 /// On return, restores raw mode and alternate sceen.
 pub fn open_in_editor(editor: &Editor, file_path: &std::path::Path) -> std::io::Result<()> {
+    open_in_editor_at(editor, file_path, None)
+}
+
+/// Opens a specified path/file in the configured editor, optionally at the given line.
+///
+/// The line is passed as `+<line>` before the path, which is understood by vi, vim, nvim,
+/// nano, emacs and most other terminal editors.
+pub fn open_in_editor_at(
+    editor: &Editor,
+    file_path: &std::path::Path,
+    line: Option<usize>,
+) -> std::io::Result<()> {
+    let mut cmd = std::process::Command::new(editor.cmd());
+    if let Some(line) = line {
+        cmd.arg(format!("+{line}"));
+    }
+    with_suspended_terminal(|| cmd.arg(file_path).status())?.map(|_| ())
+}
+
+/// Runs `f` with the TUI suspended, for programs taking over the terminal.
+///
+/// Raw mode and the alternate screen are left before `f` runs and restored once it returns.
+pub fn with_suspended_terminal<T>(f: impl FnOnce() -> T) -> io::Result<T> {
     use crossterm::{
         execute,
         terminal::{EnterAlternateScreen, LeaveAlternateScreen, disable_raw_mode, enable_raw_mode},
     };
 
-    let mut stdout = io::stdout();
     disable_raw_mode()?;
-    execute!(stdout, LeaveAlternateScreen)?;
+    execute!(io::stdout(), LeaveAlternateScreen)?;
 
-    let status = std::process::Command::new(editor.cmd())
-        .arg(file_path)
-        .status();
+    let result = f();
 
     execute!(io::stdout(), EnterAlternateScreen)?;
     enable_raw_mode()?;
-    status.map(|_| ())
+    Ok(result)
+}
+
+/// Builds the command opening several files in the configured editor at once.
+///
+/// Runs the `open_many` template through the shell, with `%m` and `%s` expanding to the files,
+/// or passes every file as an argument of the editor command if no template is set.
+///
+/// # Arguments
+/// * `editor` - The editor configuration.
+/// * `files` - The files to open.
+/// * `dir` - The current directory, `%d` in the template.
+pub fn editor_command(editor: &Editor, files: &[PathBuf], dir: &Path) -> std::process::Command {
+    let mut cmd = match editor.open_many() {
+        Some(template) => shell_command(&expand_command(
+            template,
+            &CommandPaths {
+                dir,
+                selected: files.first().map(PathBuf::as_path),
+                marked: files,
+            },
+        )),
+        None => {
+            let mut cmd = std::process::Command::new(editor.cmd());
+            cmd.args(files);
+            cmd
+        }
+    };
+    cmd.current_dir(dir);
+    cmd
+}
+
+/// Opens several files in one invocation of the configured editor, see [editor_command].
+///
+/// Runs under [with_suspended_terminal], the terminal is handed over while the editor runs.
+pub fn open_many_in_editor(editor: &Editor, files: &[PathBuf], dir: &Path) -> io::Result<()> {
+    with_suspended_terminal(|| editor_command(editor, files, dir).status())?.map(|_| ())
+}
+
+/// Opens an interactive shell in `dir`, returning once it exits.
+///
+/// Runs under [with_suspended_terminal], the terminal is handed over while the shell runs.
+/// Uses `$SHELL` on unix (falling back to `sh`) and `%COMSPEC%` on windows (falling back to `cmd`).
+pub fn open_shell(dir: &Path) -> io::Result<()> {
+    let (var, fallback) = if cfg!(windows) {
+        ("COMSPEC", "cmd")
+    } else {
+        ("SHELL", "sh")
+    };
+    let shell = std::env::var_os(var)
+        .filter(|s| !s.is_empty())
+        .unwrap_or_else(|| fallback.into());
+
+    with_suspended_terminal(|| std::process::Command::new(shell).current_dir(dir).status())?
+        .map(|_| ())
+}
+
+/// Paths a shell command template can refer to.
+///
+/// # Fields
+/// * `dir` - The current directory, `%d`.
+/// * `selected` - The selected entry, `%f`.
+/// * `marked` - The marked entries, `%m`.
+pub struct CommandPaths<'a> {
+    pub dir: &'a Path,
+    pub selected: Option<&'a Path>,
+    pub marked: &'a [PathBuf],
+}
+
+/// Expands the placeholders of a shell command template, each path quoted for the shell.
+///
+/// * `%f` - The selected entry.
+/// * `%m` - The marked entries.
+/// * `%s` - The marked entries, or the selected entry if nothing is marked.
+/// * `%d` - The current directory.
+/// * `%%` - A literal `%`.
+///
+/// Placeholders without paths expand to nothing, unknown ones are kept as typed.
+pub fn expand_command(template: &str, paths: &CommandPaths) -> String {
+    let quote_all = |list: &[PathBuf]| {
+        list.iter()
+            .map(|p| shell_quote(&p.to_string_lossy()))
+            .collect::<Vec<_>>()
+            .join(" ")
+    };
+    let selected = paths
+        .selected
+        .map(|p| shell_quote(&p.to_string_lossy()))
+        .unwrap_or_default();
+    let marked = quote_all(paths.marked);
+    let targets = if paths.marked.is_empty() {
+        selected.clone()
+    } else {
+        marked.clone()
+    };
+
+    let mut out = String::with_capacity(template.len());
+    let mut chars = template.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let expansion = match chars.peek() {
+            Some('f') => selected.clone(),
+            Some('m') => marked.clone(),
+            Some('s') => targets.clone(),
+            Some('d') => shell_quote(&paths.dir.to_string_lossy()),
+            Some('%') => "%".to_string(),
+            _ => {
+                out.push(c);
+                continue;
+            }
+        };
+        chars.next();
+        out.push_str(&expansion);
+    }
+    out
+}
+
+/// Quotes a single argument for the platform shell used by [run_shell_command].
+///
+/// cmd expands `%VAR%` even inside double quotes, so on Windows each `%` is left outside
+/// the quotes and escaped as `^%`, e.g. `100%.txt` becomes `"100"^%".txt"`.
+pub fn shell_quote(arg: &str) -> String {
+    if cfg!(windows) {
+        format!("\"{}\"", arg.replace('"', "\"\"").replace('%', "\"^%\""))
+    } else {
+        format!("'{}'", arg.replace('\'', "'\\''"))
+    }
+}
+
+/// Runs a shell command in `dir` with the terminal handed over to it.
+///
+/// Runs under [with_suspended_terminal], the terminal is handed over while the command runs,
+/// so its output stays visible in the terminal until Enter is pressed.
+/// Uses `sh -c` on unix and `cmd /S /C` on windows.
+///
+/// # Returns
+/// The exit status of the command.
+pub fn run_shell_command(cmd: &str, dir: &Path) -> io::Result<std::process::ExitStatus> {
+    with_suspended_terminal(|| {
+        let status = shell_command(cmd).current_dir(dir).status();
+        match &status {
+            Ok(s) if s.success() => println!("\n[Press Enter to return to runa]"),
+            Ok(s) => println!("\n[{}] [Press Enter to return to runa]", s),
+            Err(e) => println!("\n[Error: {}] [Press Enter to return to runa]", e),
+        }
+        let _ = io::stdout().flush();
+        let _ = io::stdin().read_line(&mut String::new());
+        status
+    })?
+}
+
+/// Runs `cmd` through the shell in `dir` with the TUI suspended, like [run_shell_command],
+/// but returns right after the command exits instead of waiting for Enter.
+///
+/// Used for openers, which are either terminal programs taking over the screen
+/// or graphical programs that leave no output behind.
+///
+/// # Returns
+/// The exit status of the command.
+pub fn run_suspended(cmd: &str, dir: &Path) -> io::Result<std::process::ExitStatus> {
+    with_suspended_terminal(|| shell_command(cmd).current_dir(dir).status())?
+}
+
+/// Shows `path` full-screen in the pager with the TUI suspended, see [run_suspended].
+///
+/// The pager is `$PAGER`, or `less` (`more` on Windows) if it is not set.
+pub fn open_in_pager(path: &Path, dir: &Path) -> io::Result<std::process::ExitStatus> {
+    let pager = std::env::var("PAGER")
+        .ok()
+        .filter(|pager| !pager.trim().is_empty())
+        .unwrap_or_else(|| if cfg!(windows) { "more" } else { "less" }.to_string());
+    let cmd = format!("{} {}", pager, shell_quote(&path.to_string_lossy()));
+    run_suspended(&cmd, dir)
+}
+
+/// Builds a command running `cmd` through `sh -c`, or `cmd /S /C` on windows.
+///
+/// cmd parses its command line itself, so `cmd` is passed on raw instead of being quoted
+/// like a regular argument. With `/S` cmd only strips the outer quotes added here.
+fn shell_command(cmd: &str) -> std::process::Command {
+    #[cfg(windows)]
+    {
+        use std::os::windows::process::CommandExt;
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/S", "/C"]).raw_arg(format!("\"{}\"", cmd));
+        command
+    }
+    #[cfg(not(windows))]
+    {
+        let mut command = std::process::Command::new("sh");
+        command.arg("-c").arg(cmd);
+        command
+    }
 }
 
 /// Finds the next available filename by appending _1, _2, etc. if the target exists
@@ -128,6 +375,275 @@ pub fn get_unused_path(path: &Path) -> PathBuf {
     }
 }
 
+/// Device names reserved by Windows, with or without an extension.
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// A problem with a file name typed in the create or rename dialog.
+///
+/// Variants:
+/// * `Invalid` - The name cannot be created on this platform, the operation is refused.
+/// * `NotPortable` - The name works here but not on Windows filesystems, only a warning.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NameIssue {
+    Invalid(&'static str),
+    NotPortable(&'static str),
+}
+
+impl NameIssue {
+    pub fn message(&self) -> &'static str {
+        match self {
+            NameIssue::Invalid(msg) | NameIssue::NotPortable(msg) => msg,
+        }
+    }
+
+    pub fn is_invalid(&self) -> bool {
+        matches!(self, NameIssue::Invalid(_))
+    }
+}
+
+/// Checks a single file name for characters and names the filesystem would reject.
+///
+/// `/` and NUL are rejected everywhere. The Windows rules (reserved device names like `CON`,
+/// `<>:"|?*\`, control characters and trailing dots or spaces) are rejected on Windows and
+/// reported as [NameIssue::NotPortable] elsewhere, as such names break on shared or synced drives.
+///
+/// An empty name is not an issue, the dialogs simply ignore it.
+pub fn check_filename(name: &str) -> Option<NameIssue> {
+    let windows_issue = |msg| {
+        if cfg!(windows) {
+            NameIssue::Invalid(msg)
+        } else {
+            NameIssue::NotPortable(msg)
+        }
+    };
+
+    if name.is_empty() {
+        return None;
+    }
+    if name == "." || name == ".." {
+        return Some(NameIssue::Invalid("reserved name"));
+    }
+    if name.contains('/') {
+        return Some(NameIssue::Invalid("contains /"));
+    }
+    if name.contains('\0') {
+        return Some(NameIssue::Invalid("contains NUL"));
+    }
+    if let Some(c) = name.chars().find(|c| "<>:\"|?*\\".contains(*c)) {
+        return Some(windows_issue(match c {
+            '<' => "contains <",
+            '>' => "contains >",
+            ':' => "contains :",
+            '"' => "contains \"",
+            '|' => "contains |",
+            '?' => "contains ?",
+            '*' => "contains *",
+            _ => "contains \\",
+        }));
+    }
+    if name.chars().any(char::is_control) {
+        return Some(windows_issue("contains control characters"));
+    }
+    if name.ends_with('.') || name.ends_with(' ') {
+        return Some(windows_issue("ends with a dot or space"));
+    }
+    let stem = name.split('.').next().unwrap_or_default().trim_end();
+    if WINDOWS_RESERVED_NAMES
+        .iter()
+        .any(|reserved| stem.eq_ignore_ascii_case(reserved))
+    {
+        return Some(windows_issue("reserved on Windows"));
+    }
+    None
+}
+
+/// Whether an error means the file is opened by another process which does not share it.
+///
+/// Windows refuses to delete or rename files other programs hold open with a sharing or
+/// lock violation, other platforms allow both.
+pub fn is_file_in_use(err: &io::Error) -> bool {
+    // ERROR_SHARING_VIOLATION and ERROR_LOCK_VIOLATION
+    cfg!(windows) && matches!(err.raw_os_error(), Some(32 | 33))
+}
+
+/// Length in bytes of the stem of a file name, the part before the last extension.
+///
+/// Leading dots belong to the stem, so `.bashrc` has no extension, and so does `notes.`.
+pub fn name_stem_len(name: &str) -> usize {
+    let leading = name.len() - name.trim_start_matches('.').len();
+    match name[leading..].rfind('.') {
+        Some(dot) if leading + dot + 1 < name.len() => leading + dot,
+        _ => name.len(),
+    }
+}
+
+/// The most entries a single create prompt may expand to.
+pub const MAX_BRACE_EXPANSION: usize = 1000;
+
+/// Expands shell style braces in a create prompt, e.g. `src/{lib,main}.rs` or `chapter{1..5}/`.
+///
+/// * `{a,b}` - Each alternative, braces may be nested.
+/// * `{1..5}`, `{5..1}`, `{01..10}` - Numeric ranges, zero padded like the wider bound.
+/// * `{a..e}` - Character ranges.
+///
+/// Braces without a comma or range are kept as typed, like in bash.
+///
+/// # Errors
+/// Returns an error message if the input expands to more than [MAX_BRACE_EXPANSION] names.
+pub fn expand_braces(input: &str) -> Result<Vec<String>, &'static str> {
+    const TOO_MANY: &str = "too many names";
+
+    let Some((start, end, alternatives)) = find_brace_group(input) else {
+        return Ok(vec![input.to_string()]);
+    };
+    let alternatives = alternatives.ok_or(TOO_MANY)?;
+
+    let prefix = &input[..start];
+    let suffixes = expand_braces(&input[end + 1..])?;
+    let mut names = Vec::new();
+    for alternative in alternatives {
+        for middle in expand_braces(&alternative)? {
+            for suffix in &suffixes {
+                if names.len() == MAX_BRACE_EXPANSION {
+                    return Err(TOO_MANY);
+                }
+                names.push(format!("{}{}{}", prefix, middle, suffix));
+            }
+        }
+    }
+    Ok(names)
+}
+
+/// Finds the first brace group of `input` which expands to anything.
+///
+/// # Returns
+/// The byte positions of `{` and the matching `}`, and the alternatives of the group,
+/// which are None for a range longer than [MAX_BRACE_EXPANSION].
+fn find_brace_group(input: &str) -> Option<(usize, usize, Option<Vec<String>>)> {
+    let bytes = input.as_bytes();
+    let mut search = 0;
+    while let Some(offset) = input[search..].find('{') {
+        let start = search + offset;
+        let mut depth = 0;
+        let mut commas = Vec::new();
+        let mut end = None;
+        for (i, &b) in bytes.iter().enumerate().skip(start) {
+            match b {
+                b'{' => depth += 1,
+                b'}' => {
+                    depth -= 1;
+                    if depth == 0 {
+                        end = Some(i);
+                        break;
+                    }
+                }
+                b',' if depth == 1 => commas.push(i),
+                _ => {}
+            }
+        }
+        let end = end?;
+        let body = &input[start + 1..end];
+
+        if !commas.is_empty() {
+            let mut alternatives = Vec::with_capacity(commas.len() + 1);
+            let mut from = start + 1;
+            for comma in commas {
+                alternatives.push(input[from..comma].to_string());
+                from = comma + 1;
+            }
+            alternatives.push(input[from..end].to_string());
+            return Some((start, end, Some(alternatives)));
+        }
+        if let Some(range) = expand_range(body) {
+            return Some((start, end, range));
+        }
+        search = start + 1;
+    }
+    None
+}
+
+/// Expands the body of a `{from..to}` range, numeric or single characters.
+///
+/// # Returns
+/// None if `body` is no range, Some(None) if the range is longer than [MAX_BRACE_EXPANSION].
+fn expand_range(body: &str) -> Option<Option<Vec<String>>> {
+    let (from, to) = body.split_once("..")?;
+
+    if let (Ok(a), Ok(b)) = (from.parse::<i64>(), to.parse::<i64>()) {
+        if a.abs_diff(b) >= MAX_BRACE_EXPANSION as u64 {
+            return Some(None);
+        }
+        let padded = |s: &str| {
+            let digits = s.trim_start_matches('-');
+            digits.len() > 1 && digits.starts_with('0')
+        };
+        let width = if padded(from) || padded(to) {
+            from.len().max(to.len())
+        } else {
+            0
+        };
+        let values: Box<dyn Iterator<Item = i64>> = if a <= b {
+            Box::new(a..=b)
+        } else {
+            Box::new((b..=a).rev())
+        };
+        return Some(Some(values.map(|n| format!("{:0width$}", n)).collect()));
+    }
+
+    let mut from_chars = from.chars();
+    let mut to_chars = to.chars();
+    match (
+        from_chars.next(),
+        from_chars.next(),
+        to_chars.next(),
+        to_chars.next(),
+    ) {
+        (Some(a), None, Some(b), None) if a.is_ascii_alphabetic() && b.is_ascii_alphabetic() => {
+            let chars: Vec<String> = if a <= b {
+                (a..=b).map(String::from).collect()
+            } else {
+                (b..=a).rev().map(String::from).collect()
+            };
+            Some(Some(chars))
+        }
+        _ => None,
+    }
+}
+
+/// Expands and checks the input of the create dialog.
+///
+/// Every expanded name may contain `/` to create inside a subdirectory, missing directories are created,
+/// and a trailing `/` creates a directory. Each path component is checked with [check_filename].
+///
+/// # Returns
+/// The expanded names and the most severe issue found, if any.
+pub fn expand_create_input(input: &str) -> (Vec<String>, Option<NameIssue>) {
+    let names = match expand_braces(input) {
+        Ok(names) => names,
+        Err(msg) => return (Vec::new(), Some(NameIssue::Invalid(msg))),
+    };
+
+    let mut issue = None;
+    for name in &names {
+        if name.starts_with('/') {
+            return (names, Some(NameIssue::Invalid("absolute path")));
+        }
+        for component in name.trim_end_matches('/').split('/') {
+            match check_filename(component) {
+                Some(found @ NameIssue::Invalid(_)) => return (names, Some(found)),
+                Some(found) => {
+                    issue.get_or_insert(found);
+                }
+                None => {}
+            }
+        }
+    }
+    (names, issue)
+}
+
 /// Util function to shorten home directory to ~.
 /// Is used by the path_str in the ui.rs render function.
 pub fn shorten_home_path<P: AsRef<Path>>(path: P) -> String {
@@ -148,6 +664,50 @@ pub fn shorten_home_path<P: AsRef<Path>>(path: P) -> String {
     path.display().to_string()
 }
 
+/// Expresses `path` relative to `base`, with `..` for every directory of `base` left behind.
+///
+/// Both paths are expected to be absolute. If they share no root, e.g. different drives
+/// on Windows, `path` is returned unchanged.
+pub fn relative_path(path: &Path, base: &Path) -> PathBuf {
+    let path_parts: Vec<_> = path.components().collect();
+    let base_parts: Vec<_> = base.components().collect();
+    let common = path_parts
+        .iter()
+        .zip(&base_parts)
+        .take_while(|(a, b)| a == b)
+        .count();
+    if common == 0 {
+        return path.to_path_buf();
+    }
+
+    let mut relative = PathBuf::new();
+    for _ in common..base_parts.len() {
+        relative.push("..");
+    }
+    for part in &path_parts[common..] {
+        relative.push(part);
+    }
+    if relative.as_os_str().is_empty() {
+        relative.push(".");
+    }
+    relative
+}
+
+/// Path of the persisted find history, `~/.local/share/runa/find_history`.
+/// Returns None if the home directory is unknown.
+pub fn find_history_path() -> Option<PathBuf> {
+    dirs::home_dir().map(|home| home.join(".local/share/runa/find_history"))
+}
+
+/// Path of the operation log of root sessions, `~/.local/share/runa/root.log`.
+///
+/// Placed in the home of the effective user (see [effective_home]), so a `sudo` keeping `$HOME`
+/// does not leave a root owned directory in the home of the invoking user.
+/// Returns None if the home directory is unknown.
+pub fn root_log_path() -> Option<PathBuf> {
+    effective_home().map(|home| home.join(".local/share/runa/root.log"))
+}
+
 /// Safely clamp the find result numbers.
 ///
 /// If the clamped value does not match the set [MAX_FIND_RESULTS_LIMIT] then its invalid and its
@@ -166,19 +726,170 @@ pub fn clamp_find_results(value: usize) -> usize {
 /// Recursively copies files and directories from `src` to `dest`.
 ///
 /// If `src` is a directory, it creates the directory at `dest` and copies all its contents recursively.
-pub fn copy_recursive(src: &Path, dest: &Path) -> io::Result<()> {
+/// Every file is copied with [copy_file_limited], so `max_bytes_per_sec` applies to each file,
+/// followed by its alternate data streams or resource fork, see [copy_streams].
+///
+/// # Arguments
+/// * `lost_streams` - Collects the files whose streams the destination could not hold.
+///
+/// # Returns
+/// The number of bytes copied.
+pub fn copy_recursive(
+    src: &Path,
+    dest: &Path,
+    max_bytes_per_sec: Option<u64>,
+    lost_streams: &mut Vec<PathBuf>,
+) -> io::Result<u64> {
     if src.is_dir() {
         fs::create_dir_all(dest)?;
+        let mut copied = 0;
         for entry in fs::read_dir(src)? {
             let entry = entry?;
             let entry_path = entry.path();
             let dest_path = dest.join(entry.file_name());
-            copy_recursive(&entry_path, &dest_path)?;
+            copied += copy_recursive(&entry_path, &dest_path, max_bytes_per_sec, lost_streams)?;
         }
+        Ok(copied)
     } else {
-        fs::copy(src, dest)?;
+        let copied = copy_file_limited(src, dest, max_bytes_per_sec)?;
+        if !copy_streams(src, dest).is_empty() {
+            lost_streams.push(src.to_path_buf());
+        }
+        Ok(copied)
+    }
+}
+
+/// Copies a single file, optionally limited to `max_bytes_per_sec`.
+///
+/// Without a limit this is [fs::copy]. With a limit the data is streamed in chunks,
+/// sleeping between them so the average rate stays below the limit, and the permissions
+/// of `src` are applied to `dest` afterwards.
+///
+/// Storage without unix permissions, like the shared storage of Android, refuses to set them.
+/// The file is then copied anyway and keeps the permissions the storage gives it.
+///
+/// # Returns
+/// The number of bytes copied.
+pub fn copy_file_limited(
+    src: &Path,
+    dest: &Path,
+    max_bytes_per_sec: Option<u64>,
+) -> io::Result<u64> {
+    if max_bytes_per_sec.is_none_or(|limit| limit == 0) {
+        match fs::copy(src, dest) {
+            // fs::copy fails on the permissions after writing the data, stream it instead
+            Err(e) if e.kind() == io::ErrorKind::PermissionDenied && dest.is_file() => {}
+            result => return result,
+        }
+    }
+
+    let mut reader = fs::File::open(src)?;
+    let mut writer = fs::File::create(dest)?;
+    let copied = copy_throttled(&mut reader, &mut writer, max_bytes_per_sec)?;
+    match fs::set_permissions(dest, reader.metadata()?.permissions()) {
+        Err(e)
+            if matches!(
+                e.kind(),
+                io::ErrorKind::PermissionDenied | io::ErrorKind::Unsupported
+            ) => {}
+        result => result?,
     }
-    Ok(())
+    Ok(copied)
+}
+
+/// Streams `reader` into `writer`, keeping the average rate below `max_bytes_per_sec`.
+///
+/// Falls back to [io::copy] when no limit is set.
+fn copy_throttled<R: Read, W: Write>(
+    reader: &mut R,
+    writer: &mut W,
+    max_bytes_per_sec: Option<u64>,
+) -> io::Result<u64> {
+    let Some(limit) = max_bytes_per_sec.filter(|limit| *limit > 0) else {
+        return io::copy(reader, writer);
+    };
+
+    let mut buf = vec![0; limit.clamp(4096, THROTTLE_CHUNK_SIZE) as usize];
+    let start = Instant::now();
+    let mut copied: u64 = 0;
+
+    loop {
+        let n = match reader.read(&mut buf) {
+            Ok(0) => break,
+            Ok(n) => n,
+            Err(e) if e.kind() == io::ErrorKind::Interrupted => continue,
+            Err(e) => return Err(e),
+        };
+        writer.write_all(&buf[..n])?;
+        copied += n as u64;
+
+        let expected = Duration::from_secs_f64(copied as f64 / limit as f64);
+        let elapsed = start.elapsed();
+        if expected > elapsed {
+            thread::sleep(expected - elapsed);
+        }
+    }
+    Ok(copied)
+}
+
+/// Checks if `dest` looks like an interrupted copy of `src`.
+///
+/// The destination qualifies if both paths are regular files, the destination is non-empty and
+/// smaller than the source, and the last [RESUME_VERIFY_WINDOW] bytes before the resume offset
+/// are identical in both files.
+///
+/// # Returns
+/// * `Some(offset)` - The byte offset from which the copy can be resumed.
+/// * `None` - If `dest` is not a resumable partial copy of `src`.
+pub fn resumable_copy_offset(src: &Path, dest: &Path) -> Option<u64> {
+    let src_meta = fs::metadata(src).ok()?;
+    let dest_meta = fs::symlink_metadata(dest).ok()?;
+    if !src_meta.is_file() || !dest_meta.is_file() {
+        return None;
+    }
+
+    let offset = dest_meta.len();
+    if offset == 0 || offset >= src_meta.len() {
+        return None;
+    }
+
+    let window = offset.min(RESUME_VERIFY_WINDOW);
+    let start = offset - window;
+    let mut src_buf = vec![0; window as usize];
+    let mut dest_buf = vec![0; window as usize];
+    read_window(src, start, &mut src_buf).ok()?;
+    read_window(dest, start, &mut dest_buf).ok()?;
+
+    (src_buf == dest_buf).then_some(offset)
+}
+
+/// Resumes an interrupted copy by appending the remainder of `src` to `dest`.
+///
+/// Should only be called with an offset returned by [resumable_copy_offset].
+/// The appended data is limited to `max_bytes_per_sec` if set.
+///
+/// # Returns
+/// The number of bytes appended to `dest`.
+pub fn resume_copy(
+    src: &Path,
+    dest: &Path,
+    offset: u64,
+    max_bytes_per_sec: Option<u64>,
+) -> io::Result<u64> {
+    let mut reader = fs::File::open(src)?;
+    reader.seek(SeekFrom::Start(offset))?;
+
+    let mut writer = fs::OpenOptions::new().write(true).open(dest)?;
+    writer.set_len(offset)?;
+    writer.seek(SeekFrom::Start(offset))?;
+    copy_throttled(&mut reader, &mut writer, max_bytes_per_sec)
+}
+
+/// Reads exactly `buf.len()` bytes of `path` starting at `start`.
+fn read_window(path: &Path, start: u64, buf: &mut [u8]) -> io::Result<()> {
+    let mut file = fs::File::open(path)?;
+    file.seek(SeekFrom::Start(start))?;
+    file.read_exact(buf)
 }
 
 /// Helpers to convert Option<&PathBuf> to Option<&Path>
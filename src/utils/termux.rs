@@ -0,0 +1,58 @@
+//! Termux compatibility for runa.
+//!
+//! Termux runs runa on Android phones, with its own prefix (`$PREFIX`) instead of `/usr`, no
+//! `fd` or `bat` unless installed with `pkg`, and few slow cores. The `termux` config option
+//! defaults to [is_termux], and then limits the threads runa starts and warns about missing tools.
+
+use std::sync::OnceLock;
+
+/// The optional tools runa starts, which are separate packages in Termux.
+const TOOLS: [&str; 2] = ["fd", "bat"];
+
+/// Threads used for parallel work in Termux, instead of one per core.
+pub const TERMUX_THREADS: usize = 2;
+
+static THREAD_LIMIT: OnceLock<usize> = OnceLock::new();
+
+/// Whether runa runs inside Termux, by the variables Termux sets for its shells.
+pub fn is_termux() -> bool {
+    std::env::var_os("TERMUX_VERSION").is_some()
+        || std::env::var("PREFIX").is_ok_and(|prefix| prefix.contains("/com.termux/"))
+}
+
+/// The tools of [TOOLS] which are not found in `PATH`.
+pub fn missing_tools() -> Vec<&'static str> {
+    TOOLS
+        .into_iter()
+        .filter(|tool| which::which(tool).is_err())
+        .collect()
+}
+
+/// A hint how to install the missing tools, `None` if all are installed.
+pub fn install_hint() -> Option<String> {
+    let missing = missing_tools();
+    if missing.is_empty() {
+        return None;
+    }
+    Some(format!(
+        "Termux: {} not found, install with `pkg install {}`",
+        missing.join(" and "),
+        missing.join(" ")
+    ))
+}
+
+/// Limits the threads for parallel work to [TERMUX_THREADS].
+///
+/// Has to be called before any parallel work starts, later calls do nothing.
+pub fn limit_threads() {
+    if THREAD_LIMIT.set(TERMUX_THREADS).is_ok() {
+        let _ = rayon::ThreadPoolBuilder::new()
+            .num_threads(TERMUX_THREADS)
+            .build_global();
+    }
+}
+
+/// The thread limit set by [limit_threads], passed on to `fd`.
+pub fn thread_limit() -> Option<usize> {
+    THREAD_LIMIT.get().copied()
+}
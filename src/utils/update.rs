@@ -0,0 +1,254 @@
+//! Self-update for runa (`rn --update`).
+//!
+//! Checks the latest GitHub release, downloads the archive for the current platform,
+//! verifies it against the `SHA256SUMS.txt` of the release and replaces the running binary.
+//! Releases are not signed, so the checksum is the only verification. The archive is kept in
+//! memory, so the verified bytes are the ones unpacked, into a private temporary directory.
+//!
+//! Like the other integrations (fd, bat, git), the network and archive work is done by external
+//! tools: `curl` for downloads and `tar` for unpacking, both shipped with Linux, macOS and Windows 10+.
+//!
+//! Only built with the `self-update` feature, so distro packages can leave updating to the
+//! package manager.
+
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// The releases page of runa.
+const RELEASES_URL: &str = "https://github.com/alexm-dev/runa/releases";
+
+/// Checks for a newer release and installs it over the current executable.
+///
+/// Progress is printed to stdout, as this runs before the TUI starts.
+///
+/// # Errors
+/// Returns an std::io::Error if the release cannot be fetched or verified,
+/// or if the executable cannot be replaced.
+pub fn self_update() -> io::Result<()> {
+    let current = env!("CARGO_PKG_VERSION");
+    let latest = latest_version()?;
+    if !is_newer(&latest, current) {
+        println!("runa {} is up to date.", current);
+        return Ok(());
+    }
+
+    let asset = asset_name().ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::Unsupported,
+            "No pre-compiled binary is published for this platform",
+        )
+    })?;
+    println!("Updating runa {} -> {} ({})", current, latest, asset);
+
+    // A fresh directory only accessible by the current user, removed when dropped
+    let work_dir = tempfile::Builder::new().prefix("runa-update-").tempdir()?;
+    download_and_install(&latest, &asset, work_dir.path())?;
+
+    println!("Updated to runa {}.", latest);
+    Ok(())
+}
+
+/// Downloads, verifies and installs the release archive `asset` of `version`.
+fn download_and_install(version: &str, asset: &str, work_dir: &Path) -> io::Result<()> {
+    let base = format!("{}/download/v{}", RELEASES_URL, version);
+    let archive = curl_fetch(&format!("{}/{}", base, asset))?;
+    let sums = curl_fetch(&format!("{}/SHA256SUMS.txt", base))?;
+
+    let expected = expected_checksum(&String::from_utf8_lossy(&sums), asset).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is not listed in SHA256SUMS.txt", asset),
+        )
+    })?;
+    let actual = sha256_hex(&archive);
+    if !actual.eq_ignore_ascii_case(&expected) {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!(
+                "Checksum mismatch for {}: expected {}, got {}",
+                asset, expected, actual
+            ),
+        ));
+    }
+
+    let unpack_dir = work_dir.join("unpacked");
+    fs::create_dir(&unpack_dir)?;
+    // GNU tar only detects the compression of files, not of archives read from stdin
+    let flags = if asset.ends_with(".tar.gz") {
+        "-xzf"
+    } else {
+        "-xf"
+    };
+    unpack(
+        Command::new("tar")
+            .args([flags, "-", "-C"])
+            .arg(&unpack_dir),
+        &archive,
+    )?;
+
+    let bin_name = if cfg!(windows) { "rn.exe" } else { "rn" };
+    let new_bin = find_file(&unpack_dir, bin_name).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} not found in {}", bin_name, asset),
+        )
+    })?;
+    replace_executable(&new_bin)
+}
+
+/// Reads the latest released version from the redirect of the `releases/latest` page.
+fn latest_version() -> io::Result<String> {
+    let output = Command::new("curl")
+        .args([
+            "-sSLI",
+            "-o",
+            if cfg!(windows) { "NUL" } else { "/dev/null" },
+        ])
+        .args(["-w", "%{url_effective}"])
+        .arg(format!("{}/latest", RELEASES_URL))
+        .output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "Checking the latest release failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    let url = String::from_utf8_lossy(&output.stdout);
+    version_from_tag_url(&url).ok_or_else(|| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("Unexpected release url: {}", url),
+        )
+    })
+}
+
+/// Extracts the version of a release tag url, e.g. `.../releases/tag/v0.5.1` -> `0.5.1`.
+pub fn version_from_tag_url(url: &str) -> Option<String> {
+    let (_, tag) = url.trim().rsplit_once("/tag/")?;
+    let version = tag.trim_start_matches('v');
+    (!version.is_empty()).then(|| version.to_string())
+}
+
+/// Returns true if `candidate` is a higher `major.minor.patch` version than `current`.
+/// Pre-release suffixes are ignored.
+pub fn is_newer(candidate: &str, current: &str) -> bool {
+    fn parse(v: &str) -> Vec<u64> {
+        v.split(['-', '+'])
+            .next()
+            .unwrap_or_default()
+            .split('.')
+            .map(|part| part.parse().unwrap_or(0))
+            .collect()
+    }
+    parse(candidate) > parse(current)
+}
+
+/// The release archive for the current platform, e.g. `runa-linux-x86_64.tar.gz`.
+pub fn asset_name() -> Option<String> {
+    let os = match std::env::consts::OS {
+        "linux" => "linux",
+        "macos" => "macos",
+        "windows" => "windows",
+        _ => return None,
+    };
+    let arch = match std::env::consts::ARCH {
+        arch @ ("x86_64" | "aarch64") => arch,
+        _ => return None,
+    };
+    let ext = if os == "windows" { "zip" } else { "tar.gz" };
+    Some(format!("runa-{}-{}.{}", os, arch, ext))
+}
+
+/// Looks up the checksum of `asset` in the contents of a `SHA256SUMS.txt`
+/// (`<hex digest>  <file name>` per line, as written by sha256sum).
+pub fn expected_checksum(sums: &str, asset: &str) -> Option<String> {
+    sums.lines().find_map(|line| {
+        let (digest, name) = line.trim().split_once(char::is_whitespace)?;
+        (name.trim().trim_start_matches('*') == asset).then(|| digest.to_string())
+    })
+}
+
+/// Hex encoded SHA-256 digest of `data`.
+pub fn sha256_hex(data: &[u8]) -> String {
+    Sha256::digest(data)
+        .iter()
+        .map(|b| format!("{:02x}", b))
+        .collect()
+}
+
+/// Downloads `url` into memory, following redirects.
+fn curl_fetch(url: &str) -> io::Result<Vec<u8>> {
+    let output = Command::new("curl").arg("-fsSL").arg(url).output()?;
+    if !output.status.success() {
+        return Err(io::Error::other(format!(
+            "Downloading {} failed: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        )));
+    }
+    Ok(output.stdout)
+}
+
+/// Runs the unpacking tool `cmd` with `archive` on its stdin,
+/// turning a failed exit status into an error.
+fn unpack(cmd: &mut Command, archive: &[u8]) -> io::Result<()> {
+    let mut child = cmd.stdin(Stdio::piped()).spawn()?;
+    // A tool exiting early closes the pipe, its exit status tells why
+    let written = match child.stdin.take() {
+        Some(mut stdin) => stdin.write_all(archive),
+        None => Ok(()),
+    };
+    let status = child.wait()?;
+    if !status.success() {
+        return Err(io::Error::other(format!(
+            "{} failed with {}",
+            cmd.get_program().to_string_lossy(),
+            status
+        )));
+    }
+    written
+}
+
+/// Searches `dir` recursively for a file called `name`.
+fn find_file(dir: &Path, name: &str) -> Option<PathBuf> {
+    for entry in fs::read_dir(dir).ok()?.flatten() {
+        let path = entry.path();
+        if path.is_dir() {
+            if let Some(found) = find_file(&path, name) {
+                return Some(found);
+            }
+        } else if entry.file_name() == name {
+            return Some(path);
+        }
+    }
+    None
+}
+
+/// Moves `new_bin` into the place of the running executable.
+///
+/// The new binary is first copied next to the executable, so the final rename stays on one
+/// filesystem. Windows does not allow replacing a running executable, so it is renamed
+/// to `rn.exe.old` first.
+fn replace_executable(new_bin: &Path) -> io::Result<()> {
+    let current = std::env::current_exe()?.canonicalize()?;
+    let staged = current.with_extension("new");
+    fs::copy(new_bin, &staged)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(&staged, fs::Permissions::from_mode(0o755))?;
+    }
+
+    #[cfg(windows)]
+    {
+        let old = current.with_extension("exe.old");
+        let _ = fs::remove_file(&old);
+        fs::rename(&current, &old)?;
+    }
+
+    fs::rename(&staged, &current)
+}
@@ -7,13 +7,23 @@
 
 use rand::rng;
 use rand::seq::SliceRandom;
-use runa_tui::app::NavState;
-use runa_tui::core::browse_dir;
+use runa_tui::app::actions::{DeleteState, FindHistory};
+use runa_tui::app::{FilterMode, Jump, JumpList, MarkerPolicy, NavState, Notifications, Severity};
+use runa_tui::core::finder::parse_user_tags;
+use runa_tui::core::platform;
+use runa_tui::core::streams::parse_stream_name;
+use runa_tui::core::{
+    DirId, FileEntry, FileInfo, FileType, FinderTags, Quarantine, SecurityContext, TagColor,
+    browse_dir, count_dir_items, find_renamed_dir, format_dir_items, list_streams, mount_fs_type,
+    parse_mounts, path_size, remove_path,
+};
 use std::collections::HashSet;
 use std::error;
+use std::ffi::OsString;
 use std::fs;
 use std::fs::File;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use tempfile::tempdir;
 
 #[test]
@@ -310,3 +320,580 @@ fn test_navstate_marker_persistence() -> Result<(), Box<dyn error::Error>> {
     assert!(nav.markers().contains(&base_path.join("banana.txt")));
     Ok(())
 }
+
+#[test]
+fn test_navstate_marker_paths_outside_current_dir() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let base_path = dir.path().to_path_buf();
+    fs::create_dir_all(base_path.join("sub/deep"))?;
+    fs::write(base_path.join("sub/deep/found.rs"), "")?;
+    fs::write(base_path.join("sub/copied.rs"), "")?;
+
+    let mut nav = NavState::new(base_path.clone());
+    nav.update_from_worker(base_path.clone(), browse_dir(&base_path)?, None);
+
+    let found = base_path.join("sub/deep/found.rs");
+    let copied = base_path.join("sub/copied.rs");
+    let mut clipboard = Some(HashSet::from([copied.clone()]));
+
+    nav.toggle_marker_path(found.clone(), &mut clipboard);
+    nav.toggle_marker_path(copied.clone(), &mut clipboard);
+    assert!(nav.markers().contains(&found));
+    assert!(nav.markers().contains(&copied));
+    assert!(clipboard.as_ref().is_some_and(|c| c.is_empty()));
+
+    let targets = nav.get_action_targets();
+    assert_eq!(targets, HashSet::from([found.clone(), copied.clone()]));
+
+    nav.toggle_marker_path(found.clone(), &mut clipboard);
+    assert!(!nav.markers().contains(&found));
+    assert_eq!(nav.markers().len(), 1);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_navstate_follows_renamed_dir() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let old = dir.path().join("old");
+    fs::create_dir(&old)?;
+    fs::write(old.join("kept.txt"), "")?;
+    fs::create_dir(dir.path().join("other"))?;
+
+    let mut nav = NavState::new(old.clone());
+    nav.update_from_worker(old.clone(), browse_dir(&old)?, None);
+    nav.set_dir_id(DirId::of(&old));
+    let mut clipboard = None;
+    nav.toggle_marker(&mut clipboard);
+
+    let new = dir.path().join("new");
+    fs::rename(&old, &new)?;
+
+    let id = nav.dir_id().ok_or("no dir id recorded")?;
+    let found = find_renamed_dir(dir.path(), id).ok_or("renamed dir not found")?;
+    assert_eq!(found, new);
+
+    nav.follow_rename(found);
+    assert_eq!(nav.current_dir(), new.as_path());
+    assert!(nav.markers().contains(&new.join("kept.txt")));
+    assert_eq!(
+        nav.selected_entry().map(|e| e.name_str().to_string()),
+        Some("kept.txt".to_string())
+    );
+    Ok(())
+}
+
+#[test]
+fn test_file_entry_memory_footprint() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let base_path = dir.path();
+    for i in 0..1000 {
+        File::create(base_path.join(format!("data_{i}.txt")))?;
+        File::create(base_path.join(format!("Report_{i}.TXT")))?;
+    }
+
+    let entries = browse_dir(base_path)?;
+    assert_eq!(entries.len(), 2000);
+    assert!(std::mem::size_of::<FileEntry>() <= 48);
+
+    // Lowercase names are stored once for the name and once for the display name,
+    // mixed case names additionally keep their lowercase form
+    let expected: usize = entries
+        .iter()
+        .map(|e| {
+            let len = e.name_str().len();
+            if e.name_str() == e.lowercase_name() {
+                2 * len
+            } else {
+                3 * len
+            }
+        })
+        .sum();
+    let measured: usize = entries.iter().map(|e| e.heap_size()).sum();
+    assert_eq!(measured, expected);
+
+    let report = entries
+        .iter()
+        .find(|e| e.name_str() == "Report_7.TXT")
+        .ok_or("Report_7.TXT not found")?;
+    assert_eq!(report.lowercase_name(), "report_7.txt");
+    assert_eq!(report.display_name(), "Report_7.TXT");
+    assert_eq!(report.name(), "Report_7.TXT");
+    Ok(())
+}
+
+#[test]
+fn test_delete_state_excludes_toggled_targets() -> Result<(), Box<dyn error::Error>> {
+    let mut state = DeleteState::default();
+    state.set_targets(vec![
+        PathBuf::from("/tmp/c.txt"),
+        PathBuf::from("/tmp/a.txt"),
+        PathBuf::from("/tmp/b.txt"),
+    ]);
+
+    assert_eq!(state.targets()[0], PathBuf::from("/tmp/a.txt"));
+    assert_eq!(state.included_count(), 3);
+
+    state.select_next();
+    state.toggle_selected();
+    assert!(state.is_excluded(1));
+    assert_eq!(
+        state.included(),
+        vec![PathBuf::from("/tmp/a.txt"), PathBuf::from("/tmp/c.txt")]
+    );
+
+    state.toggle_selected();
+    assert_eq!(state.included_count(), 3);
+
+    state.select_next();
+    state.select_next();
+    assert_eq!(state.selected(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_navstate_filter_modes() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let base_path = dir.path().to_path_buf();
+    for name in ["main.rs", "lib.rs", "Cargo.toml", "readme.md"] {
+        fs::write(base_path.join(name), "")?;
+    }
+
+    let mut nav = NavState::new(base_path.clone());
+    nav.update_from_worker(base_path.clone(), browse_dir(&base_path)?, None);
+
+    let shown = |nav: &NavState| {
+        let mut names: Vec<String> = nav
+            .shown_entries()
+            .map(|e| e.name_str().to_string())
+            .collect();
+        names.sort();
+        names
+    };
+
+    nav.set_filter("/glob:*.rs".to_string());
+    assert_eq!(shown(&nav), vec!["lib.rs", "main.rs"]);
+
+    nav.set_filter("/re:^(cargo|readme)\\.".to_string());
+    assert_eq!(shown(&nav), vec!["Cargo.toml", "readme.md"]);
+    assert!(nav.filter_error().is_none());
+
+    nav.set_filter("/fuzzy:mrs".to_string());
+    assert_eq!(shown(&nav), vec!["main.rs"]);
+    nav.set_filter("/fuzzy:MRS".to_string());
+    assert_eq!(shown(&nav), vec!["main.rs"]);
+
+    // Invalid patterns are flagged and fall back to a substring match
+    nav.set_filter("/re:lib(".to_string());
+    assert!(nav.filter_error().is_some());
+    assert_eq!(nav.shown_entries_len(), 0);
+
+    nav.set_filter_mode(FilterMode::Glob);
+    nav.set_filter("*.md".to_string());
+    assert!(nav.filter_error().is_none());
+    assert_eq!(shown(&nav), vec!["readme.md"]);
+
+    nav.clear_filters();
+    assert_eq!(nav.shown_entries_len(), 4);
+    Ok(())
+}
+
+#[test]
+fn test_count_dir_items_skips_excludes() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    fs::create_dir_all(dir.path().join("src/nested"))?;
+    File::create(dir.path().join("src/main.rs"))?;
+    File::create(dir.path().join("src/nested/lib.rs"))?;
+    fs::create_dir_all(dir.path().join("node_modules/pkg"))?;
+    File::create(dir.path().join("node_modules/pkg/index.js"))?;
+
+    let excludes: HashSet<OsString> = [OsString::from("node_modules")].into_iter().collect();
+    let items = count_dir_items(dir.path(), &excludes);
+    assert_eq!(items.count(), 4);
+    assert_eq!(items.excluded(), 1);
+    assert!(!items.truncated());
+    assert_eq!(format_dir_items(&items), "4 (1 excluded)");
+
+    let info = FileInfo::get_file_info(dir.path(), &excludes)?;
+    assert_eq!(info.items(), Some(&items));
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_links_are_not_followed() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let target = dir.path().join("target");
+    fs::create_dir(&target)?;
+    fs::write(target.join("data.bin"), vec![0u8; 4096])?;
+    let link = dir.path().join("link");
+    std::os::unix::fs::symlink(&target, &link)?;
+
+    let entries = browse_dir(dir.path())?;
+    let entry = entries.iter().find(|e| e.name() == "link").unwrap();
+    assert!(entry.is_symlink());
+    assert!(!entry.is_dir());
+
+    let info = FileInfo::get_file_info(&link, &HashSet::new())?;
+    assert_eq!(info.file_type(), &FileType::Symlink);
+    assert_eq!(info.target(), Some(target.as_path()));
+    assert!(info.items().is_none());
+
+    assert!(path_size(&link) < 4096);
+
+    // Deleting the link leaves the directory it points to alone
+    remove_path(&link)?;
+    assert!(link.symlink_metadata().is_err());
+    assert!(target.join("data.bin").exists());
+    Ok(())
+}
+
+#[test]
+fn test_finder_tags_and_quarantine_parse() {
+    // ["Red\n6", "Work", "Blue\n4", "Über\n2"] as Finder writes it, a binary property list
+    let plist = [
+        0x62, 0x70, 0x6c, 0x69, 0x73, 0x74, 0x30, 0x30, 0xa4, 0x01, 0x02, 0x03, 0x04, 0x55, 0x52,
+        0x65, 0x64, 0x0a, 0x36, 0x54, 0x57, 0x6f, 0x72, 0x6b, 0x56, 0x42, 0x6c, 0x75, 0x65, 0x0a,
+        0x34, 0x66, 0x00, 0xdc, 0x00, 0x62, 0x00, 0x65, 0x00, 0x72, 0x00, 0x0a, 0x00, 0x32, 0x08,
+        0x0d, 0x13, 0x18, 0x1f, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x01, 0x01, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x05, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00,
+        0x00, 0x00, 0x00, 0x00, 0x00, 0x2c,
+    ];
+    let names = parse_user_tags(&plist);
+    assert_eq!(names, ["Red\n6", "Work", "Blue\n4", "Über\n2"]);
+
+    // The tag without a color has no dot, the colors are in Finder order
+    let tags = FinderTags::from_names(&names);
+    let colors: Vec<TagColor> = tags.colors().collect();
+    assert_eq!(colors, [TagColor::Green, TagColor::Blue, TagColor::Red]);
+
+    assert!(parse_user_tags(b"not a plist").is_empty());
+    assert!(parse_user_tags(&plist[..40]).is_empty());
+    assert!(FinderTags::from_names(&["Work"]).is_empty());
+
+    let quarantine = Quarantine::parse(b"0083;65a1b2c3;Safari;F0A1B2C3-0000");
+    assert_eq!(quarantine.agent(), Some("Safari"));
+    assert_eq!(
+        quarantine.downloaded(),
+        Some(std::time::UNIX_EPOCH + Duration::from_secs(0x65a1b2c3))
+    );
+    let quarantine = Quarantine::parse(b"0081;zz;;");
+    assert_eq!(quarantine.agent(), None);
+    assert_eq!(quarantine.downloaded(), None);
+}
+
+#[test]
+fn test_stream_names_and_plain_files() -> Result<(), Box<dyn error::Error>> {
+    assert_eq!(
+        parse_stream_name(":Zone.Identifier:$DATA"),
+        Some("Zone.Identifier")
+    );
+    // The main data and streams of other types are no named streams
+    assert_eq!(parse_stream_name("::$DATA"), None);
+    assert_eq!(parse_stream_name(":notes:$INDEX_ALLOCATION"), None);
+
+    let dir = tempdir()?;
+    let file = dir.path().join("plain.txt");
+    fs::write(&file, "data")?;
+    assert!(list_streams(&file).is_empty());
+    let info = FileInfo::get_file_info(&file, &HashSet::new())?;
+    assert!(info.streams().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_security_context_types() {
+    let home = SecurityContext::parse(b"unconfined_u:object_r:user_home_t:s0\0").unwrap();
+    assert_eq!(home.as_str(), "unconfined_u:object_r:user_home_t:s0");
+    assert_eq!(home.selinux_type(), Some("user_home_t"));
+
+    // Another user or level is no mismatch, only another type
+    let moved = SecurityContext::parse(b"system_u:object_r:user_home_t:s0:c1").unwrap();
+    assert!(!moved.differs_from(&home));
+    let tmp = SecurityContext::parse(b"unconfined_u:object_r:user_tmp_t:s0").unwrap();
+    assert!(tmp.differs_from(&home));
+
+    assert_eq!(SecurityContext::parse(b"\0"), None);
+}
+
+#[test]
+fn test_find_history_browse_and_persist() -> Result<(), Box<dyn error::Error>> {
+    let mut history = FindHistory::new(3);
+    for query in ["crab", "shell", "  ", "crab", "claw", "reef"] {
+        history.push(query);
+    }
+    // "shell" fell out, the repeated "crab" moved up and the blank query was ignored
+    assert_eq!(history.entries(), &["crab", "claw", "reef"]);
+
+    assert_eq!(history.older("dra"), Some("reef"));
+    assert_eq!(history.older("reef"), Some("claw"));
+    assert_eq!(history.older("claw"), Some("crab"));
+    assert_eq!(history.older("crab"), None);
+    assert_eq!(history.newer(), Some("claw"));
+    assert_eq!(history.newer(), Some("reef"));
+    assert_eq!(history.newer(), Some("dra"));
+    assert!(!history.is_browsing());
+    assert_eq!(history.newer(), None);
+
+    let dir = tempdir()?;
+    let path = dir.path().join("runa/find_history");
+    history.save(&path)?;
+    let loaded = FindHistory::load(&path, 2)?;
+    assert_eq!(loaded.entries(), &["claw", "reef"]);
+    Ok(())
+}
+
+#[test]
+fn test_navstate_missing_focus_keeps_index() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    for name in ["a.txt", "b.txt", "c.txt", "d.txt"] {
+        File::create(dir.path().join(name))?;
+    }
+    let mut entries = browse_dir(dir.path())?;
+    entries.sort_by(|a, b| a.name().cmp(b.name()));
+
+    let mut nav = NavState::new(dir.path().to_path_buf());
+    nav.update_from_worker(dir.path().to_path_buf(), entries.clone(), None);
+    nav.set_selected(2);
+
+    nav.update_from_worker(
+        dir.path().to_path_buf(),
+        entries.clone(),
+        Some(OsString::from("renamed.txt")),
+    );
+    assert_eq!(nav.selected_idx(), 2);
+
+    nav.update_from_worker(
+        dir.path().to_path_buf(),
+        entries,
+        Some(OsString::from("a.txt")),
+    );
+    assert_eq!(nav.selected_idx(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_navstate_marker_policies() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let sub = dir.path().join("sub");
+    fs::create_dir(&sub)?;
+    let kept = dir.path().join("kept.txt");
+    let copied = dir.path().join("copied.txt");
+    let deleted = dir.path().join("deleted.txt");
+    for path in [&kept, &copied, &deleted] {
+        File::create(path)?;
+    }
+
+    let mut nav = NavState::new(dir.path().to_path_buf());
+    for path in [&kept, &copied, &deleted] {
+        nav.toggle_marker_path(path.clone(), &mut None);
+    }
+
+    // Operation: the targets are unmarked, markers of removed paths are dropped
+    fs::remove_file(&deleted)?;
+    nav.finish_operation(std::slice::from_ref(&copied));
+    assert_eq!(nav.markers(), &HashSet::from([kept.clone()]));
+
+    // Persist: only the markers of removed paths are dropped
+    nav.set_marker_policy(MarkerPolicy::Persist);
+    nav.toggle_marker_path(copied.clone(), &mut None);
+    nav.finish_operation(std::slice::from_ref(&copied));
+    assert_eq!(nav.markers().len(), 2);
+    nav.set_path(sub.clone());
+    assert_eq!(nav.markers().len(), 2);
+
+    // Directory: changing the directory clears all markers
+    nav.set_marker_policy(MarkerPolicy::Directory);
+    nav.set_path(sub);
+    assert_eq!(nav.markers().len(), 2, "same directory keeps markers");
+    nav.set_path(dir.path().to_path_buf());
+    assert!(nav.markers().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_navstate_mark_all_and_invert_shown() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    for name in ["a.rs", "b.rs", "c.txt"] {
+        File::create(dir.path().join(name))?;
+    }
+    let entries = browse_dir(dir.path())?;
+    let mut nav = NavState::new(dir.path().to_path_buf());
+    nav.update_from_worker(dir.path().to_path_buf(), entries, None);
+
+    let path = |name: &str| dir.path().join(name);
+    let mut clipboard = Some(HashSet::from([path("a.rs")]));
+    nav.set_filter(".rs".to_string());
+    nav.mark_all(&mut clipboard);
+    assert_eq!(nav.markers(), &HashSet::from([path("a.rs"), path("b.rs")]));
+    assert!(clipboard.as_ref().is_some_and(|c| c.is_empty()));
+
+    nav.clear_filters();
+    nav.invert_marks(&mut clipboard);
+    assert_eq!(nav.markers(), &HashSet::from([path("c.txt")]));
+    Ok(())
+}
+
+#[test]
+fn test_navstate_focus_after_removal() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    for name in ["a", "b", "c", "d"] {
+        File::create(dir.path().join(name))?;
+    }
+    let mut entries = browse_dir(dir.path())?;
+    entries.sort_by(|a, b| a.name().cmp(b.name()));
+    let mut nav = NavState::new(dir.path().to_path_buf());
+    nav.update_from_worker(dir.path().to_path_buf(), entries, None);
+    let path = |name: &str| dir.path().join(name);
+
+    nav.set_selected(1);
+    assert_eq!(
+        nav.focus_after_removal(&[path("b")]),
+        Some(OsString::from("c"))
+    );
+    assert_eq!(
+        nav.focus_after_removal(&[path("b"), path("c")]),
+        Some(OsString::from("d"))
+    );
+    // Marked entries elsewhere keep the selection where it is
+    assert_eq!(
+        nav.focus_after_removal(&[path("d")]),
+        Some(OsString::from("b"))
+    );
+
+    nav.set_selected(3);
+    assert_eq!(
+        nav.focus_after_removal(&[path("c"), path("d")]),
+        Some(OsString::from("b"))
+    );
+    let all: Vec<PathBuf> = ["a", "b", "c", "d"].iter().map(|n| path(n)).collect();
+    assert_eq!(nav.focus_after_removal(&all), None);
+    Ok(())
+}
+
+#[test]
+fn test_parse_mounts_and_innermost_fs_type() {
+    let proc_mounts = "/dev/sda1 / ext4 rw,relatime 0 0\n\
+                       server:/export /mnt/my\\040nas nfs4 rw 0 0\n";
+    let mounts = parse_mounts(proc_mounts);
+    assert_eq!(
+        mount_fs_type(&mounts, Path::new("/mnt/my nas/photos")),
+        Some("nfs4")
+    );
+    assert_eq!(
+        mount_fs_type(&mounts, Path::new("/home/user")),
+        Some("ext4")
+    );
+
+    let mount_output = "/dev/disk3s1 on / (apfs, local, journaled)\n\
+                        //user@server/share on /Volumes/share (smbfs, nodev, nosuid)\n\
+                        sshfs#host: on /home/user/remote type fuse.sshfs (rw)\n";
+    let mounts = parse_mounts(mount_output);
+    assert_eq!(
+        mount_fs_type(&mounts, Path::new("/Volumes/share/docs")),
+        Some("smbfs")
+    );
+    assert_eq!(
+        mount_fs_type(&mounts, Path::new("/home/user/remote")),
+        Some("fuse.sshfs")
+    );
+    assert_eq!(mount_fs_type(&mounts, Path::new("/Users")), Some("apfs"));
+
+    // FreeBSD lists the type like macOS, illumos keeps a tab separated /etc/mnttab
+    let freebsd = "/dev/ada0p2 on / (ufs, local, soft-updates)\n\
+                   zroot/home on /home (zfs, local, nfsv4acls)\n";
+    let mounts = parse_mounts(freebsd);
+    assert_eq!(mount_fs_type(&mounts, Path::new("/home/u")), Some("zfs"));
+    assert_eq!(mount_fs_type(&mounts, Path::new("/etc")), Some("ufs"));
+
+    let mnttab = "rpool/ROOT/omnios\t/\tzfs\tdev=4410002\t1700000000\n\
+                  server:/export\t/net/my share\tnfs\txattr,dev=8980001\t1700000000\n";
+    let mounts = parse_mounts(mnttab);
+    assert_eq!(
+        mount_fs_type(&mounts, Path::new("/net/my share/docs")),
+        Some("nfs")
+    );
+    assert_eq!(mount_fs_type(&mounts, Path::new("/opt")), Some("zfs"));
+}
+
+#[test]
+fn test_platform_fallbacks() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let sub = dir.path().join("sub");
+    fs::create_dir(&sub)?;
+    File::create(dir.path().join("file.txt"))?;
+
+    let dir_mode = platform::mode_string(&fs::metadata(&sub)?);
+    let file_mode = platform::mode_string(&fs::metadata(dir.path().join("file.txt"))?);
+    assert!(dir_mode.starts_with('d'), "{dir_mode}");
+    assert!(file_mode.starts_with('-'), "{file_mode}");
+    if cfg!(unix) {
+        assert_eq!(dir_mode.len(), 10);
+    }
+
+    // Identities exist on every unix, the BSDs and illumos included, and survive a rename
+    let id = platform::file_id(&sub);
+    assert_eq!(id.is_some(), cfg!(unix));
+    let moved = dir.path().join("moved");
+    fs::rename(&sub, &moved)?;
+    assert_eq!(platform::file_id(&moved), id);
+
+    let expected = if cfg!(target_os = "macos") {
+        "open"
+    } else if cfg!(windows) {
+        "cmd"
+    } else {
+        "xdg-open"
+    };
+    assert_eq!(platform::opener().get_program(), expected);
+    Ok(())
+}
+
+#[test]
+fn test_notifications_expire_and_renew() {
+    let mut notifications = Notifications::new();
+    let ttl = Duration::from_secs(5);
+    notifications.push(Severity::Info, "Pasted".to_string(), ttl);
+    notifications.push(
+        Severity::Error,
+        "Op Error: denied".to_string(),
+        Duration::ZERO,
+    );
+    notifications.push(Severity::Info, "Pasted".to_string(), ttl);
+    let texts = |n: &Notifications| n.iter().map(|n| n.text().to_string()).collect::<Vec<_>>();
+    assert_eq!(texts(&notifications), ["Op Error: denied", "Pasted"]);
+
+    assert!(notifications.expire(Instant::now()));
+    assert_eq!(texts(&notifications), ["Pasted"]);
+    assert!(!notifications.expire(Instant::now()));
+
+    for i in 0..10 {
+        notifications.push(Severity::Warn, format!("warning {i}"), ttl);
+    }
+    assert_eq!(notifications.len(), 5);
+    assert_eq!(notifications.latest().map(|n| n.text()), Some("warning 9"));
+}
+
+#[test]
+fn test_jump_list_retraces_positions() {
+    let at = |dir: &str, name: &str| Jump::new(PathBuf::from(dir), Some(OsString::from(name)));
+    let mut jumps = JumpList::new();
+    assert_eq!(jumps.back(at("/a", "x")), None);
+
+    jumps.push(at("/a", "one"));
+    jumps.push(at("/b", "two"));
+    assert_eq!(jumps.back(at("/c", "three")), Some(at("/b", "two")));
+    // The position changed since jumping back is remembered
+    assert_eq!(jumps.back(at("/b", "moved")), Some(at("/a", "one")));
+    assert_eq!(jumps.back(at("/a", "one")), None);
+    assert_eq!(jumps.forward(at("/a", "one")), Some(at("/b", "moved")));
+    assert_eq!(jumps.forward(at("/b", "moved")), Some(at("/c", "three")));
+    assert_eq!(jumps.forward(at("/c", "three")), None);
+
+    // A new jump drops the locations ahead and lists every directory once
+    jumps.back(at("/c", "three"));
+    jumps.push(at("/b", "again"));
+    assert_eq!(jumps.len(), 2);
+    assert_eq!(jumps.back(at("/d", "four")), Some(at("/b", "again")));
+    assert_eq!(jumps.back(at("/b", "again")), Some(at("/a", "one")));
+}
@@ -3,9 +3,13 @@
 //! If `fd` is not available, the tests will be skipped.
 //! If `bat` is not available, the tests will be skipped
 
-use runa_tui::core::{find, preview_bat};
+use runa_tui::core::{
+    FindKind, FindResult, SearchFilters, find, find_streaming, find_with_kind, git_info, grep,
+    grep_with_filters, preview_bat, split_extensions,
+};
 use std::fs;
 use std::io::Write;
+use std::process::Command;
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use tempfile::tempdir;
@@ -108,6 +112,116 @@ fn test_find_recursive_subdirectory() -> Result<(), Box<dyn std::error::Error>>
     Ok(())
 }
 
+#[test]
+fn test_find_results_mark_directories() -> Result<(), Box<dyn std::error::Error>> {
+    skip_if_no_fd!();
+    let dir = tempdir()?;
+    std::fs::create_dir(dir.path().join("crabdir"))?;
+    std::fs::File::create(dir.path().join("crabfile"))?;
+    let cancel = Arc::new(AtomicBool::new(false));
+    let mut out = Vec::new();
+    find(dir.path(), "crab", &mut out, cancel, 10)?;
+    let is_dir = |name: &str| {
+        out.iter()
+            .find(|r| r.path().file_name().is_some_and(|n| n == name))
+            .map(|r| r.is_dir())
+    };
+    assert_eq!(is_dir("crabdir"), Some(true));
+    assert_eq!(is_dir("crabfile"), Some(false));
+    Ok(())
+}
+
+#[test]
+fn test_find_split_extensions() {
+    let (exts, rest) = split_extensions("*.rs foo *.toml");
+    assert_eq!(exts, vec!["rs".to_string(), "toml".to_string()]);
+    assert_eq!(rest, "foo");
+
+    let (exts, rest) = split_extensions("*. bar");
+    assert!(exts.is_empty());
+    assert_eq!(rest, "*. bar");
+
+    assert_eq!(FindKind::All.next(), FindKind::Files);
+    assert_eq!(FindKind::Files.next(), FindKind::Dirs);
+    assert_eq!(FindKind::Dirs.next(), FindKind::All);
+}
+
+#[test]
+fn test_find_recursive_kind_and_extension() -> Result<(), Box<dyn std::error::Error>> {
+    skip_if_no_fd!();
+    let dir = tempdir()?;
+    std::fs::create_dir(dir.path().join("crab_dir"))?;
+    std::fs::File::create(dir.path().join("crab.rs"))?;
+    std::fs::File::create(dir.path().join("crab.txt"))?;
+
+    let mut out = Vec::new();
+    let cancel = Arc::new(AtomicBool::new(false));
+    find_with_kind(dir.path(), "crab", FindKind::Dirs, &mut out, cancel, 10)?;
+    assert!(out.iter().all(|r| r.path().is_dir()));
+    assert!(out.iter().any(|r| r.path().ends_with("crab_dir")));
+
+    let cancel = Arc::new(AtomicBool::new(false));
+    find_with_kind(dir.path(), "*.rs", FindKind::All, &mut out, cancel, 10)?;
+    assert_eq!(out.len(), 1);
+    assert!(out[0].path().ends_with("crab.rs"));
+    Ok(())
+}
+
+#[test]
+fn test_find_streaming_keeps_best_results() -> Result<(), Box<dyn std::error::Error>> {
+    skip_if_no_fd!();
+    let dir = tempdir()?;
+    for i in 0..50 {
+        std::fs::File::create(dir.path().join(format!("crab_{i}.txt")))?;
+    }
+    std::fs::File::create(dir.path().join("crab"))?;
+
+    let mut batches = Vec::new();
+    let mut out = Vec::new();
+    let cancel = Arc::new(AtomicBool::new(false));
+    find_streaming(
+        dir.path(),
+        "crab",
+        FindKind::Files,
+        &SearchFilters::default(),
+        &mut out,
+        cancel,
+        10,
+        |batch| batches.push(batch),
+    )?;
+
+    assert!(out.len() <= 10);
+    assert!(out.windows(2).all(|w| w[0].score() >= w[1].score()));
+    assert!(batches.iter().all(|b| b.len() <= 10));
+    Ok(())
+}
+
+#[test]
+fn test_find_extension_only_keeps_shortest_paths() -> Result<(), Box<dyn std::error::Error>> {
+    skip_if_no_fd!();
+    let dir = tempdir()?;
+    fs::create_dir_all(dir.path().join("sub/deeper"))?;
+    for i in 0..30 {
+        fs::File::create(dir.path().join(format!("sub/deeper/crab_{i}.rs")))?;
+    }
+    for name in ["b.rs", "a.rs", "ab.rs"] {
+        fs::File::create(dir.path().join(name))?;
+    }
+
+    // Every match scores 0, so the kept results are decided by the ties alone
+    let mut runs = Vec::new();
+    for _ in 0..3 {
+        let mut out = Vec::new();
+        let cancel = Arc::new(AtomicBool::new(false));
+        find_with_kind(dir.path(), "*.rs", FindKind::Files, &mut out, cancel, 3)?;
+        let names: Vec<String> = out.iter().map(|r| r.relative(dir.path())).collect();
+        runs.push(names);
+    }
+    assert_eq!(runs[0], ["a.rs", "b.rs", "ab.rs"]);
+    assert!(runs.iter().all(|run| *run == runs[0]));
+    Ok(())
+}
+
 #[test]
 fn test_preview_bat_basic() -> Result<(), Box<dyn std::error::Error>> {
     skip_if_no_bat!();
@@ -160,3 +274,143 @@ fn test_preview_bat_nonexistent_file() -> Result<(), Box<dyn std::error::Error>>
     assert!(result.is_err(), "Expected error for missing file");
     Ok(())
 }
+
+#[test]
+fn test_grep_finds_matching_lines() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    fs::write(
+        dir.path().join("notes.txt"),
+        "first\nThe Crab walks\nlast\n",
+    )?;
+    fs::create_dir(dir.path().join("node_modules"))?;
+    fs::write(dir.path().join("node_modules").join("dep.js"), "crab\n")?;
+    fs::write(dir.path().join("blob.bin"), b"crab\0\x01")?;
+
+    let mut out = Vec::new();
+    grep(
+        dir.path(),
+        "crab",
+        &mut out,
+        Arc::new(AtomicBool::new(false)),
+        10,
+    )?;
+
+    assert_eq!(out.len(), 1, "Expected one match, got {:?}", out);
+    let content = out[0].content().ok_or("Expected a content match")?;
+    assert_eq!(out[0].path().file_name().unwrap(), "notes.txt");
+    assert_eq!(content.line(), 2);
+    assert_eq!(content.text(), "The Crab walks");
+
+    // Uppercase queries are case sensitive
+    grep(
+        dir.path(),
+        "CRAB",
+        &mut out,
+        Arc::new(AtomicBool::new(false)),
+        10,
+    )?;
+    assert!(out.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_equal_scores_order_shorter_paths_first() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    fs::create_dir(dir.path().join("sub"))?;
+    for name in ["sub/a.rs", "b.rs", "a.rs", "ab.rs"] {
+        fs::write(dir.path().join(name), "crab\ncrab\n")?;
+    }
+
+    let mut out = Vec::new();
+    grep(
+        dir.path(),
+        "crab",
+        &mut out,
+        Arc::new(AtomicBool::new(false)),
+        100,
+    )?;
+    // Sorting does not depend on the order the results were found in
+    let mut sorted = out.clone();
+    sorted.sort();
+    out.reverse();
+    out.sort();
+    assert_eq!(sorted, out);
+
+    let ranked: Vec<(String, usize)> = sorted
+        .iter()
+        .map(|r| (r.relative(dir.path()), r.content().map_or(0, |c| c.line())))
+        .collect();
+    assert_eq!(
+        ranked,
+        [
+            ("a.rs".to_string(), 1),
+            ("a.rs".to_string(), 2),
+            ("b.rs".to_string(), 1),
+            ("b.rs".to_string(), 2),
+            ("ab.rs".to_string(), 1),
+            ("ab.rs".to_string(), 2),
+            ("sub/a.rs".to_string(), 1),
+            ("sub/a.rs".to_string(), 2),
+        ]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_grep_with_filters() -> Result<(), Box<dyn std::error::Error>> {
+    let dir = tempdir()?;
+    fs::create_dir(dir.path().join(".git"))?;
+    fs::write(dir.path().join(".gitignore"), "ignored.rs\n")?;
+    fs::write(dir.path().join("main.rs"), "crab\n")?;
+    fs::write(dir.path().join("ignored.rs"), "crab\n")?;
+    fs::write(dir.path().join("notes.txt"), "crab\n")?;
+    fs::create_dir(dir.path().join("vendor"))?;
+    fs::write(dir.path().join("vendor").join("dep.rs"), "crab\n")?;
+
+    let names = |out: &[FindResult]| {
+        let mut names: Vec<String> = out
+            .iter()
+            .map(|r| r.path().file_name().unwrap().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        names
+    };
+
+    let mut out = Vec::new();
+    let filters = SearchFilters::new(vec!["*.rs".into()], vec!["vendor".into()], true);
+    let cancel = Arc::new(AtomicBool::new(false));
+    grep_with_filters(dir.path(), "crab", &filters, &mut out, cancel, 10)?;
+    assert_eq!(names(&out), vec!["main.rs"]);
+
+    let filters = SearchFilters::new(vec!["*.rs".into()], Vec::new(), false);
+    let cancel = Arc::new(AtomicBool::new(false));
+    grep_with_filters(dir.path(), "crab", &filters, &mut out, cancel, 10)?;
+    assert_eq!(names(&out), vec!["dep.rs", "ignored.rs", "main.rs"]);
+    Ok(())
+}
+
+#[test]
+fn test_git_info_branch_and_dirty() -> Result<(), Box<dyn std::error::Error>> {
+    if which::which("git").is_err() {
+        return Ok(());
+    }
+    let dir = tempdir()?;
+    assert!(git_info(dir.path()).is_none(), "Not a repository yet");
+
+    let status = Command::new("git")
+        .args(["init", "--quiet", "--initial-branch", "trunk"])
+        .arg(dir.path())
+        .status()?;
+    assert!(status.success());
+
+    let info = git_info(dir.path()).ok_or("No git info for the repository")?;
+    assert_eq!(info.branch(), "trunk");
+    assert!(!info.dirty());
+
+    fs::create_dir(dir.path().join("sub"))?;
+    fs::write(dir.path().join("sub").join("new.txt"), "crab\n")?;
+    let info = git_info(&dir.path().join("sub")).ok_or("No git info for the subdirectory")?;
+    assert_eq!(info.branch(), "trunk");
+    assert!(info.dirty());
+    Ok(())
+}
@@ -7,22 +7,63 @@
 //! These tests may create temporary directories and files to simulate different UI scenarios.
 //! These temporary resources are automatically cleaned up after the tests complete.
 
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::Terminal;
+use ratatui::backend::TestBackend;
+use ratatui::buffer::Buffer;
 use ratatui::layout::Rect;
-use runa_tui::app::AppState;
-use runa_tui::config::{Config, load::RawConfig};
+use ratatui::style::Color;
+use runa_tui::app::actions::{ActionMode, InputMode};
+use runa_tui::app::tutorial::create_playground;
+use runa_tui::app::{AppState, DirSizeStatus, KeypressResult, Severity};
+use runa_tui::config::theme::DialogKind;
+use runa_tui::config::{Config, OpenAction, expand_path, load::RawConfig};
 use runa_tui::core;
-use runa_tui::core::Formatter;
+use runa_tui::core::highlight::parse_ansi_lines;
+use runa_tui::core::{Formatter, PARALLEL_FORMAT_THRESHOLD};
+use runa_tui::ui::animation::{ANIMATION_DURATION, Animator, LayerKey, grow_rect};
+use runa_tui::ui::browser::{BrowserEvent, RunaBrowser, RunaBrowserState};
+use runa_tui::ui::overlays::Overlay;
 use runa_tui::ui::render::layout_chunks;
+use runa_tui::ui::widgets::{
+    DialogAnchor, DialogLayout, DialogPosition, DialogSize, DialogStyle, dialog_area, draw_dialog,
+};
+use runa_tui::utils;
 use std::collections::HashSet;
 use std::error;
-use std::path::Path;
+use std::fs::File;
+use std::path::{Path, PathBuf};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tempfile::tempdir;
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Ticks the app until `done` holds, giving up after two seconds
+fn wait_until(app: &mut AppState, done: impl Fn(&AppState) -> bool) {
+    let deadline = Instant::now() + Duration::from_secs(2);
+    while !done(app) && Instant::now() < deadline {
+        app.tick();
+        std::thread::sleep(Duration::from_millis(10));
+    }
+}
+
+/// A character key press without modifiers
+fn key(c: char) -> KeyEvent {
+    KeyEvent::new(KeyCode::Char(c), KeyModifiers::NONE)
+}
 
 #[test]
 fn test_formatter_truncation_and_padding() -> Result<(), Box<dyn error::Error>> {
     let width = 10;
-    let formatter = Formatter::new(true, true, true, false, Arc::new(HashSet::new()), width);
+    let formatter = Formatter::new(
+        true,
+        true,
+        true,
+        false,
+        Arc::new(HashSet::new()),
+        width,
+        false,
+    );
 
     let path = Path::new(".");
     let mut entries = core::browse_dir(path)?;
@@ -73,10 +114,187 @@ fn test_formatter_truncation_and_padding() -> Result<(), Box<dyn error::Error>>
     Ok(())
 }
 
+#[test]
+fn test_emoji_names_truncate_between_graphemes() -> Result<(), Box<dyn error::Error>> {
+    let family = "👨\u{200d}👩\u{200d}👧";
+    let names = [
+        format!("{family}{family}{family}.txt"),
+        "👍🏽👍🏽👍🏽👍🏽👍🏽".to_string(),
+        "🇩🇪🇫🇷🇯🇵🇺🇸".to_string(),
+        "cafe\u{301}-caf\u{e9}-❤\u{fe0f}-1\u{fe0f}\u{20e3}".to_string(),
+        "漢字漢字漢字".to_string(),
+    ];
+    for width in 1..12 {
+        for name in &names {
+            let (truncated, w) = core::truncate_to_width(name, width);
+            assert_eq!(unicode_width::UnicodeWidthStr::width(truncated.as_str()), w);
+            assert!(w <= width, "{name:?} at {width} got width {w}");
+            let kept = truncated.trim_end_matches('…');
+            assert!(name.starts_with(kept));
+            // The cut never lands inside a grapheme cluster
+            assert_eq!(
+                name[..kept.len()].graphemes(true).count()
+                    + name[kept.len()..].graphemes(true).count(),
+                name.graphemes(true).count()
+            );
+
+            let line = core::sanitize_to_exact_width(name, width);
+            assert_eq!(unicode_width::UnicodeWidthStr::width(line.as_str()), width);
+            let kept = line.trim_end_matches(' ');
+            assert_eq!(
+                name[..kept.len()].graphemes(true).count()
+                    + name[kept.len()..].graphemes(true).count(),
+                name.graphemes(true).count()
+            );
+        }
+    }
+
+    let (family_cut, w) = core::truncate_to_width(&names[0], 5);
+    assert_eq!(family_cut, format!("{family}{family}…"));
+    assert_eq!(w, 5);
+    Ok(())
+}
+
+#[test]
+fn test_bidi_controls_are_shown_but_not_applied() {
+    let spoof = "photo\u{202e}gpj.exe";
+    assert_eq!(
+        core::display_safe_name(spoof, false),
+        "photo\u{fffd}gpj.exe"
+    );
+    assert_eq!(
+        core::display_safe_name("a\u{2067}b\u{200f}c\u{061c}", false),
+        "a\u{fffd}b\u{fffd}c\u{fffd}"
+    );
+    assert!(matches!(
+        core::display_safe_name("plain.txt", false),
+        std::borrow::Cow::Borrowed(_)
+    ));
+
+    let isolated = core::display_safe_name("\u{5e9}\u{5dc}\u{5d5}\u{5dd}.txt", true);
+    assert!(isolated.starts_with('\u{2066}') && isolated.ends_with('\u{2069}'));
+    assert_eq!(
+        unicode_width::UnicodeWidthStr::width(isolated.as_ref()),
+        unicode_width::UnicodeWidthStr::width("\u{5e9}\u{5dc}\u{5d5}\u{5dd}.txt")
+    );
+
+    // The listing shows the sanitized name, operations keep the raw one
+    let (shown, _) = core::truncate_to_width(&core::display_safe_name(spoof, false), 8);
+    assert_eq!(shown, "photo\u{fffd}g\u{2026}");
+}
+
+#[test]
+fn test_preview_tab_width_and_control_chars() {
+    let line = "a\tb\x1b[0m\r";
+    assert_eq!(core::sanitize_to_exact_width(line, 10), "a   b[0m  ");
+
+    let text = core::TextRendering {
+        tab_width: 8,
+        show_control_chars: true,
+    };
+    assert_eq!(
+        core::render_to_exact_width(line, 16, text),
+        "a       b^[[0m^M"
+    );
+    // Notations are kept or cut whole at the pane edge
+    assert_eq!(
+        core::render_to_exact_width(line, 15, text),
+        "a       b^[[0m "
+    );
+    assert_eq!(
+        core::render_to_exact_width("\x7f\u{85}", 10, text),
+        "^?\\u{85}  "
+    );
+}
+
+#[test]
+fn test_formatter_parallel_sort_matches_sequential() -> Result<(), Box<dyn error::Error>> {
+    let temp_dir = tempdir()?;
+    let base = temp_dir.path();
+    for i in 0..PARALLEL_FORMAT_THRESHOLD {
+        File::create(base.join(format!("f{:05}", PARALLEL_FORMAT_THRESHOLD - i)))?;
+    }
+    for name in ["Dup", "dup", "DUP"] {
+        File::create(base.join(name))?;
+    }
+    std::fs::create_dir(base.join("zdir"))?;
+
+    let formatter = Formatter::new(true, true, true, true, Arc::new(HashSet::new()), 12, false);
+    let mut parallel = core::browse_dir(base)?;
+    formatter.format(&mut parallel);
+    assert!(parallel.len() >= PARALLEL_FORMAT_THRESHOLD);
+
+    // Reference order: directories first, then the case insensitive name, then the raw name
+    let mut sequential = core::browse_dir(base)?;
+    sequential.sort_by(|a, b| {
+        (!a.is_dir(), a.lowercase_name(), a.name()).cmp(&(
+            !b.is_dir(),
+            b.lowercase_name(),
+            b.name(),
+        ))
+    });
+    let names = |entries: &[core::FileEntry]| -> Vec<String> {
+        entries.iter().map(|e| e.name_str().to_string()).collect()
+    };
+    assert_eq!(names(&parallel), names(&sequential));
+    assert_eq!(parallel[0].name_str(), "zdir");
+    assert!(
+        parallel
+            .iter()
+            .all(|e| e.display_name().chars().count() == 12)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_formatter_sort_expr() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    for name in ["b.txt", "A.rs", "c.rs", "Makefile", ".env"] {
+        File::create(dir.path().join(name))?;
+    }
+    std::fs::create_dir(dir.path().join("src"))?;
+
+    let config = Config::from(toml::from_str::<RawConfig>(
+        r#"sort_expr = "is_dir desc, ext, name""#,
+    )?);
+    let expr = config.sort_expr().cloned();
+    let formatter = Formatter::new(true, true, true, true, Arc::new(HashSet::new()), 12, false)
+        .with_sort_expr(expr);
+    let mut entries = core::browse_dir(dir.path())?;
+    formatter.format(&mut entries);
+    let names: Vec<&str> = entries.iter().map(|e| e.name_str()).collect();
+    assert_eq!(names, ["src", ".env", "Makefile", "A.rs", "c.rs", "b.txt"]);
+
+    let expr = core::SortExpr::parse("is_hidden desc, name desc")?;
+    assert_eq!(
+        expr.terms(),
+        [(core::SortKey::IsHidden, true), (core::SortKey::Name, true)]
+    );
+    let formatter = Formatter::new(true, true, true, true, Arc::new(HashSet::new()), 12, false)
+        .with_sort_expr(Some(Arc::new(expr)));
+    formatter.format(&mut entries);
+    let names: Vec<&str> = entries.iter().map(|e| e.name_str()).collect();
+    assert_eq!(names, [".env", "src", "Makefile", "c.rs", "b.txt", "A.rs"]);
+
+    assert!(core::SortExpr::parse("size").is_err());
+    assert!(core::SortExpr::parse("name up").is_err());
+    assert!(core::SortExpr::parse("name,").is_err());
+    assert!(toml::from_str::<RawConfig>(r#"sort_expr = "is_dir sideways""#).is_err());
+    Ok(())
+}
+
 #[test]
 fn test_formatter_empty_dir() -> Result<(), Box<dyn error::Error>> {
     let width = 15;
-    let formatter = Formatter::new(true, true, true, false, Arc::new(HashSet::new()), width);
+    let formatter = Formatter::new(
+        true,
+        true,
+        true,
+        false,
+        Arc::new(HashSet::new()),
+        width,
+        false,
+    );
 
     let temp_dir = tempdir()?;
 
@@ -115,7 +333,7 @@ fn test_layout_chunks_with_config() -> Result<(), Box<dyn error::Error>> {
     let raw: RawConfig = toml::from_str(toml_content)?;
     let config = Config::from(raw);
 
-    let app = AppState::new(&config).expect("Failed to create AppState");
+    let app = AppState::new(config).expect("Failed to create AppState");
 
     let chunks = layout_chunks(size, &app);
 
@@ -126,3 +344,1631 @@ fn test_layout_chunks_with_config() -> Result<(), Box<dyn error::Error>> {
     assert!(chunks[0].width >= 33 && chunks[0].width <= 34);
     Ok(())
 }
+
+#[test]
+fn test_date_and_size_formats() -> Result<(), Box<dyn error::Error>> {
+    let toml_content = r#"
+            [display]
+            date_format = "%d.%m.%Y"
+            size_format = "binary"
+        "#;
+
+    let raw: RawConfig = toml::from_str(toml_content)?;
+    let config = Config::from(raw);
+    let display = config.display();
+    assert_eq!(display.size_format(), core::SizeFormat::Binary);
+    assert_eq!(
+        core::format_file_size(Some(1536), false, display.size_format()),
+        "1.50 KiB"
+    );
+    assert_eq!(
+        core::format_file_size(Some(1500), false, core::SizeFormat::Decimal),
+        "1.50 kB"
+    );
+
+    let date = core::format_file_time(Some(std::time::SystemTime::now()), display.date_format());
+    assert_eq!(date.len(), "01.05.2024".len());
+    assert_eq!(core::format_file_time(None, display.date_format()), "-");
+
+    // Unknown specifiers would panic while drawing, so they are rejected with the config
+    assert!(!core::is_valid_date_format("%Y-%Q"));
+    assert!(toml::from_str::<RawConfig>("[display]\ndate_format = \"%Q\"").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_parse_ansi_lines_strips_escapes() -> Result<(), Box<dyn error::Error>> {
+    let raw = vec![
+        "\x1b[38;5;203mfn\x1b[0m main() {".to_string(),
+        "\x1b[1m}\x1b[0m".to_string(),
+    ];
+    let (plain, styled) = parse_ansi_lines(raw);
+    let styled = styled.ok_or("Expected styled lines for ANSI input")?;
+
+    assert_eq!(plain, vec!["fn main() {", "}"]);
+    assert_eq!(styled.len(), 2);
+    assert_eq!(styled[0].width(), "fn main() {".len());
+
+    let untouched = vec!["plain text".to_string()];
+    let (plain, styled) = parse_ansi_lines(untouched.clone());
+    assert_eq!(plain, untouched);
+    assert!(styled.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_dialog_size_cells_and_percent() -> Result<(), Box<dyn error::Error>> {
+    #[derive(serde::Deserialize)]
+    struct Holder {
+        size: DialogSize,
+    }
+    let parse = |raw: &str| toml::from_str::<Holder>(&format!("size = {raw}")).map(|h| h.size);
+
+    assert_eq!(parse("\"40x10\"")?, DialogSize::Cells(40, 10));
+    assert_eq!(parse("\"40%x10%\"")?, DialogSize::Percent(40, 10));
+    assert_eq!(parse("[30, 8]")?, DialogSize::Cells(30, 8));
+    assert!(parse("\"40%x10\"").is_err());
+    assert!(parse("\"140%x10%\"").is_err());
+
+    let area = Rect::new(0, 0, 200, 50);
+    let cells = dialog_area(area, DialogSize::Cells(40, 10), DialogPosition::Center);
+    assert_eq!((cells.width, cells.height), (40, 10));
+    let percent = dialog_area(area, DialogSize::Percent(50, 20), DialogPosition::Center);
+    assert_eq!((percent.width, percent.height), (100, 10));
+    Ok(())
+}
+
+#[test]
+fn test_dialog_position_anchored_offsets() -> Result<(), Box<dyn error::Error>> {
+    #[derive(serde::Deserialize)]
+    struct Holder {
+        position: DialogPosition,
+    }
+    let parsed: Holder =
+        toml::from_str("position = { anchor = \"bottom_right\", dx = -2, dy = -1 }")?;
+    assert_eq!(
+        parsed.position,
+        DialogPosition::Anchored(DialogAnchor::BottomRight, -2, -1)
+    );
+    assert!(toml::from_str::<Holder>("position = { anchor = \"middle\" }").is_err());
+
+    let area = Rect::new(0, 0, 100, 40);
+    let size = DialogSize::Cells(20, 5);
+    let rect = dialog_area(area, size, parsed.position);
+    assert_eq!((rect.x, rect.y), (78, 34));
+
+    // Offsets pushing the dialog off screen are clamped to the frame
+    let clamped = dialog_area(
+        area,
+        size,
+        DialogPosition::Anchored(DialogAnchor::TopLeft, -5, 100),
+    );
+    assert_eq!((clamped.x, clamped.y), (0, 35));
+    Ok(())
+}
+
+#[test]
+fn test_widget_theme_per_dialog_sections() -> Result<(), Box<dyn error::Error>> {
+    let toml_content = r#"
+            [theme.widget]
+            position = "center"
+            size = "small"
+            confirm_size = "large"
+
+            [theme.widget.find]
+            size = "60%x50%"
+
+            [theme.widget.rename]
+            position = "bottom_right"
+
+            [theme.widget.confirm]
+            size = "50x12"
+        "#;
+
+    let raw: RawConfig = toml::from_str(toml_content)?;
+    let config = Config::from(raw);
+    let widget = config.theme().widget();
+
+    let find = widget.for_dialog(DialogKind::Find);
+    assert_eq!(find.find_size(), Some(DialogSize::Percent(60, 50)));
+    assert_eq!(*find.position(), Some(DialogPosition::Center));
+
+    let rename = widget.for_dialog(DialogKind::Rename);
+    assert_eq!(*rename.position(), Some(DialogPosition::BottomRight));
+    assert_eq!(*rename.size(), Some(DialogSize::Small));
+
+    let confirm = widget.for_dialog(DialogKind::Confirm);
+    assert_eq!(
+        confirm.confirm_size_or(DialogSize::Large),
+        DialogSize::Cells(50, 12)
+    );
+    assert_eq!(*confirm.size(), Some(DialogSize::Small));
+    Ok(())
+}
+
+#[test]
+fn test_draw_dialog_shadow() -> Result<(), Box<dyn error::Error>> {
+    use ratatui::Terminal;
+    use ratatui::backend::TestBackend;
+    use ratatui::style::Color;
+    use ratatui::widgets::BorderType;
+
+    let mut terminal = Terminal::new(TestBackend::new(40, 12))?;
+    let size = DialogSize::Cells(20, 5);
+    let position = DialogPosition::Center;
+    let style = DialogStyle {
+        shadow: true,
+        ..DialogStyle::default()
+    };
+
+    terminal.draw(|frame| {
+        let layout = DialogLayout {
+            area: frame.area(),
+            position,
+            size,
+        };
+        draw_dialog(frame, layout, BorderType::Plain, &style, "hi", None);
+    })?;
+
+    let dialog = dialog_area(Rect::new(0, 0, 40, 12), size, position);
+    let buffer = terminal.backend().buffer();
+    let right = &buffer[(dialog.right(), dialog.y + 1)];
+    let bottom = &buffer[(dialog.x + 1, dialog.bottom())];
+    assert_eq!(right.bg, Color::Black);
+    assert_eq!(bottom.bg, Color::Black);
+    // The corner above the shadow stays untouched
+    assert_ne!(buffer[(dialog.right(), dialog.y)].bg, Color::Black);
+    Ok(())
+}
+
+#[test]
+fn test_animation_grow_rect_anchor() -> Result<(), Box<dyn error::Error>> {
+    let area = Rect::new(0, 0, 100, 40);
+
+    let centered = Rect::new(30, 10, 40, 20);
+    assert_eq!(grow_rect(centered, area, 0.5), Rect::new(40, 15, 20, 10));
+    assert_eq!(grow_rect(centered, area, 1.0), centered);
+
+    // A toast in the bottom right corner grows out of that corner
+    let corner = Rect::new(80, 36, 20, 4);
+    assert_eq!(grow_rect(corner, area, 0.5), Rect::new(90, 38, 10, 2));
+
+    let collapsed = grow_rect(centered, area, 0.0);
+    assert_eq!((collapsed.width, collapsed.height), (1, 1));
+    Ok(())
+}
+
+#[test]
+fn test_animation_open_and_close() -> Result<(), Box<dyn error::Error>> {
+    let area = Rect::new(0, 0, 20, 10);
+    let before = Buffer::empty(area);
+    let mut drawn = before.clone();
+    for y in 3..7 {
+        for x in 5..15 {
+            drawn[(x, y)].set_symbol("#");
+        }
+    }
+
+    let mut animator = Animator::default();
+    let start = Instant::now();
+    animator.update(Some(LayerKey::Input(InputMode::Rename)), start);
+    assert!(animator.is_running());
+    assert!(!animator.background_dimmed(start));
+
+    // At the start only a single cell of the dialog is revealed
+    let mut buf = drawn.clone();
+    animator.apply(&mut buf, &before, area, start);
+    let revealed = (0..10)
+        .flat_map(|y| (0..20).map(move |x| (x, y)))
+        .filter(|&(x, y)| buf[(x, y)].symbol() == "#")
+        .count();
+    assert_eq!(revealed, 1);
+
+    let done = start + ANIMATION_DURATION;
+    let mut buf = drawn.clone();
+    animator.apply(&mut buf, &before, area, done);
+    assert_eq!(buf, drawn);
+    assert!(!animator.is_running());
+
+    // Once opened, frames keep the dialog cells without a snapshot
+    assert!(!animator.needs_snapshot());
+    drawn[(10, 5)].set_symbol("@");
+    animator.refresh(&drawn);
+
+    // Closing plays back the last drawn dialog shrinking away
+    animator.update(None, done);
+    assert!(animator.is_running());
+    assert!(animator.needs_snapshot());
+    let mut buf = before.clone();
+    animator.apply(&mut buf, &before, area, done);
+    assert_eq!(buf[(10, 5)].symbol(), "@");
+
+    let mut buf = before.clone();
+    animator.apply(&mut buf, &before, area, done + ANIMATION_DURATION);
+    assert_eq!(buf, before);
+    assert!(!animator.is_running());
+    Ok(())
+}
+
+#[test]
+fn test_runa_browser_picks_file() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::File::create(dir.path().join("crab.txt"))?;
+
+    let config = Config::default();
+    let mut state = RunaBrowserState::with_dir(config, dir.path());
+
+    wait_until(state.app_mut(), |app| {
+        app.nav().selected_shown_entry().is_some()
+    });
+    assert_eq!(state.selected_path(), Some(dir.path().join("crab.txt")));
+
+    // The status line, overlays and toasts stay inside the area as well
+    state.handle_key(key('i'));
+    state
+        .app_mut()
+        .notify(Severity::Info, "toast".to_string(), Duration::from_secs(5));
+    let mut terminal = Terminal::new(TestBackend::new(60, 12))?;
+    let area = Rect::new(0, 2, 40, 10);
+    terminal.draw(|f| RunaBrowser::new().render(f, area, &mut state))?;
+    let buffer = terminal.backend().buffer();
+    let outside: String = (0..12)
+        .flat_map(|y| (0..60).map(move |x| (x, y)))
+        .filter(|&(x, y)| !area.contains((x, y).into()))
+        .map(|pos| buffer[pos].symbol().to_string())
+        .collect();
+    assert!(outside.trim().is_empty(), "Browser drew outside its area");
+    state.handle_key(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+    assert_eq!(
+        state.handle_key(enter),
+        BrowserEvent::Picked(dir.path().join("crab.txt"))
+    );
+
+    let quit = key('q');
+    assert_eq!(state.handle_key(quit), BrowserEvent::Cancelled);
+    Ok(())
+}
+
+#[test]
+fn test_tutorial_advances_and_renders() -> Result<(), Box<dyn error::Error>> {
+    let playground = create_playground()?;
+    assert!(playground.join("backup").is_dir());
+    assert!(playground.join("docs/readme.md").is_file());
+
+    let config = Config::default();
+    let mut app = AppState::with_dir(config, playground.clone());
+    app.start_tutorial();
+
+    app.handle_keypress(key('x'));
+    assert_eq!(app.tutorial().map(|t| t.step()), Some(0));
+    app.handle_keypress(key('j'));
+    assert_eq!(app.tutorial().map(|t| t.step()), Some(1));
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 24))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(screen.contains("Tutorial 2/"));
+
+    // Quitting before the last step closes the tutorial instead of runa
+    app.handle_keypress(key('q'));
+    assert!(app.tutorial().is_none());
+
+    drop(app);
+    std::fs::remove_dir_all(playground)?;
+    Ok(())
+}
+
+#[test]
+fn test_goto_shortcuts() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let downloads = dir.path().join("downloads");
+    std::fs::create_dir(&downloads)?;
+    let toml_content = format!("[goto]\n\"d\" = {:?}\n", downloads.to_string_lossy());
+
+    let raw: RawConfig = toml::from_str(&toml_content)?;
+    let mut app = AppState::with_dir(Config::from(raw), dir.path().to_path_buf());
+
+    app.handle_keypress(key('g'));
+    app.handle_keypress(key('d'));
+    assert_eq!(app.nav().current_dir(), downloads);
+
+    app.handle_keypress(key('g'));
+    app.handle_keypress(key('r'));
+    assert_eq!(app.nav().current_dir().parent(), None);
+
+    // A key without a shortcut cancels the sequence instead of running its own action
+    app.handle_keypress(key('g'));
+    app.handle_keypress(key('h'));
+    let home = app.nav().current_dir().to_path_buf();
+    app.handle_keypress(key('g'));
+    let result = app.handle_keypress(key('q'));
+    assert!(!matches!(result, KeypressResult::Quit));
+    assert_eq!(app.nav().current_dir(), home);
+
+    // Goto shortcuts are recorded in the jump list
+    app.handle_keypress(KeyEvent::new(KeyCode::Char('o'), KeyModifiers::CONTROL));
+    assert_eq!(app.nav().current_dir().parent(), None);
+    Ok(())
+}
+
+#[test]
+fn test_key_sequences() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    for name in ["a.txt", "b.txt", "c.txt"] {
+        std::fs::write(dir.path().join(name), "text")?;
+    }
+    let config = Config::from(toml::from_str::<RawConfig>(
+        r#"
+        show_hidden = true
+        [keys]
+        toggle_hidden = ["g"]
+    "#,
+    )?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    wait_until(&mut app, |app| !app.nav().entries().is_empty());
+
+    app.handle_keypress(KeyEvent::new(KeyCode::Char('G'), KeyModifiers::SHIFT));
+    assert_eq!(app.nav().selected_idx(), 2);
+    app.handle_keypress(key('g'));
+    assert_eq!(app.pending_keys().len(), 1);
+    app.handle_keypress(key('g'));
+    assert_eq!(app.nav().selected_idx(), 0);
+    assert!(app.pending_keys().is_empty());
+
+    // "g" is bound on its own next to "g g", a key not continuing the sequence runs both
+    app.handle_keypress(key('g'));
+    assert!(app.show_hidden());
+    app.handle_keypress(key('j'));
+    assert!(!app.show_hidden());
+    assert_eq!(app.nav().selected_idx(), 1);
+
+    // Without a next key the sequence runs the binding of "g" once it timed out
+    app.handle_keypress(key('g'));
+    assert!(app.expire_key_sequence().is_none());
+    std::thread::sleep(runa_tui::app::KEY_SEQUENCE_TIMEOUT);
+    assert!(app.expire_key_sequence().is_some());
+    assert!(app.show_hidden());
+    assert!(app.pending_keys().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_is_binary_file_detection() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let text = dir.path().join("notes.txt");
+    std::fs::write(&text, "just some text\n")?;
+    let blob = dir.path().join("data.bin");
+    std::fs::write(&blob, [0x7f, b'E', b'L', b'F', 0, 1, 2])?;
+    let photo = dir.path().join("photo.JPG");
+    std::fs::write(&photo, "not read, known by its extension")?;
+
+    assert!(!core::is_binary_file(&text));
+    assert!(core::is_binary_file(&blob));
+    assert!(core::is_binary_file(&photo));
+    assert!(!core::is_binary_file(&dir.path().join("missing")));
+    Ok(())
+}
+
+#[test]
+fn test_openers_match_extensions() -> Result<(), Box<dyn error::Error>> {
+    let toml_content = r#"
+            [openers]
+            "PNG" = "feh %f"
+            ".gz" = "zcat %f"
+            "tar.gz" = "tar -tzf %f"
+        "#;
+    let config = Config::from(toml::from_str::<RawConfig>(toml_content)?);
+    let openers = config.openers();
+
+    assert_eq!(openers.get(Path::new("/tmp/Photo.png")), Some("feh %f"));
+    assert_eq!(openers.get(Path::new("logs.tar.gz")), Some("tar -tzf %f"));
+    assert_eq!(openers.get(Path::new("notes.gz")), Some("zcat %f"));
+    assert_eq!(openers.get(Path::new(".png")), None);
+    assert_eq!(openers.get(Path::new("readme.md")), None);
+    Ok(())
+}
+
+#[test]
+fn test_enter_actions_by_file_class() -> Result<(), Box<dyn error::Error>> {
+    let toml_content = r#"
+            animations = false
+            [open.enter]
+            binary = "system"
+            executable = "prompt"
+            "MD" = "prompt"
+            ".tar.gz" = "preview"
+        "#;
+    let config = Config::from(toml::from_str::<RawConfig>(toml_content)?);
+    let enter = config.open().enter();
+
+    assert_eq!(
+        enter.get(Path::new("notes.md"), true, || false),
+        Some(OpenAction::Prompt)
+    );
+    assert_eq!(
+        enter.get(Path::new("logs.tar.gz"), false, || true),
+        Some(OpenAction::Preview)
+    );
+    assert_eq!(
+        enter.get(Path::new("run.sh"), true, || false),
+        Some(OpenAction::Prompt)
+    );
+    assert_eq!(
+        enter.get(Path::new("photo.png"), false, || true),
+        Some(OpenAction::System)
+    );
+    // No text action, so the built-in default applies
+    assert_eq!(enter.get(Path::new("main.rs"), false, || false), None);
+
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join("notes.md"), "# Notes")?;
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    wait_until(&mut app, |app| !app.nav().entries().is_empty());
+    app.handle_keypress(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(matches!(
+        app.actions().mode(),
+        ActionMode::Input { mode: InputMode::OpenWith, prompt }
+            if prompt == "Open notes.md: [E]ditor [S]ystem [P]review"
+    ));
+    // Other keys keep the prompt open, Esc closes it without opening anything
+    app.handle_keypress(key('x'));
+    assert!(app.actions().is_input_mode());
+    app.handle_keypress(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(!app.actions().is_input_mode());
+    Ok(())
+}
+
+#[test]
+fn test_details_filled_in_by_metadata_worker() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join("crab.txt"), vec![b'x'; 2000])?;
+
+    let raw: RawConfig = toml::from_str("[display]\ndetails = true\ngit_status = false")?;
+    let config = Config::from(raw);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+
+    let name = std::ffi::OsStr::new("crab.txt");
+    wait_until(&mut app, |app| app.nav().metadata(name).is_some());
+    let meta = app.nav().metadata(name).ok_or("Metadata was not loaded")?;
+    assert_eq!(meta.size(), Some(2000));
+    assert!(meta.modified().is_some());
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 10))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(screen.contains("2 kB"), "Size column missing");
+    Ok(())
+}
+
+#[test]
+fn test_marked_overview_lists_and_unmarks() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::create_dir(dir.path().join("sub"))?;
+    std::fs::write(dir.path().join("sub/b.txt"), vec![b'x'; 300])?;
+    std::fs::write(dir.path().join("a.txt"), vec![b'x'; 200])?;
+
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    wait_until(&mut app, |app| app.nav().shown_entries_len() >= 2);
+
+    app.handle_keypress(key(' '));
+    app.handle_keypress(key(' '));
+    assert_eq!(app.nav().markers().len(), 2);
+
+    let listed = |app: &AppState| match app.overlays().top() {
+        Some(Overlay::MarkedList { marked }) => Some(marked.targets().len()),
+        _ => None,
+    };
+    app.handle_keypress(key('m'));
+    assert_eq!(listed(&app), Some(2));
+    wait_until(&mut app, |app| app.marked_summary().2);
+    assert_eq!(app.marked_summary().1, 500);
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 24))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(screen.contains("2 marked items"));
+    assert!(screen.contains("Total size: 500 B"));
+
+    // Unmarking the last path closes the overview
+    app.handle_keypress(key('u'));
+    assert_eq!(app.nav().markers().len(), 1);
+    assert_eq!(listed(&app), Some(1));
+    app.handle_keypress(key('u'));
+    assert!(app.nav().markers().is_empty());
+    assert!(app.overlays().top().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_marked_overview_jumps_to_path() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::create_dir(dir.path().join("sub"))?;
+    std::fs::write(dir.path().join("sub/b.txt"), "")?;
+    std::fs::write(dir.path().join("a.txt"), "")?;
+
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().join("sub"));
+    wait_until(&mut app, |app| app.nav().shown_entries_len() == 1);
+    app.handle_keypress(key(' '));
+
+    app.handle_keypress(key('h'));
+    wait_until(&mut app, |app| app.nav().shown_entries_len() == 2);
+    assert_eq!(app.nav().current_dir(), dir.path());
+    app.handle_keypress(key('j'));
+    app.handle_keypress(key(' '));
+    assert_eq!(app.nav().markers().len(), 2);
+
+    // Keys go to the overview while it is open, `j` moves its selection to sub/b.txt
+    app.handle_keypress(key('m'));
+    assert!(matches!(
+        app.overlays().top(),
+        Some(Overlay::MarkedList { .. })
+    ));
+    app.handle_keypress(key('j'));
+    app.handle_keypress(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(app.overlays().top().is_none());
+
+    let selected = |app: &AppState| {
+        app.nav()
+            .selected_shown_entry()
+            .map(|e| e.name().to_os_string())
+    };
+    wait_until(&mut app, |app| {
+        selected(app).is_some_and(|name| name == "b.txt")
+    });
+    assert_eq!(app.nav().current_dir(), dir.path().join("sub"));
+    Ok(())
+}
+
+#[test]
+fn test_status_line_shows_marked_summary() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::create_dir(dir.path().join("sub"))?;
+    std::fs::write(dir.path().join("sub/b.txt"), vec![b'x'; 300])?;
+    std::fs::write(dir.path().join("a.txt"), vec![b'x'; 200])?;
+
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    wait_until(&mut app, |app| app.nav().shown_entries_len() >= 2);
+
+    app.handle_keypress(key(' '));
+    app.handle_keypress(key(' '));
+    wait_until(&mut app, |app| app.marked_summary().2);
+    assert_eq!(app.marked_summary(), (2, 500, true));
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 10))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(screen.contains("Marked: 2 (500 B)"));
+
+    app.handle_keypress(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+    assert_eq!(app.marked_summary(), (0, 0, true));
+    Ok(())
+}
+
+#[test]
+fn test_toggle_instant_preview_at_runtime() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    assert!(!app.instant_preview());
+
+    let ctrl_p = KeyEvent::new(KeyCode::Char('p'), KeyModifiers::CONTROL);
+    app.handle_keypress(ctrl_p);
+    assert!(app.instant_preview());
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 10))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(screen.contains("Instant preview: on"));
+
+    app.handle_keypress(ctrl_p);
+    assert!(!app.instant_preview());
+    Ok(())
+}
+
+#[test]
+fn test_quick_find_jumps_within_current_listing() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::create_dir(dir.path().join("sub"))?;
+    std::fs::write(dir.path().join("sub/beta_deep.txt"), "")?;
+    for name in ["alpha.txt", "beta.log", "gamma.txt"] {
+        std::fs::write(dir.path().join(name), "")?;
+    }
+
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    wait_until(&mut app, |app| app.nav().shown_entries_len() >= 4);
+    let origin = app.nav().selected_idx();
+    let selected = |app: &AppState| {
+        app.nav()
+            .selected_entry()
+            .map(|e| e.name_str().to_string())
+            .unwrap_or_default()
+    };
+
+    app.handle_keypress(key('/'));
+    assert!(app.actions().is_input_mode());
+    for c in "gam".chars() {
+        app.handle_keypress(key(c));
+    }
+    assert_eq!(selected(&app), "gamma.txt");
+    assert_eq!(app.nav().shown_entries_len(), 4);
+
+    app.handle_keypress(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    app.handle_keypress(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    app.handle_keypress(KeyEvent::new(KeyCode::Backspace, KeyModifiers::NONE));
+    app.handle_keypress(key('t'));
+    app.handle_keypress(key('x'));
+    let first = selected(&app);
+    app.handle_keypress(KeyEvent::new(KeyCode::Down, KeyModifiers::NONE));
+    let second = selected(&app);
+    assert_ne!(first, second);
+    assert!(first.ends_with(".txt") && second.ends_with(".txt"));
+
+    app.handle_keypress(KeyEvent::new(KeyCode::Esc, KeyModifiers::NONE));
+    assert!(!app.actions().is_input_mode());
+    assert_eq!(app.nav().selected_idx(), origin);
+
+    app.handle_keypress(key('/'));
+    for c in "beta".chars() {
+        app.handle_keypress(key(c));
+    }
+    app.handle_keypress(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert_eq!(selected(&app), "beta.log");
+    assert_eq!(app.nav().current_dir(), dir.path());
+    Ok(())
+}
+
+#[test]
+fn test_new_file_template_fills_matching_files() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let toml = r##"
+        animations = false
+        [templates.rust-main]
+        names = ["main.rs"]
+        text = "fn main() {}\n"
+        [templates.script]
+        names = ["*.SH"]
+        text = "#!/bin/sh\n"
+    "##;
+    let config = Config::from(toml::from_str::<RawConfig>(toml)?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    let enter = KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE);
+
+    // The first matching template is preselected
+    app.handle_keypress(key('n'));
+    for c in "src/main.rs".chars() {
+        app.handle_keypress(key(c));
+    }
+    app.handle_keypress(enter);
+    assert_eq!(app.actions().template_state().templates(), ["rust-main"]);
+    app.handle_keypress(enter);
+    let main_rs = dir.path().join("src/main.rs");
+    wait_until(&mut app, |_| main_rs.exists());
+    assert_eq!(std::fs::read_to_string(&main_rs)?, "fn main() {}\n");
+
+    // "Empty file" skips the template
+    app.handle_keypress(key('n'));
+    for c in "run.sh".chars() {
+        app.handle_keypress(key(c));
+    }
+    app.handle_keypress(enter);
+    app.handle_keypress(KeyEvent::new(KeyCode::Up, KeyModifiers::NONE));
+    app.handle_keypress(enter);
+    let run_sh = dir.path().join("run.sh");
+    wait_until(&mut app, |_| run_sh.exists());
+    assert_eq!(std::fs::read_to_string(&run_sh)?, "");
+
+    // Without a matching template the file is created right away
+    app.handle_keypress(key('n'));
+    for c in "notes.txt".chars() {
+        app.handle_keypress(key(c));
+    }
+    app.handle_keypress(enter);
+    assert!(!app.actions().is_input_mode());
+    Ok(())
+}
+
+#[test]
+fn test_toggle_hidden_keeps_selection() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join(".hidden"), "")?;
+    std::fs::write(dir.path().join("a.txt"), "")?;
+    std::fs::write(dir.path().join("b.txt"), "")?;
+    let config = Config::from(toml::from_str::<RawConfig>(
+        r#"
+        animations = false
+        show_hidden = false
+    "#,
+    )?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    let selected = |app: &AppState| {
+        app.nav()
+            .selected_shown_entry()
+            .map(|e| e.name().to_string_lossy().into_owned())
+    };
+
+    wait_until(&mut app, |app| app.nav().entries().len() == 2);
+    app.handle_keypress(key('j'));
+    assert_eq!(selected(&app).as_deref(), Some("b.txt"));
+
+    app.handle_keypress(key('.'));
+    assert!(app.show_hidden());
+    wait_until(&mut app, |app| app.nav().entries().len() == 3);
+    assert_eq!(app.nav().entries().len(), 3);
+    assert_eq!(selected(&app).as_deref(), Some("b.txt"));
+
+    app.handle_keypress(key('.'));
+    wait_until(&mut app, |app| app.nav().entries().len() == 2);
+    assert_eq!(app.nav().entries().len(), 2);
+    assert_eq!(selected(&app).as_deref(), Some("b.txt"));
+    Ok(())
+}
+
+#[test]
+fn test_toggle_panes_relayouts_and_reloads() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join("crab.txt"), "pinch")?;
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    wait_until(&mut app, |app| !app.nav().entries().is_empty());
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 12))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let shown = *app.metrics();
+    assert!(shown.preview_width > 0);
+
+    // The metrics follow right away, before the next frame is drawn
+    app.handle_keypress(KeyEvent::new(KeyCode::Char('P'), KeyModifiers::SHIFT));
+    assert!(!app.show_preview());
+    assert_eq!(app.metrics().preview_width, 0);
+    assert!(app.metrics().main_width > shown.main_width);
+
+    app.handle_keypress(KeyEvent::new(KeyCode::Char('H'), KeyModifiers::SHIFT));
+    assert!(!app.show_parent());
+    assert_eq!(app.metrics().parent_width, 0);
+
+    app.handle_keypress(KeyEvent::new(KeyCode::Char('H'), KeyModifiers::SHIFT));
+    app.handle_keypress(KeyEvent::new(KeyCode::Char('P'), KeyModifiers::SHIFT));
+    assert_eq!(app.metrics().preview_width, shown.preview_width);
+    assert_eq!(app.metrics().parent_width, shown.parent_width);
+    wait_until(&mut app, |app| {
+        app.preview().current_path() == Some(&dir.path().join("crab.txt"))
+            && !app.parent().entries().is_empty()
+    });
+    assert_eq!(
+        app.preview().current_path(),
+        Some(&dir.path().join("crab.txt"))
+    );
+    assert!(!app.parent().entries().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_readonly_blocks_mutating_actions() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join("notes.txt"), "text")?;
+    let config = Config::from(toml::from_str::<RawConfig>(
+        r#"
+        animations = false
+        readonly = true
+        [commands]
+        "x" = "touch created.txt"
+    "#,
+    )?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    wait_until(&mut app, |app| !app.nav().entries().is_empty());
+    assert!(app.readonly());
+
+    app.handle_keypress(key('d'));
+    assert!(!app.actions().is_input_mode());
+    let latest = app
+        .notifications()
+        .latest()
+        .expect("a notice of the blocked delete");
+    assert_eq!(latest.severity(), Severity::Warn);
+    assert_eq!(latest.text(), "Read-only mode: delete is disabled");
+
+    // Commands and the shell may change anything
+    for c in [':', 'x', 'w'] {
+        app.handle_keypress(key(c));
+        assert!(!app.actions().is_input_mode());
+    }
+    let latest = app
+        .notifications()
+        .latest()
+        .expect("a notice of the blocked shell");
+    assert_eq!(latest.text(), "Read-only mode: the shell is disabled");
+    assert!(!dir.path().join("created.txt").exists());
+
+    // Actions not changing the filesystem still work
+    app.handle_keypress(key('y'));
+    assert!(app.actions().clipboard().is_some());
+
+    app.set_readonly(false);
+    app.handle_keypress(key('d'));
+    assert!(app.actions().is_input_mode());
+    Ok(())
+}
+
+#[test]
+fn test_root_guard_locks_and_logs() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let log = dir.path().join("logs/root.log");
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    let unlock = KeyEvent::new(KeyCode::Char('U'), KeyModifiers::SHIFT);
+
+    // Without the guard the unlock key has nothing to do
+    app.handle_keypress(unlock);
+    assert!(!app.readonly());
+
+    app.enable_root_guard(Some(log.clone()));
+    assert!(app.readonly());
+    app.handle_keypress(key('n'));
+    assert!(!app.actions().is_input_mode());
+    let latest = app.notifications().latest().expect("a notice of the lock");
+    assert_eq!(
+        latest.text(),
+        "Running as root: create is disabled until Shift+u unlocks it"
+    );
+    app.handle_keypress(key(':'));
+    assert!(!app.actions().is_input_mode());
+    let latest = app.notifications().latest().expect("a notice of the lock");
+    assert_eq!(
+        latest.text(),
+        "Running as root: running commands is disabled until Shift+u unlocks it"
+    );
+
+    app.handle_keypress(unlock);
+    assert!(!app.readonly());
+    assert!(app.root_guard().is_some_and(|g| !g.locked()));
+    app.handle_keypress(key('n'));
+    for c in "notes.txt".chars() {
+        app.handle_keypress(key(c));
+    }
+    app.handle_keypress(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+
+    app.handle_keypress(unlock);
+    assert!(app.readonly());
+
+    let lines: Vec<String> = std::fs::read_to_string(&log)?
+        .lines()
+        .map(|line| line.splitn(4, ' ').nth(3).unwrap_or_default().to_string())
+        .collect();
+    assert_eq!(
+        lines,
+        [
+            format!("session started in {}", dir.path().display()),
+            "unlocked".to_string(),
+            format!("create {}", dir.path().join("notes.txt").display()),
+            "locked".to_string(),
+        ]
+    );
+
+    // The readonly option is not lifted by the unlock key
+    app.set_readonly(true);
+    app.handle_keypress(unlock);
+    assert!(app.readonly());
+    Ok(())
+}
+
+#[test]
+fn test_paste_asks_before_overwriting() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let src = dir.path().join("src");
+    std::fs::create_dir(&src)?;
+    std::fs::write(src.join("notes.txt"), "new")?;
+    let existing = dir.path().join("notes.txt");
+    std::fs::write(&existing, "old")?;
+    let config = Config::from(toml::from_str::<RawConfig>(
+        r#"
+        animations = false
+        paste_conflict = "ask"
+    "#,
+    )?);
+    let mut app = AppState::with_dir(config, src.clone());
+
+    wait_until(&mut app, |app| !app.nav().entries().is_empty());
+    app.handle_keypress(key('y'));
+    app.handle_keypress(key('h'));
+    wait_until(&mut app, |app| app.nav().entries().len() == 2);
+
+    // Skipping keeps the existing file
+    app.handle_keypress(key('p'));
+    assert_eq!(app.actions().conflict_targets(), [existing.as_path()]);
+    app.handle_keypress(key('s'));
+    assert!(!app.actions().is_input_mode());
+    wait_until(&mut app, |app| !app.notifications().is_empty());
+    assert_eq!(std::fs::read_to_string(&existing)?, "old");
+    assert!(!dir.path().join("notes_1.txt").exists());
+
+    app.handle_keypress(key('p'));
+    app.handle_keypress(key('y'));
+    wait_until(&mut app, |_| {
+        std::fs::read_to_string(&existing).is_ok_and(|text| text == "new")
+    });
+    assert_eq!(std::fs::read_to_string(&existing)?, "new");
+    Ok(())
+}
+
+#[test]
+fn test_start_find_prefills_the_query() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    app.start_find("crab rs".to_string());
+
+    // Without fd the dialog stays closed and a warning explains why
+    if which::which("fd").is_err() {
+        assert!(!app.actions().is_input_mode());
+        let latest = app.notifications().latest();
+        assert_eq!(latest.map(|n| n.severity()), Some(Severity::Warn));
+        return Ok(());
+    }
+    assert!(matches!(
+        app.actions().mode(),
+        ActionMode::Input {
+            mode: InputMode::Find,
+            ..
+        }
+    ));
+    assert_eq!(app.actions().input_buffer(), "crab rs");
+    assert_eq!(app.actions().input_cursor_pos(), "crab rs".len());
+    Ok(())
+}
+
+#[test]
+fn test_operation_results_are_notified() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+
+    app.handle_keypress(key('n'));
+    for c in "notes.txt".chars() {
+        app.handle_keypress(key(c));
+    }
+    app.handle_keypress(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    wait_until(&mut app, |app| !app.notifications().is_empty());
+    let latest = app.notifications().latest();
+    assert_eq!(latest.map(|n| n.text()), Some("Created"));
+    assert_eq!(latest.map(|n| n.severity()), Some(Severity::Info));
+
+    // With message_secs = 0 results are not queued
+    let config = Config::from(toml::from_str::<RawConfig>("message_secs = 0")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    app.handle_keypress(key('n'));
+    for c in "todo.txt".chars() {
+        app.handle_keypress(key(c));
+    }
+    app.handle_keypress(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    let created = dir.path().join("todo.txt");
+    wait_until(&mut app, |_| created.exists());
+    std::thread::sleep(Duration::from_millis(50));
+    app.tick();
+    assert!(created.exists());
+    assert!(app.notifications().is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_rename_starts_before_the_extension() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join("report.draft.txt"), "")?;
+
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    wait_until(&mut app, |app| app.nav().shown_entries_len() >= 1);
+
+    let select_stem = KeyEvent::new(KeyCode::Char('a'), KeyModifiers::CONTROL);
+    app.handle_keypress(key('r'));
+    assert_eq!(app.actions().input_cursor_pos(), "report.draft".len());
+    app.handle_keypress(key('2'));
+    assert_eq!(app.actions().input_buffer(), "report.draft2.txt");
+
+    // The first press selects the stem, the second the whole name
+    app.handle_keypress(select_stem);
+    assert_eq!(app.actions().input_selection(), Some(0..13));
+    app.handle_keypress(key('n'));
+    assert_eq!(app.actions().input_buffer(), "n.txt");
+    app.handle_keypress(select_stem);
+    app.handle_keypress(select_stem);
+    assert_eq!(app.actions().input_selection(), Some(0..5));
+    app.handle_keypress(KeyEvent::new(KeyCode::Left, KeyModifiers::NONE));
+    assert_eq!(app.actions().input_selection(), None);
+    assert_eq!(app.actions().input_cursor_pos(), 0);
+
+    assert_eq!(utils::name_stem_len(".bashrc"), 7);
+    assert_eq!(utils::name_stem_len("archive.tar.gz"), 11);
+    assert_eq!(utils::name_stem_len("notes."), 6);
+    Ok(())
+}
+
+#[test]
+fn test_profiles_apply_to_matching_dirs() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let nas = dir.path().join("nas");
+    std::fs::create_dir(&nas)?;
+    let toml_content = format!(
+        r#"
+            animations = false
+            [profiles.network]
+            paths = [{:?}]
+            fs_types = ["nfs"]
+            instant_preview = true
+            watch = false
+        "#,
+        nas.to_string_lossy()
+    );
+    let config = Config::from(toml::from_str::<RawConfig>(&toml_content)?);
+
+    let profiles = config.profiles();
+    assert!(profiles.find(&nas.join("share"), None).is_some());
+    assert!(profiles.find(Path::new("/srv"), Some("NFS")).is_some());
+    assert!(profiles.find(dir.path(), Some("ext4")).is_none());
+
+    let mut app = AppState::with_dir(config, nas);
+    wait_until(&mut app, |app| app.active_profile().is_some());
+    assert_eq!(app.active_profile(), Some("network"));
+    assert!(app.instant_preview());
+    assert!(app.default_instant_preview());
+    Ok(())
+}
+
+#[test]
+fn test_termux_option_and_prefix_paths() -> Result<(), Box<dyn error::Error>> {
+    let config = Config::from(toml::from_str::<RawConfig>("termux = true")?);
+    assert!(config.termux());
+    let config = Config::from(toml::from_str::<RawConfig>("termux = false")?);
+    assert!(!config.termux());
+
+    let prefix = std::env::var_os("PREFIX").map(PathBuf::from);
+    for path in ["$PREFIX/share/runa", "${PREFIX}/share/runa"] {
+        let expected = match &prefix {
+            Some(prefix) => prefix.join("share/runa"),
+            None => PathBuf::from(path),
+        };
+        assert_eq!(expand_path(path), expected);
+    }
+    assert_eq!(
+        expand_path("$PREFIXES/runa"),
+        PathBuf::from("$PREFIXES/runa")
+    );
+    if let Some(home) = dirs::home_dir() {
+        assert_eq!(expand_path("~/notes"), home.join("notes"));
+    }
+    assert_eq!(expand_path("/etc/runa"), PathBuf::from("/etc/runa"));
+    Ok(())
+}
+
+#[test]
+fn test_set_config_applies_keys_and_options() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join(".hidden"), "")?;
+    std::fs::write(dir.path().join("a.txt"), "")?;
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    wait_until(&mut app, |app| app.nav().entries().len() == 2);
+    assert_eq!(app.nav().entries().len(), 2);
+
+    let config_dir = tempdir()?;
+    let path = config_dir.path().join("runa.toml");
+    std::fs::write(
+        &path,
+        "animations = false\nshow_hidden = false\n[keys]\ntoggle_hidden = [\"Ctrl+h\"]\n",
+    )?;
+    app.set_config(Config::load_from(&path)?);
+    assert!(!app.show_hidden());
+    wait_until(&mut app, |app| app.nav().entries().len() == 1);
+    assert_eq!(app.nav().entries().len(), 1);
+
+    // "." is unbound now, the new key toggles
+    app.handle_keypress(key('.'));
+    assert!(!app.show_hidden());
+    app.handle_keypress(KeyEvent::new(KeyCode::Char('h'), KeyModifiers::CONTROL));
+    assert!(app.show_hidden());
+
+    std::fs::write(&path, "show_hidden = false\nwatch = \"yes\"\n")?;
+    let err = Config::load_from(&path)
+        .err()
+        .ok_or("Invalid config was loaded")?;
+    assert!(err.contains("line 2"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_theme_files_merge_under_inline_theme() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let themes = dir.path().join("themes");
+    std::fs::create_dir(&themes)?;
+    std::fs::write(
+        themes.join("ocean.toml"),
+        "name = \"gruvbox-dark\"\n[selection]\nbg = \"#112233\"\n[directory]\nfg = \"green\"\n",
+    )?;
+    std::fs::write(themes.join("broken.toml"), "[selection]\nbg = 5\n")?;
+    let path = dir.path().join("runa.toml");
+
+    std::fs::write(
+        &path,
+        "[theme]\nname = \"ocean\"\n[theme.directory]\nfg = \"red\"\n",
+    )?;
+    let config = Config::load_from(&path)?;
+    let theme = config.theme();
+    assert!(config.warnings().is_empty());
+    assert_eq!(
+        theme.selection_style().bg,
+        Some(Color::Rgb(0x11, 0x22, 0x33))
+    );
+    assert_eq!(theme.directory_style().fg, Some(Color::Red));
+    assert_eq!(theme.syntax_theme_name(), "base16-eighties.dark");
+
+    std::fs::write(&path, "[theme]\nname = \"broken\"\n")?;
+    let config = Config::load_from(&path)?;
+    assert!(config.warnings()[0].contains("line 2"));
+
+    std::fs::write(&path, "[theme]\nname = \"missing\"\n")?;
+    let config = Config::load_from(&path)?;
+    assert!(config.warnings()[0].contains("Unknown theme"));
+    Ok(())
+}
+
+#[test]
+fn test_cached_dir_sizes_shown_in_preview_and_details() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::create_dir(dir.path().join("sub"))?;
+    std::fs::write(dir.path().join("sub/b.txt"), vec![b'x'; 3000])?;
+    std::fs::write(dir.path().join("a.txt"), "")?;
+
+    let raw: RawConfig = toml::from_str("animations = false\n[display]\ndetails = true")?;
+    let mut app = AppState::with_dir(Config::from(raw), dir.path().to_path_buf());
+    let sub = std::ffi::OsStr::new("sub");
+    let size = |app: &AppState| match app.nav().dir_size_status(sub) {
+        DirSizeStatus::Exact(size) => Some(size.bytes()),
+        _ => None,
+    };
+    wait_until(&mut app, |app| app.nav().entries().len() == 2);
+    assert_eq!(app.nav().dir_size_status(sub), DirSizeStatus::Unknown);
+
+    app.handle_keypress(KeyEvent::new(KeyCode::Char('D'), KeyModifiers::SHIFT));
+    wait_until(&mut app, |app| size(app) == Some(3000));
+    assert_eq!(size(&app), Some(3000));
+
+    // Leaving the directory keeps the size for the next visit
+    app.handle_keypress(key('l'));
+    wait_until(&mut app, |app| app.nav().current_dir().ends_with("sub"));
+    app.handle_keypress(key('h'));
+    wait_until(&mut app, |app| {
+        app.nav().entries().len() == 2 && !app.nav().current_dir().ends_with("sub")
+    });
+    assert_eq!(size(&app), Some(3000));
+    wait_until(&mut app, |app| app.nav().metadata(sub).is_some());
+
+    let mut terminal = Terminal::new(TestBackend::new(120, 10))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let screen: String = terminal
+        .backend()
+        .buffer()
+        .content()
+        .iter()
+        .map(|c| c.symbol())
+        .collect();
+    assert!(screen.contains("Size: 3 kB"), "Preview header missing");
+    assert_eq!(screen.matches("3 kB").count(), 2, "Size column missing");
+    Ok(())
+}
+
+#[test]
+fn test_file_info_details_read_in_background() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::create_dir_all(dir.path().join("sub/nested"))?;
+    std::fs::write(dir.path().join("sub/a.txt"), "")?;
+
+    let raw: RawConfig = toml::from_str("animations = false")?;
+    let mut app = AppState::with_dir(Config::from(raw), dir.path().to_path_buf());
+    let items = |app: &AppState| match app.overlays().top() {
+        Some(Overlay::ShowInfo { info }) => info.items().map(|items| items.count()),
+        _ => None,
+    };
+    wait_until(&mut app, |app| app.nav().entries().len() == 1);
+
+    app.handle_keypress(key('i'));
+    match app.overlays().top() {
+        Some(Overlay::ShowInfo { info }) => assert_eq!(info.name(), "sub"),
+        _ => panic!("file info overlay not shown"),
+    }
+    wait_until(&mut app, |app| items(app).is_some());
+    assert_eq!(items(&app), Some(2));
+    Ok(())
+}
+
+#[test]
+fn test_inline_rename_edits_the_selected_row() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join("alpha.txt"), "")?;
+    std::fs::write(dir.path().join("beta.txt"), "")?;
+
+    let raw: RawConfig = toml::from_str("animations = false\n[display]\ninline_rename = true")?;
+    let mut app = AppState::with_dir(Config::from(raw), dir.path().to_path_buf());
+    wait_until(&mut app, |app| app.nav().entries().len() >= 2);
+
+    app.handle_keypress(key('r'));
+    assert!(app.is_inline_rename());
+    for c in "-2".chars() {
+        app.handle_keypress(key(c));
+    }
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 10))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let rows: Vec<String> = (0..10)
+        .map(|y| {
+            (0..80)
+                .map(|x| terminal.backend().buffer()[(x, y)].symbol())
+                .collect()
+        })
+        .collect();
+    assert!(
+        !rows.iter().any(|row| row.contains("Rename")),
+        "Dialog shown"
+    );
+    let row = rows
+        .iter()
+        .position(|row| row.contains("alpha-2.txt"))
+        .ok_or("Inline field missing")?;
+    assert!(rows[row + 1].contains("beta.txt"), "Sibling names hidden");
+
+    app.handle_keypress(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE));
+    assert!(!app.is_inline_rename());
+    let renamed = dir.path().join("alpha-2.txt");
+    wait_until(&mut app, |_| renamed.exists());
+    assert!(renamed.exists());
+    Ok(())
+}
+
+#[test]
+fn test_file_styles_from_ls_colors() -> Result<(), Box<dyn error::Error>> {
+    use ratatui::style::{Modifier, Style};
+    use runa_tui::ui::filestyle::{FileStyles, parse_sgr};
+
+    assert_eq!(
+        parse_sgr("01;31"),
+        Some(Style::new().fg(Color::Red).add_modifier(Modifier::BOLD))
+    );
+    assert_eq!(
+        parse_sgr("38;5;208;48;2;1;2;3"),
+        Some(Style::new().fg(Color::Indexed(208)).bg(Color::Rgb(1, 2, 3)))
+    );
+    assert_eq!(parse_sgr("target"), None);
+
+    let dir = tempdir()?;
+    for name in ["a.tar.gz", "b.GZ", "README", "c.txt", "d.png"] {
+        std::fs::write(dir.path().join(name), "")?;
+    }
+    let entries = core::browse_dir(dir.path())?;
+    let style_of = |styles: &FileStyles, name: &str| {
+        let entry = entries
+            .iter()
+            .find(|e| e.name() == name)
+            .expect("entry listed");
+        styles.style(entry, false)
+    };
+
+    let styles =
+        FileStyles::from_ls_colors("di=01;34:ln=target:*.gz=31:*.tar.gz=32:*README=33:ex=01;32");
+    assert_eq!(style_of(&styles, "a.tar.gz"), parse_sgr("32"));
+    assert_eq!(style_of(&styles, "b.GZ"), parse_sgr("31"));
+    assert_eq!(style_of(&styles, "README"), parse_sgr("33"));
+    assert_eq!(style_of(&styles, "c.txt"), None);
+    assert!(styles.colors_executables());
+
+    let builtin = FileStyles::builtin();
+    assert_eq!(
+        style_of(&builtin, "d.png").and_then(|s| s.fg),
+        Some(Color::Magenta)
+    );
+    assert_eq!(style_of(&FileStyles::default(), "d.png"), None);
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_builtin_file_colors_in_main_pane() -> Result<(), Box<dyn error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let dir = tempdir()?;
+    let script = dir.path().join("run.sh");
+    std::fs::write(&script, "#!/bin/sh\n")?;
+    std::fs::set_permissions(&script, std::fs::Permissions::from_mode(0o755))?;
+    std::fs::write(dir.path().join("notes.zip"), "")?;
+
+    let raw: RawConfig = toml::from_str("[display]\nfile_colors = \"builtin\"")?;
+    let mut app = AppState::with_dir(Config::from(raw), dir.path().to_path_buf());
+    let name = std::ffi::OsStr::new("run.sh");
+    wait_until(&mut app, |app| app.nav().metadata(name).is_some());
+    assert!(app.nav().metadata(name).is_some_and(|m| m.executable()));
+
+    let mut terminal = Terminal::new(TestBackend::new(80, 10))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let buffer = terminal.backend().buffer();
+    let fg_of = |text: &str| {
+        (0..buffer.area.height).find_map(|y| {
+            let row: String = (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect();
+            let x = row.find(text)?;
+            Some(buffer[(x as u16, y)].fg)
+        })
+    };
+    assert_eq!(fg_of("notes.zip"), Some(Color::Red));
+    assert_eq!(fg_of("run.sh"), Some(Color::Green));
+    Ok(())
+}
+
+#[test]
+fn test_navigate_descends_into_best_match() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::create_dir_all(dir.path().join("bravo/charlie"))?;
+    std::fs::create_dir(dir.path().join("apple"))?;
+    std::fs::write(dir.path().join("notes.txt"), "")?;
+    std::fs::write(dir.path().join("bravo/delta.txt"), "")?;
+
+    let config = Config::from(toml::from_str::<RawConfig>("animations = false")?);
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    let special = |code| KeyEvent::new(code, KeyModifiers::NONE);
+    wait_until(&mut app, |app| app.nav().entries().len() == 3);
+
+    app.handle_keypress(KeyEvent::new(KeyCode::Char('F'), KeyModifiers::SHIFT));
+    for c in "brv".chars() {
+        app.handle_keypress(key(c));
+    }
+    assert_eq!(app.nav().shown_entries_len(), 1);
+    app.handle_keypress(special(KeyCode::Enter));
+    assert!(app.nav().current_dir().ends_with("bravo"));
+    assert_eq!(app.actions().input_buffer(), "");
+
+    // Typed before the directory has loaded, the best match is selected once it has
+    for c in "chr".chars() {
+        app.handle_keypress(key(c));
+    }
+    wait_until(&mut app, |app| app.nav().shown_entries_len() == 1);
+    app.handle_keypress(special(KeyCode::Enter));
+    assert!(app.nav().current_dir().ends_with("bravo/charlie"));
+
+    app.handle_keypress(special(KeyCode::Backspace));
+    assert!(app.nav().current_dir().ends_with("bravo"));
+    wait_until(&mut app, |app| app.nav().entries().len() == 2);
+    app.handle_keypress(key('d'));
+    app.handle_keypress(special(KeyCode::Esc));
+    assert!(matches!(app.actions().mode(), ActionMode::Normal));
+    assert_eq!(app.nav().filter(), "");
+    assert_eq!(app.nav().shown_entries_len(), 2);
+
+    // The directories passed through keep no filter
+    app.handle_keypress(key('h'));
+    assert_eq!(app.nav().filter(), "");
+    Ok(())
+}
+
+#[test]
+fn test_theme_modifiers() -> Result<(), Box<dyn error::Error>> {
+    use ratatui::style::Modifier;
+
+    let dir = tempdir()?;
+    let path = dir.path().join("runa.toml");
+    std::fs::write(
+        &path,
+        "[theme.directory]\nfg = \"red\"\nmodifiers = [\"bold\", \"Italic\"]\n\
+         [theme.path]\nmodifiers = [\"underline\"]\n\
+         [theme.selection]\nmodifiers = [\"reversed\"]\n",
+    )?;
+    let config = Config::load_from(&path)?;
+    let theme = config.theme();
+    let directory = theme.directory_style();
+    assert_eq!(directory.fg, Some(Color::Red));
+    assert_eq!(directory.add_modifier, Modifier::BOLD | Modifier::ITALIC);
+    assert_eq!(theme.path_style().add_modifier, Modifier::UNDERLINED);
+    assert_eq!(theme.entry_style().add_modifier, Modifier::empty());
+
+    let mut app = AppState::with_dir(config, dir.path().to_path_buf());
+    wait_until(&mut app, |app| !app.nav().entries().is_empty());
+    let mut terminal = Terminal::new(TestBackend::new(80, 10))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let buffer = terminal.backend().buffer();
+    let (x, y) = (0..buffer.area.height)
+        .find_map(|y| {
+            let row: String = (0..buffer.area.width)
+                .map(|x| buffer[(x, y)].symbol())
+                .collect();
+            row.find("runa.toml").map(|x| (x as u16, y))
+        })
+        .ok_or("Entry not drawn")?;
+    assert!(buffer[(x, y)].modifier.contains(Modifier::REVERSED));
+
+    std::fs::write(&path, "[theme.entry]\nmodifiers = [\"shiny\"]\n")?;
+    let err = Config::load_from(&path)
+        .err()
+        .ok_or("Unknown modifier was accepted")?;
+    assert!(err.contains("shiny"), "{err}");
+    Ok(())
+}
+
+#[test]
+fn test_icon_overrides_in_main_pane() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::create_dir(dir.path().join("src"))?;
+    std::fs::write(dir.path().join("main.rs"), "")?;
+    std::fs::write(dir.path().join("justfile"), "")?;
+    std::fs::write(dir.path().join("notes.xyz"), "")?;
+
+    let raw: RawConfig = toml::from_str(
+        "[display]\nicons = true\n\
+         [icons]\ndefault = \"?\"\n\
+         [icons.extensions]\nRS = { icon = \"R\", fg = \"red\" }\n\
+         [icons.files]\njustfile = \"J\"\n\
+         [icons.dirs]\nsrc = { fg = \"green\" }\n",
+    )?;
+    let mut app = AppState::with_dir(Config::from(raw), dir.path().to_path_buf());
+    wait_until(&mut app, |app| app.nav().entries().len() >= 4);
+
+    let mut terminal = Terminal::new(TestBackend::new(100, 10))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let buffer = terminal.backend().buffer();
+    // The icon cell and its color, two cells before the name
+    let icon_of = |name: &str| {
+        (0..buffer.area.height).find_map(|y| {
+            let cells: Vec<_> = (0..buffer.area.width).map(|x| &buffer[(x, y)]).collect();
+            let row: String = cells.iter().map(|cell| cell.symbol()).collect();
+            let start = row.find(name)?;
+            let x = cells
+                .iter()
+                .scan(0, |offset, cell| {
+                    *offset += cell.symbol().len();
+                    Some(*offset)
+                })
+                .take_while(|&offset| offset <= start)
+                .count();
+            let icon = cells[..x].iter().rev().nth(1)?;
+            Some((icon.symbol().to_string(), icon.fg))
+        })
+    };
+    assert_eq!(icon_of("main.rs"), Some(("R".to_string(), Color::Red)));
+    assert_eq!(icon_of("justfile").map(|(icon, _)| icon), Some("J".into()));
+    assert_eq!(icon_of("notes.xyz").map(|(icon, _)| icon), Some("?".into()));
+    let (icon, fg) = icon_of("src").ok_or("Directory not drawn")?;
+    assert_eq!(icon, runa_tui::ui::icons::DIR_ICON);
+    assert_eq!(fg, Color::Green);
+    Ok(())
+}
+
+#[test]
+fn test_dump_state_writes_snapshot() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let browse = dir.path().join("quote\"dir");
+    std::fs::create_dir(&browse)?;
+    std::fs::write(browse.join("a.txt"), "")?;
+
+    let mut app = AppState::with_dir(Config::default(), browse.clone());
+    wait_until(&mut app, |app| !app.nav().entries().is_empty());
+    app.toggle_stats();
+
+    let path = app.dump_state(&dir.path().join("snapshots"))?;
+    assert!(path.starts_with(dir.path().join("snapshots")));
+    let json = std::fs::read_to_string(&path)?;
+    assert!(json.starts_with('{') && json.ends_with('}'));
+    assert!(json.contains(&format!("\"version\": {}", runa_tui::app::SNAPSHOT_VERSION)));
+    let escaped = browse.to_string_lossy().replace('"', "\\\"");
+    assert!(json.contains(&format!("\"path\": \"{escaped}\"")), "{json}");
+    assert!(json.contains(&format!("\"request_id\": {}", app.nav().request_id())));
+    assert!(json.contains("\"entries\": 1"), "{json}");
+    assert!(json.contains("\"mode\": \"normal\""), "{json}");
+    assert!(json.contains("\"overlays\": [\"stats\"]"), "{json}");
+    assert!(json.contains("\"queued\": { \"io\": "), "{json}");
+    Ok(())
+}
+
+#[test]
+fn test_ascii_only_icons_and_truncation() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::create_dir(dir.path().join("a_rather_long_folder"))?;
+    std::fs::write(dir.path().join("main.rs"), "")?;
+
+    let formatter = Formatter::new(true, true, true, true, Arc::new(HashSet::new()), 8, true);
+    let mut entries = core::browse_dir(dir.path())?;
+    formatter.format(&mut entries);
+    assert_eq!(entries[0].display_name(), "a_rathe~");
+
+    let raw: RawConfig = toml::from_str("[display]\nicons = true\nascii_only = true")?;
+    let mut app = AppState::with_dir(Config::from(raw), dir.path().to_path_buf());
+    wait_until(&mut app, |app| app.nav().entries().len() >= 2);
+    let mut terminal = Terminal::new(TestBackend::new(80, 10))?;
+    terminal.draw(|f| runa_tui::ui::render(f, &mut app))?;
+    let rows: Vec<String> = (0..10)
+        .map(|y| {
+            (0..80)
+                .map(|x| terminal.backend().buffer()[(x, y)].symbol())
+                .collect()
+        })
+        .collect();
+    assert!(
+        rows.iter().any(|row| row.contains("- main.rs")),
+        "{rows:#?}"
+    );
+    assert!(
+        rows.iter().any(|row| row.contains("d a_rather")),
+        "{rows:#?}"
+    );
+    // No Nerd Font glyphs, which live in the private use areas
+    let is_private_use = |c: char| matches!(c, '\u{e000}'..='\u{f8ff}' | '\u{f0000}'..);
+    assert!(!rows.iter().any(|row| row.contains(is_private_use)));
+    Ok(())
+}
+
+#[test]
+fn test_slow_loads_are_reported() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    std::fs::write(dir.path().join("a.txt"), "")?;
+
+    let raw: RawConfig = toml::from_str("[workers]\nslow_warning_ms = 1")?;
+    let mut app = AppState::with_dir(Config::from(raw), dir.path().to_path_buf());
+    // Let the first load run past the threshold before the app looks at it
+    std::thread::sleep(Duration::from_millis(20));
+    wait_until(&mut app, |app| !app.nav().entries().is_empty());
+
+    let hints: Vec<&str> = app
+        .notifications()
+        .iter()
+        .filter(|n| n.severity() == Severity::Warn)
+        .map(|n| n.text())
+        .collect();
+    let loading = format!("Slow filesystem: loading {}", dir.path().display());
+    assert_eq!(
+        hints.iter().filter(|t| t.starts_with(&loading)).count(),
+        1,
+        "{hints:?}"
+    );
+    assert!(!hints.iter().any(|t| t.contains("directory")), "{hints:?}");
+
+    let stats = app.session_stats();
+    assert!(stats.slow_count() >= 1);
+    let first = stats
+        .slow_operations()
+        .front()
+        .ok_or("No slow load logged")?;
+    assert_eq!(first.kind(), runa_tui::app::SlowKind::Directory);
+    assert_eq!(first.path(), dir.path());
+    assert!(first.elapsed() >= Duration::from_millis(20));
+    Ok(())
+}
@@ -2,12 +2,21 @@
 //! These tests ensure that the function correctly generates unused file paths
 //!
 //! Is used by correctly handling name collisions by appending numerical suffixes.
+//! Also covers detecting and resuming interrupted copies, throttled copies
+//! the expansion of shell commands and editor commands, the validation of file names and the release parsing of the self-update.
 //! Temporary directories and files are created for testing purposes and
 //! are automatically cleaned up after the tests complete.
 
-use runa_tui::utils::get_unused_path;
+use runa_tui::config::Editor;
+use runa_tui::utils::{
+    CommandPaths, NameIssue, check_filename, copy_file_limited, editor_command, expand_braces,
+    expand_command, expand_create_input, get_unused_path, is_file_in_use, osc52_sequence,
+    relative_path, resumable_copy_offset, resume_copy,
+};
 use std::error;
-use std::fs::File;
+use std::fs::{self, File};
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 use tempfile::tempdir;
 
 #[test]
@@ -118,3 +127,286 @@ fn test_get_unused_path_complex_extension() -> Result<(), Box<dyn error::Error>>
     assert!(name.contains("_1"), "Suffix missing: got {:?}", name);
     Ok(())
 }
+
+#[test]
+fn test_resume_interrupted_copy() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let src = dir.path().join("big.bin");
+    let dest = dir.path().join("copy.bin");
+
+    let data: Vec<u8> = (0..200_000u32).map(|i| (i % 251) as u8).collect();
+    fs::write(&src, &data)?;
+    fs::write(&dest, &data[..120_000])?;
+
+    assert_eq!(resumable_copy_offset(&src, &dest), Some(120_000));
+    assert_eq!(resume_copy(&src, &dest, 120_000, None)?, 80_000);
+    assert_eq!(fs::read(&dest)?, data);
+
+    // A complete copy is no longer resumable
+    assert_eq!(resumable_copy_offset(&src, &dest), None);
+    Ok(())
+}
+
+#[test]
+fn test_resume_rejects_mismatched_tail() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let src = dir.path().join("a.txt");
+    let dest = dir.path().join("b.txt");
+
+    fs::write(&src, b"hello world, this is the source")?;
+    fs::write(&dest, b"hello there")?;
+    assert_eq!(resumable_copy_offset(&src, &dest), None);
+
+    File::create(dir.path().join("empty.txt"))?;
+    assert_eq!(
+        resumable_copy_offset(&src, &dir.path().join("empty.txt")),
+        None
+    );
+    Ok(())
+}
+
+#[test]
+fn test_copy_file_limited_throttles() -> Result<(), Box<dyn error::Error>> {
+    let dir = tempdir()?;
+    let src = dir.path().join("src.bin");
+    let dest = dir.path().join("dest.bin");
+
+    let data = vec![7u8; 64 * 1024];
+    fs::write(&src, &data)?;
+
+    // 64 KiB at 128 KiB/s should take roughly half a second
+    let start = Instant::now();
+    let copied = copy_file_limited(&src, &dest, Some(128 * 1024))?;
+    assert!(start.elapsed() >= Duration::from_millis(400));
+    assert_eq!(copied, data.len() as u64);
+    assert_eq!(fs::read(&dest)?, data);
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn test_editor_command_opens_many_files() -> Result<(), Box<dyn error::Error>> {
+    let files = vec![PathBuf::from("/tmp/a.rs"), PathBuf::from("/tmp/b c.rs")];
+    let dir = PathBuf::from("/tmp");
+    let args = |cmd: &std::process::Command| {
+        cmd.get_args()
+            .map(|a| a.to_string_lossy().into_owned())
+            .collect::<Vec<_>>()
+    };
+
+    // Without a template every file is one argument of the editor
+    let editor: Editor = toml::from_str(r#"cmd = "vim""#)?;
+    let cmd = editor_command(&editor, &files, &dir);
+    assert_eq!(cmd.get_program(), "vim");
+    assert_eq!(args(&cmd), ["/tmp/a.rs", "/tmp/b c.rs"]);
+
+    let editor: Editor = toml::from_str(r#"open_many = "nvim -p %m""#)?;
+    let cmd = editor_command(&editor, &files, &dir);
+    assert_eq!(cmd.get_program(), "sh");
+    assert_eq!(args(&cmd), ["-c", "nvim -p '/tmp/a.rs' '/tmp/b c.rs'"]);
+    assert_eq!(cmd.get_current_dir(), Some(dir.as_path()));
+    Ok(())
+}
+
+#[test]
+fn test_expand_command_placeholders() {
+    let marked = vec![PathBuf::from("/tmp/a b"), PathBuf::from("/tmp/it's")];
+    let selected = PathBuf::from("/tmp/x.tar");
+    let dir = PathBuf::from("/tmp");
+    let paths = CommandPaths {
+        dir: &dir,
+        selected: Some(&selected),
+        marked: &marked,
+    };
+
+    if cfg!(windows) {
+        assert_eq!(
+            expand_command("du -sh %s", &paths),
+            "du -sh \"/tmp/a b\" \"/tmp/it's\""
+        );
+        assert_eq!(
+            expand_command("tar -xf %f -C %d", &paths),
+            "tar -xf \"/tmp/x.tar\" -C \"/tmp\""
+        );
+        // cmd would expand %PATH% inside quotes
+        let percent = PathBuf::from("C:/%PATH%.txt");
+        let percent = CommandPaths {
+            dir: &dir,
+            selected: Some(&percent),
+            marked: &[],
+        };
+        assert_eq!(
+            expand_command("type %f", &percent),
+            "type \"C:/\"^%\"PATH\"^%\".txt\""
+        );
+    } else {
+        assert_eq!(
+            expand_command("du -sh %s", &paths),
+            "du -sh '/tmp/a b' '/tmp/it'\\''s'"
+        );
+        assert_eq!(
+            expand_command("tar -xf %f -C %d", &paths),
+            "tar -xf '/tmp/x.tar' -C '/tmp'"
+        );
+    }
+    assert_eq!(expand_command("ls -la", &paths), "ls -la");
+    assert_eq!(expand_command("echo %q", &paths), "echo %q");
+
+    let unmarked = CommandPaths {
+        dir: &dir,
+        selected: Some(&selected),
+        marked: &[],
+    };
+    assert_eq!(expand_command("echo 100%% %m", &unmarked), "echo 100% ");
+    assert_eq!(
+        expand_command("rm %s", &unmarked),
+        expand_command("rm %f", &unmarked)
+    );
+}
+
+#[cfg(feature = "self-update")]
+#[test]
+fn test_update_release_parsing() -> Result<(), Box<dyn error::Error>> {
+    use runa_tui::utils::update::{expected_checksum, is_newer, sha256_hex, version_from_tag_url};
+
+    assert_eq!(
+        version_from_tag_url("https://github.com/alexm-dev/runa/releases/tag/v0.6.0\n"),
+        Some("0.6.0".to_string())
+    );
+    assert_eq!(
+        version_from_tag_url("https://github.com/alexm-dev/runa/releases"),
+        None
+    );
+
+    assert!(is_newer("0.10.0", "0.9.3"));
+    assert!(is_newer("1.0.0", "0.5.1"));
+    assert!(!is_newer("0.5.1", "0.5.1"));
+    assert!(!is_newer("0.5.0-rc1", "0.5.1"));
+
+    let sums = "aaaa  runa-linux-x86_64.tar.gz\nbbbb *runa-windows-x86_64.zip\n";
+    assert_eq!(
+        expected_checksum(sums, "runa-windows-x86_64.zip"),
+        Some("bbbb".to_string())
+    );
+    assert_eq!(expected_checksum(sums, "runa-macos-aarch64.tar.gz"), None);
+
+    assert_eq!(
+        sha256_hex(b"abc"),
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_check_filename() {
+    assert_eq!(check_filename(""), None);
+    assert_eq!(check_filename("notes.txt"), None);
+    assert_eq!(check_filename(".hidden"), None);
+    assert_eq!(check_filename("console.log"), None);
+    assert!(check_filename("a/b").is_some_and(|i| i.is_invalid()));
+    assert!(check_filename("a\0b").is_some_and(|i| i.is_invalid()));
+    assert!(check_filename("..").is_some_and(|i| i.is_invalid()));
+
+    let windows_only = ["CON", "con.txt", "LPT1", "name.", "name ", "a:b", "what?"];
+    for name in windows_only {
+        let issue = check_filename(name);
+        if cfg!(windows) {
+            assert!(matches!(issue, Some(NameIssue::Invalid(_))), "{}", name);
+        } else {
+            assert!(matches!(issue, Some(NameIssue::NotPortable(_))), "{}", name);
+        }
+    }
+}
+
+#[test]
+fn test_expand_braces() {
+    assert_eq!(
+        expand_braces("src/{lib,main}.rs"),
+        Ok(vec!["src/lib.rs".to_string(), "src/main.rs".to_string()])
+    );
+    assert_eq!(
+        expand_braces("chapter{1..3}/"),
+        Ok(vec![
+            "chapter1/".to_string(),
+            "chapter2/".to_string(),
+            "chapter3/".to_string()
+        ])
+    );
+    assert_eq!(
+        expand_braces("{08..10}"),
+        Ok(vec!["08".to_string(), "09".to_string(), "10".to_string()])
+    );
+    assert_eq!(
+        expand_braces("{c..a}{,x}"),
+        Ok(vec![
+            "c".to_string(),
+            "cx".to_string(),
+            "b".to_string(),
+            "bx".to_string(),
+            "a".to_string(),
+            "ax".to_string()
+        ])
+    );
+    assert_eq!(
+        expand_braces("a{b,{c,d}}"),
+        Ok(vec!["ab".to_string(), "ac".to_string(), "ad".to_string()])
+    );
+    assert_eq!(expand_braces("{x}.txt"), Ok(vec!["{x}.txt".to_string()]));
+    assert_eq!(
+        expand_braces("open{.txt"),
+        Ok(vec!["open{.txt".to_string()])
+    );
+    assert!(expand_braces("{1..100000}").is_err());
+    assert!(expand_braces("{1..40}{1..40}").is_err());
+
+    let (names, issue) = expand_create_input("{a,b/..}");
+    assert_eq!(names.len(), 2);
+    assert!(issue.is_some_and(|i| i.is_invalid()));
+    assert_eq!(expand_create_input("docs/{a,b}.md").1, None);
+}
+
+#[test]
+fn test_relative_path_climbs_out_of_base() {
+    let rel = |path: &str, base: &str| relative_path(Path::new(path), Path::new(base));
+    assert_eq!(
+        rel("/home/u/src/main.rs", "/home/u"),
+        PathBuf::from("src/main.rs")
+    );
+    assert_eq!(
+        rel("/home/u/docs/a.md", "/home/u/src"),
+        PathBuf::from("../docs/a.md")
+    );
+    assert_eq!(
+        rel("/etc/hosts", "/home/u/src"),
+        PathBuf::from("../../../etc/hosts")
+    );
+    assert_eq!(rel("/home/u", "/home/u"), PathBuf::from("."));
+    assert_eq!(rel("/home/u", "/home/u/src/deep"), PathBuf::from("../.."));
+}
+
+#[test]
+fn test_sharing_violations_mean_file_in_use() {
+    // Windows reports files other programs hold open as sharing or lock violations
+    for code in [32, 33] {
+        let err = std::io::Error::from_raw_os_error(code);
+        assert_eq!(is_file_in_use(&err), cfg!(windows));
+    }
+    let denied = std::io::Error::from(std::io::ErrorKind::PermissionDenied);
+    assert!(!is_file_in_use(&denied));
+}
+
+#[test]
+fn test_osc52_sequence_encodes_base64() {
+    assert_eq!(osc52_sequence("hello", false), "\x1b]52;c;aGVsbG8=\x07");
+    assert_eq!(osc52_sequence("ab", false), "\x1b]52;c;YWI=\x07");
+    assert_eq!(osc52_sequence("abc", false), "\x1b]52;c;YWJj\x07");
+    assert_eq!(osc52_sequence("", false), "\x1b]52;c;\x07");
+    assert_eq!(
+        osc52_sequence("/tmp/ä", false),
+        "\x1b]52;c;L3RtcC/DpA==\x07"
+    );
+    assert_eq!(
+        osc52_sequence("ab", true),
+        "\x1bPtmux;\x1b\x1b]52;c;YWI=\x07\x1b\\"
+    );
+}
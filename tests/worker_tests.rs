@@ -1,20 +1,27 @@
 //! Tests for the worker pool handling directory loading and file finding.
 //! These tests ensure that the worker pool can handle multiple
-//! concurrent requests correctly and efficiently.
+//! concurrent requests correctly and efficiently, that full queues apply
+//! their overflow policy and count their work, and that large directories are streamed in chunks.
 //!
 //! Temporary directories and files are created for testing purposes and
 //! are automatically cleaned up after the tests complete.
 
 use rand::{Rng, rng};
+use runa_tui::config::WorkerOptions;
 use runa_tui::config::display::PreviewMethod;
-use runa_tui::core::worker::{FileOperation, WorkerResponse, WorkerTask, Workers};
+use runa_tui::core::worker::{
+    FileOperation, OverflowPolicy, PasteConflict, PreviewParams, WorkerResponse, WorkerTask,
+    Workers,
+};
+use runa_tui::core::{DirId, FindKind, SearchFilters};
 use std::collections::HashSet;
 use std::env;
+use std::ffi::OsString;
 use std::fs::{self, File};
 use std::sync::Arc;
 use std::sync::atomic::AtomicBool;
 use std::thread;
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use tempfile::tempdir;
 use unicode_width::UnicodeWidthStr;
 
@@ -29,13 +36,17 @@ fn test_worker_load_current_dir() -> Result<(), Box<dyn std::error::Error>> {
     task_tx.send(WorkerTask::LoadDirectory {
         path: curr_dir,
         focus: None,
+        dir_id: None,
         dirs_first: true,
+        sort_expr: None,
         show_hidden: false,
         show_system: false,
         case_insensitive: true,
         always_show: Arc::new(HashSet::new()),
         pane_width: 20,
+        ascii_only: false,
         request_id: 1,
+        requested: Instant::now(),
     })?;
 
     match res_rx.recv()? {
@@ -58,6 +69,127 @@ fn test_worker_load_current_dir() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_worker_streams_large_dir_in_chunks() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    for i in 0..25 {
+        File::create(temp.path().join(format!("file_{:02}.txt", i)))?;
+    }
+    let options: WorkerOptions = toml::from_str("dir_chunk_size = 10")?;
+    let workers = Workers::spawn_with(&options);
+
+    workers.io_tx().send(WorkerTask::LoadDirectory {
+        path: temp.path().to_path_buf(),
+        focus: None,
+        dir_id: None,
+        dirs_first: true,
+        sort_expr: None,
+        show_hidden: false,
+        show_system: false,
+        case_insensitive: true,
+        always_show: Arc::new(HashSet::new()),
+        pane_width: 40,
+        ascii_only: false,
+        request_id: 1,
+        requested: Instant::now(),
+    })?;
+
+    let recv = || workers.response_rx().recv_timeout(Duration::from_secs(2));
+    match recv()? {
+        WorkerResponse::DirectoryLoaded {
+            entries, complete, ..
+        } => {
+            assert!(!complete, "The first chunk should be partial");
+            assert_eq!(entries.len(), 10);
+        }
+        other => return Err(format!("Unexpected response: {:?}", other).into()),
+    }
+    match recv()? {
+        WorkerResponse::DirectoryChunk { entries, .. } => assert_eq!(entries.len(), 10),
+        other => return Err(format!("Unexpected response: {:?}", other).into()),
+    }
+    match recv()? {
+        WorkerResponse::DirectoryLoaded {
+            entries, complete, ..
+        } => {
+            assert!(complete);
+            assert_eq!(entries.len(), 25);
+            assert!(entries.windows(2).all(|w| w[0].name() < w[1].name()));
+        }
+        other => return Err(format!("Unexpected response: {:?}", other).into()),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_worker_reports_missing_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let workers = Workers::spawn();
+    let dir = tempdir()?;
+    let gone = dir.path().join("gone");
+    fs::create_dir(&gone)?;
+    fs::remove_dir(&gone)?;
+
+    workers.io_tx().send(WorkerTask::LoadDirectory {
+        path: gone.clone(),
+        focus: None,
+        dir_id: None,
+        dirs_first: true,
+        sort_expr: None,
+        show_hidden: false,
+        show_system: false,
+        case_insensitive: true,
+        always_show: Arc::new(HashSet::new()),
+        pane_width: 20,
+        ascii_only: false,
+        request_id: 7,
+        requested: Instant::now(),
+    })?;
+
+    match workers.response_rx().recv_timeout(Duration::from_secs(5))? {
+        WorkerResponse::DirectoryMissing {
+            path,
+            renamed,
+            request_id,
+        } => {
+            assert_eq!(path, gone);
+            assert_eq!(renamed, None);
+            assert_eq!(request_id, 7);
+        }
+        other => panic!("Unexpected worker response: {:?}", other),
+    }
+
+    // A directory renamed since its last load is found again by its identity
+    let old = dir.path().join("old");
+    fs::create_dir(&old)?;
+    let dir_id = DirId::of(&old);
+    let new = dir.path().join("new");
+    fs::rename(&old, &new)?;
+    workers.io_tx().send(WorkerTask::LoadDirectory {
+        path: old.clone(),
+        focus: None,
+        dir_id,
+        dirs_first: true,
+        sort_expr: None,
+        show_hidden: false,
+        show_system: false,
+        case_insensitive: true,
+        always_show: Arc::new(HashSet::new()),
+        pane_width: 20,
+        ascii_only: false,
+        request_id: 8,
+        requested: Instant::now(),
+    })?;
+
+    match workers.response_rx().recv_timeout(Duration::from_secs(5))? {
+        WorkerResponse::DirectoryMissing { path, renamed, .. } => {
+            assert_eq!(path, old);
+            assert_eq!(renamed, Some(new));
+        }
+        other => panic!("Unexpected worker response: {:?}", other),
+    }
+    Ok(())
+}
+
 #[test]
 fn worker_dir_load_requests_multithreaded() -> Result<(), Box<dyn std::error::Error>> {
     let temp_dir = tempdir()?;
@@ -89,13 +221,17 @@ fn worker_dir_load_requests_multithreaded() -> Result<(), Box<dyn std::error::Er
                     .send(WorkerTask::LoadDirectory {
                         path: dir.clone(),
                         focus: None,
+                        dir_id: None,
                         dirs_first: rng.random_bool(0.5),
+                        sort_expr: None,
                         show_hidden: rng.random_bool(0.5),
                         show_system: rng.random_bool(0.5),
                         case_insensitive: rng.random_bool(0.5),
                         always_show: Arc::new(HashSet::new()),
                         pane_width: pane_base + rng.random_range(0..10),
+                        ascii_only: false,
                         request_id: (t * requests_per_thread + i) as u64,
+                        requested: Instant::now(),
                     })
                     .expect("Couldn't send task to worker");
                 if i % 50 == 0 {
@@ -176,6 +312,8 @@ fn test_worker_find_pool() -> Result<(), Box<dyn std::error::Error>> {
     find_tx.send(WorkerTask::FindRecursive {
         base_dir: dir.path().to_path_buf(),
         query: "crab".to_string(),
+        kind: FindKind::All,
+        filters: SearchFilters::default(),
         max_results: 10,
         cancel: Arc::new(AtomicBool::new(false)),
         request_id: req_id,
@@ -245,6 +383,8 @@ fn test_find_worker_finds_file() -> Result<(), Box<dyn std::error::Error>> {
     workers.find_tx().send(WorkerTask::FindRecursive {
         base_dir: temp.path().to_path_buf(),
         query: "crab".to_string(),
+        kind: FindKind::All,
+        filters: SearchFilters::default(),
         max_results: 5,
         cancel: cancel.clone(),
         request_id: 2,
@@ -280,7 +420,9 @@ fn test_preview_worker_internal() -> Result<(), Box<dyn std::error::Error>> {
         pane_width: 40,
         preview_method: PreviewMethod::Internal,
         args: vec![],
+        params: PreviewParams::default(),
         request_id: 3,
+        requested: Instant::now(),
     })?;
 
     match workers
@@ -298,6 +440,49 @@ fn test_preview_worker_internal() -> Result<(), Box<dyn std::error::Error>> {
     Ok(())
 }
 
+#[test]
+fn test_preview_worker_internal_highlighted() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let preview_file = temp.path().join("main.rs");
+    std::fs::write(&preview_file, "fn main() {\n    let x = 1;\n}\n")?;
+    let workers = Workers::spawn();
+    workers.preview_tx().send(WorkerTask::LoadPreview {
+        path: preview_file.clone(),
+        max_lines: 10,
+        pane_width: 40,
+        preview_method: PreviewMethod::Internal,
+        args: vec![],
+        params: PreviewParams {
+            syntax_theme: Some("base16-ocean.dark".to_string()),
+            ..PreviewParams::default()
+        },
+        request_id: 5,
+        requested: Instant::now(),
+    })?;
+
+    match workers
+        .response_rx()
+        .recv_timeout(std::time::Duration::from_secs(2))?
+    {
+        WorkerResponse::PreviewLoaded { lines, styled, .. } => {
+            let styled = styled.ok_or("Expected highlighted lines for a .rs file")?;
+            if styled.len() != lines.len() {
+                return Err(format!(
+                    "Highlighted line count {} did not match plain {}",
+                    styled.len(),
+                    lines.len()
+                )
+                .into());
+            }
+            if styled[0].spans.len() < 2 {
+                return Err("Expected multiple styled spans on the first line".into());
+            }
+        }
+        r => return Err(format!("Unexpected response: {:?}", r).into()),
+    }
+    Ok(())
+}
+
 #[test]
 fn test_fileop_worker_create_and_delete_file() -> Result<(), Box<dyn std::error::Error>> {
     let temp = tempfile::tempdir()?;
@@ -306,8 +491,10 @@ fn test_fileop_worker_create_and_delete_file() -> Result<(), Box<dyn std::error:
 
     workers.fileop_tx().send(WorkerTask::FileOp {
         op: FileOperation::Create {
-            path: file_path.clone(),
-            is_dir: false,
+            entries: vec![(file_path.clone(), false)],
+            file_mode: None,
+            dir_mode: None,
+            template: None,
         },
         request_id: 4,
     })?;
@@ -342,3 +529,333 @@ fn test_fileop_worker_create_and_delete_file() -> Result<(), Box<dyn std::error:
     }
     Ok(())
 }
+
+#[test]
+fn test_fileop_worker_creates_batch() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    fs::create_dir(temp.path().join("src"))?;
+    let workers = Workers::spawn();
+
+    workers.fileop_tx().send(WorkerTask::FileOp {
+        op: FileOperation::Create {
+            entries: vec![
+                (temp.path().join("src/lib.rs"), false),
+                (temp.path().join("src/main.rs"), false),
+                (temp.path().join("chapter1"), true),
+            ],
+            file_mode: None,
+            dir_mode: None,
+            template: None,
+        },
+        request_id: 6,
+    })?;
+
+    let r = workers
+        .response_rx()
+        .recv_timeout(std::time::Duration::from_secs(2))?;
+    match r {
+        WorkerResponse::OperationComplete { message, .. } => {
+            assert_eq!(message, "Created 3 entries");
+            assert!(temp.path().join("src/lib.rs").is_file());
+            assert!(temp.path().join("src/main.rs").is_file());
+            assert!(temp.path().join("chapter1").is_dir());
+        }
+        other => return Err(format!("Unexpected response: {:?}", other).into()),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_fileop_worker_creates_missing_parents() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let workers = Workers::spawn();
+
+    workers.fileop_tx().send(WorkerTask::FileOp {
+        op: FileOperation::Create {
+            entries: vec![(temp.path().join("src/bin/tool.rs"), false)],
+            file_mode: None,
+            dir_mode: None,
+            template: None,
+        },
+        request_id: 8,
+    })?;
+
+    let r = workers
+        .response_rx()
+        .recv_timeout(std::time::Duration::from_secs(2))?;
+    match r {
+        WorkerResponse::OperationComplete {
+            focus, focus_dir, ..
+        } => {
+            assert!(temp.path().join("src/bin/tool.rs").is_file());
+            assert_eq!(focus, Some("tool.rs".into()));
+            assert_eq!(focus_dir, Some(temp.path().join("src/bin")));
+        }
+        other => return Err(format!("Unexpected response: {:?}", other).into()),
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+#[test]
+fn test_fileop_worker_creates_with_mode() -> Result<(), Box<dyn std::error::Error>> {
+    use std::os::unix::fs::PermissionsExt;
+
+    let temp = tempfile::tempdir()?;
+    let file = temp.path().join("secret.txt");
+    let dir = temp.path().join("private");
+    let workers = Workers::spawn();
+
+    workers.fileop_tx().send(WorkerTask::FileOp {
+        op: FileOperation::Create {
+            entries: vec![(file.clone(), false), (dir.clone(), true)],
+            file_mode: Some(0o600),
+            dir_mode: Some(0o700),
+            template: None,
+        },
+        request_id: 7,
+    })?;
+
+    let r = workers
+        .response_rx()
+        .recv_timeout(std::time::Duration::from_secs(2))?;
+    match r {
+        WorkerResponse::OperationComplete { .. } => {
+            // The umask can only remove permissions, none of these bits are in a usual umask
+            assert_eq!(fs::metadata(&file)?.permissions().mode() & 0o777, 0o600);
+            assert_eq!(fs::metadata(&dir)?.permissions().mode() & 0o777, 0o700);
+        }
+        other => return Err(format!("Unexpected response: {:?}", other).into()),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_preview_queue_drops_oldest_when_full() -> Result<(), Box<dyn std::error::Error>> {
+    let workers = Workers::spawn();
+    let dir = tempdir()?;
+    let file_path = dir.path().join("queued.txt");
+    fs::write(&file_path, "queued")?;
+
+    // Far more requests than the preview queue holds, sending must never block
+    for request_id in 0..100 {
+        workers.preview_tx().send(WorkerTask::LoadPreview {
+            path: file_path.clone(),
+            max_lines: 5,
+            pane_width: 20,
+            preview_method: PreviewMethod::Internal,
+            args: Vec::new(),
+            params: PreviewParams::default(),
+            request_id,
+            requested: Instant::now(),
+        })?;
+        assert!(workers.preview_tx().len() <= 4);
+    }
+
+    let mut last_id = None;
+    while let Ok(resp) = workers
+        .response_rx()
+        .recv_timeout(Duration::from_millis(500))
+    {
+        if let WorkerResponse::PreviewLoaded { request_id, .. } = resp {
+            last_id = Some(request_id);
+        }
+    }
+    assert_eq!(last_id, Some(99), "The newest preview must be answered");
+    Ok(())
+}
+
+#[test]
+fn test_fileop_backlog_never_blocks_or_drops() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let options: WorkerOptions =
+        toml::from_str("queue_size = 1\nresponse_queue_size = 1\nfileop_overflow = \"block\"")?;
+    assert_eq!(options.fileop_overflow(), OverflowPolicy::Backlog);
+    assert!(toml::from_str::<WorkerOptions>("fileop_overflow = \"drop_oldest\"").is_err());
+    let workers = Workers::spawn_with(&options);
+
+    // Nobody reads the responses yet, the worker soon waits on the full response queue
+    for i in 0..20 {
+        workers.fileop_tx().send(WorkerTask::FileOp {
+            op: FileOperation::Create {
+                entries: vec![(temp.path().join(format!("f{i}")), false)],
+                file_mode: None,
+                dir_mode: None,
+                template: None,
+            },
+            request_id: i,
+        })?;
+    }
+    assert!(workers.fileop_tx().len() > 1);
+
+    let deadline = Instant::now() + Duration::from_secs(5);
+    let mut done = 0;
+    while done < 20 && Instant::now() < deadline {
+        workers.flush();
+        if let Ok(WorkerResponse::OperationComplete { .. }) = workers
+            .response_rx()
+            .recv_timeout(Duration::from_millis(10))
+        {
+            done += 1;
+        }
+    }
+    assert_eq!(done, 20);
+    assert!((0..20).all(|i| temp.path().join(format!("f{i}")).exists()));
+    Ok(())
+}
+
+#[test]
+fn test_fileop_worker_counts_copied_and_deleted_bytes() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let src_dir = temp.path().join("src");
+    fs::create_dir_all(src_dir.join("nested"))?;
+    fs::write(src_dir.join("a.txt"), [b'a'; 100])?;
+    fs::write(src_dir.join("nested/b.txt"), [b'b'; 50])?;
+    let dest = temp.path().join("dest");
+    fs::create_dir(&dest)?;
+    let workers = Workers::spawn();
+
+    workers.fileop_tx().send(WorkerTask::FileOp {
+        op: FileOperation::Copy {
+            src: vec![src_dir.clone()],
+            dest: dest.clone(),
+            cut: false,
+            focus: None,
+            resume: false,
+            max_bytes_per_sec: None,
+            conflict: PasteConflict::Rename,
+        },
+        request_id: 1,
+    })?;
+    workers.response_rx().recv_timeout(Duration::from_secs(2))?;
+    assert_eq!(workers.stats().bytes_copied(), 150);
+
+    workers.fileop_tx().send(WorkerTask::FileOp {
+        op: FileOperation::Delete(vec![dest.join("src")]),
+        request_id: 2,
+    })?;
+    workers.response_rx().recv_timeout(Duration::from_secs(2))?;
+    assert_eq!(workers.stats().bytes_deleted(), 150);
+    assert_eq!(workers.stats().file_ops(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_fileop_worker_resolves_paste_conflicts() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempfile::tempdir()?;
+    let src = temp.path().join("src");
+    let dest = temp.path().join("dest");
+    fs::create_dir(&src)?;
+    fs::create_dir(&dest)?;
+    fs::write(src.join("notes.txt"), "new")?;
+    fs::write(dest.join("notes.txt"), "old")?;
+    let workers = Workers::spawn();
+    let paste = |conflict, request_id| {
+        workers.fileop_tx().send(WorkerTask::FileOp {
+            op: FileOperation::Copy {
+                src: vec![src.join("notes.txt")],
+                dest: dest.clone(),
+                cut: false,
+                focus: None,
+                resume: false,
+                max_bytes_per_sec: None,
+                conflict,
+            },
+            request_id,
+        })?;
+        workers.response_rx().recv_timeout(Duration::from_secs(2))?;
+        Ok::<_, Box<dyn std::error::Error>>(())
+    };
+
+    paste(PasteConflict::Skip, 1)?;
+    assert_eq!(fs::read_to_string(dest.join("notes.txt"))?, "old");
+    assert!(!dest.join("notes_1.txt").exists());
+
+    paste(PasteConflict::Rename, 2)?;
+    assert_eq!(fs::read_to_string(dest.join("notes.txt"))?, "old");
+    assert_eq!(fs::read_to_string(dest.join("notes_1.txt"))?, "new");
+
+    paste(PasteConflict::Overwrite, 3)?;
+    assert_eq!(fs::read_to_string(dest.join("notes.txt"))?, "new");
+    assert!(!dest.join("notes_2.txt").exists());
+    Ok(())
+}
+
+#[test]
+fn test_watch_worker_reports_changed_dir() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    let workers = Workers::spawn();
+
+    workers.watch_tx().send(WorkerTask::Watch {
+        paths: vec![temp.path().to_path_buf()],
+    })?;
+    // Give the watcher time to register the directory
+    thread::sleep(Duration::from_millis(200));
+
+    File::create(temp.path().join("new.txt"))?;
+    File::create(temp.path().join("other.txt"))?;
+
+    match workers.response_rx().recv_timeout(Duration::from_secs(3))? {
+        WorkerResponse::DirectoryChanged { path } => assert_eq!(path, temp.path()),
+        other => return Err(format!("Unexpected response: {:?}", other).into()),
+    }
+    // Both files are reported as one change
+    assert!(
+        workers
+            .response_rx()
+            .recv_timeout(Duration::from_millis(500))
+            .is_err()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_dir_size_worker_sums_and_cancels() -> Result<(), Box<dyn std::error::Error>> {
+    let temp = tempdir()?;
+    fs::create_dir_all(temp.path().join("big/nested"))?;
+    fs::write(temp.path().join("big/a.bin"), vec![0u8; 1000])?;
+    fs::write(temp.path().join("big/nested/b.bin"), vec![0u8; 500])?;
+    fs::create_dir_all(temp.path().join("big/node_modules/pkg"))?;
+    fs::write(
+        temp.path().join("big/node_modules/pkg/c.bin"),
+        vec![0u8; 700],
+    )?;
+    fs::create_dir(temp.path().join("empty"))?;
+    let workers = Workers::spawn();
+    let excludes = Arc::new(HashSet::from([OsString::from("node_modules")]));
+
+    workers.size_tx().send(WorkerTask::DirSize {
+        path: temp.path().to_path_buf(),
+        names: vec!["big".into(), "empty".into()],
+        excludes: Arc::clone(&excludes),
+        cancel: Arc::new(AtomicBool::new(false)),
+        request_id: 1,
+    })?;
+    let mut sizes = Vec::new();
+    for _ in 0..2 {
+        match workers.response_rx().recv_timeout(Duration::from_secs(2))? {
+            WorkerResponse::DirSizeLoaded { name, size, .. } => {
+                sizes.push((name, size.bytes(), size.excluded()))
+            }
+            other => return Err(format!("Unexpected response: {:?}", other).into()),
+        }
+    }
+    // The excluded node_modules is tallied, not summed
+    assert_eq!(sizes, vec![("big".into(), 1500, 1), ("empty".into(), 0, 0)]);
+
+    // A cancelled task does not report anything
+    workers.size_tx().send(WorkerTask::DirSize {
+        path: temp.path().to_path_buf(),
+        names: vec!["big".into()],
+        excludes,
+        cancel: Arc::new(AtomicBool::new(true)),
+        request_id: 2,
+    })?;
+    assert!(
+        workers
+            .response_rx()
+            .recv_timeout(Duration::from_millis(200))
+            .is_err()
+    );
+    Ok(())
+}